@@ -0,0 +1,53 @@
+//! Check verdict cache
+//!
+//! One entry in `.noslop/cache/check/`, named by a key that hashes every
+//! input of the verdict (staged tree, effective config, staged acks,
+//! actor). A changed input is a different key, so nothing is ever
+//! invalidated explicitly; writing a new entry just drops the old one.
+//! Gitignored, per-clone working state.
+
+use std::fs;
+
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+use crate::adapters::git::state_path;
+
+/// Where verdicts are cached
+pub const CHECK_CACHE_DIR: &str = ".noslop/cache/check";
+
+/// Cached verdict for `key`, if any.
+///
+/// An unreadable or stale-format entry is a miss, never an error.
+#[must_use]
+pub fn load<T: DeserializeOwned>(key: &str) -> Option<T> {
+    let path = state_path(CHECK_CACHE_DIR).join(format!("{key}.json"));
+    let content = fs::read_to_string(path).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+/// The verdict cached last, whatever its key: the previous run's, since
+/// each run replaces the cache. A miss when nothing readable is cached.
+#[must_use]
+pub fn latest<T: DeserializeOwned>() -> Option<T> {
+    let newest = fs::read_dir(state_path(CHECK_CACHE_DIR))
+        .ok()?
+        .filter_map(Result::ok)
+        .max_by_key(|entry| entry.metadata().and_then(|m| m.modified()).ok())?;
+    serde_json::from_str(&fs::read_to_string(newest.path()).ok()?).ok()
+}
+
+/// Replace the cache with the verdict for `key`.
+///
+/// # Errors
+///
+/// Returns an error if the cache directory cannot be written.
+pub fn store<T: Serialize>(key: &str, verdict: &T) -> anyhow::Result<()> {
+    let dir = state_path(CHECK_CACHE_DIR);
+    if dir.exists() {
+        fs::remove_dir_all(&dir)?;
+    }
+    fs::create_dir_all(&dir)?;
+    fs::write(dir.join(format!("{key}.json")), serde_json::to_string(verdict)?)?;
+    Ok(())
+}
@@ -5,10 +5,14 @@
 //! 2. Known agent-harness environment fingerprints
 //! 3. CI environments (treated as agents: they must verify, never no-action)
 //! 4. TTY heuristic - an interactive terminal on stdin means a person
+//!
+//! Identity (who stands behind the actor) resolves separately:
+//! `--identity` flag, then `NOSLOP_IDENTITY`, then `.noslop/identity`,
+//! then `git config user.email`.
 
 use std::io::IsTerminal;
 
-use crate::core::models::Actor;
+use crate::core::models::{Actor, Identity, IdentitySource};
 
 /// Environment fingerprints for known agent harnesses, checked in order
 const AGENT_FINGERPRINTS: &[(&str, &str)] = &[
@@ -58,6 +62,47 @@ fn detect_from(env: impl Fn(&str) -> Option<String>, stdin_is_tty: bool) -> Acto
     }
 }
 
+/// Whether this process runs as a GitHub Actions step, where workflow
+/// commands printed to stdout become annotations
+#[must_use]
+pub fn in_github_actions() -> bool {
+    std::env::var("GITHUB_ACTIONS").is_ok_and(|value| value == "true")
+}
+
+/// Per-clone identity file (written by `noslop whoami --set`)
+pub const IDENTITY_PATH: &str = ".noslop/identity";
+
+/// Resolve the identity behind the current actor, `None` when no source
+/// names one
+#[must_use]
+pub fn resolve_identity(flag: Option<&str>) -> Option<Identity> {
+    resolve_identity_from(
+        flag,
+        |k| std::env::var(k).ok(),
+        || std::fs::read_to_string(crate::adapters::git::state_path(IDENTITY_PATH)).ok(),
+        crate::adapters::git::user_email,
+    )
+}
+
+/// Resolution chain, injectable for tests; blank values fall through
+fn resolve_identity_from(
+    flag: Option<&str>,
+    env: impl Fn(&str) -> Option<String>,
+    file: impl FnOnce() -> Option<String>,
+    git_email: impl FnOnce() -> Option<String>,
+) -> Option<Identity> {
+    let found = |value: Option<String>, source| {
+        value
+            .map(|v| v.trim().to_string())
+            .filter(|v| !v.is_empty())
+            .map(|name| Identity { name, source })
+    };
+    found(flag.map(String::from), IdentitySource::Flag)
+        .or_else(|| found(env("NOSLOP_IDENTITY"), IdentitySource::Env))
+        .or_else(|| found(file(), IdentitySource::File))
+        .or_else(|| found(git_email(), IdentitySource::GitConfig))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -99,6 +144,23 @@ mod tests {
         assert_eq!(actor, Actor::Human);
     }
 
+    #[test]
+    fn identity_chain_precedence() {
+        let file = || Some("file@example.com\n".to_string());
+        let git = || Some("git@example.com".to_string());
+        let env = env_of(&[("NOSLOP_IDENTITY", "env@example.com")]);
+
+        let id = resolve_identity_from(Some("flag@example.com"), &env, file, git).unwrap();
+        assert_eq!(id.source, IdentitySource::Flag);
+        let id = resolve_identity_from(None, &env, file, git).unwrap();
+        assert_eq!(id.name, "env@example.com");
+        let id = resolve_identity_from(None, env_of(&[("NOSLOP_IDENTITY", " ")]), file, git);
+        assert_eq!(id.unwrap().name, "file@example.com");
+        let id = resolve_identity_from(None, env_of(&[]), || None, git).unwrap();
+        assert_eq!(id.source, IdentitySource::GitConfig);
+        assert!(resolve_identity_from(None, env_of(&[]), || None, || None).is_none());
+    }
+
     #[test]
     fn no_tty_no_fingerprint_means_unknown_agent() {
         let actor = detect_from(env_of(&[]), false);
@@ -20,7 +20,7 @@ impl FileStore {
     /// # Errors
     ///
     /// Returns an error if the file exists but cannot be read or parsed.
-    pub fn load_staged_acks() -> anyhow::Result<Vec<Acknowledgment>> {
+    pub fn load_staged_acks() -> crate::Result<Vec<Acknowledgment>> {
         let path = state_path(STAGED_ACKS_PATH);
         if !path.exists() {
             return Ok(Vec::new());
@@ -34,7 +34,7 @@ impl FileStore {
     /// # Errors
     ///
     /// Returns an error if the file cannot be written.
-    pub fn save_staged_acks(acks: &[Acknowledgment]) -> anyhow::Result<()> {
+    pub fn save_staged_acks(acks: &[Acknowledgment]) -> crate::Result<()> {
         let path = state_path(STAGED_ACKS_PATH);
         // Ensure directory exists
         if let Some(parent) = path.parent() {
@@ -50,7 +50,7 @@ impl FileStore {
     /// # Errors
     ///
     /// Returns an error if the file exists but cannot be deleted.
-    pub fn clear_staged_acks() -> anyhow::Result<()> {
+    pub fn clear_staged_acks() -> crate::Result<()> {
         let path = state_path(STAGED_ACKS_PATH);
         if path.exists() {
             fs::remove_file(path)?;
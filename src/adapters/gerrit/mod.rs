@@ -0,0 +1,70 @@
+//! Gerrit review adapter
+//!
+//! Reads the approved labels of a change over Gerrit's REST API
+//! (`GET /changes/<id>/detail`). Only anonymously readable changes are
+//! supported: noslop holds no Gerrit credentials.
+
+use crate::core::services::reviews::ExternalApproval;
+
+/// Prefix Gerrit puts before every JSON response against XSSI
+const XSSI_PREFIX: &str = ")]}'";
+
+/// The approvals on change `change`: one per label Gerrit reports as
+/// approved, by whoever approved it.
+///
+/// # Errors
+///
+/// Returns an error if the server is unreachable or the change unknown.
+pub fn change_approvals(url: &str, change: &str) -> anyhow::Result<Vec<ExternalApproval>> {
+    let base = url.trim_end_matches('/');
+    let body = ureq::get(&format!("{base}/changes/{change}/detail?o=CURRENT_REVISION"))
+        .timeout(std::time::Duration::from_secs(10))
+        .call()
+        .map_err(|e| anyhow::anyhow!("Gerrit change {change}: {e}"))?
+        .into_string()?;
+    parse_approvals(&body, &format!("{base}/c/{change}"))
+}
+
+fn parse_approvals(body: &str, url: &str) -> anyhow::Result<Vec<ExternalApproval>> {
+    let detail: serde_json::Value =
+        serde_json::from_str(body.trim_start().trim_start_matches(XSSI_PREFIX))?;
+    let Some(labels) = detail["labels"].as_object() else {
+        return Ok(Vec::new());
+    };
+    let revision = detail["current_revision"].as_str().map(String::from);
+    Ok(labels
+        .iter()
+        .filter_map(|(label, state)| {
+            let approver = &state["approved"];
+            let reviewer =
+                ["username", "email", "name"].iter().find_map(|key| approver[key].as_str())?;
+            Some(ExternalApproval {
+                reviewer: reviewer.to_string(),
+                labels: vec![label.clone()],
+                url: url.to_string(),
+                revision: revision.clone(),
+            })
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_approvals_reads_approved_labels() {
+        let body = r#")]}'
+{"current_revision": "abc123", "labels": {
+  "Code-Review": {"approved": {"_account_id": 1, "username": "alice"}},
+  "Security-Review": {"approved": {"email": "bob@example.com"}},
+  "Verified": {"recommended": {"username": "ci"}}
+}}"#;
+        let approvals = parse_approvals(body, "https://g/c/42").unwrap();
+        let summary: Vec<(&str, &str)> =
+            approvals.iter().map(|a| (a.reviewer.as_str(), a.labels[0].as_str())).collect();
+        assert_eq!(summary, [("alice", "Code-Review"), ("bob@example.com", "Security-Review")]);
+        assert_eq!(approvals[0].url, "https://g/c/42");
+        assert_eq!(approvals[1].revision.as_deref(), Some("abc123"));
+    }
+}
@@ -1,12 +1,13 @@
 //! GitHub review-history adapter
 //!
-//! Pulls human review comments from merged PRs via the `gh` CLI (which
-//! carries its own authentication). noslop never talks to the GitHub API
+//! Pulls human review comments from merged PRs, and the approvals on a PR,
+//! via the `gh` CLI (which carries its own authentication). noslop never talks to the GitHub API
 //! directly and holds no tokens.
 
 use std::process::Command;
 
 use crate::core::services::discovery::ReviewComment;
+use crate::core::services::reviews::ExternalApproval;
 
 /// Bot author fragments filtered out of mining input.
 const BOT_LOGIN_FRAGMENTS: &[&str] = &["bot", "copilot", "coderabbit", "dependabot"];
@@ -72,12 +73,72 @@ pub fn fetch_review_comments(slug: &str, max_pages: usize) -> anyhow::Result<Vec
     Ok(comments)
 }
 
+/// The standing approvals on pull request `pr`: one per reviewer whose
+/// latest verdict approves, carrying the PR's labels and the commit the
+/// verdict was given on.
+///
+/// # Errors
+///
+/// Returns an error if `gh` is unavailable or the PR cannot be read.
+pub fn pr_approvals(slug: &str, pr: &str) -> anyhow::Result<Vec<ExternalApproval>> {
+    let labels: Vec<String> =
+        gh(&["api", &format!("repos/{slug}/issues/{pr}/labels"), "--jq", ".[].name"])?
+            .lines()
+            .map(String::from)
+            .collect();
+    let raw = gh(&[
+        "api",
+        "--paginate",
+        &format!("repos/{slug}/pulls/{pr}/reviews"),
+        "--jq",
+        ".[] | {login: .user.login, state: .state, url: .html_url, commit: .commit_id}",
+    ])?;
+
+    // Later reviews supersede earlier ones; comments leave the verdict be
+    let mut latest: Vec<(String, String, String, Option<String>)> = Vec::new();
+    for line in raw.lines().filter(|l| !l.trim().is_empty()) {
+        let review: serde_json::Value = serde_json::from_str(line)?;
+        let (Some(login), Some(state)) = (review["login"].as_str(), review["state"].as_str())
+        else {
+            continue;
+        };
+        if state == "COMMENTED" || state == "PENDING" {
+            continue;
+        }
+        let url = review["url"].as_str().unwrap_or_default().to_string();
+        let commit = review["commit"].as_str().map(String::from);
+        latest.retain(|(l, _, _, _)| l != login);
+        latest.push((login.to_string(), state.to_string(), url, commit));
+    }
+    Ok(latest
+        .into_iter()
+        .filter(|(_, state, _, _)| state == "APPROVED")
+        .map(|(reviewer, _, url, revision)| ExternalApproval {
+            reviewer,
+            labels: labels.clone(),
+            url,
+            revision,
+        })
+        .collect())
+}
+
+/// Whether `login` is an active member of `team` (`org/team-slug`).
+/// Unknown teams and unreadable memberships count as not a member.
+#[must_use]
+pub fn is_team_member(team: &str, login: &str) -> bool {
+    let Some((org, slug)) = team.split_once('/') else {
+        return false;
+    };
+    gh(&["api", &format!("orgs/{org}/teams/{slug}/memberships/{login}"), "--jq", ".state"])
+        .is_ok_and(|state| state.trim() == "active")
+}
+
 /// Run a `gh` subcommand and capture stdout.
 fn gh(args: &[&str]) -> anyhow::Result<String> {
     let output = Command::new("gh").args(args).output().map_err(|_| {
         anyhow::anyhow!(
-            "mining needs the GitHub CLI: install `gh` and run `gh auth login`, \
-             or mine from an export with --from-file"
+            "this needs the GitHub CLI: install `gh` and run `gh auth login` \
+             (mining can also read an export with --from-file)"
         )
     })?;
 
@@ -4,27 +4,116 @@
 //! - pre-commit: Validates checks are acknowledged
 //! - commit-msg: Adds acknowledgment trailers to commit message
 //! - post-commit: Clears staged acknowledgments after commit
+//! - prepare-commit-msg (opt-in): Lists pending checks in the message editor
+//!
+//! An existing non-noslop hook is never overwritten: noslop's script is
+//! chained after it, and uninstalling cuts only that section back out.
 
 use std::fs;
-use std::path::Path;
+use std::path::PathBuf;
+use std::process::Command;
 
-/// Install a hook script, appending to any existing non-noslop hook
-fn install_hook(name: &str, content: &str) -> anyhow::Result<()> {
-    let hooks_dir = Path::new(".git/hooks");
-    if !hooks_dir.exists() {
-        anyhow::bail!("Not a git repository (.git/hooks not found)");
+/// Hooks directory as git resolves it (`git rev-parse --git-path hooks`)
+///
+/// In a linked worktree or a submodule `.git` is a file, not a directory;
+/// git also honors `core.hooksPath` here.
+fn hooks_dir() -> anyhow::Result<PathBuf> {
+    let output = Command::new("git").args(["rev-parse", "--git-path", "hooks"]).output()?;
+    if !output.status.success() {
+        anyhow::bail!("Not a git repository (hooks directory not found)");
     }
+    let dir = PathBuf::from(String::from_utf8_lossy(&output.stdout).trim());
+    fs::create_dir_all(&dir)?;
+    Ok(dir)
+}
+
+/// Path of the `name` hook in the hooks directory (installed or not)
+///
+/// # Errors
+///
+/// Returns an error if not in a git repository.
+pub fn hook_path(name: &str) -> anyhow::Result<PathBuf> {
+    Ok(hooks_dir()?.join(name))
+}
+
+/// Hooks noslop can install, in install order
+pub const HOOKS: [&str; 4] = ["pre-commit", "commit-msg", "post-commit", "prepare-commit-msg"];
+
+/// Hooks `noslop init` installs (prepare-commit-msg is opt-in)
+pub const DEFAULT_HOOKS: [&str; 3] = ["pre-commit", "commit-msg", "post-commit"];
+
+/// Separates an existing hook from the noslop section chained after it
+const CHAIN_MARKER: &str = "\n\n# noslop\n";
+
+/// What installing a hook did to its file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HookInstall {
+    /// No hook existed; the noslop script was written
+    Created,
+    /// A non-noslop hook existed; the noslop script was appended to it
+    Chained,
+    /// The hook already calls noslop; nothing was written
+    AlreadyInstalled,
+}
+
+/// What uninstalling a hook did to its file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HookRemoval {
+    /// The hook was only noslop's; the file was deleted
+    Removed,
+    /// The noslop section was cut out; the rest of the hook was kept
+    Unchained,
+    /// The hook does not call noslop; nothing was changed
+    NotInstalled,
+}
+
+/// The script noslop installs as the `name` hook
+fn script(name: &str) -> Option<&'static str> {
+    Some(match name {
+        "pre-commit" => {
+            "#!/bin/sh\n# noslop pre-commit hook\n# Checks that checks are acknowledged before allowing commit\n\nnoslop check\n"
+        },
+        "commit-msg" => {
+            "#!/bin/sh\n# noslop commit-msg hook\n# Adds acknowledgment trailers to commit message\n\nnoslop add-trailers \"$1\"\n"
+        },
+        "post-commit" => {
+            "#!/bin/sh\n# noslop post-commit hook\n# Clears staged acknowledgments after successful commit\n\nnoslop clear-staged\n"
+        },
+        "prepare-commit-msg" => {
+            "#!/bin/sh\n# noslop prepare-commit-msg hook\n# Lists pending checks in the commit message editor\n\nnoslop prepare-message \"$1\" $2\n"
+        },
+        _ => return None,
+    })
+}
+
+fn known_script(name: &str) -> anyhow::Result<&'static str> {
+    script(name).ok_or_else(|| {
+        anyhow::anyhow!("Unknown hook '{name}'. noslop installs: {}", HOOKS.join(", "))
+    })
+}
+
+/// Install noslop's `name` hook, chaining after any existing non-noslop
+/// hook instead of overwriting it
+///
+/// # Errors
+///
+/// Returns an error for a hook noslop does not provide, outside a git
+/// repository, or if the hook cannot be written.
+pub fn install(name: &str) -> anyhow::Result<HookInstall> {
+    let content = known_script(name)?;
+    let hook_path = hooks_dir()?.join(name);
 
-    let hook_path = hooks_dir.join(name);
-    if hook_path.exists() {
+    let outcome = if hook_path.exists() {
         let existing = fs::read_to_string(&hook_path)?;
         if existing.contains("noslop") {
-            return Ok(()); // Already installed
+            return Ok(HookInstall::AlreadyInstalled);
         }
-        fs::write(&hook_path, format!("{}\n\n# noslop\n{}", existing.trim(), content))?;
+        fs::write(&hook_path, format!("{}{CHAIN_MARKER}{content}", existing.trim()))?;
+        HookInstall::Chained
     } else {
         fs::write(&hook_path, content)?;
-    }
+        HookInstall::Created
+    };
 
     // Make executable
     #[cfg(unix)]
@@ -35,7 +124,49 @@ fn install_hook(name: &str, content: &str) -> anyhow::Result<()> {
         fs::set_permissions(&hook_path, perms)?;
     }
 
-    Ok(())
+    Ok(outcome)
+}
+
+/// Remove noslop's `name` hook, keeping whatever else the hook runs
+///
+/// # Errors
+///
+/// Returns an error for a hook noslop does not provide, outside a git
+/// repository, if the noslop section was edited by hand, or if the hook
+/// cannot be rewritten.
+pub fn uninstall(name: &str) -> anyhow::Result<HookRemoval> {
+    let content = known_script(name)?;
+    let hook_path = hooks_dir()?.join(name);
+    let existing = fs::read_to_string(&hook_path).unwrap_or_default();
+    if !existing.contains("noslop") {
+        return Ok(HookRemoval::NotInstalled);
+    }
+
+    let edited = || {
+        anyhow::anyhow!(
+            "The noslop section of {} was edited by hand; remove it there",
+            hook_path.display()
+        )
+    };
+    let rest = if let Some((before, after)) = existing.split_once(CHAIN_MARKER) {
+        let after = after.strip_prefix(content).ok_or_else(edited)?;
+        format!("{before}\n{after}")
+    } else {
+        let after = existing.strip_prefix(content).ok_or_else(edited)?;
+        if after.trim().is_empty() {
+            String::new()
+        } else {
+            format!("#!/bin/sh\n{}", after.trim_start())
+        }
+    };
+
+    if rest.is_empty() {
+        fs::remove_file(&hook_path)?;
+        Ok(HookRemoval::Removed)
+    } else {
+        fs::write(&hook_path, rest)?;
+        Ok(HookRemoval::Unchained)
+    }
 }
 
 /// Install the pre-commit hook
@@ -43,11 +174,8 @@ fn install_hook(name: &str, content: &str) -> anyhow::Result<()> {
 /// # Errors
 ///
 /// Returns an error if not in a git repository or the hook cannot be written.
-pub fn install_pre_commit() -> anyhow::Result<()> {
-    install_hook(
-        "pre-commit",
-        "#!/bin/sh\n# noslop pre-commit hook\n# Checks that checks are acknowledged before allowing commit\n\nnoslop check\n",
-    )
+pub fn install_pre_commit() -> anyhow::Result<HookInstall> {
+    install("pre-commit")
 }
 
 /// Install the commit-msg hook (used instead of prepare-commit-msg for trailers)
@@ -55,11 +183,8 @@ pub fn install_pre_commit() -> anyhow::Result<()> {
 /// # Errors
 ///
 /// Returns an error if not in a git repository or the hook cannot be written.
-pub fn install_commit_msg() -> anyhow::Result<()> {
-    install_hook(
-        "commit-msg",
-        "#!/bin/sh\n# noslop commit-msg hook\n# Adds acknowledgment trailers to commit message\n\nnoslop add-trailers \"$1\"\n",
-    )
+pub fn install_commit_msg() -> anyhow::Result<HookInstall> {
+    install("commit-msg")
 }
 
 /// Install the post-commit hook
@@ -67,9 +192,15 @@ pub fn install_commit_msg() -> anyhow::Result<()> {
 /// # Errors
 ///
 /// Returns an error if not in a git repository or the hook cannot be written.
-pub fn install_post_commit() -> anyhow::Result<()> {
-    install_hook(
-        "post-commit",
-        "#!/bin/sh\n# noslop post-commit hook\n# Clears staged acknowledgments after successful commit\n\nnoslop clear-staged\n",
-    )
+pub fn install_post_commit() -> anyhow::Result<HookInstall> {
+    install("post-commit")
+}
+
+/// Install the prepare-commit-msg hook (opt-in commit template)
+///
+/// # Errors
+///
+/// Returns an error if not in a git repository or the hook cannot be written.
+pub fn install_prepare_commit_msg() -> anyhow::Result<HookInstall> {
+    install("prepare-commit-msg")
 }
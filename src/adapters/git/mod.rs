@@ -3,18 +3,27 @@
 //! Implements `VersionControl` trait using git commands.
 //!
 //! - [`hooks`] - Git hooks installation
+//! - [`sandbox`] - Throwaway worktree for dry runs
 //! - [`staging`] - Staged file detection
 
 pub mod hooks;
+pub mod sandbox;
 pub mod staging;
 
 use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::sync::OnceLock;
 
 use crate::core::ports::VersionControl;
+use crate::error::Error;
 
-pub use hooks::{install_commit_msg, install_post_commit, install_pre_commit};
-pub use staging::{get_staged_files, staged_tree_oid, tracked_files};
+pub use hooks::{
+    hook_path, install_commit_msg, install_post_commit, install_pre_commit,
+    install_prepare_commit_msg,
+};
+pub use staging::{
+    diff_changes, get_staged_changes, get_staged_files, staged_tree_oid, tracked_files,
+};
 
 /// Git-based version control implementation
 #[derive(Debug, Clone)]
@@ -35,9 +44,37 @@ impl GitVersionControl {
     /// # Errors
     ///
     /// Returns an error if the current directory cannot be determined.
-    pub fn current_dir() -> anyhow::Result<Self> {
+    pub fn current_dir() -> crate::Result<Self> {
         Ok(Self::new(std::env::current_dir()?))
     }
+
+    /// Whether the repository has no work tree (`git rev-parse
+    /// --is-bare-repository`), as in a server-side receive hook
+    #[must_use]
+    pub fn is_bare(&self) -> bool {
+        self.git().args(["rev-parse", "--is-bare-repository"]).output().is_ok_and(|o| {
+            o.status.success() && String::from_utf8_lossy(&o.stdout).trim() == "true"
+        })
+    }
+
+    /// A git command run from `workdir`
+    ///
+    /// `GIT_DIR` and `GIT_WORK_TREE` are honored as git would read them
+    /// from this process: relative values are resolved against the process
+    /// working directory, not `workdir`.
+    fn git(&self) -> Command {
+        let mut command = Command::new("git");
+        command.current_dir(&self.workdir);
+        for var in ["GIT_DIR", "GIT_WORK_TREE"] {
+            if let Some(value) = std::env::var_os(var)
+                && Path::new(&value).is_relative()
+                && let Ok(absolute) = std::path::absolute(&value)
+            {
+                command.env(var, absolute);
+            }
+        }
+        command
+    }
 }
 
 impl Default for GitVersionControl {
@@ -47,35 +84,38 @@ impl Default for GitVersionControl {
 }
 
 impl VersionControl for GitVersionControl {
-    fn staged_files(&self) -> anyhow::Result<Vec<String>> {
-        get_staged_files()
+    fn staged_files(&self) -> crate::Result<Vec<String>> {
+        get_staged_files().map_err(Error::git)
     }
 
     fn repo_name(&self) -> String {
         get_repo_name()
     }
 
-    fn repo_root(&self) -> anyhow::Result<PathBuf> {
-        let output = Command::new("git")
-            .current_dir(&self.workdir)
-            .args(["rev-parse", "--show-toplevel"])
-            .output()?;
+    /// The work tree root; for a bare repository, the git directory
+    fn repo_root(&self) -> crate::Result<PathBuf> {
+        let flag = if self.is_bare() {
+            "--absolute-git-dir"
+        } else {
+            "--show-toplevel"
+        };
+        let output = self.git().args(["rev-parse", flag]).output().map_err(Error::git)?;
 
         if !output.status.success() {
-            anyhow::bail!("Not a git repository");
+            return Err(Error::git("Not a git repository"));
         }
 
         let root = String::from_utf8_lossy(&output.stdout).trim().to_string();
         Ok(PathBuf::from(root))
     }
 
-    fn install_hooks(&self, force: bool) -> anyhow::Result<()> {
+    fn install_hooks(&self, force: bool) -> crate::Result<()> {
         if force {
             // TODO: Remove existing hooks first if force is true
         }
-        install_pre_commit()?;
-        install_commit_msg()?;
-        install_post_commit()?;
+        install_pre_commit().map_err(Error::git)?;
+        install_commit_msg().map_err(Error::git)?;
+        install_post_commit().map_err(Error::git)?;
         Ok(())
     }
 
@@ -93,11 +133,8 @@ impl VersionControl for GitVersionControl {
             .is_ok_and(|o| o.status.success())
     }
 
-    fn current_branch(&self) -> anyhow::Result<Option<String>> {
-        let output = Command::new("git")
-            .current_dir(&self.workdir)
-            .args(["branch", "--show-current"])
-            .output()?;
+    fn current_branch(&self) -> crate::Result<Option<String>> {
+        let output = self.git().args(["branch", "--show-current"]).output().map_err(Error::git)?;
 
         if !output.status.success() {
             return Ok(None);
@@ -112,6 +149,37 @@ impl VersionControl for GitVersionControl {
     }
 }
 
+/// Whether a working `git` executable is on PATH (probed once per process)
+#[must_use]
+pub fn git_available() -> bool {
+    static AVAILABLE: OnceLock<bool> = OnceLock::new();
+    *AVAILABLE.get_or_init(|| {
+        Command::new("git").arg("--version").output().is_ok_and(|o| o.status.success())
+    })
+}
+
+/// Whether the work tree is a sparse checkout (`core.sparseCheckout`)
+///
+/// Paths outside the sparse cone are in the index but not on disk.
+#[must_use]
+pub fn sparse_checkout() -> bool {
+    Command::new("git")
+        .args(["config", "--bool", "core.sparseCheckout"])
+        .output()
+        .is_ok_and(|o| o.status.success() && String::from_utf8_lossy(&o.stdout).trim() == "true")
+}
+
+/// `git config user.email`, if set
+#[must_use]
+pub fn user_email() -> Option<String> {
+    Command::new("git")
+        .args(["config", "user.email"])
+        .output()
+        .ok()
+        .filter(|o| o.status.success())
+        .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
+}
+
 /// Absolute repository root (`git rev-parse --show-toplevel`), falling
 /// back to the current directory outside a work tree.
 #[must_use]
@@ -127,6 +195,19 @@ pub fn repo_root_or_cwd() -> PathBuf {
         )
 }
 
+/// Create an empty repository in `dir` (`git init`)
+///
+/// # Errors
+///
+/// Returns git's stderr if the repository cannot be created.
+pub fn init_repo(dir: &Path) -> anyhow::Result<()> {
+    let output = Command::new("git").args(["init", "--quiet"]).current_dir(dir).output()?;
+    if !output.status.success() {
+        anyhow::bail!("git init: {}", String::from_utf8_lossy(&output.stderr).trim());
+    }
+    Ok(())
+}
+
 /// A `.noslop` state path anchored to the repository root.
 ///
 /// Every module that touches `.noslop/` state must resolve through here:
@@ -159,3 +240,29 @@ pub fn get_repo_name() -> String {
         })
         .unwrap_or_else(|| "project".to_string())
 }
+
+/// Whether git is replaying existing commits (rebase, cherry-pick, revert).
+///
+/// Replayed commits run post-commit but never pre-commit, so they must not
+/// be mistaken for commits that skipped the gate.
+#[must_use]
+pub fn replaying_commits() -> bool {
+    if std::env::var_os("GIT_REFLOG_ACTION").is_some() {
+        return true;
+    }
+    ["rebase-merge", "rebase-apply", "CHERRY_PICK_HEAD", "REVERT_HEAD"]
+        .iter()
+        .any(|name| git_path(name).is_some_and(|path| path.exists()))
+}
+
+/// Absolute path of a file inside the git directory (`git rev-parse
+/// --git-path`), honoring `GIT_INDEX_FILE` and linked worktrees
+#[must_use]
+pub fn git_path(name: &str) -> Option<PathBuf> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--git-path", name])
+        .output()
+        .ok()
+        .filter(|o| o.status.success())?;
+    std::path::absolute(String::from_utf8_lossy(&output.stdout).trim()).ok()
+}
@@ -0,0 +1,176 @@
+//! Throwaway worktree for dry-running the hook pipeline
+//!
+//! A [`Sandbox`] is a detached worktree at HEAD with the current index
+//! applied to it and the staged acks copied in. Everything the pipeline
+//! writes (events, caches, the sandbox commit itself) lands there; the
+//! worktree is removed when the sandbox is dropped.
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+/// Staged acks, copied so the sandbox gate sees the same answers
+const STAGED_ACKS: &str = ".noslop/staged-acks.json";
+
+/// Name of a sandbox worktree, before the pid of the run that made it
+pub const WORKTREE_PREFIX: &str = "noslop-sandbox-";
+
+/// A sandbox worktree git has on record, from this run or an earlier one
+#[derive(Debug)]
+pub struct SandboxWorktree {
+    /// The worktree root
+    pub path: PathBuf,
+    /// Process id of the `noslop sandbox` run that created it
+    pub pid: u32,
+}
+
+impl SandboxWorktree {
+    /// Whether nothing will clean it up any more: its directory is gone, or
+    /// the run that created it has exited
+    #[must_use]
+    pub fn abandoned(&self) -> bool {
+        !self.path.exists() || !process_alive(self.pid)
+    }
+
+    /// Remove the worktree, or prune git's record of it when the directory
+    /// is already gone
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if git cannot remove or prune it.
+    pub fn remove(&self) -> anyhow::Result<()> {
+        let mut git = Command::new("git");
+        if self.path.exists() {
+            git.args(["worktree", "remove", "--force"]).arg(&self.path);
+        } else {
+            git.args(["worktree", "prune"]);
+        }
+        let output = git.output()?;
+        if !output.status.success() {
+            anyhow::bail!(
+                "Failed to remove sandbox worktree {}: {}",
+                self.path.display(),
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Sandbox worktrees in git's worktree list (empty when git cannot list them)
+#[must_use]
+pub fn worktrees() -> Vec<SandboxWorktree> {
+    let Ok(output) = Command::new("git").args(["worktree", "list", "--porcelain"]).output() else {
+        return Vec::new();
+    };
+    if !output.status.success() {
+        return Vec::new();
+    }
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|l| l.strip_prefix("worktree "))
+        .map(PathBuf::from)
+        .filter_map(|path| {
+            let pid = path.file_name()?.to_str()?.strip_prefix(WORKTREE_PREFIX)?.parse().ok()?;
+            Some(SandboxWorktree { path, pid })
+        })
+        .collect()
+}
+
+/// Whether process `pid` is running (`kill -0`). Where that cannot be
+/// asked, the process is assumed alive so its worktree is left alone.
+fn process_alive(pid: u32) -> bool {
+    if pid == std::process::id() {
+        return true;
+    }
+    if cfg!(unix) {
+        // The shell builtin: a standalone `kill` binary is not always installed
+        return Command::new("sh")
+            .args(["-c", "kill -0 \"$1\" 2>/dev/null", "sh", &pid.to_string()])
+            .status()
+            .map_or(true, |status| status.success());
+    }
+    true
+}
+
+/// A detached worktree mirroring HEAD plus the staged changes
+#[derive(Debug)]
+pub struct Sandbox {
+    path: PathBuf,
+}
+
+impl Sandbox {
+    /// Create the worktree under the system temp directory.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there is no commit to branch from, or the
+    /// worktree cannot be created or the staged diff applied.
+    pub fn create() -> anyhow::Result<Self> {
+        let root = super::repo_root_or_cwd();
+        let path = std::env::temp_dir().join(format!("{WORKTREE_PREFIX}{}", std::process::id()));
+        let added = Command::new("git")
+            .args(["worktree", "add", "--detach", "--quiet"])
+            .arg(&path)
+            .arg("HEAD")
+            .output()?;
+        if !added.status.success() {
+            anyhow::bail!(
+                "Failed to create sandbox worktree: {}",
+                String::from_utf8_lossy(&added.stderr).trim()
+            );
+        }
+        // From here on, Drop cleans up
+        let sandbox = Self { path };
+
+        let staged = Command::new("git").args(["diff", "--cached", "--binary"]).output()?;
+        if !staged.status.success() {
+            anyhow::bail!("Failed to read staged changes");
+        }
+        if !staged.stdout.is_empty() {
+            let mut apply = Command::new("git")
+                .args(["apply", "--index", "--whitespace=nowarn"])
+                .current_dir(&sandbox.path)
+                .stdin(Stdio::piped())
+                .stderr(Stdio::piped())
+                .spawn()?;
+            if let Some(mut stdin) = apply.stdin.take() {
+                stdin.write_all(&staged.stdout)?;
+            }
+            let applied = apply.wait_with_output()?;
+            if !applied.status.success() {
+                anyhow::bail!(
+                    "Failed to apply staged changes in the sandbox: {}",
+                    String::from_utf8_lossy(&applied.stderr).trim()
+                );
+            }
+        }
+
+        let acks = root.join(STAGED_ACKS);
+        if acks.exists() {
+            let target = sandbox.path.join(STAGED_ACKS);
+            if let Some(parent) = target.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::copy(acks, target)?;
+        }
+        Ok(sandbox)
+    }
+
+    /// The worktree root
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for Sandbox {
+    fn drop(&mut self) {
+        let _ = Command::new("git")
+            .args(["worktree", "remove", "--force"])
+            .arg(&self.path)
+            .output();
+        let _ = std::fs::remove_dir_all(&self.path);
+        let _ = Command::new("git").args(["worktree", "prune"]).output();
+    }
+}
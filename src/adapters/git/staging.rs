@@ -2,8 +2,12 @@
 //!
 //! Provides utilities for detecting files staged for commit.
 
+use std::collections::BTreeMap;
+use std::path::Path;
 use std::process::Command;
 
+use crate::core::models::{ChangeKind, CommitStats, FileChange, SubmoduleMode};
+
 /// Get list of staged files
 ///
 /// # Errors
@@ -41,7 +45,96 @@ pub fn staged_tree_oid() -> anyhow::Result<String> {
     Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
 }
 
-/// Files changed since `base` (`git diff --name-only <base>...HEAD`).
+/// Staged changes with their kind (`git diff --cached --raw -M`)
+///
+/// Submodule pointer changes are presented per `submodules`.
+///
+/// # Errors
+///
+/// Returns an error if git command fails.
+pub fn get_staged_changes(submodules: SubmoduleMode) -> anyhow::Result<Vec<FileChange>> {
+    let output = Command::new("git").args(["diff", "--cached"]).args(RAW_ARGS).output()?;
+
+    if !output.status.success() {
+        anyhow::bail!("Failed to get staged changes");
+    }
+
+    Ok(resolve_submodules(
+        parse_raw(&String::from_utf8_lossy(&output.stdout)),
+        submodules,
+    ))
+}
+
+/// Size and shape of the staged changes, or with `base` of the branch
+/// diff (`<base>...HEAD`), for commit checks
+///
+/// A staged change set is a merge while `MERGE_HEAD` exists; a branch diff
+/// is one when its range holds a merge commit.
+///
+/// # Errors
+///
+/// Returns an error if git command fails (e.g. unknown base ref).
+pub fn change_stats(base: Option<&str>) -> anyhow::Result<CommitStats> {
+    if let Some(base) = base {
+        return range_stats(base, "HEAD");
+    }
+    let mut stats = numstat(Command::new("git").args(["diff", "--numstat", "--cached"]))?;
+    stats.merge = resolve_rev("MERGE_HEAD").is_ok();
+    Ok(stats)
+}
+
+/// Size and shape of what `head` changed since it forked from `base`
+/// (`<base>...<head>`); a merge when the range holds a merge commit
+///
+/// # Errors
+///
+/// Returns an error if git command fails (e.g. unknown ref).
+pub fn range_stats(base: &str, head: &str) -> anyhow::Result<CommitStats> {
+    let fork = merge_base(base, head)?;
+    let mut stats = numstat(Command::new("git").args(["diff", "--numstat", &fork, head]))?;
+    let merges = Command::new("git")
+        .args(["rev-list", "--merges", &format!("{base}..{head}")])
+        .output()?;
+    stats.merge = !merges.stdout.is_empty();
+    Ok(stats)
+}
+
+/// Size and shape of commit `rev`, against its first parent
+///
+/// # Errors
+///
+/// Returns an error if git command fails (e.g. unknown revision).
+pub fn commit_stats(rev: &str) -> anyhow::Result<CommitStats> {
+    let mut stats = numstat(
+        Command::new("git")
+            .args(["diff-tree", "--no-commit-id", "-r", "--numstat"])
+            .args(commit_diff_revs(rev)),
+    )?;
+    stats.merge = resolve_rev(&format!("{rev}^2")).is_ok();
+    Ok(stats)
+}
+
+/// Files and added lines in a `--numstat` listing
+fn numstat(command: &mut Command) -> anyhow::Result<CommitStats> {
+    let output = command.output()?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "Failed to read diff stats: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+
+    let mut stats = CommitStats::default();
+    for line in String::from_utf8_lossy(&output.stdout).lines() {
+        stats.files += 1;
+        // Binary files report "-" for both counts
+        stats.added_lines +=
+            line.split('\t').next().and_then(|n| n.parse::<usize>().ok()).unwrap_or(0);
+    }
+    Ok(stats)
+}
+
+/// Changes since `base` (`git diff --raw -M <base>...HEAD`).
 ///
 /// This is the CI view of a pull request: everything the branch touched,
 /// regardless of how commits were staged, squashed, or amended locally.
@@ -49,9 +142,27 @@ pub fn staged_tree_oid() -> anyhow::Result<String> {
 /// # Errors
 ///
 /// Returns an error if git command fails (e.g. unknown base ref).
-pub fn diff_files(base: &str) -> anyhow::Result<Vec<String>> {
-    let range = format!("{base}...HEAD");
-    let output = Command::new("git").args(["diff", "--name-only", &range]).output()?;
+pub fn diff_changes(base: &str, submodules: SubmoduleMode) -> anyhow::Result<Vec<FileChange>> {
+    diff_changes_to(base, "HEAD", submodules)
+}
+
+/// Changes `head` made since it forked from `base`
+/// (`git diff --raw -M <base>...<head>`).
+///
+/// # Errors
+///
+/// Returns an error if git command fails (e.g. unknown ref).
+pub fn diff_changes_to(
+    base: &str,
+    head: &str,
+    submodules: SubmoduleMode,
+) -> anyhow::Result<Vec<FileChange>> {
+    let fork = merge_base(base, head)?;
+    let output = Command::new("git")
+        .arg("diff")
+        .args(RAW_ARGS)
+        .args([fork.as_str(), head])
+        .output()?;
 
     if !output.status.success() {
         anyhow::bail!(
@@ -60,8 +171,540 @@ pub fn diff_files(base: &str) -> anyhow::Result<Vec<String>> {
         );
     }
 
+    Ok(resolve_submodules(
+        parse_raw(&String::from_utf8_lossy(&output.stdout)),
+        submodules,
+    ))
+}
+
+/// First changed line of each file in the staged diff, or with `base` in
+/// the branch diff from its merge base, for pointing at a change. Files
+/// the diff deletes are left out.
+///
+/// # Errors
+///
+/// Returns an error if git command fails (e.g. unknown base ref).
+pub fn first_changed_lines(base: Option<&str>) -> anyhow::Result<BTreeMap<String, usize>> {
+    let diff = match base {
+        Some(base) => zero_context_diff(&[merge_base(base, "HEAD")?.as_str(), "HEAD"])?,
+        None => zero_context_diff(&[])?,
+    };
+
+    let mut lines = BTreeMap::new();
+    let mut current: Option<String> = None;
+    for line in diff.lines() {
+        if let Some(path) = line.strip_prefix("+++ ") {
+            current = path.strip_prefix("b/").map(String::from);
+        } else if line.starts_with("@@ ")
+            && let Some(path) = current.take()
+        {
+            // `@@ -a,b +c,d @@`: c is where the new side starts (0 when the
+            // hunk only removes lines at the top)
+            let start = line
+                .split_whitespace()
+                .nth(2)
+                .and_then(|new| new.trim_start_matches('+').split(',').next()?.parse().ok())
+                .unwrap_or(1);
+            lines.insert(path, usize::max(start, 1));
+        }
+    }
+    Ok(lines)
+}
+
+/// Diff without context lines (`git diff -U0`) between `revs`, or of the
+/// index against HEAD when `revs` is empty. Paths carry `a/` and `b/`
+/// prefixes whatever the user's git config says.
+///
+/// # Errors
+///
+/// Returns an error if git command fails (e.g. unknown revision).
+pub fn zero_context_diff(revs: &[&str]) -> anyhow::Result<String> {
+    let mut diff = Command::new("git");
+    diff.args([
+        "diff",
+        "-U0",
+        "--no-color",
+        "--no-ext-diff",
+        "--src-prefix=a/",
+        "--dst-prefix=b/",
+    ]);
+    if revs.is_empty() {
+        diff.arg("--cached");
+    } else {
+        diff.args(revs);
+    }
+    let output = diff.output()?;
+    if !output.status.success() {
+        anyhow::bail!("Failed to diff: {}", String::from_utf8_lossy(&output.stderr).trim());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Commits in `base..HEAD` that touched `path`, newest first, as
+/// (abbreviated SHA, author name).
+///
+/// # Errors
+///
+/// Returns an error if git command fails.
+pub fn commits_touching(base: &str, path: &str) -> anyhow::Result<Vec<(String, String)>> {
+    let range = format!("{base}..HEAD");
+    let output = Command::new("git")
+        .args(["log", "--format=%h%x09%an", &range, "--", path])
+        .output()?;
+
+    if !output.status.success() {
+        anyhow::bail!("Failed to list commits touching {path}");
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| line.split_once('\t'))
+        .map(|(sha, author)| (sha.to_string(), author.to_string()))
+        .collect())
+}
+
+/// Commits reachable from HEAD as (full SHA, committer date in RFC 3339),
+/// newest first; `since` is passed to `git log --since`
+///
+/// # Errors
+///
+/// Returns an error if git command fails.
+pub fn log_commits(since: Option<&str>) -> anyhow::Result<Vec<(String, String)>> {
+    let mut cmd = Command::new("git");
+    cmd.args(["log", "--format=%H%x09%cI"]);
+    if let Some(since) = since {
+        cmd.arg(format!("--since={since}"));
+    }
+    let output = cmd.output()?;
+
+    if !output.status.success() {
+        // No commits yet: an empty history, not an error
+        return Ok(Vec::new());
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| line.split_once('\t'))
+        .map(|(sha, date)| (sha.to_string(), date.to_string()))
+        .collect())
+}
+
+/// Commits reachable from HEAD that changed `path` (repo-root relative),
+/// oldest first, as (full SHA, author name, author date in RFC 3339)
+///
+/// # Errors
+///
+/// Returns an error if git command fails.
+pub fn file_log(path: &str) -> anyhow::Result<Vec<(String, String, String)>> {
+    let output = Command::new("git")
+        .args(["log", "--reverse", "--format=%H%x09%an%x09%aI", "--"])
+        .arg(format!(":(top){path}"))
+        .output()?;
+
+    if !output.status.success() {
+        // No commits yet: an empty history, not an error
+        return Ok(Vec::new());
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.splitn(3, '\t');
+            Some((
+                fields.next()?.to_string(),
+                fields.next()?.to_string(),
+                fields.next()?.to_string(),
+            ))
+        })
+        .collect())
+}
+
+/// Author date (`YYYY-MM-DD`) of the first commit reachable from `rev`
+/// that added `needle` to `path` (`git log -S`), `None` if none has
+///
+/// # Errors
+///
+/// Returns an error if git command fails.
+pub fn first_added(rev: &str, path: &str, needle: &str) -> anyhow::Result<Option<String>> {
+    let output = Command::new("git")
+        .args(["log", "--reverse", "--format=%as"])
+        .arg(format!("-S{needle}"))
+        .args([rev, "--"])
+        .arg(format!(":(top){path}"))
+        .output()?;
+
+    if !output.status.success() {
+        // No commits yet: nothing was ever added
+        return Ok(None);
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).lines().next().map(str::to_string))
+}
+
+/// Author email of commit `rev`, `None` when it cannot be read
+#[must_use]
+pub fn author_email(rev: &str) -> Option<String> {
+    git_line(&["log", "-1", "--format=%ae", rev])
+}
+
+/// Full SHAs of the commits in `base..head`, newest first
+///
+/// # Errors
+///
+/// Returns an error if git command fails (e.g. unknown ref).
+pub fn commits_in_range(base: &str, head: &str) -> anyhow::Result<Vec<String>> {
+    let output = Command::new("git").args(["rev-list", &format!("{base}..{head}")]).output()?;
+
+    if !output.status.success() {
+        anyhow::bail!(
+            "Failed to list commits in {base}..{head}: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).lines().map(String::from).collect())
+}
+
+/// The branch `branch` is stacked on, when it is not `base` itself.
+///
+/// `git config branch.<name>.noslopBase <parent>` names it explicitly.
+/// Otherwise the parent is the local or remote-tracking branch that shares
+/// the most commits with `branch` beyond its fork from `base`, among those
+/// that have moved on since (a parent `branch` already contains needs no
+/// carrying forward). `None` when nothing qualifies.
+///
+/// # Errors
+///
+/// Returns an error if git command fails (e.g. unknown ref).
+pub fn stacked_parent(branch: &str, base: &str) -> anyhow::Result<Option<String>> {
+    let name = if branch == "HEAD" {
+        git_line(&["symbolic-ref", "--quiet", "--short", "HEAD"])
+    } else {
+        Some(branch.to_string())
+    };
+    if let Some(name) = &name
+        && let Some(parent) = git_line(&["config", "--get", &format!("branch.{name}.noslopBase")])
+    {
+        return Ok(Some(parent));
+    }
+
+    let tip = resolve_rev(branch)?;
+    let Some(base_fork) = git_line(&["merge-base", base, branch]) else {
+        return Ok(None);
+    };
+    let refs = Command::new("git")
+        .args(["for-each-ref", "--format=%(refname:short)", "refs/heads", "refs/remotes"])
+        .output()?;
+    let mut best: Option<(usize, String)> = None;
+    for candidate in String::from_utf8_lossy(&refs.stdout).lines() {
+        if candidate.ends_with("/HEAD") || Some(candidate) == name.as_deref() || candidate == base {
+            continue;
+        }
+        let Some(fork) = git_line(&["merge-base", candidate, branch]) else {
+            continue;
+        };
+        let candidate_tip = resolve_rev(candidate)?;
+        // Same fork as base: unrelated. Fork at either tip: one contains the other.
+        if fork == base_fork || fork == tip || fork == candidate_tip {
+            continue;
+        }
+        let shared = commits_in_range(&base_fork, &fork)?.len();
+        if shared > 0 && best.as_ref().is_none_or(|(most, _)| shared > *most) {
+            best = Some((shared, candidate.to_string()));
+        }
+    }
+    Ok(best.map(|(_, parent)| parent))
+}
+
+/// Where `head` forked from `base` (`git merge-base`).
+///
+/// # Errors
+///
+/// Returns an error if either ref is unknown or they share no history. In a
+/// shallow clone that usually means the fork point was never fetched, and
+/// the error says how to fetch it.
+pub fn merge_base(base: &str, head: &str) -> anyhow::Result<String> {
+    if let Some(fork) = git_line(&["merge-base", base, head]) {
+        return Ok(fork);
+    }
+    resolve_rev(base)?;
+    resolve_rev(head)?;
+    if git_line(&["rev-parse", "--is-shallow-repository"]).as_deref() == Some("true") {
+        anyhow::bail!(
+            "No merge base between '{base}' and '{head}' in this shallow clone; \
+             fetch more history (git fetch --unshallow, or --deepen=<n>)"
+        );
+    }
+    anyhow::bail!("'{base}' and '{head}' share no history")
+}
+
+/// The ref to compare against for the base branch `name`: the local branch
+/// when there is one, else its remote-tracking copy (CI checkouts often
+/// only fetch `origin/<name>`).
+///
+/// The remote is the current branch's upstream remote, or `origin`. A
+/// `name` that is already a remote-tracking ref or any other revision is
+/// used as is.
+///
+/// # Errors
+///
+/// Returns an error if neither `name` nor `<remote>/<name>` resolves.
+pub fn base_ref(name: &str) -> anyhow::Result<String> {
+    if resolve_rev(&format!("refs/heads/{name}")).is_ok() {
+        return Ok(name.to_string());
+    }
+    let remote = git_line(&["symbolic-ref", "--quiet", "--short", "HEAD"])
+        .and_then(|branch| git_line(&["config", "--get", &format!("branch.{branch}.remote")]))
+        .filter(|remote| remote != ".")
+        .unwrap_or_else(|| "origin".to_string());
+    let tracking = format!("{remote}/{name}");
+    if resolve_rev(&format!("refs/remotes/{tracking}")).is_ok() {
+        return Ok(tracking);
+    }
+    if resolve_rev(name).is_ok() {
+        return Ok(name.to_string());
+    }
+    anyhow::bail!("Base branch '{name}' not found locally or as {tracking}")
+}
+
+/// The repository's default branch, for when no base branch is configured
+///
+/// The branch `origin/HEAD` points at, then `init.defaultBranch`, then the
+/// first of main, master, trunk and develop that exists locally or on
+/// `origin`.
+#[must_use]
+pub fn default_branch() -> Option<String> {
+    if let Some(head) =
+        git_line(&["symbolic-ref", "--quiet", "--short", "refs/remotes/origin/HEAD"])
+    {
+        return Some(head.strip_prefix("origin/").unwrap_or(&head).to_string());
+    }
+    let exists = |name: &str| {
+        resolve_rev(&format!("refs/heads/{name}")).is_ok()
+            || resolve_rev(&format!("refs/remotes/origin/{name}")).is_ok()
+    };
+    git_line(&["config", "--get", "init.defaultBranch"])
+        .filter(|name| exists(name))
+        .or_else(|| {
+            ["main", "master", "trunk", "develop"]
+                .into_iter()
+                .find(|name| exists(name))
+                .map(String::from)
+        })
+}
+
+/// First line of a git command's stdout, `None` on failure or no output
+fn git_line(args: &[&str]) -> Option<String> {
+    let output = Command::new("git").args(args).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let line = String::from_utf8_lossy(&output.stdout).lines().next()?.trim().to_string();
+    (!line.is_empty()).then_some(line)
+}
+
+/// Changes a single commit introduced (`git diff-tree --root --raw -M`).
+///
+/// A root commit diffs against the empty tree, a merge against its first
+/// parent: everything the merge brought into the branch.
+///
+/// # Errors
+///
+/// Returns an error if git command fails (e.g. unknown revision).
+pub fn commit_changes(rev: &str, submodules: SubmoduleMode) -> anyhow::Result<Vec<FileChange>> {
+    let output = Command::new("git")
+        .args(["diff-tree", "--no-commit-id", "-r"])
+        .args(RAW_ARGS)
+        .args(commit_diff_revs(rev))
+        .output()?;
+
+    if !output.status.success() {
+        anyhow::bail!(
+            "Failed to list changes in '{rev}': {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+
+    Ok(resolve_submodules(
+        parse_raw(&String::from_utf8_lossy(&output.stdout)),
+        submodules,
+    ))
+}
+
+/// `git diff-tree` revisions for what commit `rev` introduced: against
+/// its first parent, or with `--root` against the empty tree
+pub(crate) fn commit_diff_revs(rev: &str) -> Vec<String> {
+    let parent = format!("{rev}^");
+    if resolve_rev(&parent).is_ok() {
+        vec![parent, rev.to_string()]
+    } else {
+        vec!["--root".to_string(), rev.to_string()]
+    }
+}
+
+/// Full object id of `rev` (`git rev-parse --verify`), e.g. `HEAD` or
+/// `HEAD^{tree}`
+///
+/// # Errors
+///
+/// Returns an error if the revision does not resolve.
+pub fn resolve_rev(rev: &str) -> anyhow::Result<String> {
+    let output = Command::new("git").args(["rev-parse", "--verify", "--quiet", rev]).output()?;
+
+    if !output.status.success() {
+        anyhow::bail!("Unknown revision '{rev}'");
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Raw diff with full object ids, so submodule commits can be diffed
+const RAW_ARGS: [&str; 3] = ["--raw", "--no-abbrev", "-M"];
+
+/// Git's file mode for a submodule pointer
+const GITLINK_MODE: &str = "160000";
+
+/// A parsed raw-diff entry: the change, plus the old and new commits when
+/// the path is a submodule pointer
+type RawChange = (FileChange, Option<(String, String)>);
+
+/// Parse `--raw` output. Copies and type changes count as modifications of
+/// the destination path.
+fn parse_raw(stdout: &str) -> Vec<RawChange> {
+    stdout
+        .lines()
+        .filter_map(|line| {
+            let (meta, paths) = line.strip_prefix(':')?.split_once('\t')?;
+            let meta: Vec<&str> = meta.split_whitespace().collect();
+            let [old_mode, new_mode, old_oid, new_oid, status] = meta[..] else {
+                return None;
+            };
+            let mut paths = paths.split('\t');
+            let first = paths.next()?;
+            let change = match status.chars().next()? {
+                'A' => FileChange::new(first, ChangeKind::Add),
+                'D' => FileChange::new(first, ChangeKind::Delete),
+                'R' => FileChange::renamed(first, paths.next()?),
+                'C' => FileChange::new(paths.next()?, ChangeKind::Add),
+                _ => FileChange::new(first, ChangeKind::Modify),
+            };
+            let gitlink = (old_mode == GITLINK_MODE || new_mode == GITLINK_MODE)
+                .then(|| (old_oid.to_string(), new_oid.to_string()));
+            Some((change, gitlink))
+        })
+        .collect()
+}
+
+/// Apply the submodule mode to parsed changes
+///
+/// Recursion diffs the submodule's own checkout between the two pointer
+/// commits. Anything it cannot resolve (submodule not checked out, commit
+/// not fetched, submodule removed) falls back to the pointer path, so a
+/// submodule change never silently disappears from the gate.
+fn resolve_submodules(raw: Vec<RawChange>, mode: SubmoduleMode) -> Vec<FileChange> {
+    let root = super::repo_root_or_cwd();
+    let mut changes = Vec::new();
+    for (change, gitlink) in raw {
+        match (gitlink, mode) {
+            (None, _) | (Some(_), SubmoduleMode::Path) => changes.push(change),
+            (Some(_), SubmoduleMode::Skip) => {},
+            (Some((old, new)), SubmoduleMode::Recurse) => {
+                match submodule_changes(&root.join(&change.path), &old, &new) {
+                    Some(inner) => {
+                        changes.extend(inner.into_iter().map(|c| prefixed(c, &change.path)));
+                    },
+                    None => changes.push(change),
+                }
+            },
+        }
+    }
+    changes
+}
+
+/// Files changed inside a submodule between two commits, relative to it
+fn submodule_changes(dir: &Path, old: &str, new: &str) -> Option<Vec<FileChange>> {
+    let is_null = |oid: &str| oid.bytes().all(|b| b == b'0');
+    if is_null(new) {
+        return None;
+    }
+    let output = if is_null(old) {
+        Command::new("git")
+            .current_dir(dir)
+            .args(["ls-tree", "-r", "--name-only", new])
+            .output()
+            .ok()?
+    } else {
+        Command::new("git")
+            .current_dir(dir)
+            .arg("diff")
+            .args(RAW_ARGS)
+            .args([old, new])
+            .output()
+            .ok()?
+    };
+    if !output.status.success() {
+        return None;
+    }
     let stdout = String::from_utf8_lossy(&output.stdout);
-    Ok(stdout.lines().map(String::from).filter(|s| !s.is_empty()).collect())
+    if is_null(old) {
+        return Some(stdout.lines().map(|p| FileChange::new(p, ChangeKind::Add)).collect());
+    }
+    // Nested submodules stay pointers: one level of recursion
+    Some(parse_raw(&stdout).into_iter().map(|(change, _)| change).collect())
+}
+
+/// Re-root a submodule-relative change under the submodule path
+fn prefixed(change: FileChange, prefix: &str) -> FileChange {
+    FileChange {
+        path: format!("{prefix}/{}", change.path),
+        kind: change.kind,
+        old_path: change.old_path.map(|p| format!("{prefix}/{p}")),
+    }
+}
+
+/// Staged diff limited to `files` (`git diff --cached -- <files>`).
+///
+/// # Errors
+///
+/// Returns an error if git command fails.
+pub fn staged_diff(files: &[String]) -> anyhow::Result<String> {
+    let output = Command::new("git")
+        .args(["diff", "--cached", "-M", "--"])
+        .args(files)
+        .output()?;
+
+    if !output.status.success() {
+        anyhow::bail!(
+            "Failed to diff staged files: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Git object id of arbitrary content (`git hash-object --stdin`).
+///
+/// Used as a content digest that any clone can recompute with plain git.
+///
+/// # Errors
+///
+/// Returns an error if git command fails.
+pub fn hash_content(content: &str) -> anyhow::Result<String> {
+    use std::io::Write;
+    use std::process::Stdio;
+
+    let mut child = Command::new("git")
+        .args(["hash-object", "--stdin"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(content.as_bytes())?;
+    }
+    let output = child.wait_with_output()?;
+
+    if !output.status.success() {
+        anyhow::bail!("Failed to hash content");
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
 }
 
 /// All tracked files in the repository (`git ls-files`).
@@ -79,3 +722,317 @@ pub fn tracked_files() -> anyhow::Result<Vec<String>> {
     let stdout = String::from_utf8_lossy(&output.stdout);
     Ok(stdout.lines().map(String::from).filter(|s| !s.is_empty()).collect())
 }
+
+/// Git blob ids of `files` as staged (`git ls-files -s`); files not in the
+/// index are left out.
+///
+/// # Errors
+///
+/// Returns an error if git command fails.
+pub fn staged_blobs(files: &[String]) -> anyhow::Result<BTreeMap<String, String>> {
+    blobs_from(&["ls-files", "-s", "-z", "--"], 1, files)
+}
+
+/// Git blob ids of `files` in the tree of `rev` (`git ls-tree -r`); files
+/// not in it are left out.
+///
+/// # Errors
+///
+/// Returns an error if git command fails (e.g. unknown revision).
+pub fn blobs_at(rev: &str, files: &[String]) -> anyhow::Result<BTreeMap<String, String>> {
+    blobs_from(&["ls-tree", "-r", "-z", rev, "--"], 2, files)
+}
+
+/// Path to blob id from a NUL-separated `<fields>\t<path>` listing whose
+/// field `blob_field` is the blob id (after the mode, field 0)
+fn blobs_from(
+    args: &[&str],
+    blob_field: usize,
+    files: &[String],
+) -> anyhow::Result<BTreeMap<String, String>> {
+    if files.is_empty() {
+        return Ok(BTreeMap::new());
+    }
+    let output = Command::new("git").arg("--literal-pathspecs").args(args).args(files).output()?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "Failed to read blob ids: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    let mut blobs = BTreeMap::new();
+    for record in String::from_utf8_lossy(&output.stdout).split('\0') {
+        let Some((meta, path)) = record.split_once('\t') else {
+            continue;
+        };
+        let fields: Vec<&str> = meta.split_whitespace().collect();
+        if let (Some(&mode), Some(&blob)) = (fields.first(), fields.get(blob_field))
+            && mode != GITLINK_MODE
+        {
+            blobs.insert(path.to_string(), blob.to_string());
+        }
+    }
+    Ok(blobs)
+}
+
+/// All tracked files, handed to `batch` `size` at a time as `git ls-files`
+/// streams them, so the full list is never held at once.
+///
+/// # Errors
+///
+/// Returns an error if git command fails, or the first error `batch`
+/// returns (which stops the listing).
+pub fn tracked_files_in_batches(
+    size: usize,
+    mut batch: impl FnMut(&[String]) -> anyhow::Result<()>,
+) -> anyhow::Result<()> {
+    use std::io::BufRead;
+
+    let mut child = Command::new("git")
+        .args(["ls-files", "-z"])
+        .stdout(std::process::Stdio::piped())
+        .spawn()?;
+    let stdout = child.stdout.take().ok_or_else(|| anyhow::anyhow!("git ls-files: no output"))?;
+    let mut pending = Vec::with_capacity(size.max(1));
+    let mut handed = Ok(());
+    for path in std::io::BufReader::new(stdout).split(b'\0') {
+        let path = String::from_utf8_lossy(&path?).into_owned();
+        if path.is_empty() {
+            continue;
+        }
+        pending.push(path);
+        if pending.len() >= size.max(1) {
+            handed = batch(&pending);
+            pending.clear();
+            if handed.is_err() {
+                break;
+            }
+        }
+    }
+    if handed.is_err() {
+        let _ = child.kill();
+        let _ = child.wait();
+        return handed;
+    }
+    if !child.wait()?.success() {
+        anyhow::bail!("Failed to list tracked files");
+    }
+    if pending.is_empty() {
+        Ok(())
+    } else {
+        batch(&pending)
+    }
+}
+
+/// Files in the tree of `rev` (`git ls-tree -r --name-only`).
+///
+/// # Errors
+///
+/// Returns an error if git command fails (e.g. unknown revision).
+pub fn files_at(rev: &str) -> anyhow::Result<Vec<String>> {
+    let output = Command::new("git").args(["ls-tree", "-r", "--name-only", rev]).output()?;
+
+    if !output.status.success() {
+        anyhow::bail!("Failed to list files at '{rev}'");
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    Ok(stdout.lines().map(String::from).filter(|s| !s.is_empty()).collect())
+}
+
+/// Content of `path` as of `rev` (`git show <rev>:<path>`).
+///
+/// # Errors
+///
+/// Returns an error if the path does not exist at that revision.
+pub fn file_at(rev: &str, path: &str) -> anyhow::Result<String> {
+    let output = Command::new("git").args(["show", &format!("{rev}:{path}")]).output()?;
+
+    if !output.status.success() {
+        anyhow::bail!("No {path} at '{rev}'");
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Commit `tree` on top of `parent` without touching any ref or running
+/// hooks (`git commit-tree`); `trailers` become the message's last
+/// paragraph. Returns the new commit id.
+///
+/// # Errors
+///
+/// Returns an error if git cannot write the commit.
+pub fn commit_tree(
+    tree: &str,
+    parent: &str,
+    message: &str,
+    trailers: &str,
+) -> anyhow::Result<String> {
+    git_run(
+        &["commit-tree", tree, "-p", parent, "-m", message, "-m", trailers],
+        "write commit",
+    )
+}
+
+/// Commit the index without running hooks (`git commit --no-verify`);
+/// `trailers` become the message's last paragraph
+///
+/// # Errors
+///
+/// Returns an error if git cannot commit (e.g. nothing staged).
+pub fn commit_unverified(message: &str, trailers: &str) -> anyhow::Result<()> {
+    git_run(&["commit", "--no-verify", "--quiet", "-m", message, "-m", trailers], "commit")?;
+    Ok(())
+}
+
+/// Create branch `name` at `rev` (`git branch`)
+///
+/// # Errors
+///
+/// Returns an error if the branch exists or `rev` is unknown.
+pub fn create_branch(name: &str, rev: &str) -> anyhow::Result<()> {
+    git_run(&["branch", name, rev], &format!("create branch {name}"))?;
+    Ok(())
+}
+
+/// Delete the fully merged branch `name` (`git branch -d`)
+///
+/// # Errors
+///
+/// Returns an error if the branch is unknown or not merged.
+pub fn delete_branch(name: &str) -> anyhow::Result<()> {
+    git_run(&["branch", "--quiet", "-d", name], &format!("delete branch {name}"))?;
+    Ok(())
+}
+
+/// Local branches whose names start with `prefix`
+///
+/// # Errors
+///
+/// Returns an error if git cannot list refs.
+pub fn branches_with_prefix(prefix: &str) -> anyhow::Result<Vec<String>> {
+    let out = git_run(
+        &["for-each-ref", "--format=%(refname:short)", &format!("refs/heads/{prefix}")],
+        "list branches",
+    )?;
+    Ok(out.lines().map(String::from).collect())
+}
+
+/// Point HEAD at branch `name` without touching the index or work tree
+/// (`git symbolic-ref`)
+///
+/// # Errors
+///
+/// Returns an error if git cannot update HEAD.
+pub fn point_head_at(name: &str) -> anyhow::Result<()> {
+    git_run(&["symbolic-ref", "HEAD", &format!("refs/heads/{name}")], "move HEAD")?;
+    Ok(())
+}
+
+/// Check out branch `name` (`git switch`)
+///
+/// # Errors
+///
+/// Returns an error if the branch is unknown or local changes would be
+/// overwritten.
+pub fn switch_branch(name: &str) -> anyhow::Result<()> {
+    git_run(&["switch", "--quiet", name], &format!("switch to {name}"))?;
+    Ok(())
+}
+
+/// Merge `branch` into the current branch as a merge commit, without
+/// running hooks; a failed merge is aborted before the error returns
+///
+/// # Errors
+///
+/// Returns an error if the merge conflicts or cannot be made.
+pub fn merge_no_ff(branch: &str, message: &str) -> anyhow::Result<()> {
+    let merged = git_run(
+        &["merge", "--no-ff", "--no-verify", "--quiet", "-m", message, branch],
+        &format!("merge {branch}"),
+    );
+    if merged.is_err() {
+        let _ = Command::new("git").args(["merge", "--abort"]).output();
+    }
+    merged.map(|_| ())
+}
+
+/// Whether tracked files outside `excluded` differ from HEAD, staged or not
+///
+/// # Errors
+///
+/// Returns an error if git cannot read the status.
+pub fn tracked_changes_outside(excluded: &str) -> anyhow::Result<bool> {
+    let status = git_run(
+        &[
+            "status",
+            "--porcelain",
+            "--untracked-files=no",
+            "--",
+            ".",
+            &format!(":!{excluded}"),
+        ],
+        "read status",
+    )?;
+    Ok(!status.is_empty())
+}
+
+/// Values of trailer `key` across the history of `rev`, newest first
+///
+/// # Errors
+///
+/// Returns an error if `rev` is unknown.
+pub fn trailer_values(rev: &str, key: &str) -> anyhow::Result<Vec<String>> {
+    let out = git_run(
+        &["log", &format!("--format=%(trailers:key={key},valueonly)"), rev],
+        &format!("read {key} trailers"),
+    )?;
+    Ok(out.lines().map(str::trim).filter(|v| !v.is_empty()).map(String::from).collect())
+}
+
+/// Run git for its trimmed stdout; on failure the error names `what` failed
+/// and carries git's stderr
+fn git_run(args: &[&str], what: &str) -> anyhow::Result<String> {
+    let output = Command::new("git").args(args).output()?;
+    if !output.status.success() {
+        anyhow::bail!("Failed to {what}: {}", String::from_utf8_lossy(&output.stderr).trim());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const Z: &str = "0000000000000000000000000000000000000000";
+    const A: &str = "1111111111111111111111111111111111111111";
+    const B: &str = "2222222222222222222222222222222222222222";
+
+    #[test]
+    fn parses_raw_kinds() {
+        let out = format!(
+            ":000000 100644 {Z} {A} A\tnew.rs\n:100644 100644 {A} {B} M\tsrc/lib.rs\n:100644 000000 {A} {Z} D\tgone.rs\n:100644 100644 {A} {B} R087\told.rs\tmoved.rs\n"
+        );
+        let changes: Vec<FileChange> = parse_raw(&out).into_iter().map(|(c, _)| c).collect();
+        assert_eq!(
+            changes,
+            vec![
+                FileChange::new("new.rs", ChangeKind::Add),
+                FileChange::new("src/lib.rs", ChangeKind::Modify),
+                FileChange::new("gone.rs", ChangeKind::Delete),
+                FileChange::renamed("old.rs", "moved.rs"),
+            ]
+        );
+    }
+
+    #[test]
+    fn flags_submodule_pointers() {
+        let out =
+            format!(":160000 160000 {A} {B} M\tvendor/lib\n:100644 100644 {A} {B} M\tsrc/lib.rs\n");
+        let raw = parse_raw(&out);
+        assert_eq!(raw[0].1, Some((A.to_string(), B.to_string())));
+        assert_eq!(raw[1].1, None);
+
+        let skipped = resolve_submodules(raw, SubmoduleMode::Skip);
+        assert_eq!(skipped, vec![FileChange::new("src/lib.rs", ChangeKind::Modify)]);
+    }
+}
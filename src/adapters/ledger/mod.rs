@@ -15,8 +15,9 @@ use std::process::Command;
 
 use serde::{Deserialize, Serialize};
 
+use crate::adapters::git::staging::commit_diff_revs;
 use crate::adapters::git::state_path;
-use crate::core::models::Acknowledgment;
+use crate::core::models::{Acknowledgment, Attachment};
 
 /// Version of the on-disk ack record and history line format
 pub const SCHEMA_VERSION: u32 = 1;
@@ -27,6 +28,9 @@ pub const ACKS_DIR: &str = ".noslop/acks";
 /// Append-only compacted history (union-merged across branches)
 pub const HISTORY_FILE: &str = ".noslop/history.jsonl";
 
+/// Evidence files attached to acks, each named by its git blob id
+pub const EVIDENCE_DIR: &str = ".noslop/evidence";
+
 /// A versioned acknowledgment record as stored in the tree
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LedgerRecord {
@@ -61,6 +65,85 @@ pub fn record(ack: &Acknowledgment) -> anyhow::Result<PathBuf> {
     Ok(path)
 }
 
+/// Copy an evidence file into `.noslop/evidence/<blob-id>` and stage it
+///
+/// Naming the copy by its content hash makes it tamper-evident: a commit
+/// whose evidence path no longer hashes to its name fails
+/// [`check_attachments`].
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read, hashed, copied or staged.
+pub fn attach(path: &Path) -> anyhow::Result<Attachment> {
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .ok_or_else(|| anyhow::anyhow!("Not a file: {}", path.display()))?;
+    let output = Command::new("git").args(["hash-object", "--"]).arg(path).output()?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "Failed to hash evidence {}: {}",
+            path.display(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    let blob = String::from_utf8_lossy(&output.stdout).trim().to_string();
+
+    let dir = state_path(EVIDENCE_DIR);
+    fs::create_dir_all(&dir)?;
+    let stored = dir.join(&blob);
+    fs::copy(path, &stored)?;
+    git_add(&stored)?;
+    Ok(Attachment { name, blob })
+}
+
+/// Delete the stored evidence file at `path`, and drop it from the index
+/// (evidence is staged when attached)
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be deleted.
+pub fn remove_evidence(path: &Path) -> anyhow::Result<()> {
+    fs::remove_file(path)?;
+    // Best-effort: the file may never have been staged
+    let _ = Command::new("git")
+        .args(["rm", "--quiet", "--cached", "--ignore-unmatch", "--"])
+        .arg(path)
+        .output();
+    Ok(())
+}
+
+/// Problems with `ack`'s evidence in `commit`'s tree: attachments that are
+/// missing, or whose stored content no longer hashes to the recorded blob
+///
+/// # Errors
+///
+/// Returns an error if git cannot be run.
+pub fn check_attachments(commit: &str, ack: &Acknowledgment) -> anyhow::Result<Vec<String>> {
+    let mut problems = Vec::new();
+    for attachment in &ack.attachments {
+        let spec = format!("{commit}:{EVIDENCE_DIR}/{}", attachment.blob);
+        let output = Command::new("git").args(["rev-parse", "--verify", "-q", &spec]).output()?;
+        let found = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        if !output.status.success() {
+            problems.push(format!("{}: evidence {} is missing", ack.check_id, attachment.name));
+        } else if found != attachment.blob {
+            problems.push(format!(
+                "{}: evidence {} was altered (expected {}, found {})",
+                ack.check_id,
+                attachment.name,
+                short(&attachment.blob),
+                short(&found)
+            ));
+        }
+    }
+    Ok(problems)
+}
+
+fn short(oid: &str) -> &str {
+    &oid[..oid.len().min(12)]
+}
+
 /// Fold all pending ack records into `.noslop/history.jsonl`
 ///
 /// Returns the number of records compacted. Intended to run at merge time
@@ -159,7 +242,76 @@ pub fn load_all_records() -> anyhow::Result<Vec<LedgerRecord>> {
     Ok(records)
 }
 
-fn pending_records() -> anyhow::Result<Vec<LedgerRecord>> {
+/// Acknowledgments whose records `commit` added to `.noslop/acks/` (for a
+/// merge, relative to its first parent)
+///
+/// # Errors
+///
+/// Returns an error if git cannot list or read the commit's files.
+pub fn added_in_commit(commit: &str) -> anyhow::Result<Vec<Acknowledgment>> {
+    let output = Command::new("git")
+        .args(["diff-tree", "--no-commit-id", "-r", "--name-only", "--diff-filter=A"])
+        .args(commit_diff_revs(commit))
+        .args(["--", ACKS_DIR])
+        .output()?;
+    if !output.status.success() {
+        anyhow::bail!("Failed to list ack records in '{commit}'");
+    }
+
+    let mut acks = Vec::new();
+    for path in String::from_utf8_lossy(&output.stdout).lines() {
+        let show = Command::new("git").args(["show", &format!("{commit}:{path}")]).output()?;
+        if let Ok(record) = serde_json::from_slice::<LedgerRecord>(&show.stdout) {
+            acks.push(record.ack);
+        }
+    }
+    Ok(acks)
+}
+
+/// What the ledger holds on disk, for health reporting
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct Inventory {
+    /// Records in `.noslop/acks/` awaiting `noslop compact`
+    pub pending: usize,
+    /// Lines in `.noslop/history.jsonl`
+    pub history: usize,
+    /// Records or lines that do not parse as any known record
+    pub unreadable: usize,
+    /// Records written by a newer schema than this binary reads
+    pub newer_schema: usize,
+}
+
+/// Count ledger records by state
+///
+/// # Errors
+///
+/// Returns an error only if an existing file cannot be read.
+pub fn inventory() -> anyhow::Result<Inventory> {
+    fn tally(raw: &str, inv: &mut Inventory) {
+        match serde_json::from_str::<LedgerRecord>(raw) {
+            Ok(record) if record.schema > SCHEMA_VERSION => inv.newer_schema += 1,
+            Ok(_) => {},
+            Err(_) => inv.unreadable += 1,
+        }
+    }
+
+    let mut inv = Inventory::default();
+
+    for path in pending_paths()? {
+        inv.pending += 1;
+        tally(&fs::read_to_string(&path)?, &mut inv);
+    }
+    let history_path = state_path(HISTORY_FILE);
+    if history_path.exists() {
+        for line in fs::read_to_string(&history_path)?.lines().filter(|l| !l.trim().is_empty()) {
+            inv.history += 1;
+            tally(line, &mut inv);
+        }
+    }
+    Ok(inv)
+}
+
+fn pending_paths() -> anyhow::Result<Vec<PathBuf>> {
     let acks_dir = state_path(ACKS_DIR);
     if !acks_dir.exists() {
         return Ok(Vec::new());
@@ -170,9 +322,12 @@ fn pending_records() -> anyhow::Result<Vec<LedgerRecord>> {
         .filter(|p| p.extension().is_some_and(|ext| ext == "json"))
         .collect();
     entries.sort();
+    Ok(entries)
+}
 
+fn pending_records() -> anyhow::Result<Vec<LedgerRecord>> {
     let mut records = Vec::new();
-    for path in entries {
+    for path in pending_paths()? {
         if let Ok(record) = serde_json::from_str::<LedgerRecord>(&fs::read_to_string(&path)?) {
             records.push(record);
         }
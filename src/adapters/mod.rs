@@ -2,34 +2,42 @@
 //!
 //! This module contains concrete implementations that handle I/O:
 //!
+//! - [`cache`] - Check verdict cache keyed by staged state
 //! - [`env`] - Actor detection from the process environment
 //! - [`mod@file`] - JSON file acknowledgment staging storage
+//! - [`gerrit`] - Approved labels of Gerrit changes
 //! - [`gh`] - Review-history fetching via the GitHub CLI
 //! - [`git`] - Git operations (hooks, staging, version control)
 //! - [`ledger`] - Durable ack records in the tree (squash-proof)
 //! - [`proposals`] - Staged check proposals awaiting review
+//! - [`release`] - Release feed and verified binary swap for self-update
 //! - [`rules`] - Rules-file discovery (CLAUDE.md, AGENTS.md, .cursor/rules)
 //! - [`runner`] - Agent CLI subprocess for mining prompts
 //! - [`telemetry`] - Local check-fire event log for stats
 //! - [`mod@toml`] - `.noslop.toml` file parsing and writing
 //! - [`trailer`] - Commit trailer acknowledgment storage
+//! - [`workspace`] - Monorepo package discovery from tracked manifests
 
 pub mod agent_spend;
+pub mod cache;
 pub mod env;
 pub mod file;
+pub mod gerrit;
 pub mod gh;
 pub mod git;
 pub mod ledger;
 pub mod proposals;
+pub mod release;
 pub mod remote;
 pub mod rules;
 pub mod runner;
 pub mod telemetry;
 pub mod toml;
 pub mod trailer;
+pub mod workspace;
 
 // Re-export main types for convenience
-pub use env::detect_actor;
+pub use env::{detect_actor, in_github_actions, resolve_identity};
 pub use file::FileStore;
 pub use git::{GitVersionControl, get_repo_name};
 pub use toml::TomlCheckRepository;
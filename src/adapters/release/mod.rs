@@ -0,0 +1,112 @@
+//! Release feed and binary replacement for `noslop self-update`
+//!
+//! Same trust model as `scripts/install.sh`: the sha256 asset published
+//! by the release workflow is mandatory, and a missing or mismatched
+//! checksum aborts before anything is replaced. The new binary is staged
+//! next to the current one and renamed over it, so an interrupted update
+//! never leaves a half-written executable.
+
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::Duration;
+
+use crate::core::services::update::Release;
+
+/// GitHub repository publishing release assets
+const REPO: &str = "noslop-sh/noslop";
+
+/// Fetch the release feed, newest first.
+///
+/// # Errors
+///
+/// Returns an error if the feed is unreachable or malformed.
+pub fn fetch_releases() -> anyhow::Result<Vec<Release>> {
+    let url = format!("https://api.github.com/repos/{REPO}/releases");
+    let releases: Vec<Release> = ureq::get(&url)
+        .set("User-Agent", "noslop-self-update")
+        .timeout(Duration::from_secs(10))
+        .call()?
+        .into_json()?;
+    Ok(releases)
+}
+
+/// Download `asset` from release `tag`, verify its sha256, and atomically
+/// replace the running executable.
+///
+/// # Errors
+///
+/// Returns an error if a download fails, the checksum is missing or does
+/// not match, extraction fails, or the executable cannot be replaced.
+pub fn install(tag: &str, asset: &str) -> anyhow::Result<PathBuf> {
+    let base = format!("https://github.com/{REPO}/releases/download/{tag}/{asset}");
+    let archive = download(&base)?;
+    let checksum = String::from_utf8(download(&format!("{base}.sha256"))?)?;
+    let expected = checksum.split_whitespace().next().unwrap_or_default();
+
+    let current = std::env::current_exe()?;
+    let dir = current.parent().map_or_else(|| PathBuf::from("."), Path::to_path_buf);
+    let work = dir.join(".noslop-update");
+    fs::create_dir_all(&work)?;
+    let result = stage_and_swap(&work, &archive, expected, &current);
+    let _ = fs::remove_dir_all(&work);
+    result.map(|()| current)
+}
+
+fn stage_and_swap(
+    work: &Path,
+    archive: &[u8],
+    expected: &str,
+    current: &Path,
+) -> anyhow::Result<()> {
+    let archive_path = work.join("noslop.tar.gz");
+    fs::write(&archive_path, archive)?;
+
+    let actual = sha256(&archive_path)?;
+    if expected.is_empty() || expected != actual {
+        anyhow::bail!("Checksum mismatch (expected '{expected}', got '{actual}'); not updating");
+    }
+
+    let status = Command::new("tar").arg("xzf").arg(&archive_path).current_dir(work).status()?;
+    if !status.success() {
+        anyhow::bail!("Failed to extract release archive");
+    }
+    let staged = work.join("noslop");
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&staged, fs::Permissions::from_mode(0o755))?;
+    }
+
+    // Same directory as the target, so the rename is atomic
+    fs::rename(&staged, current)?;
+    Ok(())
+}
+
+fn download(url: &str) -> anyhow::Result<Vec<u8>> {
+    let mut bytes = Vec::new();
+    ureq::get(url)
+        .set("User-Agent", "noslop-self-update")
+        .timeout(Duration::from_mins(1))
+        .call()?
+        .into_reader()
+        .read_to_end(&mut bytes)?;
+    Ok(bytes)
+}
+
+/// sha256 via the platform tool (`sha256sum`, or `shasum -a 256` on macOS)
+fn sha256(path: &Path) -> anyhow::Result<String> {
+    let output = Command::new("sha256sum")
+        .arg(path)
+        .output()
+        .or_else(|_| Command::new("shasum").args(["-a", "256"]).arg(path).output())?;
+    if !output.status.success() {
+        anyhow::bail!("Failed to compute sha256 of {}", path.display());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .split_whitespace()
+        .next()
+        .unwrap_or_default()
+        .to_string())
+}
@@ -23,7 +23,7 @@ pub const CACHE_FILE: &str = ".noslop/remote-checks.json";
 const CACHE_TTL_SECS: u64 = 300;
 
 /// Default env var holding the repo token
-const DEFAULT_TOKEN_ENV: &str = "NOSLOP_CLOUD_TOKEN";
+pub const DEFAULT_TOKEN_ENV: &str = "NOSLOP_CLOUD_TOKEN";
 
 /// One check as distributed by the cloud (mirrors the API contract)
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -5,15 +5,37 @@
 //! reads it to compute fire rates and the no-action join locally; the
 //! future CI Action aggregates the team view from check-run payloads
 //! instead.
+//!
+//! Opted-in repos also get `.noslop/metrics.jsonl`: one line per command
+//! invocation, read only by `noslop metrics report`.
+//!
+//! Bypass detection keeps two more: `.noslop/last-gate` (the staged tree
+//! the pre-commit gate last passed) and `.noslop/bypasses.jsonl` (commits
+//! that landed without it, surfaced by `noslop status`).
+//!
+//! Warnings with a deadline that commits leave unanswered are kept in
+//! `.noslop/debts.json` until a later commit answers them.
 
 use std::fs::OpenOptions;
 use std::io::Write;
 
 use crate::adapters::git::state_path;
-use crate::core::models::CheckFireEvent;
+use crate::core::models::{AttestationDebt, BypassEvent, CheckFireEvent, CommandMetric};
 
 const EVENTS_PATH: &str = ".noslop/events.jsonl";
 
+/// Local command metrics log (opt-in via `[metrics] enabled = true`)
+const METRICS_PATH: &str = ".noslop/metrics.jsonl";
+
+/// Staged tree the pre-commit gate last passed
+const GATE_PATH: &str = ".noslop/last-gate";
+
+/// Commits detected as having skipped the gate
+const BYPASSES_PATH: &str = ".noslop/bypasses.jsonl";
+
+/// Unanswered warnings awaiting attestation
+const DEBTS_PATH: &str = ".noslop/debts.json";
+
 /// Append fire events to the local telemetry log.
 ///
 /// # Errors
@@ -51,3 +73,124 @@ pub fn load_events() -> anyhow::Result<Vec<CheckFireEvent>> {
         .filter_map(|l| serde_json::from_str(l).ok())
         .collect())
 }
+
+/// Append one command metric to the local metrics log.
+///
+/// # Errors
+///
+/// Returns an error if the log cannot be written.
+pub fn append_metric(metric: &CommandMetric) -> anyhow::Result<()> {
+    let path = state_path(METRICS_PATH);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", serde_json::to_string(metric)?)?;
+    Ok(())
+}
+
+/// Load all command metrics, skipping unparsable lines.
+///
+/// # Errors
+///
+/// Returns an error only if an existing log cannot be read.
+pub fn load_metrics() -> anyhow::Result<Vec<CommandMetric>> {
+    let path = state_path(METRICS_PATH);
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let content = std::fs::read_to_string(path)?;
+    Ok(content
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .filter_map(|l| serde_json::from_str(l).ok())
+        .collect())
+}
+
+/// Remember that the pre-commit gate passed for `tree_oid`.
+///
+/// # Errors
+///
+/// Returns an error if the marker cannot be written.
+pub fn record_gate(tree_oid: &str) -> anyhow::Result<()> {
+    let path = state_path(GATE_PATH);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, format!("{tree_oid}\n"))?;
+    Ok(())
+}
+
+/// The staged tree the pre-commit gate last passed, if any
+#[must_use]
+pub fn last_gate() -> Option<String> {
+    let content = std::fs::read_to_string(state_path(GATE_PATH)).ok()?;
+    Some(content.trim().to_string()).filter(|oid| !oid.is_empty())
+}
+
+/// Append one bypass event to the local bypass log.
+///
+/// # Errors
+///
+/// Returns an error if the log cannot be written.
+pub fn append_bypass(event: &BypassEvent) -> anyhow::Result<()> {
+    let path = state_path(BYPASSES_PATH);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", serde_json::to_string(event)?)?;
+    Ok(())
+}
+
+/// Load all bypass events, skipping unparsable lines.
+///
+/// # Errors
+///
+/// Returns an error only if an existing log cannot be read.
+pub fn load_bypasses() -> anyhow::Result<Vec<BypassEvent>> {
+    let path = state_path(BYPASSES_PATH);
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let content = std::fs::read_to_string(path)?;
+    Ok(content
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .filter_map(|l| serde_json::from_str(l).ok())
+        .collect())
+}
+
+/// Load the outstanding attestation debts.
+///
+/// # Errors
+///
+/// Returns an error if an existing debts file cannot be read or parsed.
+pub fn load_debts() -> anyhow::Result<Vec<AttestationDebt>> {
+    let path = state_path(DEBTS_PATH);
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    Ok(serde_json::from_str(&std::fs::read_to_string(path)?)?)
+}
+
+/// Replace the outstanding attestation debts, removing the file when none
+/// remain.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be written or removed.
+pub fn save_debts(debts: &[AttestationDebt]) -> anyhow::Result<()> {
+    let path = state_path(DEBTS_PATH);
+    if debts.is_empty() {
+        if path.exists() {
+            std::fs::remove_file(path)?;
+        }
+        return Ok(());
+    }
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, serde_json::to_string_pretty(debts)?)?;
+    Ok(())
+}
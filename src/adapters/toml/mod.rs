@@ -5,13 +5,22 @@
 //! - [`parser`] - Read and deserialize .noslop.toml files
 //! - [`writer`] - Create and modify .noslop.toml files
 //! - [`repository`] - `CheckRepository` implementation
+//! - [`templates`] - Bundled check templates
 
 pub mod parser;
 pub mod repository;
+pub mod templates;
 pub mod writer;
 
 pub use parser::{
-    CheckEntry, NoslopFile, ProjectConfig, RemoteConfig, find_noslop_files, load_file,
+    AckConfig, CheckEntry, ConfigDiscovery, GitConfig, HooksConfig, MetricsConfig, NoslopFile,
+    OwnerRule, PendingConfig, ProjectConfig, Ramp, RampPhase, RemindersConfig, RemoteConfig,
+    ReviewsConfig, SeverityOverride, When, find_noslop_files, find_noslop_files_within, load_file,
+    load_file_with, parse_file,
 };
 pub use repository::TomlCheckRepository;
-pub use writer::{add_check, format_noslop_file, generate_prefix_from_repo};
+pub use templates::{TEMPLATES, Template, find_template};
+pub use writer::{
+    add_check, add_checks, format_check, format_noslop_file, generate_prefix_from_repo,
+    toml_pattern,
+};
@@ -1,15 +1,46 @@
 //! TOML parser for .noslop.toml files
 //!
 //! Handles reading and deserializing noslop configuration files.
+//!
+//! A file may split its checks out with `include = ["noslop/security.toml"]`
+//! (paths relative to the including file). Included checks are merged into
+//! the including file's list, evaluated from its directory, and keep their
+//! defining file as [`CheckEntry::origin`].
+//!
+//! Discovery walks up from a path to the repository root; a file with
+//! `root = true` stops it there, so configs in parent directories (e.g. a
+//! home directory) are never picked up.
 
+use std::collections::BTreeMap;
+use std::collections::HashMap;
 use std::fs;
-use std::path::{Path, PathBuf};
+use std::path::{Component, Path, PathBuf};
 
-use serde::Deserialize;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use crate::core::models::{
+    ChangeClass, ChangeKind, CommitCondition, Severity, SubmoduleMode, TargetKind,
+};
+use crate::core::services::ack_policy::AckRequirement;
+use crate::core::services::{composite_patterns, matches_target};
 
 /// A .noslop.toml file structure
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Default, Deserialize)]
 pub struct NoslopFile {
+    /// Stop discovery here: configs in parent directories do not apply
+    #[serde(default)]
+    pub root: bool,
+
+    /// Files whose `[[check]]` entries are merged into this one
+    #[serde(default)]
+    pub include: Vec<String>,
+
+    /// Paths holding third-party or generated code, beyond the built-in
+    /// heuristics (repo-root config; patterns like check targets)
+    #[serde(default)]
+    pub vendored: Vec<String>,
+
     /// Project configuration
     #[serde(default)]
     pub project: ProjectConfig,
@@ -22,6 +53,39 @@ pub struct NoslopFile {
     #[serde(default)]
     pub remote: RemoteConfig,
 
+    /// Local usage metrics opt-in
+    #[serde(default)]
+    pub metrics: MetricsConfig,
+
+    /// Post-commit reminders about unacknowledged checks
+    #[serde(default)]
+    pub reminders: RemindersConfig,
+
+    /// `.noslop/pending.md` opt-in
+    #[serde(default)]
+    pub pending: PendingConfig,
+
+    /// Git change discovery
+    #[serde(default)]
+    pub git: GitConfig,
+
+    /// Ack message requirements per severity
+    #[serde(default)]
+    pub ack: AckConfig,
+
+    /// Scripts run before and after the check pipeline
+    #[serde(default)]
+    pub hooks: HooksConfig,
+
+    /// Command aliases (repo-root config): `ship = ["check --ci", "!git push"]`
+    /// runs each step in order as `noslop ship`; `!` steps go to `sh -c`
+    #[serde(default)]
+    pub alias: BTreeMap<String, Vec<String>>,
+
+    /// Which external review approvals answer which checks
+    #[serde(default)]
+    pub reviews: ReviewsConfig,
+
     /// Checks in this file
     #[serde(default, rename = "check")]
     pub checks: Vec<CheckEntry>,
@@ -32,7 +96,7 @@ pub struct NoslopFile {
 /// Absence simply means local-only — no compat shim needed. The token is
 /// never stored in the file; `token_env` names the environment variable
 /// holding the repo token.
-#[derive(Debug, Clone, Default, Deserialize)]
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
 #[serde(default)]
 pub struct RemoteConfig {
     /// Base URL of the noslop cloud API (e.g. `https://ingest.noslop.sh`)
@@ -42,8 +106,130 @@ pub struct RemoteConfig {
     pub token_env: Option<String>,
 }
 
+/// `[metrics]` configuration: opt-in local command metrics
+///
+/// Off by default. When enabled, every invocation appends a line to
+/// `.noslop/metrics.jsonl`; nothing leaves the clone.
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct MetricsConfig {
+    /// Record command invocations, durations, and outcomes
+    pub enabled: bool,
+}
+
+/// `[pending]` configuration: opt-in `.noslop/pending.md`
+///
+/// Off by default. When enabled, a blocked pre-commit run writes the
+/// blocking checks, their files, and the ack commands to answer them to
+/// `.noslop/pending.md`, for editors and agents watching the workspace; a
+/// passing run removes it.
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct PendingConfig {
+    /// Write the file
+    pub enabled: bool,
+}
+
+/// `[reminders]` configuration: post-commit nudges (on by default)
+///
+/// After a commit that passed the gate with checks still unanswered
+/// (warnings, or a human's blocking checks), the post-commit hook lists
+/// them so they can be acknowledged before pushing.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
+#[serde(default)]
+pub struct RemindersConfig {
+    /// Print the reminder
+    pub enabled: bool,
+}
+
+impl Default for RemindersConfig {
+    fn default() -> Self {
+        Self { enabled: true }
+    }
+}
+
+/// `[git]` configuration: how staged changes are discovered, and what a
+/// branch is compared against
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct GitConfig {
+    /// Submodule pointer changes: `path` (default), `skip`, or `recurse`
+    pub submodules: SubmoduleMode,
+    /// Branch that branches merge into, when it is not the remote's default
+    pub base_branch: Option<String>,
+    /// Per-branch overrides of `base_branch`: branch glob to base branch
+    /// (`"release/*" = "release"`); the longest matching glob wins
+    pub base_branches: BTreeMap<String, String>,
+}
+
+/// `[reviews]` configuration: approvals `noslop import-reviews` turns into
+/// acks
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct ReviewsConfig {
+    /// Gerrit server to read changes from (e.g. `https://review.example.com`)
+    pub gerrit_url: Option<String>,
+    /// Approval to the check IDs (or groups) it answers: `"@login"`,
+    /// `"@org/team"`, or a review label (`"Security-Review" = ["SEC-1"]`)
+    pub labels: BTreeMap<String, Vec<String>>,
+}
+
+/// `[ack]` configuration: what ack messages must contain, per severity
+/// (`[ack.<severity>]`), and where acks may be written
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct AckConfig {
+    /// Gate in the commit-msg hook, so `[ack ID: message]` lines in the
+    /// commit message count
+    pub in_message: bool,
+    /// Requirements for acks on info checks
+    pub info: AckRequirement,
+    /// Requirements for acks on warn checks
+    pub warn: AckRequirement,
+    /// Requirements for acks on block checks
+    pub block: AckRequirement,
+}
+
+impl AckConfig {
+    /// Requirement for acks on a check of `severity`
+    #[must_use]
+    pub const fn for_severity(&self, severity: Severity) -> &AckRequirement {
+        match severity {
+            Severity::Info => &self.info,
+            Severity::Warn => &self.warn,
+            Severity::Block => &self.block,
+        }
+    }
+}
+
+/// `[hooks]` configuration: scripts around the check pipeline
+///
+/// Each runs through `sh -c` from the repository root; its output is
+/// captured into the check report. A non-zero exit is reported at
+/// `on_failure` severity: `block` fails gated runs like a blocking check.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub struct HooksConfig {
+    /// Runs before checks are evaluated (e.g. `./scripts/gen-manifest.sh`)
+    pub pre_check: Option<String>,
+    /// Runs after checks are evaluated; sees `NOSLOP_CHECK_PASSED`
+    pub post_check: Option<String>,
+    /// Severity of a failing script: info, warn (default), block
+    pub on_failure: Severity,
+}
+
+impl Default for HooksConfig {
+    fn default() -> Self {
+        Self {
+            pre_check: None,
+            post_check: None,
+            on_failure: Severity::Warn,
+        }
+    }
+}
+
 /// `[discover]` configuration
-#[derive(Debug, Default, Deserialize)]
+#[derive(Debug, Default, Deserialize, Serialize)]
 #[serde(default)]
 pub struct DiscoverConfig {
     /// Command that runs mining prompts (must read the prompt on stdin and
@@ -52,7 +238,7 @@ pub struct DiscoverConfig {
 }
 
 /// Project-level configuration
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 #[serde(default)]
 pub struct ProjectConfig {
     /// 3-letter prefix for check IDs (e.g., "NSL" for noslop-123)
@@ -79,23 +265,308 @@ pub struct CheckEntry {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub id: Option<String>,
 
-    /// Target pattern (glob or path)
+    /// Target pattern (glob or path), or a list of them with `!`
+    /// exclusions (kept as its array text); unused by commit checks
+    #[serde(default, deserialize_with = "pattern_or_list")]
     pub target: String,
 
+    /// `file` (default) or `commit`
+    #[serde(default)]
+    pub target_kind: TargetKind,
+
+    /// Commit checks: `min_files`, `min_added_lines`, `merge`
+    #[serde(flatten)]
+    pub commit: CommitCondition,
+
     /// The check message
     pub message: String,
 
-    /// Severity: info, warn, block
-    #[serde(default = "default_severity")]
-    pub severity: String,
+    /// Severity: info, warn, block (default block)
+    #[serde(default)]
+    pub severity: Severity,
 
     /// Optional tags
     #[serde(default)]
     pub tags: Vec<String>,
+
+    /// Change kinds to fire on (default: add, modify)
+    #[serde(default)]
+    pub on: Vec<ChangeKind>,
+
+    /// Agent acks need a human co-sign (`noslop approve`) to count
+    #[serde(default)]
+    pub requires_human: bool,
+
+    /// Ack group: one ack of the group name satisfies every member
+    #[serde(default)]
+    pub group: Option<String>,
+
+    /// Who to contact when the check blocks (e.g. "@team-payments")
+    #[serde(default)]
+    pub owner: Option<String>,
+
+    /// Per-path severities (`[[check.override]]`); the first whose scope
+    /// matches a file wins there
+    #[serde(default, rename = "override")]
+    pub overrides: Vec<SeverityOverride>,
+
+    /// Turned off (`noslop check disable`): kept with its ID, never fired
+    #[serde(default)]
+    pub disabled: bool,
+
+    /// Fire with full severity on vendored paths too (by default a match
+    /// there only warns)
+    #[serde(default)]
+    pub include_vendored: bool,
+
+    /// How CODEOWNERS ownership of the matched file changes the check
+    /// (`codeowners = ["ack", "warn"]`)
+    #[serde(default)]
+    pub codeowners: Vec<OwnerRule>,
+
+    /// Rollout schedule: the check warns instead of blocking until its ramp
+    /// ends (`ramp = { warn_until = "2025-09-01" }` or `{ warn_days = 14 }`)
+    #[serde(default)]
+    pub ramp: Option<Ramp>,
+
+    /// Content every matched file must have (a regex): the check only
+    /// fires on files where it finds no match
+    #[serde(default)]
+    pub must_match: Option<String>,
+
+    /// Days a warning left unanswered in a commit may stay unattested;
+    /// once they pass, the next commit blocks until it is answered
+    #[serde(default)]
+    pub attest_within_days: Option<u32>,
+
+    /// Conditions on the diff itself (`when.change_kind = "signature-change"`)
+    #[serde(default)]
+    pub when: When,
+
+    /// The included file that defines this entry (`None` when it is
+    /// defined in the file it was loaded from)
+    #[serde(skip)]
+    pub origin: Option<PathBuf>,
+}
+
+impl CheckEntry {
+    /// Severity where the check matched `file`: the first override whose
+    /// scope matches it (resolved like the target, from `base_dir`), else
+    /// the check's own
+    #[must_use]
+    pub fn severity_for(&self, file: &str, base_dir: &Path, cwd: &Path) -> Severity {
+        self.overrides
+            .iter()
+            .find(|o| matches_target(&o.scope, file, base_dir, cwd))
+            .map_or(self.severity, |o| o.severity)
+    }
+
+    /// What changed from `old` to this definition of the same check, one
+    /// line per field (empty when nothing did)
+    #[must_use]
+    pub fn changes_from(&self, old: &Self) -> Vec<String> {
+        let mut changes = Vec::new();
+        let mut field = |name: &str, before: String, after: String| {
+            if before != after {
+                changes.push(format!("{name}: {before} -> {after}"));
+            }
+        };
+        field("target", old.describe_target(), self.describe_target());
+        field("message", format!("{:?}", old.message), format!("{:?}", self.message));
+        field("severity", old.severity.to_string(), self.severity.to_string());
+        field("on", format!("{:?}", old.on), format!("{:?}", self.on));
+        field("tags", format!("{:?}", old.tags), format!("{:?}", self.tags));
+        field("group", format!("{:?}", old.group), format!("{:?}", self.group));
+        field("owner", format!("{:?}", old.owner), format!("{:?}", self.owner));
+        field(
+            "requires_human",
+            old.requires_human.to_string(),
+            self.requires_human.to_string(),
+        );
+        field("disabled", old.disabled.to_string(), self.disabled.to_string());
+        field(
+            "include_vendored",
+            old.include_vendored.to_string(),
+            self.include_vendored.to_string(),
+        );
+        field("codeowners", format!("{:?}", old.codeowners), format!("{:?}", self.codeowners));
+        field("ramp", format!("{:?}", old.ramp), format!("{:?}", self.ramp));
+        field("must_match", format!("{:?}", old.must_match), format!("{:?}", self.must_match));
+        field("when", format!("{:?}", old.when), format!("{:?}", self.when));
+        field(
+            "attest_within_days",
+            format!("{:?}", old.attest_within_days),
+            format!("{:?}", self.attest_within_days),
+        );
+        let overrides = |e: &Self| {
+            e.overrides
+                .iter()
+                .map(|o| format!("{}={}", o.scope, o.severity))
+                .collect::<Vec<_>>()
+        };
+        field("overrides", format!("{:?}", overrides(old)), format!("{:?}", overrides(self)));
+        changes
+    }
+
+    /// Whether `content` already has what `must_match` asks for, so the
+    /// check need not fire on it (`false` without `must_match`, or when
+    /// the file could not be read)
+    #[must_use]
+    pub fn content_satisfies(&self, content: Option<&str>) -> bool {
+        let (Some(pattern), Some(content)) = (&self.must_match, content) else {
+            return false;
+        };
+        regex::Regex::new(pattern).is_ok_and(|re| re.is_match(content))
+    }
+
+    /// The target pattern, or the commit condition of a commit check
+    #[must_use]
+    pub fn describe_target(&self) -> String {
+        match self.target_kind {
+            TargetKind::File => self.target.clone(),
+            TargetKind::Commit => self.commit.describe(),
+        }
+    }
+}
+
+/// `[[check.override]]`: a different severity for part of a check's target
+#[derive(Debug, Clone, Deserialize)]
+pub struct SeverityOverride {
+    /// Paths the override covers, as a target pattern (e.g. `examples/**`)
+    /// or list
+    #[serde(deserialize_with = "pattern_or_list")]
+    pub scope: String,
+    /// Severity for those paths
+    pub severity: Severity,
+}
+
+/// `ramp = { ... }`: when a blocking check starts to block
+///
+/// Until then it fires as a warning, so a stricter rule can roll out
+/// without stopping everyone the day it lands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Ramp {
+    /// Warn before this date, block from it on
+    #[serde(default)]
+    pub warn_until: Option<NaiveDate>,
+    /// Warn for this many days after the check was first committed
+    #[serde(default)]
+    pub warn_days: Option<u32>,
 }
 
-fn default_severity() -> String {
-    "block".to_string()
+/// `when = { ... }`: what the diff of a matched file must do for the check
+/// to fire
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct When {
+    /// Hunk classes, one or a list: the check fires only on a file whose
+    /// diff has one of them (empty = any change)
+    #[serde(default, deserialize_with = "class_or_list")]
+    pub change_kind: Vec<ChangeClass>,
+
+    /// Fire only on a dependency manifest or lockfile whose dependencies
+    /// changed (`Cargo.toml`, `Cargo.lock`, `package.json`,
+    /// `pnpm-lock.yaml`, `go.mod`, ...)
+    #[serde(default)]
+    pub dependency_change: bool,
+}
+
+impl When {
+    /// TOML value of `when.change_kind`: a string for one class, a list
+    /// otherwise
+    #[must_use]
+    pub fn change_kind_toml(&self) -> String {
+        match self.change_kind.as_slice() {
+            [one] => format!("\"{one}\""),
+            many => {
+                let names: Vec<String> = many.iter().map(ToString::to_string).collect();
+                format!("{names:?}")
+            },
+        }
+    }
+}
+
+/// A CODEOWNERS rule a check opts into
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OwnerRule {
+    /// Only an ack made or approved by an owner of the matched file counts
+    Ack,
+    /// Only warn when the committer owns the matched file
+    Warn,
+}
+
+impl std::fmt::Display for OwnerRule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Ack => write!(f, "ack"),
+            Self::Warn => write!(f, "warn"),
+        }
+    }
+}
+
+/// Where a ramped check stands on a given day
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RampPhase {
+    /// Still rolling out: warns until the date
+    Warn(NaiveDate),
+    /// Blocking since the date
+    Block(NaiveDate),
+}
+
+impl Ramp {
+    /// The phase on `today`, for a check first committed on `introduced`
+    /// (`None` while uncommitted: its `warn_days` have not started).
+    /// `None` when the ramp sets neither field.
+    #[must_use]
+    pub fn phase(&self, introduced: Option<NaiveDate>, today: NaiveDate) -> Option<RampPhase> {
+        let blocks_from = self.warn_until.or_else(|| {
+            let days = self.warn_days?;
+            Some(introduced.unwrap_or(today) + chrono::Days::new(u64::from(days)))
+        })?;
+        Some(if today < blocks_from {
+            RampPhase::Warn(blocks_from)
+        } else {
+            RampPhase::Block(blocks_from)
+        })
+    }
+
+    /// `severity` on `today`: a block is a warning while the ramp runs
+    #[must_use]
+    pub fn apply(
+        &self,
+        severity: Severity,
+        introduced: Option<NaiveDate>,
+        today: NaiveDate,
+    ) -> Severity {
+        match self.phase(introduced, today) {
+            Some(RampPhase::Warn(_)) if severity == Severity::Block => Severity::Warn,
+            _ => severity,
+        }
+    }
+
+    /// TOML inline table for this ramp
+    #[must_use]
+    pub fn to_toml(&self) -> String {
+        let mut fields = Vec::new();
+        if let Some(date) = self.warn_until {
+            fields.push(format!("warn_until = \"{date}\""));
+        }
+        if let Some(days) = self.warn_days {
+            fields.push(format!("warn_days = {days}"));
+        }
+        format!("{{ {} }}", fields.join(", "))
+    }
+}
+
+impl std::fmt::Display for RampPhase {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Warn(date) => write!(f, "ramping: warns until {date}, blocks from then"),
+            Self::Block(date) => write!(f, "ramp ended: blocking since {date}"),
+        }
+    }
 }
 
 /// Find all .noslop.toml files from path up to repo root
@@ -112,7 +583,11 @@ pub fn find_noslop_files(from: &Path) -> Vec<PathBuf> {
     loop {
         let noslop_file = current.join(".noslop.toml");
         if noslop_file.exists() {
+            let is_root = fs::read_to_string(&noslop_file).is_ok_and(|c| is_root_marker(&c));
             files.push(noslop_file);
+            if is_root {
+                break;
+            }
         }
 
         // Stop at repo root (.git) or filesystem root
@@ -133,13 +608,263 @@ pub fn find_noslop_files(from: &Path) -> Vec<PathBuf> {
     files
 }
 
+/// Find all .noslop.toml files from path up to `root`
+///
+/// Unlike [`find_noslop_files`], nested `.git` markers below `root` are
+/// walked past, so files inside a submodule still pick up the
+/// superproject's checks. Paths outside `root` fall back to
+/// [`find_noslop_files`].
+#[must_use]
+pub fn find_noslop_files_within(from: &Path, root: &Path) -> Vec<PathBuf> {
+    let read = |p: &Path| Ok(fs::read_to_string(p)?);
+    ConfigDiscovery::new(root, &read).files_for(from)
+}
+
+/// Whether config `content` sets `root = true`
+fn is_root_marker(content: &str) -> bool {
+    #[derive(Deserialize)]
+    struct Marker {
+        #[serde(default)]
+        root: bool,
+    }
+    toml::from_str::<Marker>(content).is_ok_and(|m| m.root)
+}
+
+/// Memoized `.noslop.toml` discovery and loading below a repository root
+///
+/// Changed files share directories, so across one run each directory is
+/// looked up once and each config file parsed once, instead of once per
+/// changed file. Files are read through `read`; one that fails to read is
+/// taken as absent.
+pub struct ConfigDiscovery<'a> {
+    root: PathBuf,
+    read: &'a dyn Fn(&Path) -> anyhow::Result<String>,
+    dirs: HashMap<PathBuf, Vec<PathBuf>>,
+    files: HashMap<PathBuf, NoslopFile>,
+}
+
+impl std::fmt::Debug for ConfigDiscovery<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ConfigDiscovery")
+            .field("root", &self.root)
+            .field("dirs", &self.dirs)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a> ConfigDiscovery<'a> {
+    /// Discovery below `root`, reading files through `read`
+    #[must_use]
+    pub fn new(root: &Path, read: &'a dyn Fn(&Path) -> anyhow::Result<String>) -> Self {
+        Self {
+            root: root.to_path_buf(),
+            read,
+            dirs: HashMap::new(),
+            files: HashMap::new(),
+        }
+    }
+
+    /// Config files governing `from`, root first, as
+    /// [`find_noslop_files_within`] finds them
+    pub fn files_for(&mut self, from: &Path) -> Vec<PathBuf> {
+        if !from.starts_with(&self.root) {
+            return find_noslop_files(from);
+        }
+        self.in_dir(from)
+    }
+
+    /// Config files from `dir` up to the root or the nearest root marker
+    fn in_dir(&mut self, dir: &Path) -> Vec<PathBuf> {
+        if let Some(files) = self.dirs.get(dir) {
+            return files.clone();
+        }
+        let config = dir.join(".noslop.toml");
+        let content = (self.read)(&config).ok();
+        let mut files = match dir.parent() {
+            _ if content.as_deref().is_some_and(is_root_marker) => Vec::new(),
+            Some(parent) if dir != self.root => self.in_dir(parent),
+            _ => Vec::new(),
+        };
+        if content.is_some() {
+            files.push(config);
+        }
+        self.dirs.insert(dir.to_path_buf(), files.clone());
+        files
+    }
+
+    /// Load the config file at `path` (with its includes), once
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or parsed.
+    pub fn load(&mut self, path: &Path) -> anyhow::Result<&NoslopFile> {
+        if !self.files.contains_key(path) {
+            let file = load_file_with(path, self.read)?;
+            self.files.insert(path.to_path_buf(), file);
+        }
+        Ok(&self.files[path])
+    }
+}
+
 /// Load checks from a .noslop.toml file
 ///
 /// # Errors
 ///
 /// Returns an error if the file cannot be read or parsed.
 pub fn load_file(path: &Path) -> anyhow::Result<NoslopFile> {
-    let content = fs::read_to_string(path)?;
-    let file: NoslopFile = toml::from_str(&content)?;
+    load_file_with(path, &|p| Ok(fs::read_to_string(p)?))
+}
+
+/// Load a .noslop.toml file and its includes through `read`, e.g. from a
+/// past commit's tree instead of the working tree
+///
+/// # Errors
+///
+/// Returns an error if a file cannot be read or parsed, or the includes
+/// form a cycle.
+pub fn load_file_with(
+    path: &Path,
+    read: &dyn Fn(&Path) -> anyhow::Result<String>,
+) -> anyhow::Result<NoslopFile> {
+    let path = normalize(path);
+    let mut file = parse_file(&read(&path)?)?;
+    let mut stack = vec![path.clone()];
+    let included = included_checks(&path, &file.include, read, &mut stack)?;
+    file.checks.extend(included);
+    Ok(file)
+}
+
+/// Checks from `includes` (relative to `including`) and their own
+/// includes, depth first; `stack` is the include chain, for cycles
+fn included_checks(
+    including: &Path,
+    includes: &[String],
+    read: &dyn Fn(&Path) -> anyhow::Result<String>,
+    stack: &mut Vec<PathBuf>,
+) -> anyhow::Result<Vec<CheckEntry>> {
+    let dir = including.parent().unwrap_or_else(|| Path::new(""));
+    let mut checks = Vec::new();
+    for include in includes {
+        let path = normalize(&dir.join(include));
+        if stack.contains(&path) {
+            let chain: Vec<String> =
+                stack.iter().chain([&path]).map(|p| p.display().to_string()).collect();
+            anyhow::bail!("Include cycle: {}", chain.join(" -> "));
+        }
+        let content = read(&path).map_err(|e| {
+            anyhow::anyhow!("{} includes {}: {e}", including.display(), path.display())
+        })?;
+        let file = parse_file(&content)?;
+
+        stack.push(path.clone());
+        let nested = included_checks(&path, &file.include, read, stack)?;
+        stack.pop();
+
+        checks.extend(file.checks.into_iter().map(|mut entry| {
+            entry.origin = Some(path.clone());
+            entry
+        }));
+        checks.extend(nested);
+    }
+    Ok(checks)
+}
+
+/// Resolve `.` and `..` lexically, so the same file is recognized however
+/// it was reached
+fn normalize(path: &Path) -> PathBuf {
+    let mut out = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {},
+            Component::ParentDir if out.file_name().is_some() => {
+                out.pop();
+            },
+            other => out.push(other),
+        }
+    }
+    out
+}
+
+/// A target written as one pattern or as a list of them
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum PatternOrList {
+    One(String),
+    List(Vec<String>),
+}
+
+/// Read a target or scope; a list is kept as its JSON array text, which
+/// [`composite_patterns`] reads back
+fn pattern_or_list<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<String, D::Error> {
+    match PatternOrList::deserialize(deserializer)? {
+        PatternOrList::One(pattern) => Ok(pattern),
+        PatternOrList::List(patterns) => {
+            serde_json::to_string(&patterns).map_err(serde::de::Error::custom)
+        },
+    }
+}
+
+/// One hunk class or a list of them
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ClassOrList {
+    One(ChangeClass),
+    List(Vec<ChangeClass>),
+}
+
+fn class_or_list<'de, D: serde::Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Vec<ChangeClass>, D::Error> {
+    Ok(match ClassOrList::deserialize(deserializer)? {
+        ClassOrList::One(class) => vec![class],
+        ClassOrList::List(classes) => classes,
+    })
+}
+
+/// Why a composite target or scope is unusable, `None` when it is fine or
+/// not a list
+fn invalid_patterns(target: &str) -> Option<String> {
+    let patterns = composite_patterns(target)?;
+    if patterns.is_empty() {
+        return Some("is an empty list".to_string());
+    }
+    if let Some(blank) = patterns.iter().find(|p| p.trim_start_matches('!').trim().is_empty()) {
+        return Some(format!("has an empty pattern ({blank:?})"));
+    }
+    None
+}
+
+/// Parse `.noslop.toml` content (e.g. read from a past commit)
+///
+/// # Errors
+///
+/// Returns an error if the content is not a valid config.
+pub fn parse_file(content: &str) -> anyhow::Result<NoslopFile> {
+    let file: NoslopFile = toml::from_str(content)?;
+    for entry in &file.checks {
+        let name = entry.id.as_deref().unwrap_or(&entry.message);
+        match entry.target_kind {
+            TargetKind::File if entry.target.is_empty() => {
+                anyhow::bail!("Check '{name}' has no target")
+            },
+            TargetKind::Commit if entry.commit.is_empty() => {
+                anyhow::bail!("Commit check '{name}' needs min_files, min_added_lines, or merge")
+            },
+            _ => {},
+        }
+        if let Some(pattern) = &entry.must_match
+            && let Err(e) = regex::Regex::new(pattern)
+        {
+            anyhow::bail!("Check '{name}': must_match is not a valid regex: {e}");
+        }
+        if let Some(problem) = invalid_patterns(&entry.target) {
+            anyhow::bail!("Check '{name}': target {problem}");
+        }
+        for o in &entry.overrides {
+            if let Some(problem) = invalid_patterns(&o.scope) {
+                anyhow::bail!("Check '{name}': override scope {problem}");
+            }
+        }
+    }
     Ok(file)
 }
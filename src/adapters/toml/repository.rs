@@ -7,6 +7,7 @@ use std::fs;
 use crate::core::models::{Check, Severity};
 use crate::core::ports::CheckRepository;
 use crate::core::services::matches_target;
+use crate::error::Error;
 
 use super::parser::{find_noslop_files, load_file};
 use super::writer::add_check;
@@ -30,13 +31,13 @@ impl TomlCheckRepository {
     /// # Errors
     ///
     /// Returns an error if the current directory cannot be determined.
-    pub fn current_dir() -> anyhow::Result<Self> {
+    pub fn current_dir() -> crate::Result<Self> {
         Ok(Self::new(std::env::current_dir()?))
     }
 }
 
 impl CheckRepository for TomlCheckRepository {
-    fn find_for_files(&self, files: &[String]) -> anyhow::Result<Vec<(Check, String)>> {
+    fn find_for_files(&self, files: &[String]) -> crate::Result<Vec<(Check, String)>> {
         let mut result = Vec::new();
 
         for file in files {
@@ -44,7 +45,7 @@ impl CheckRepository for TomlCheckRepository {
             let noslop_files = find_noslop_files(&file_path);
 
             for noslop_path in noslop_files {
-                let noslop_file = load_file(&noslop_path)?;
+                let noslop_file = load_file(&noslop_path).map_err(Error::config)?;
                 let noslop_dir = noslop_path.parent().unwrap_or(&self.base_dir);
 
                 for entry in &noslop_file.checks {
@@ -53,7 +54,7 @@ impl CheckRepository for TomlCheckRepository {
                             entry.id.clone(),
                             entry.target.clone(),
                             entry.message.clone(),
-                            entry.severity.parse().unwrap_or(Severity::Block),
+                            entry.severity,
                         );
                         result.push((check, file.clone()));
                     }
@@ -68,23 +69,23 @@ impl CheckRepository for TomlCheckRepository {
         Ok(result)
     }
 
-    fn add(&self, target: &str, message: &str, severity: Severity) -> anyhow::Result<String> {
-        add_check(target, message, &severity.to_string())
+    fn add(&self, target: &str, message: &str, severity: Severity) -> crate::Result<String> {
+        add_check(target, message, severity).map_err(Error::config)
     }
 
-    fn remove(&self, id: &str) -> anyhow::Result<()> {
+    fn remove(&self, id: &str) -> crate::Result<()> {
         let path = self.base_dir.join(".noslop.toml");
         if !path.exists() {
-            anyhow::bail!("No .noslop.toml file found");
+            return Err(Error::config("No .noslop.toml file found"));
         }
 
-        let mut file = load_file(&path)?;
+        let mut file = load_file(&path).map_err(Error::config)?;
         let initial_len = file.checks.len();
 
         file.checks.retain(|c| c.id.as_deref() != Some(id));
 
         if file.checks.len() == initial_len {
-            anyhow::bail!("Check not found: {id}");
+            return Err(Error::config(format!("Check not found: {id}")));
         }
 
         let content = super::writer::format_noslop_file(&file);
@@ -93,16 +94,16 @@ impl CheckRepository for TomlCheckRepository {
         Ok(())
     }
 
-    fn list(&self) -> anyhow::Result<Vec<Check>> {
+    fn list(&self) -> crate::Result<Vec<Check>> {
         self.list_filtered(None)
     }
 
-    fn list_filtered(&self, target_filter: Option<&str>) -> anyhow::Result<Vec<Check>> {
+    fn list_filtered(&self, target_filter: Option<&str>) -> crate::Result<Vec<Check>> {
         let noslop_files = find_noslop_files(&self.base_dir);
         let mut checks = Vec::new();
 
         for noslop_path in noslop_files {
-            let noslop_file = load_file(&noslop_path)?;
+            let noslop_file = load_file(&noslop_path).map_err(Error::config)?;
 
             for entry in noslop_file.checks {
                 // Apply target filter if provided
@@ -112,12 +113,7 @@ impl CheckRepository for TomlCheckRepository {
                     continue;
                 }
 
-                let check = Check::new(
-                    entry.id,
-                    entry.target,
-                    entry.message,
-                    entry.severity.parse().unwrap_or(Severity::Block),
-                );
+                let check = Check::new(entry.id, entry.target, entry.message, entry.severity);
                 checks.push(check);
             }
         }
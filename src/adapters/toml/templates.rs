@@ -0,0 +1,50 @@
+//! Bundled check templates
+//!
+//! Ready-made `[[check]]` entries for common gates, added to a config with
+//! `noslop check template <name>`. Each template is plain `.noslop.toml`
+//! text, parsed like any other config, so it shows exactly what lands in
+//! the file.
+
+use super::parser::{CheckEntry, parse_file};
+
+/// A named set of checks
+#[derive(Debug, Clone, Copy)]
+pub struct Template {
+    /// Name to add it by
+    pub name: &'static str,
+    /// One line on what it gates
+    pub description: &'static str,
+    /// The `[[check]]` entries, without IDs
+    source: &'static str,
+}
+
+impl Template {
+    /// The template's checks
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the bundled source does not parse (a bug).
+    pub fn checks(&self) -> anyhow::Result<Vec<CheckEntry>> {
+        Ok(parse_file(self.source)?.checks)
+    }
+}
+
+/// Every bundled template
+pub const TEMPLATES: [Template; 1] = [Template {
+    name: "supply-chain",
+    description: "New dependencies require a supply-chain review attestation",
+    source: r#"
+[[check]]
+target = "*"
+message = "Supply-chain review done for the new dependencies: maintained, license ok, no known advisories?"
+severity = "block"
+tags = ["supply-chain"]
+when.dependency_change = true
+"#,
+}];
+
+/// The bundled template named `name`
+#[must_use]
+pub fn find_template(name: &str) -> Option<&'static Template> {
+    TEMPLATES.iter().find(|t| t.name == name)
+}
@@ -6,23 +6,81 @@ use std::fmt::Write;
 use std::fs;
 use std::path::Path;
 
-use super::parser::{CheckEntry, NoslopFile, ProjectConfig, load_file};
+use super::parser::{CheckEntry, NoslopFile, ProjectConfig, When, load_file};
+use crate::core::models::{CommitCondition, Severity, SubmoduleMode, TargetKind};
+use crate::core::services::ack_policy::AckRequirement;
+use crate::core::services::composite_patterns;
+
+/// A target or scope as a TOML value: a composite target is already an
+/// array, anything else is quoted
+#[must_use]
+pub fn toml_pattern(pattern: &str) -> String {
+    if composite_patterns(pattern).is_some() {
+        pattern.to_string()
+    } else {
+        format!("\"{pattern}\"")
+    }
+}
 
 /// Create or update a .noslop.toml file with a new check
 ///
 /// # Errors
 ///
 /// Returns an error if the file cannot be read or written.
-pub fn add_check(target: &str, message: &str, severity: &str) -> anyhow::Result<String> {
+pub fn add_check(target: &str, message: &str, severity: Severity) -> anyhow::Result<String> {
+    let entry = CheckEntry {
+        id: None,
+        target: target.to_string(),
+        target_kind: TargetKind::File,
+        commit: CommitCondition::default(),
+        message: message.to_string(),
+        severity,
+        tags: Vec::new(),
+        on: Vec::new(),
+        requires_human: false,
+        group: None,
+        owner: None,
+        overrides: Vec::new(),
+        disabled: false,
+        include_vendored: false,
+        codeowners: Vec::new(),
+        ramp: None,
+        must_match: None,
+        attest_within_days: None,
+        when: When::default(),
+        origin: None,
+    };
+    let ids = add_checks(vec![entry])?;
+    Ok(ids.into_iter().next().unwrap_or_default())
+}
+
+/// Append checks to ./.noslop.toml (created if missing), each with the
+/// next free `PREFIX-N` ID, and return the IDs
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read or written.
+pub fn add_checks(entries: Vec<CheckEntry>) -> anyhow::Result<Vec<String>> {
     let path = Path::new(".noslop.toml");
 
     let mut file = if path.exists() {
         load_file(path)?
     } else {
         NoslopFile {
+            root: false,
+            include: Vec::new(),
+            vendored: Vec::new(),
             project: ProjectConfig::default(),
             discover: super::parser::DiscoverConfig::default(),
             remote: super::parser::RemoteConfig::default(),
+            metrics: super::parser::MetricsConfig::default(),
+            reminders: super::parser::RemindersConfig::default(),
+            pending: super::parser::PendingConfig::default(),
+            git: super::parser::GitConfig::default(),
+            ack: super::parser::AckConfig::default(),
+            hooks: super::parser::HooksConfig::default(),
+            alias: std::collections::BTreeMap::new(),
+            reviews: super::parser::ReviewsConfig::default(),
             checks: Vec::new(),
         }
     };
@@ -39,24 +97,20 @@ pub fn add_check(target: &str, message: &str, severity: &str) -> anyhow::Result<
         .max()
         .map_or(1, |n| n + 1);
 
-    // Generate JIRA-style ID
-    let generated_id = format!("{}-{}", file.project.prefix, next_num);
-
-    let entry = CheckEntry {
-        id: Some(generated_id.clone()),
-        target: target.to_string(),
-        message: message.to_string(),
-        severity: severity.to_string(),
-        tags: Vec::new(),
-    };
-
-    file.checks.push(entry);
+    let mut ids = Vec::new();
+    for (num, mut entry) in (next_num..).zip(entries) {
+        // Generate JIRA-style ID
+        let generated_id = format!("{}-{num}", file.project.prefix);
+        entry.id = Some(generated_id.clone());
+        file.checks.push(entry);
+        ids.push(generated_id);
+    }
 
     // Write back
     let content = format_noslop_file(&file);
     fs::write(path, content)?;
 
-    Ok(generated_id)
+    Ok(ids)
 }
 
 /// Format a `NoslopFile` as TOML
@@ -65,6 +119,17 @@ pub fn format_noslop_file(file: &NoslopFile) -> String {
     let mut out = String::new();
     out.push_str("# noslop checks\n\n");
 
+    // Top-level keys must precede every table
+    if file.root {
+        out.push_str("root = true\n\n");
+    }
+    if !file.include.is_empty() {
+        let _ = writeln!(out, "include = {:?}\n", file.include);
+    }
+    if !file.vendored.is_empty() {
+        let _ = writeln!(out, "vendored = {:?}\n", file.vendored);
+    }
+
     // Add project config if prefix is not default
     if file.project.prefix != "CHK" {
         out.push_str("[project]\n");
@@ -72,6 +137,19 @@ pub fn format_noslop_file(file: &NoslopFile) -> String {
         out.push('\n');
     }
 
+    // Preserve the cloud binding across rewrites
+    let remote = &file.remote;
+    if remote.url.is_some() || remote.token_env.is_some() {
+        out.push_str("[remote]\n");
+        if let Some(url) = &remote.url {
+            let _ = writeln!(out, "url = {url:?}");
+        }
+        if let Some(env) = &remote.token_env {
+            let _ = writeln!(out, "token_env = {env:?}");
+        }
+        out.push('\n');
+    }
+
     // Preserve discover config across rewrites
     if let Some(runner) = &file.discover.runner {
         out.push_str("[discover]\n");
@@ -79,20 +157,176 @@ pub fn format_noslop_file(file: &NoslopFile) -> String {
         out.push('\n');
     }
 
-    for entry in &file.checks {
-        out.push_str("[[check]]\n");
-        if let Some(id) = &entry.id {
-            let _ = writeln!(out, "id = \"{id}\"");
+    // Preserve the metrics opt-in across rewrites
+    if file.metrics.enabled {
+        out.push_str("[metrics]\nenabled = true\n\n");
+    }
+
+    // Preserve the pending.md opt-in across rewrites
+    if file.pending.enabled {
+        out.push_str("[pending]\nenabled = true\n\n");
+    }
+
+    // Preserve the reminders opt-out across rewrites
+    if !file.reminders.enabled {
+        out.push_str("[reminders]\nenabled = false\n\n");
+    }
+
+    // Preserve submodule handling and base branches across rewrites
+    let git = &file.git;
+    if git.submodules != SubmoduleMode::Path || git.base_branch.is_some() {
+        out.push_str("[git]\n");
+        if git.submodules != SubmoduleMode::Path {
+            let _ = writeln!(out, "submodules = \"{}\"", git.submodules);
+        }
+        if let Some(base) = &git.base_branch {
+            let _ = writeln!(out, "base_branch = {base:?}");
+        }
+        out.push('\n');
+    }
+    if !git.base_branches.is_empty() {
+        out.push_str("[git.base_branches]\n");
+        for (branch, base) in &git.base_branches {
+            let _ = writeln!(out, "{branch:?} = {base:?}");
+        }
+        out.push('\n');
+    }
+
+    // Preserve pipeline scripts across rewrites
+    let hooks = &file.hooks;
+    if hooks.pre_check.is_some() || hooks.post_check.is_some() {
+        out.push_str("[hooks]\n");
+        if let Some(command) = &hooks.pre_check {
+            let _ = writeln!(out, "pre_check = {command:?}");
+        }
+        if let Some(command) = &hooks.post_check {
+            let _ = writeln!(out, "post_check = {command:?}");
         }
-        let _ = writeln!(out, "target = \"{}\"", entry.target);
-        let _ = writeln!(out, "message = \"{}\"", entry.message);
-        let _ = writeln!(out, "severity = \"{}\"", entry.severity);
-        if !entry.tags.is_empty() {
-            let _ = writeln!(out, "tags = {:?}", entry.tags);
+        let _ = writeln!(out, "on_failure = \"{}\"\n", hooks.on_failure);
+    }
+
+    // Preserve command aliases across rewrites
+    if !file.alias.is_empty() {
+        out.push_str("[alias]\n");
+        for (name, steps) in &file.alias {
+            let _ = writeln!(out, "{name} = {steps:?}");
+        }
+        out.push('\n');
+    }
+
+    // Preserve the review import mapping across rewrites
+    let reviews = &file.reviews;
+    if let Some(url) = &reviews.gerrit_url {
+        let _ = writeln!(out, "[reviews]\ngerrit_url = {url:?}\n");
+    }
+    if !reviews.labels.is_empty() {
+        out.push_str("[reviews.labels]\n");
+        for (label, checks) in &reviews.labels {
+            let _ = writeln!(out, "{label:?} = {checks:?}");
         }
         out.push('\n');
     }
 
+    // Preserve ack settings across rewrites
+    if file.ack.in_message {
+        out.push_str("[ack]\nin_message = true\n\n");
+    }
+    for severity in [Severity::Info, Severity::Warn, Severity::Block] {
+        let req = file.ack.for_severity(severity);
+        if *req != AckRequirement::default() {
+            let _ = writeln!(out, "[ack.{severity}]");
+            if req.min_length > 0 {
+                let _ = writeln!(out, "min_length = {}", req.min_length);
+            }
+            if req.require_reference {
+                out.push_str("require_reference = true\n");
+            }
+            out.push('\n');
+        }
+    }
+
+    // Included entries stay in the file that defines them
+    for entry in file.checks.iter().filter(|e| e.origin.is_none()) {
+        out.push_str(&format_check(entry));
+    }
+
+    out
+}
+
+/// Format one check as its `[[check]]` table, trailing blank line included
+#[must_use]
+pub fn format_check(entry: &CheckEntry) -> String {
+    let mut out = String::new();
+    out.push_str("[[check]]\n");
+    if let Some(id) = &entry.id {
+        let _ = writeln!(out, "id = \"{id}\"");
+    }
+    if entry.target_kind == TargetKind::Commit {
+        out.push_str("target_kind = \"commit\"\n");
+        if let Some(min) = entry.commit.min_files {
+            let _ = writeln!(out, "min_files = {min}");
+        }
+        if let Some(min) = entry.commit.min_added_lines {
+            let _ = writeln!(out, "min_added_lines = {min}");
+        }
+        if let Some(merge) = entry.commit.merge {
+            let _ = writeln!(out, "merge = {merge}");
+        }
+    } else {
+        let _ = writeln!(out, "target = {}", toml_pattern(&entry.target));
+    }
+    let _ = writeln!(out, "message = \"{}\"", entry.message);
+    let _ = writeln!(out, "severity = \"{}\"", entry.severity);
+    if !entry.tags.is_empty() {
+        let _ = writeln!(out, "tags = {:?}", entry.tags);
+    }
+    if !entry.on.is_empty() {
+        let on: Vec<String> = entry.on.iter().map(ToString::to_string).collect();
+        let _ = writeln!(out, "on = {on:?}");
+    }
+    if entry.requires_human {
+        out.push_str("requires_human = true\n");
+    }
+    if let Some(group) = &entry.group {
+        let _ = writeln!(out, "group = \"{group}\"");
+    }
+    if let Some(owner) = &entry.owner {
+        let _ = writeln!(out, "owner = \"{owner}\"");
+    }
+    if entry.disabled {
+        out.push_str("disabled = true\n");
+    }
+    if entry.include_vendored {
+        out.push_str("include_vendored = true\n");
+    }
+    if !entry.codeowners.is_empty() {
+        let rules: Vec<String> = entry.codeowners.iter().map(ToString::to_string).collect();
+        let _ = writeln!(out, "codeowners = {rules:?}");
+    }
+    if let Some(ramp) = &entry.ramp {
+        let _ = writeln!(out, "ramp = {}", ramp.to_toml());
+    }
+    if let Some(pattern) = &entry.must_match {
+        let _ = writeln!(out, "must_match = {pattern:?}");
+    }
+    if let Some(days) = entry.attest_within_days {
+        let _ = writeln!(out, "attest_within_days = {days}");
+    }
+    if !entry.when.change_kind.is_empty() {
+        let _ = writeln!(out, "when.change_kind = {}", entry.when.change_kind_toml());
+    }
+    if entry.when.dependency_change {
+        let _ = writeln!(out, "when.dependency_change = true");
+    }
+    for o in &entry.overrides {
+        let _ = writeln!(
+            out,
+            "\n[[check.override]]\nscope = {}\nseverity = \"{}\"",
+            toml_pattern(&o.scope),
+            o.severity
+        );
+    }
+    out.push('\n');
     out
 }
 
@@ -4,15 +4,20 @@
 //! Stores acknowledgments as:
 //!   `Noslop-Ack: <check> | <message> | <by>`
 //!
+//! followed by one `Noslop-Evidence: <check> | <name> | <blob-id>` per
+//! evidence file attached to the ack.
+//!
 //! This is the most portable format - visible in GitHub, GitLab, etc.
 
 use std::process::Command;
 
 use crate::adapters::file::FileStore;
-use crate::core::models::Acknowledgment;
+use crate::core::models::{Acknowledgment, Attachment};
 use crate::core::ports::AcknowledgmentStore;
+use crate::error::Error;
 
 const ACK_TRAILER: &str = "Noslop-Ack";
+const EVIDENCE_TRAILER: &str = "Noslop-Evidence";
 
 /// Acknowledgment store using commit trailers
 #[derive(Debug, Clone, Copy)]
@@ -33,40 +38,44 @@ impl Default for TrailerAckStore {
 }
 
 impl AcknowledgmentStore for TrailerAckStore {
-    fn stage(&self, ack: &Acknowledgment) -> anyhow::Result<()> {
+    fn stage(&self, ack: &Acknowledgment) -> crate::Result<()> {
         let mut staged = FileStore::load_staged_acks()?;
         staged.push(ack.clone());
         FileStore::save_staged_acks(&staged)?;
         Ok(())
     }
 
-    fn staged(&self) -> anyhow::Result<Vec<Acknowledgment>> {
+    fn staged(&self) -> crate::Result<Vec<Acknowledgment>> {
         FileStore::load_staged_acks()
     }
 
-    fn clear_staged(&self) -> anyhow::Result<()> {
+    fn clear_staged(&self) -> crate::Result<()> {
         FileStore::clear_staged_acks()
     }
 
     fn format_trailers(&self, acks: &[Acknowledgment]) -> String {
         acks.iter()
-            .map(|a| {
-                format!(
+            .flat_map(|a| {
+                let ack = format!(
                     "{}: {} | {} | {}",
                     ACK_TRAILER,
                     a.check_id,
                     a.message.replace('|', "-"),
                     a.acknowledged_by
-                )
+                );
+                std::iter::once(ack).chain(a.attachments.iter().map(|e| {
+                    format!("{EVIDENCE_TRAILER}: {} | {} | {}", a.check_id, e.name, e.blob)
+                }))
             })
             .collect::<Vec<_>>()
             .join("\n")
     }
 
-    fn parse_from_commit(&self, commit_sha: &str) -> anyhow::Result<Vec<Acknowledgment>> {
+    fn parse_from_commit(&self, commit_sha: &str) -> crate::Result<Vec<Acknowledgment>> {
         let output = Command::new("git")
             .args(["log", "-1", "--format=%(trailers)", commit_sha])
-            .output()?;
+            .output()
+            .map_err(Error::git)?;
 
         if !output.status.success() {
             return Ok(Vec::new());
@@ -80,6 +89,11 @@ impl AcknowledgmentStore for TrailerAckStore {
                 && let Some(ack) = parse_ack_trailer(value)
             {
                 acks.push(ack);
+            } else if let Some(value) = line.strip_prefix(&format!("{EVIDENCE_TRAILER}: "))
+                && let Some((check_id, attachment)) = parse_evidence_trailer(value)
+                && let Some(ack) = acks.iter_mut().rev().find(|a| a.check_id == check_id)
+            {
+                ack.attachments.push(attachment);
             }
         }
 
@@ -101,6 +115,21 @@ fn parse_ack_trailer(value: &str) -> Option<Acknowledgment> {
     }
 }
 
+/// Parse an evidence trailer: "check | name | blob"
+fn parse_evidence_trailer(value: &str) -> Option<(String, Attachment)> {
+    let parts: Vec<&str> = value.splitn(3, " | ").collect();
+    match parts.as_slice() {
+        [check, name, blob] => Some((
+            check.trim().to_string(),
+            Attachment {
+                name: name.trim().to_string(),
+                blob: blob.trim().to_string(),
+            },
+        )),
+        _ => None,
+    }
+}
+
 /// Helper to append trailers to a commit message
 #[must_use]
 pub fn append_trailers(message: &str, trailers: &str) -> String {
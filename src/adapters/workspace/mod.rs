@@ -0,0 +1,294 @@
+//! Workspace package discovery adapter
+//!
+//! Finds the packages of a monorepo from its tracked manifests:
+//! `Cargo.toml` with a `[package]` name, `package.json` with a `name`, and
+//! `go.mod` with a `module` line. Virtual manifests (a Cargo workspace
+//! root, a private npm root without a name) are not packages themselves.
+//!
+//! It also reads the dependencies manifests and lockfiles declare, so a
+//! change to one can be told apart from an edit that leaves them alone.
+
+use std::collections::BTreeMap;
+
+use crate::core::services::packages::Package;
+
+/// Manifest file names that define a package
+const MANIFESTS: &[&str] = &["Cargo.toml", "package.json", "go.mod"];
+
+/// Packages defined by the manifests in `files` (repo-relative tracked
+/// paths), each read with `read`
+///
+/// The caller picks the source: the working tree, or the index on a
+/// sparse checkout where manifests outside the cone are not on disk.
+#[must_use]
+pub fn discover_packages(files: &[String], read: impl Fn(&str) -> Option<String>) -> Vec<Package> {
+    files
+        .iter()
+        .filter(|f| !f.split('/').any(|part| part == "node_modules" || part == "vendor"))
+        .filter_map(|file| {
+            let (dir, manifest) = file.rsplit_once('/').unwrap_or(("", file.as_str()));
+            if !MANIFESTS.contains(&manifest) {
+                return None;
+            }
+            let content = read(file)?;
+            package_name(manifest, &content).map(|name| Package {
+                name,
+                dir: dir.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Manifests and lockfiles that pin dependencies, by file name
+const DEPENDENCY_FILES: &[&str] = &[
+    "Cargo.toml",
+    "Cargo.lock",
+    "package.json",
+    "package-lock.json",
+    "pnpm-lock.yaml",
+    "yarn.lock",
+    "go.mod",
+    "go.sum",
+];
+
+/// How a change to a dependency file changes the dependencies
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DependencyChange {
+    /// Dependencies that were not there before, as `name version`. Empty
+    /// for files whose entries are not read (`yarn.lock`, `go.sum`).
+    pub added: Vec<String>,
+}
+
+/// The dependency change from `before` to `after` (`None` for a file that
+/// did not exist on that side) of `path`
+///
+/// `None` when `path` is not a dependency file, or when its dependencies
+/// are the same on both sides (a description or script edit).
+#[must_use]
+pub fn dependency_change(
+    path: &str,
+    before: Option<&str>,
+    after: Option<&str>,
+) -> Option<DependencyChange> {
+    let file = path.rsplit('/').next().unwrap_or(path);
+    if !DEPENDENCY_FILES.contains(&file) || before == after {
+        return None;
+    }
+    let read =
+        |content: Option<&str>| content.map_or(Some(BTreeMap::new()), |c| dependencies(file, c));
+    let (Some(old), Some(new)) = (read(before), read(after)) else {
+        // Entries not read: any change to the file counts
+        return Some(DependencyChange { added: Vec::new() });
+    };
+    if old == new {
+        return None;
+    }
+    let added = new
+        .iter()
+        .filter(|(name, _)| !old.contains_key(*name))
+        .map(|(name, version)| format!("{name} {version}"))
+        .collect();
+    Some(DependencyChange { added })
+}
+
+/// Dependencies `content` of a dependency file declares, name to version
+/// (requirement or pin); `None` when the file kind is not read or does not
+/// parse
+fn dependencies(file: &str, content: &str) -> Option<BTreeMap<String, String>> {
+    let mut deps = BTreeMap::new();
+    match file {
+        "Cargo.toml" => {
+            let value: toml::Value = toml::from_str(content).ok()?;
+            let mut tables = vec![&value];
+            tables.extend(value.get("workspace"));
+            tables.extend(
+                value
+                    .get("target")
+                    .and_then(toml::Value::as_table)
+                    .into_iter()
+                    .flat_map(|t| t.values()),
+            );
+            for table in tables {
+                for section in ["dependencies", "dev-dependencies", "build-dependencies"] {
+                    let Some(entries) = table.get(section).and_then(toml::Value::as_table) else {
+                        continue;
+                    };
+                    for (name, spec) in entries {
+                        let version =
+                            spec.as_str().or_else(|| spec.get("version")?.as_str()).unwrap_or("*");
+                        deps.insert(name.clone(), version.to_string());
+                    }
+                }
+            }
+        },
+        "Cargo.lock" => {
+            let value: toml::Value = toml::from_str(content).ok()?;
+            for package in value.get("package")?.as_array()? {
+                let (Some(name), Some(version)) = (
+                    package.get("name").and_then(toml::Value::as_str),
+                    package.get("version").and_then(toml::Value::as_str),
+                ) else {
+                    continue;
+                };
+                pin(&mut deps, name, version);
+            }
+        },
+        "package.json" => {
+            let value: serde_json::Value = serde_json::from_str(content).ok()?;
+            for section in
+                ["dependencies", "devDependencies", "peerDependencies", "optionalDependencies"]
+            {
+                for (name, version) in
+                    value.get(section).and_then(|s| s.as_object()).into_iter().flatten()
+                {
+                    deps.insert(name.clone(), version.as_str().unwrap_or("*").to_string());
+                }
+            }
+        },
+        "package-lock.json" => {
+            let value: serde_json::Value = serde_json::from_str(content).ok()?;
+            for (key, package) in value.get("packages")?.as_object()? {
+                let Some((_, name)) = key.rsplit_once("node_modules/") else {
+                    continue;
+                };
+                pin(
+                    &mut deps,
+                    name,
+                    package.get("version").and_then(|v| v.as_str()).unwrap_or("*"),
+                );
+            }
+        },
+        "pnpm-lock.yaml" => {
+            // `packages:` entries are `  name@version:` (quoted when scoped)
+            let mut in_packages = false;
+            for line in content.lines().filter(|l| !l.trim().is_empty()) {
+                if !line.starts_with(' ') {
+                    in_packages = line.trim_end() == "packages:";
+                    continue;
+                }
+                let Some(key) = line.strip_prefix("  ").filter(|k| !k.starts_with(' ')) else {
+                    continue;
+                };
+                if !in_packages {
+                    continue;
+                }
+                let key =
+                    key.trim_end().trim_end_matches(':').trim_matches('\'').trim_start_matches('/');
+                let key = key.split('(').next().unwrap_or(key);
+                if let Some(at) = key.get(1..).and_then(|rest| rest.rfind('@')) {
+                    pin(&mut deps, &key[..=at], &key[at + 2..]);
+                }
+            }
+        },
+        "go.mod" => {
+            let mut in_require = false;
+            for line in content.lines() {
+                let line = line.split("//").next().unwrap_or(line).trim();
+                let entry = if in_require {
+                    in_require = line != ")";
+                    line
+                } else if line == "require (" {
+                    in_require = true;
+                    continue;
+                } else if let Some(entry) = line.strip_prefix("require ") {
+                    entry
+                } else {
+                    continue;
+                };
+                if let Some((module, version)) = entry.split_once(char::is_whitespace) {
+                    deps.insert(module.to_string(), version.trim().to_string());
+                }
+            }
+        },
+        _ => return None,
+    }
+    Some(deps)
+}
+
+/// Record `version` of a locked package, several versions comma-separated
+fn pin(deps: &mut BTreeMap<String, String>, name: &str, version: &str) {
+    deps.entry(name.to_string())
+        .and_modify(|versions| {
+            if !versions.split(", ").any(|v| v == version) {
+                versions.push_str(", ");
+                versions.push_str(version);
+            }
+        })
+        .or_insert_with(|| version.to_string());
+}
+
+/// The package name a manifest declares, `None` for virtual manifests
+fn package_name(manifest: &str, content: &str) -> Option<String> {
+    match manifest {
+        "Cargo.toml" => {
+            let value: toml::Value = toml::from_str(content).ok()?;
+            value.get("package")?.get("name")?.as_str().map(String::from)
+        },
+        "package.json" => {
+            let value: serde_json::Value = serde_json::from_str(content).ok()?;
+            value.get("name")?.as_str().map(String::from)
+        },
+        "go.mod" => content
+            .lines()
+            .find_map(|line| line.trim().strip_prefix("module "))
+            .map(|module| module.trim().trim_matches('"').to_string()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_names_from_each_manifest_kind() {
+        assert_eq!(
+            package_name("Cargo.toml", "[package]\nname = \"api\"\nversion = \"0.1.0\"\n"),
+            Some("api".to_string())
+        );
+        assert_eq!(package_name("Cargo.toml", "[workspace]\nmembers = [\"crates/*\"]\n"), None);
+        assert_eq!(
+            package_name("package.json", r#"{"name": "@acme/web"}"#),
+            Some("@acme/web".to_string())
+        );
+        assert_eq!(package_name("package.json", r#"{"private": true}"#), None);
+        assert_eq!(
+            package_name("go.mod", "// svc\nmodule github.com/acme/svc\n\ngo 1.22\n"),
+            Some("github.com/acme/svc".to_string())
+        );
+    }
+
+    #[test]
+    fn dependency_change_lists_added_dependencies() {
+        let before = "[package]\nname = \"api\"\n\n[dependencies]\nserde = \"1\"\n";
+        let after = "[package]\nname = \"api\"\ndescription = \"d\"\n\n[dependencies]\n\
+                     serde = \"1\"\n";
+        assert_eq!(dependency_change("Cargo.toml", Some(before), Some(after)), None);
+        let after = format!("{before}anyhow = {{ version = \"1.0.80\" }}\n");
+        assert_eq!(
+            dependency_change("crates/api/Cargo.toml", Some(before), Some(&after)),
+            Some(DependencyChange {
+                added: vec!["anyhow 1.0.80".to_string()]
+            })
+        );
+
+        let lock = "lockfileVersion: '9.0'\n\npackages:\n\n  left-pad@1.3.0:\n    \
+                    resolution: {integrity: x}\n\n  '@acme/ui@2.0.0(react@18.0.0)':\n    \
+                    resolution: {integrity: y}\n";
+        assert_eq!(
+            dependency_change("pnpm-lock.yaml", None, Some(lock)).unwrap().added,
+            ["@acme/ui 2.0.0", "left-pad 1.3.0"]
+        );
+        let go = "module x\n\nrequire (\n\tgithub.com/a/b v1.2.0 // indirect\n)\n";
+        assert_eq!(
+            dependency_change("go.mod", Some("module x\n"), Some(go)).unwrap().added,
+            ["github.com/a/b v1.2.0"]
+        );
+        // Entries not read: any change counts
+        assert_eq!(
+            dependency_change("go.sum", Some("a"), Some("b")),
+            Some(DependencyChange { added: Vec::new() })
+        );
+        assert_eq!(dependency_change("src/main.rs", None, Some("fn main() {}")), None);
+    }
+}
@@ -1,8 +1,13 @@
 //! CLI definitions and entry point
 
-use clap::{Parser, Subcommand};
+use std::time::Instant;
+
+use clap::{CommandFactory, FromArgMatches, Parser, Subcommand};
 
 use super::commands;
+use crate::noslop_file;
+use noslop::adapters::{detect_actor, git, telemetry};
+use noslop::core::models::{ChangeKind, CommandMetric, Outcome, Severity};
 use noslop::output::OutputMode;
 
 /// noslop - Pre-commit checks with acknowledgment tracking
@@ -24,6 +29,11 @@ pub struct Cli {
     #[arg(long, global = true)]
     pub json: bool,
 
+    /// Identity to record on acks and approvals (overrides NOSLOP_IDENTITY,
+    /// .noslop/identity, and git config user.email)
+    #[arg(long, global = true, value_name = "WHO")]
+    pub identity: Option<String>,
+
     #[command(subcommand)]
     pub command: Option<Command>,
 }
@@ -48,6 +58,32 @@ pub enum Command {
         #[arg(long, value_name = "REF")]
         diff_base: Option<String>,
 
+        /// Check the commits in BASE..HEAD from git objects alone, with
+        /// the config at HEAD (works in a bare repo, e.g. a receive hook)
+        #[arg(long, value_name = "BASE..HEAD", conflicts_with_all = ["ci", "diff_base"])]
+        against: Option<String>,
+
+        /// Check this list of changed files instead of the index: a file
+        /// (or - for stdin) with one path or `git diff --name-status` line
+        /// per line
+        #[arg(long, value_name = "FILE", conflicts_with_all = ["diff_base", "against"])]
+        staged_from: Option<String>,
+
+        /// Also list checks considered for each file that did not fire, and why
+        #[arg(long)]
+        explain_misses: bool,
+
+        /// Instead of the report, print one instruction per blocking check:
+        /// what to verify and the exact ack command (with --json, a
+        /// `prompt` string per check), for agent commit loops
+        #[arg(long, conflicts_with = "against")]
+        prompt: bool,
+
+        /// Also report what changed since the previous run: newly blocking
+        /// and newly satisfied checks, files gaining or losing matches
+        #[arg(long, conflicts_with_all = ["diff_base", "against", "staged_from", "prompt"])]
+        compare: bool,
+
         #[command(subcommand)]
         action: Option<CheckAction>,
     },
@@ -75,6 +111,32 @@ pub enum Command {
         /// Acknowledgment message
         #[arg(short, long)]
         message: String,
+
+        /// Show the staged diff the check matched before acknowledging
+        #[arg(long)]
+        show_diff: bool,
+
+        /// Attach an evidence file (repeatable); stored under
+        /// .noslop/evidence/ and recorded by hash
+        #[arg(long, value_name = "PATH")]
+        evidence: Vec<String>,
+    },
+
+    /// Co-sign an agent's pending ack on a check that requires a human
+    Approve {
+        /// Check ID whose staged acknowledgment to approve
+        id: String,
+    },
+
+    /// Stage acks for checks that approvals in an external review system
+    /// answer, as mapped by `[reviews.labels]`
+    ImportReviews {
+        /// Review system: github-review, gerrit
+        #[arg(long)]
+        from: String,
+
+        /// Pull request number or Gerrit change ID
+        reference: String,
     },
 
     /// Add acknowledgment trailers to commit message (used by commit-msg hook)
@@ -84,6 +146,16 @@ pub enum Command {
         commit_msg_file: String,
     },
 
+    /// List pending checks in the commit message (used by prepare-commit-msg hook)
+    #[command(hide = true)]
+    PrepareMessage {
+        /// Path to commit message file
+        commit_msg_file: String,
+
+        /// Message source git passes (message, template, merge, squash, commit)
+        source: Option<String>,
+    },
+
     /// Clear staged acknowledgments (used by post-commit hook)
     #[command(hide = true)]
     ClearStaged,
@@ -136,8 +208,155 @@ pub enum Command {
         markdown: bool,
     },
 
+    /// CI integration helpers
+    Ci {
+        #[command(subcommand)]
+        action: CiAction,
+    },
+
+    /// Export acknowledgment history (commit, check, who, when, files, message)
+    History {
+        #[command(subcommand)]
+        action: HistoryAction,
+    },
+
+    /// Review what agents did (fires and acks, in order)
+    Agent {
+        #[command(subcommand)]
+        action: AgentAction,
+    },
+
+    /// Local usage metrics (opt in with `[metrics] enabled = true`)
+    Metrics {
+        #[command(subcommand)]
+        action: MetricsAction,
+    },
+
+    /// Install git hooks, or run one by hand
+    Hooks {
+        #[command(subcommand)]
+        action: HooksAction,
+    },
+
+    /// Guided first-time setup: starter check, hooks, a sample blocked commit
+    Onboard {
+        /// Accept every default without asking
+        #[arg(long)]
+        yes: bool,
+    },
+
+    /// Verify this clone is set up: config, hooks, noslop on PATH
+    Doctor,
+
+    /// Show the detected actor and the identity recorded on acks
+    Whoami {
+        /// Store an identity for this clone in .noslop/identity
+        #[arg(long, value_name = "WHO")]
+        set: Option<String>,
+    },
+
+    /// Show staged acknowledgments and commits that skipped the gate
+    Status,
+
+    /// Inspect acknowledgments staged for the next commit
+    Staged {
+        #[command(subcommand)]
+        action: StagedAction,
+    },
+
+    /// Commit past blocking checks to a quarantine branch, and merge it
+    /// back once they are acknowledged
+    Quarantine {
+        #[command(subcommand)]
+        action: QuarantineAction,
+    },
+
+    /// Dry-run the hook pipeline on the staged changes in a throwaway worktree
+    Sandbox {
+        /// Commit message to simulate
+        #[arg(short, long, default_value = "sandbox commit")]
+        message: String,
+    },
+
+    /// Audit one commit: its checks (as of that commit) and the acks it carries
+    VerifyCommit {
+        /// Commit to audit (any revision, e.g. HEAD~2)
+        sha: String,
+
+        /// Audit against the policy recorded by `noslop snapshot <VERSION>`
+        /// instead of the commit's own
+        #[arg(long, value_name = "VERSION")]
+        policy_snapshot: Option<String>,
+    },
+
+    /// Remove stale per-clone state: unknown staged acks, orphaned
+    /// evidence, unused caches, dead sandbox worktrees
+    Gc {
+        /// Report what would be removed without removing it
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Create a new service directory with its checks and hooks in place
+    Scaffold {
+        #[command(subcommand)]
+        action: ScaffoldAction,
+    },
+
+    /// Show the configuration: its files, or the resolved settings and
+    /// checks with where each comes from
+    Config {
+        #[command(subcommand)]
+        action: ConfigAction,
+    },
+
+    /// Record the effective policy for a release, to commit with its tag
+    Snapshot {
+        /// Release the snapshot is for (e.g. v1.2.0)
+        version: String,
+
+        /// Revision to read the policy from
+        #[arg(long, default_value = "HEAD")]
+        rev: String,
+
+        /// Replace an existing snapshot for this version
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Search check and acknowledgment text (ranked, tagged by type)
+    Search {
+        /// Words to look for (all must match)
+        query: String,
+    },
+
+    /// Update this binary from the release feed (sha256-verified)
+    SelfUpdate {
+        /// Release channel: stable, nightly
+        #[arg(long, default_value = "stable")]
+        channel: String,
+
+        /// Only report whether an update is available
+        #[arg(long)]
+        check_only: bool,
+    },
+
+    /// Read a built-in guide: checks, acks, trailers, agents
+    HelpTopic {
+        /// Topic to read (omit to list them)
+        subject: Option<String>,
+
+        /// Output format: text, markdown
+        #[arg(long, default_value = "text")]
+        format: String,
+    },
+
     /// Show version
     Version,
+
+    /// A command alias defined under `[alias]` in .noslop.toml
+    #[command(external_subcommand)]
+    Alias(Vec<String>),
 }
 
 #[derive(Subcommand, Debug)]
@@ -153,7 +372,7 @@ pub enum CheckAction {
 
         /// Severity: info, warn, block
         #[arg(short, long, default_value = "block")]
-        severity: String,
+        severity: Severity,
     },
 
     /// List checks
@@ -161,6 +380,20 @@ pub enum CheckAction {
         /// Filter by file
         #[arg(short, long)]
         target: Option<String>,
+
+        /// Only checks owned by this owner (e.g. "@team-payments")
+        #[arg(long)]
+        owner: Option<String>,
+
+        /// One row per check instead of grouped by file: table, json, tsv
+        #[arg(long)]
+        format: Option<String>,
+
+        /// Columns to show, comma-separated (implies --format table):
+        /// id, severity, target, message, owner, group, tags, status, ramp,
+        /// file
+        #[arg(long, value_delimiter = ',')]
+        columns: Vec<String>,
     },
 
     /// Remove a check
@@ -168,11 +401,233 @@ pub enum CheckAction {
         /// Check ID
         id: String,
     },
+
+    /// Show a check's definition, who added it and when, and every change since
+    Show {
+        /// Check ID (e.g. NOS-3)
+        id: String,
+    },
+
+    /// Turn a check off without deleting it (keeps its ID and history)
+    Disable {
+        /// Check ID (e.g. NOS-3)
+        id: String,
+    },
+
+    /// Turn a disabled check back on
+    Enable {
+        /// Check ID (e.g. NOS-3)
+        id: String,
+    },
+
+    /// Map directories to the checks that cover them (gaps and overlaps)
+    Map {
+        /// Tracked files matched per batch (bounds memory on huge repos)
+        #[arg(long, default_value_t = 5000, value_parser = clap::value_parser!(u64).range(1..))]
+        batch_size: u64,
+
+        /// Threads matching each batch
+        #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u64).range(1..=64))]
+        threads: u64,
+    },
+
+    /// Flag checks defined identically in more than one config file
+    Lint,
+
+    /// Try a candidate target before adding it: matching files and recent commits it would fire on
+    Test {
+        /// Candidate file or pattern
+        target: String,
+
+        /// Change kinds it would fire on (default: add, modify)
+        #[arg(long, value_delimiter = ',')]
+        on: Vec<ChangeKind>,
+
+        /// How many recent commits to replay
+        #[arg(long, default_value = "50")]
+        commits: usize,
+    },
+
+    /// Show which checks a branch fires and whether its commit trailers answer them
+    VerifyBranch {
+        /// Branch (or any ref) to verify
+        #[arg(long, default_value = "HEAD")]
+        branch: String,
+
+        /// Base the branch forked from (default: `[git] base_branch`, or
+        /// the repository's default branch)
+        #[arg(long)]
+        base: Option<String>,
+    },
+
+    /// Add a bundled template's checks (e.g. supply-chain), or list the
+    /// templates
+    Template {
+        /// Template to add (omit to list them)
+        name: Option<String>,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum CiAction {
+    /// Print a pipeline config that runs the diff-base gate on pull requests
+    Generate {
+        /// CI provider: github, gitlab, circleci
+        #[arg(long, default_value = "github")]
+        provider: String,
+
+        /// Base branch pull requests merge into (default: `[git]
+        /// base_branch`, the repository's default branch, or main)
+        #[arg(long)]
+        base: Option<String>,
+
+        /// noslop version to run ("latest" or e.g. "0.2.0"); GitHub pins
+        /// the Action's release tag, GitLab and CircleCI install and cache it
+        #[arg(long, default_value = "latest")]
+        version: String,
+
+        /// Write to the provider's conventional path instead of stdout
+        #[arg(long)]
+        write: bool,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum HooksAction {
+    /// Install noslop's git hooks (as `noslop init` does), chained after
+    /// any existing hook
+    Install {
+        /// Also list pending checks in the commit message editor
+        /// (prepare-commit-msg hook)
+        #[arg(long)]
+        commit_template: bool,
+
+        /// Install only these hooks, e.g. `--only pre-commit,commit-msg`
+        #[arg(long, value_delimiter = ',', value_name = "HOOKS")]
+        only: Vec<String>,
+    },
+
+    /// Remove noslop from the given hooks, keeping anything else they run
+    Uninstall {
+        /// Hook names (pre-commit, commit-msg, post-commit, prepare-commit-msg)
+        #[arg(required = true)]
+        hooks: Vec<String>,
+    },
+
+    /// Run an installed hook as git would, narrating each step
+    Run {
+        /// Hook name (pre-commit, commit-msg, post-commit, ...)
+        hook: String,
+
+        /// Commit message file passed to commit-msg (edited in place, as git does)
+        #[arg(long)]
+        message_file: Option<String>,
+
+        /// Further arguments passed through to the hook
+        #[arg(last = true)]
+        args: Vec<String>,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum MetricsAction {
+    /// Per-command runs, block/error counts, and durations
+    Report,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum StagedAction {
+    /// Staged acknowledgments with the check each one answers
+    Show {
+        /// Only acknowledgments answering this check (directly or through
+        /// its group)
+        id: Option<String>,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum QuarantineAction {
+    /// Commit the staged changes to a new quarantine branch, pending
+    /// review of the checks they leave unanswered
+    Commit {
+        /// Commit message
+        #[arg(short, long)]
+        message: String,
+    },
+
+    /// Quarantine branches and the checks each still needs acknowledged
+    List,
+
+    /// Commit the staged acks to a quarantine branch and merge it into the
+    /// branch it came from, once no blocking check is left unanswered
+    Promote {
+        /// Quarantine branch (default: the current branch)
+        branch: Option<String>,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ScaffoldAction {
+    /// Create `<name>/` with a `.noslop.toml` of pack and starter checks
+    Service {
+        /// Service directory to create
+        name: String,
+
+        /// Org pack: a file of `[[check]]` entries to start from
+        #[arg(long, value_name = "PATH")]
+        pack: Option<String>,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ConfigAction {
+    /// List the config files, or with --effective print the resolved
+    /// configuration (TOML; --json for JSON), each key with its origin
+    Show {
+        /// Print the resolved settings and checks instead of the files
+        #[arg(long)]
+        effective: bool,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum HistoryAction {
+    /// Write one row per acknowledgment to stdout
+    Export {
+        /// Output format: csv, jsonl, table, tsv
+        #[arg(long, default_value = "csv")]
+        format: String,
+
+        /// Only commits since this date (YYYY-MM-DD)
+        #[arg(long, value_name = "DATE")]
+        since: Option<String>,
+
+        /// Columns to show with table or tsv, comma-separated: commit,
+        /// check_id, acknowledged_by, timestamp, files, message
+        #[arg(long, value_delimiter = ',')]
+        columns: Vec<String>,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum AgentAction {
+    /// Chronological log of checks fired for and acked by agents
+    Log {
+        /// Only this actor (e.g. "claude-code")
+        #[arg(long)]
+        agent: Option<String>,
+
+        /// Only the last window: 30m, 12h, 1d, 2w
+        #[arg(long, value_name = "WINDOW")]
+        since: Option<String>,
+    },
 }
 
 /// Run the CLI
 pub fn run() -> anyhow::Result<()> {
-    let cli = Cli::parse();
+    let matches = Cli::command().get_matches();
+    let command_name = matches.subcommand_name().map(str::to_string);
+    let cli = Cli::from_arg_matches(&matches)?;
 
     if cli.verbose {
         env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("debug")).init();
@@ -186,13 +641,112 @@ pub fn run() -> anyhow::Result<()> {
         OutputMode::Human
     };
 
-    match cli.command {
+    if let (Some(name), Some(command)) = (&command_name, &cli.command)
+        && needs_git(command)
+        && !git::git_available()
+    {
+        anyhow::bail!(
+            "'noslop {name}' needs git, which was not found in PATH.\n\
+             Without git these still work: {GIT_FREE_COMMANDS}"
+        );
+    }
+
+    let started = Instant::now();
+    let result = dispatch(cli.command, cli.identity.as_deref(), output_mode);
+    if let Some(command) = command_name {
+        record_metric(command, started, &result);
+    }
+    result
+}
+
+/// Commands that only read and write local files, listed when git is missing
+const GIT_FREE_COMMANDS: &str = "check add/list/remove/disable/enable/template, status, whoami, search, \
+                                 agent log, envelope, metrics report, ci generate, self-update, \
+                                 help-topic, version";
+
+/// Whether `command` reads the index, history, or hooks (see
+/// [`GIT_FREE_COMMANDS`] for the ones that do not)
+const fn needs_git(command: &Command) -> bool {
+    !matches!(
+        command,
+        Command::Check {
+            action: Some(
+                CheckAction::Add { .. }
+                    | CheckAction::List { .. }
+                    | CheckAction::Remove { .. }
+                    | CheckAction::Disable { .. }
+                    | CheckAction::Enable { .. }
+                    | CheckAction::Template { .. }
+            ),
+            ..
+        } | Command::Status
+            | Command::Whoami { .. }
+            | Command::Search { .. }
+            | Command::Agent { .. }
+            | Command::Envelope { .. }
+            | Command::Metrics { .. }
+            | Command::Ci { .. }
+            | Command::SelfUpdate { .. }
+            | Command::HelpTopic { .. }
+            | Command::Version
+            | Command::Alias(_)
+    )
+}
+
+/// Append this invocation to the local metrics log when the repo opted in.
+/// Best-effort: a metrics failure never changes the command's outcome.
+fn record_metric(command: String, started: Instant, result: &anyhow::Result<()>) {
+    if !noslop_file::metrics_enabled() {
+        return;
+    }
+    let outcome = match result {
+        Ok(()) => Outcome::Ok,
+        Err(e) if e.is::<commands::Blocked>() => Outcome::Blocked,
+        Err(_) => Outcome::Error,
+    };
+    let duration_ms = u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX);
+    let metric =
+        CommandMetric::new(command, duration_ms, outcome, detect_actor().name().to_string());
+    let _ = telemetry::append_metric(&metric);
+}
+
+fn dispatch(
+    command: Option<Command>,
+    identity: Option<&str>,
+    output_mode: OutputMode,
+) -> anyhow::Result<()> {
+    match command {
         Some(Command::Init { force }) => commands::init(force, output_mode),
+        Some(Command::Check {
+            action: None,
+            against: Some(range),
+            ..
+        }) => commands::check_range(&range, output_mode),
+        Some(Command::Check {
+            action: None,
+            staged_from: Some(list),
+            ci,
+            explain_misses,
+            prompt,
+            ..
+        }) => commands::check_listed(&list, ci, explain_misses, prompt, output_mode),
+        Some(Command::Check {
+            action: None,
+            compare: true,
+            ci,
+            explain_misses,
+            ..
+        }) => commands::check_compare(ci, explain_misses, output_mode),
         Some(Command::Check {
             action: None,
             ci,
             diff_base,
-        }) => commands::check_validate(ci, diff_base.as_deref(), output_mode),
+            explain_misses,
+            prompt,
+            ..
+        }) => {
+            commands::check_validate(ci, diff_base.as_deref(), explain_misses, prompt, output_mode)
+        },
         Some(Command::Check {
             action: Some(action),
             ..
@@ -202,8 +756,21 @@ pub fn run() -> anyhow::Result<()> {
             mine,
             from_file,
         }) => commands::discover(review, mine, from_file.as_deref(), output_mode),
-        Some(Command::Ack { id, message }) => commands::ack(&id, &message, output_mode),
+        Some(Command::Ack {
+            id,
+            message,
+            show_diff,
+            evidence,
+        }) => commands::ack(&id, &message, show_diff, &evidence, identity, output_mode),
+        Some(Command::Approve { id }) => commands::approve(&id, identity, output_mode),
+        Some(Command::ImportReviews { from, reference }) => {
+            commands::import_reviews(&from, &reference, output_mode)
+        },
         Some(Command::AddTrailers { commit_msg_file }) => commands::add_trailers(&commit_msg_file),
+        Some(Command::PrepareMessage {
+            commit_msg_file,
+            source,
+        }) => commands::prepare_message(&commit_msg_file, source.as_deref()),
         Some(Command::ClearStaged) => commands::clear_staged(),
         Some(Command::Compact) => commands::compact(),
         Some(Command::Envelope {
@@ -217,6 +784,39 @@ pub fn run() -> anyhow::Result<()> {
         }) => commands::envelope(&check, &repo, &sha, &pr, &base, &branch, &pr_title),
         Some(Command::Stats { markdown }) => commands::stats(markdown, output_mode),
         Some(Command::Curate { markdown }) => commands::curate(markdown, output_mode),
+        Some(Command::Ci { action }) => commands::ci(action),
+        Some(Command::History { action }) => commands::history(action),
+        Some(Command::Agent { action }) => commands::agent(action, output_mode),
+        Some(Command::Metrics { action }) => commands::metrics(action, output_mode),
+        Some(Command::Hooks { action }) => commands::hooks(action),
+        Some(Command::Onboard { yes }) => commands::onboard(yes),
+        Some(Command::Doctor) => commands::doctor(output_mode),
+        Some(Command::Whoami { set }) => commands::whoami(identity, set.as_deref(), output_mode),
+        Some(Command::Status) => commands::status(output_mode),
+        Some(Command::Staged { action }) => commands::staged(action, output_mode),
+        Some(Command::Quarantine { action }) => commands::quarantine(action, output_mode),
+        Some(Command::Sandbox { message }) => commands::sandbox(&message, output_mode),
+        Some(Command::VerifyCommit {
+            sha,
+            policy_snapshot,
+        }) => commands::verify_commit(&sha, policy_snapshot.as_deref(), output_mode),
+        Some(Command::Gc { dry_run }) => commands::gc(dry_run, output_mode),
+        Some(Command::Scaffold { action }) => commands::scaffold(action, output_mode),
+        Some(Command::Config { action }) => commands::config(action, output_mode),
+        Some(Command::Snapshot {
+            version,
+            rev,
+            force,
+        }) => commands::snapshot(&version, &rev, force, output_mode),
+        Some(Command::Search { query }) => commands::search(&query, output_mode),
+        Some(Command::SelfUpdate {
+            channel,
+            check_only,
+        }) => commands::self_update(&channel, check_only, output_mode),
+        Some(Command::HelpTopic { subject, format }) => {
+            commands::help_topic(subject.as_deref(), &format, output_mode)
+        },
+        Some(Command::Alias(args)) => commands::alias(&args, identity, output_mode),
         Some(Command::Version) => {
             if output_mode == OutputMode::Json {
                 println!(
@@ -1,27 +1,143 @@
 //! Ack command - provide acknowledgments for checks
 
+use std::path::Path;
+
 use noslop::output::OutputMode;
 
 use crate::noslop_file;
-use noslop::adapters::{agent_spend, detect_actor, ledger, telemetry};
-use noslop::core::models::Acknowledgment;
+use noslop::adapters::{agent_spend, detect_actor, ledger, resolve_identity, telemetry};
+use noslop::core::models::{Acknowledgment, Check, Severity};
+use noslop::core::services::ack_policy::violations;
+use noslop::core::services::diff::excerpt;
 use noslop::storage;
 
-/// Acknowledge a check by its exact ID
-pub fn ack(check_ref: &str, message: &str, _mode: OutputMode) -> anyhow::Result<()> {
-    // The referenced check must exist: acks against unknown IDs would be
-    // silent no-action answers that never match anything.
-    let Some(check) = noslop_file::find_check_by_id(check_ref)? else {
-        let known: Vec<String> =
-            noslop_file::load_all_checks()?.into_iter().map(|c| c.id).collect();
+/// Diff body lines shown per file by `--show-diff`
+const DIFF_LINES_PER_FILE: usize = 40;
+
+/// Acknowledge a check by its exact ID, or every member of a check group
+/// by the group's name
+///
+/// With `show_diff`, the staged diff of the files the check matched is
+/// printed first. Each `evidence`
+/// file is copied into `.noslop/evidence/` and named on the ack by hash.
+pub fn ack(
+    check_ref: &str,
+    message: &str,
+    show_diff: bool,
+    evidence: &[String],
+    identity: Option<&str>,
+    _mode: OutputMode,
+) -> anyhow::Result<()> {
+    let members = answered_checks(check_ref, message)?;
+    let check = &members[0];
+    let is_group = check.id != check_ref;
+    let ack_id = check_ref;
+    let member_ids: Vec<&str> = members.iter().map(|c| c.id.as_str()).collect();
+
+    let files = matched_files(&member_ids)?;
+    if show_diff {
+        show_matched_diff(ack_id, &files)?;
+    }
+    // Best-effort: without digests the ack answers whatever content lands
+    let file_digests = crate::git::staged::staged_blobs(&files).unwrap_or_default();
+
+    let attachments = evidence
+        .iter()
+        .map(|path| ledger::attach(Path::new(path)))
+        .collect::<Result<_, _>>()?;
+
+    let actor = detect_actor();
+    let ack = Acknowledgment::by_actor(ack_id.to_string(), message.to_string(), &actor)
+        .with_file_digests(file_digests)
+        .with_attachments(attachments)
+        .with_identity(resolve_identity(identity).map(|i| i.name));
+    let ack = with_evidence(ack, &member_ids);
+
+    // Stage via storage abstraction (drives the pre-commit gate and trailers)
+    let store = storage::ack_store();
+    store.stage(&ack)?;
+
+    // Durable ledger record: staged into the same commit, survives squash
+    let record_path = ledger::record(&ack)?;
+
+    match &ack.identity {
+        Some(identity) => println!("Staged acknowledgment (as {}, {identity}):", actor.name()),
+        None => println!("Staged acknowledgment (as {}):", actor.name()),
+    }
+    if is_group {
+        println!("  For: group {ack_id} ({})", member_ids.join(", "));
+    } else {
+        println!("  For: {} - {}", check.id, check.message);
+    }
+    println!("  Message: {}", message);
+    println!("  Ledger: {}", record_path.display());
+    for attachment in &ack.attachments {
+        println!("  Evidence: {} ({})", attachment.name, &attachment.blob[..12]);
+    }
+    if members.iter().any(|c| c.requires_human) && !ack.is_human_signed() {
+        println!("\n{ack_id} requires a human co-sign: this ack is pending until a human");
+        println!("runs 'noslop approve {ack_id}'.");
+    }
+    println!("\nThis will be recorded as a commit trailer:");
+    println!("  {}", store.format_trailers(&[ack]));
+    println!("\nRun 'git commit' to finalize.");
+
+    Ok(())
+}
+
+/// Stage an ack written in the commit message as `[ack ID: message]`
+///
+/// Held to the same rules as `noslop ack`: the ID must name a check or
+/// group, and the message must meet the `[ack.<severity>]` requirements.
+/// The commit's tree is fixed by the time the commit-msg hook runs, so no
+/// ledger record is written: the trailer is the record.
+pub fn ack_from_message(check_ref: &str, message: &str) -> anyhow::Result<()> {
+    let members = answered_checks(check_ref, message)?;
+    let member_ids: Vec<&str> = members.iter().map(|c| c.id.as_str()).collect();
+    let ack = Acknowledgment::by_actor(check_ref.to_string(), message.to_string(), &detect_actor())
+        .with_identity(resolve_identity(None).map(|i| i.name));
+    Ok(storage::ack_store().stage(&with_evidence(ack, &member_ids))?)
+}
+
+/// The checks an ack for `check_ref` answers: the check with that exact
+/// ID, or every member of the group by that name
+///
+/// Fails for unknown references and for messages short of the
+/// `[ack.<severity>]` requirements of the strictest member.
+pub fn answered_checks(check_ref: &str, message: &str) -> anyhow::Result<Vec<Check>> {
+    // The referenced check (or group) must exist: acks against unknown IDs
+    // would be silent no-action answers that never match anything.
+    let all = noslop_file::load_all_checks()?;
+    let members: Vec<Check> = match all.iter().find(|c| c.id == check_ref) {
+        Some(check) => vec![check.clone()],
+        None => all.iter().filter(|c| c.group.as_deref() == Some(check_ref)).cloned().collect(),
+    };
+    if members.is_empty() {
+        let known: Vec<&str> = all.iter().map(|c| c.id.as_str()).collect();
         if known.is_empty() {
             anyhow::bail!(
                 "No check with ID '{check_ref}'. No checks are defined yet; add one with 'noslop check add'."
             );
         }
         anyhow::bail!("No check with ID '{check_ref}'. Known check IDs: {}", known.join(", "));
-    };
+    }
 
+    // A group ack is recorded under the group name and held to the bar of
+    // its strictest member
+    let severity = strictest(&members.iter().collect::<Vec<_>>());
+    let problems = violations(message, noslop_file::ack_config().for_severity(severity));
+    if !problems.is_empty() {
+        anyhow::bail!(
+            "Ack for {check_ref} does not meet the [ack.{severity}] requirements:\n  - {}",
+            problems.join("\n  - ")
+        );
+    }
+    Ok(members)
+}
+
+/// Complete `ack` with the staged tree, fire and spend evidence for the
+/// checks in `member_ids`
+fn with_evidence(ack: Acknowledgment, member_ids: &[&str]) -> Acknowledgment {
     let actor = detect_actor();
     // Copy the latest local fire event into the record so it is
     // self-contained evidence (fire tree + time vs ack tree + time) —
@@ -29,7 +145,7 @@ pub fn ack(check_ref: &str, message: &str, _mode: OutputMode) -> anyhow::Result<
     let last_fire = telemetry::load_events()
         .unwrap_or_default()
         .into_iter()
-        .filter(|e| e.check_id == check.id)
+        .filter(|e| member_ids.contains(&e.check_id.as_str()))
         .max_by(|a, b| a.created_at.cmp(&b.created_at));
     // Session spend: deltas between the fire snapshots and now, guarded
     // against counter resets (new session between fire and ack). Fresh
@@ -46,25 +162,46 @@ pub fn ack(check_ref: &str, message: &str, _mode: OutputMode) -> anyhow::Result<
     };
     let model = spend.and_then(|s| s.model);
 
-    let ack = Acknowledgment::by_actor(check.id.clone(), message.to_string(), &actor)
-        .with_tree_oid(crate::git::staged::staged_tree_oid().ok())
+    ack.with_tree_oid(crate::git::staged::staged_tree_oid().ok())
         .with_fire(last_fire.as_ref().map(|e| e.tree_oid.clone()), last_fire.map(|e| e.created_at))
-        .with_spend(tokens_to_answer, cached_to_answer, model);
+        .with_spend(tokens_to_answer, cached_to_answer, model)
+}
 
-    // Stage via storage abstraction (drives the pre-commit gate and trailers)
-    let store = storage::ack_store();
-    store.stage(&ack)?;
+/// Strictest severity among the checks an ack answers
+fn strictest(checks: &[&Check]) -> Severity {
+    let has = |severity| checks.iter().any(|c| c.severity == severity);
+    if has(Severity::Block) {
+        Severity::Block
+    } else if has(Severity::Warn) {
+        Severity::Warn
+    } else {
+        Severity::Info
+    }
+}
 
-    // Durable ledger record: staged into the same commit, survives squash
-    let record_path = ledger::record(&ack)?;
+/// Staged files the checks in `ids` match, sorted
+fn matched_files(ids: &[&str]) -> anyhow::Result<Vec<String>> {
+    let changes = crate::git::staged::get_staged_changes(noslop_file::submodule_mode())?;
+    let mut files: Vec<String> = noslop_file::load_checks_for_changes(&changes)?
+        .into_iter()
+        .filter(|(c, _)| ids.contains(&c.id.as_str()))
+        .map(|(_, file)| file)
+        .collect();
+    files.sort();
+    files.dedup();
+    Ok(files)
+}
 
-    println!("Staged acknowledgment (as {}):", actor.name());
-    println!("  For: {} - {}", check.id, check.message);
-    println!("  Message: {}", message);
-    println!("  Ledger: {}", record_path.display());
-    println!("\nThis will be recorded as a commit trailer:");
-    println!("  {}", store.format_trailers(&[ack]));
-    println!("\nRun 'git commit' to finalize.");
+/// Print the staged diff of `files`, matched by `check_id`
+fn show_matched_diff(check_id: &str, files: &[String]) -> anyhow::Result<()> {
+    if files.is_empty() {
+        println!("No staged changes match {check_id}; nothing to show.\n");
+        return Ok(());
+    }
 
+    let diff = crate::git::staged::staged_diff(files)?;
+    println!("Staged diff for {check_id} ({} file(s)):\n", files.len());
+    print!("{}", excerpt(&diff, DIFF_LINES_PER_FILE));
+    println!();
     Ok(())
 }
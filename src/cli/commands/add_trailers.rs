@@ -1,19 +1,54 @@
 //! Add acknowledgment trailers to commit message
 //!
 //! This command is called by the commit-msg hook to append
-//! acknowledgment trailers to the commit message.
+//! acknowledgment trailers to the commit message. Acks written in the
+//! message itself (`[ack ID: message]`) are staged first.
 
 use std::fs;
 use std::path::Path;
 
+use noslop::core::services::ack_message::extract_acks;
+use noslop::output::OutputMode;
+
+use super::ack::ack_from_message;
+use super::check_validate::gate;
+use crate::noslop_file;
+
 /// Add acknowledgment trailers to commit message file
 ///
 /// Called by commit-msg hook with the commit message file path.
-/// Appends Noslop-Ack trailers from staged acknowledgments.
+/// Appends Noslop-Ack trailers from staged acknowledgments and marks
+/// them consumed-pending in `.noslop/consumed-acks.json`; they stay
+/// staged until post-commit sees them in the commit.
 pub fn add_trailers(commit_msg_file: &str) -> anyhow::Result<()> {
+    accept_message_acks(commit_msg_file)?;
     add_trailers_in(&noslop::adapters::git::repo_root_or_cwd(), commit_msg_file)
 }
 
+/// Stage the `[ack ID: message]` lines of the commit message and drop them
+/// from it; with `[ack] in_message`, then run the gate the pre-commit hook
+/// deferred
+///
+/// An unknown ID or a message short of the `[ack.<severity>]`
+/// requirements aborts the commit.
+fn accept_message_acks(commit_msg_file: &str) -> anyhow::Result<()> {
+    let msg_path = Path::new(commit_msg_file);
+    if !msg_path.exists() {
+        return Ok(()); // add_trailers_in reports it
+    }
+    let (rest, acks) = extract_acks(&fs::read_to_string(msg_path)?);
+    for ack in &acks {
+        ack_from_message(&ack.check_ref, &ack.message)?;
+    }
+    if !acks.is_empty() {
+        fs::write(msg_path, rest)?;
+    }
+    if noslop_file::ack_config().in_message {
+        gate(false, None, false, false, None, OutputMode::Human)?;
+    }
+    Ok(())
+}
+
 /// Add acknowledgment trailers in a specific directory (for testing)
 fn add_trailers_in(base_dir: &Path, commit_msg_file: &str) -> anyhow::Result<()> {
     use noslop::core::models::Acknowledgment;
@@ -55,10 +90,23 @@ fn add_trailers_in(base_dir: &Path, commit_msg_file: &str) -> anyhow::Result<()>
         let trailer =
             format!("Noslop-Ack: {} | {} | {}\n", ack.check_id, ack.message, ack.acknowledged_by);
         msg.push_str(&trailer);
+        for evidence in &ack.attachments {
+            msg.push_str(&format!(
+                "Noslop-Evidence: {} | {} | {}\n",
+                ack.check_id, evidence.name, evidence.blob
+            ));
+        }
     }
 
     // Write back to commit message file
     fs::write(msg_path, msg)?;
 
+    // Mark them consumed, pending the commit: post-commit clears only
+    // what the commit turns out to carry and restores the rest
+    fs::write(
+        base_dir.join(".noslop/consumed-acks.json"),
+        serde_json::to_string_pretty(&acks)?,
+    )?;
+
     Ok(())
 }
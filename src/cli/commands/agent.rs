@@ -0,0 +1,57 @@
+//! Agent command - review what an autonomous agent actually did
+//!
+//! Reads local fire events and the ack ledger; see
+//! `core::services::activity` for the merge.
+
+use crate::cli::app::AgentAction;
+use noslop::adapters::{ledger, telemetry};
+use noslop::core::services::activity::{ActivityKind, activity_log, parse_since};
+use noslop::output::OutputMode;
+
+/// Handle agent subcommands
+pub fn agent(action: AgentAction, mode: OutputMode) -> anyhow::Result<()> {
+    match action {
+        AgentAction::Log { agent, since } => log(agent.as_deref(), since.as_deref(), mode),
+    }
+}
+
+/// Print the activity log, optionally for one actor and a recent window.
+fn log(agent: Option<&str>, since: Option<&str>, mode: OutputMode) -> anyhow::Result<()> {
+    let cutoff = since
+        .map(parse_since)
+        .transpose()
+        .map_err(anyhow::Error::msg)?
+        .map(|window| {
+            chrono::Utc::now().checked_sub_signed(window).ok_or_else(|| {
+                anyhow::anyhow!("--since window reaches before the earliest representable date")
+            })
+        })
+        .transpose()?;
+
+    let events = telemetry::load_events()?;
+    let acks = ledger::load_all()?;
+    // Humans are never gated; the log is about agents unless one is named
+    let log: Vec<_> = activity_log(&events, &acks, agent, cutoff)
+        .into_iter()
+        .filter(|e| agent.is_some() || e.actor != "human")
+        .collect();
+
+    if mode == OutputMode::Json {
+        println!("{}", serde_json::json!({ "activity": log }));
+        return Ok(());
+    }
+
+    if log.is_empty() {
+        println!("No agent activity recorded.");
+        return Ok(());
+    }
+
+    for e in &log {
+        let kind = match e.kind {
+            ActivityKind::Fired => "fired",
+            ActivityKind::Acked => "acked",
+        };
+        println!("{}  {:<12} {kind} {:<10} {}", e.at, e.actor, e.check_id, e.detail);
+    }
+    Ok(())
+}
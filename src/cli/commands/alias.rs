@@ -0,0 +1,108 @@
+//! Alias command - run a `[alias]` sequence from the config
+//!
+//! `[alias] ship = ["check --ci", "!git push"]` makes `noslop ship` run
+//! each step in order, stopping at the first that fails. A step is a
+//! noslop command line, or a shell command after `!`. Arguments given
+//! after the alias name are appended to the last step. Built-in commands
+//! always win over an alias of the same name.
+
+use std::process::Command;
+
+use super::check_validate::Blocked;
+use crate::noslop_file;
+use noslop::output::OutputMode;
+
+/// Aliases already running in this process chain, `:`-separated
+const CHAIN_VAR: &str = "NOSLOP_ALIAS_CHAIN";
+
+/// Run the alias `args[0]` with the remaining arguments passed through
+pub fn alias(args: &[String], identity: Option<&str>, mode: OutputMode) -> anyhow::Result<()> {
+    let Some((name, extra)) = args.split_first() else {
+        anyhow::bail!("No command given");
+    };
+    let aliases = noslop_file::aliases();
+    let Some(steps) = aliases.get(name) else {
+        anyhow::bail!(
+            "Unknown command '{name}' (not a built-in, and no [alias] entry in .noslop.toml)"
+        );
+    };
+    let chain = std::env::var(CHAIN_VAR).unwrap_or_default();
+    if chain.split(':').any(|n| n == name) {
+        anyhow::bail!("Alias '{name}' calls itself ({chain}:{name})");
+    }
+    let chain = if chain.is_empty() {
+        name.clone()
+    } else {
+        format!("{chain}:{name}")
+    };
+
+    let exe = std::env::current_exe()?;
+    for (i, step) in steps.iter().enumerate() {
+        let last = i + 1 == steps.len();
+        eprintln!("noslop: [{name} {}/{}] {step}", i + 1, steps.len());
+        let mut command = if let Some(script) = step.strip_prefix('!') {
+            // "$@" hands the passed-through arguments over unmangled
+            let mut command = Command::new("sh");
+            command.args(["-c", &format!("{script} \"$@\""), name]);
+            if last {
+                command.args(extra);
+            }
+            command
+        } else {
+            let mut command = Command::new(&exe);
+            command.args(split_words(step)?);
+            if last {
+                command.args(extra);
+            }
+            if mode == OutputMode::Json {
+                command.arg("--json");
+            }
+            command
+        };
+        if let Some(identity) = identity {
+            command.env("NOSLOP_IDENTITY", identity);
+        }
+        let status = command.env(CHAIN_VAR, &chain).status()?;
+        if !status.success() {
+            // The step already reported why
+            return Err(Blocked { quiet: true }.into());
+        }
+    }
+    Ok(())
+}
+
+/// Split a step into words on whitespace, honouring single and double
+/// quotes
+fn split_words(step: &str) -> anyhow::Result<Vec<String>> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut in_word = false;
+    let mut quote = None;
+    for c in step.chars() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), c) => word.push(c),
+            (None, '"' | '\'') => {
+                quote = Some(c);
+                in_word = true;
+            },
+            (None, c) if c.is_whitespace() => {
+                if in_word {
+                    words.push(std::mem::take(&mut word));
+                    in_word = false;
+                }
+            },
+            (None, c) => {
+                word.push(c);
+                in_word = true;
+            },
+        }
+    }
+    if quote.is_some() {
+        anyhow::bail!("Unterminated quote in alias step: {step}");
+    }
+    if in_word {
+        words.push(word);
+    }
+    Ok(words)
+}
@@ -0,0 +1,50 @@
+//! Approve command - human co-sign for agent acknowledgments
+//!
+//! Checks marked `requires_human = true` don't count an agent's ack until
+//! a human approves it. Approval stamps `approved_by` (the approver's
+//! identity, see `noslop whoami`) on the staged ack and
+//! rewrites its (still uncommitted) ledger record.
+
+use noslop::adapters::{detect_actor, ledger, resolve_identity};
+use noslop::output::OutputMode;
+use noslop::storage::FileStore;
+
+/// Approve the pending staged acknowledgment(s) for a check
+pub fn approve(check_id: &str, identity: Option<&str>, _mode: OutputMode) -> anyhow::Result<()> {
+    let actor = detect_actor();
+    if actor.is_gated() {
+        anyhow::bail!(
+            "Only a human can approve acknowledgments (detected actor: {}).",
+            actor.name()
+        );
+    }
+
+    // Stamp who approved when known; "human" otherwise
+    let approver = resolve_identity(identity).map_or_else(|| actor.name().to_string(), |i| i.name);
+    let mut staged = FileStore::load_staged_acks()?;
+    let mut approved = 0;
+    for ack in staged.iter_mut().filter(|a| a.check_id == check_id && !a.is_human_signed()) {
+        ack.approved_by = Some(approver.clone());
+        ledger::record(ack)?;
+        approved += 1;
+    }
+
+    if approved == 0 {
+        let pending: Vec<&str> = staged
+            .iter()
+            .filter(|a| !a.is_human_signed())
+            .map(|a| a.check_id.as_str())
+            .collect();
+        if pending.is_empty() {
+            anyhow::bail!("No staged acknowledgment for '{check_id}' awaits approval.");
+        }
+        anyhow::bail!(
+            "No staged acknowledgment for '{check_id}' awaits approval. Pending: {}",
+            pending.join(", ")
+        );
+    }
+
+    FileStore::save_staged_acks(&staged)?;
+    println!("Approved {approved} acknowledgment(s) for {check_id}.");
+    Ok(())
+}
@@ -1,25 +1,74 @@
-//! Check management command - add, list, remove checks
+//! Check management command - add, list, show, remove, disable and enable
+//! checks
 
+use std::collections::BTreeMap;
+use std::io::IsTerminal;
 use std::path::Path;
 
+use super::check_validate::stacked_acks;
 use crate::cli::app::CheckAction;
-use crate::noslop_file;
-use noslop::output::OutputMode;
+use crate::{git, noslop_file};
+use noslop::adapters::toml::{TEMPLATES, add_checks, find_template};
+use noslop::core::models::{ChangeKind, Check, FileChange, Severity, TargetKind};
+use noslop::core::services::coverage::Coverage;
+use noslop::core::services::duplicates::{Definition, find_duplicates};
+use noslop::core::services::matcher::matches_target;
+use noslop::output::{OutputMode, Table, TableFormat};
+use noslop::storage;
 
 /// Handle check management subcommands
-pub fn check_manage(action: CheckAction, _mode: OutputMode) -> anyhow::Result<()> {
+pub fn check_manage(action: CheckAction, mode: OutputMode) -> anyhow::Result<()> {
     match action {
         CheckAction::Add {
             target,
             message,
             severity,
-        } => add(&target, &message, &severity),
-        CheckAction::List { target } => list(target.as_deref()),
+        } => add(&target, &message, severity),
+        CheckAction::List {
+            target,
+            owner,
+            format,
+            columns,
+        } => {
+            let format = format.map(|f| f.parse::<TableFormat>()).transpose();
+            let format = format.map_err(anyhow::Error::msg)?;
+            if format.is_some() || !columns.is_empty() {
+                list_table(
+                    target.as_deref(),
+                    owner.as_deref(),
+                    format.unwrap_or_default(),
+                    &columns,
+                )
+            } else {
+                list(target.as_deref(), owner.as_deref())
+            }
+        },
+        CheckAction::Show { id } => show(&id, mode),
         CheckAction::Remove { id } => remove(&id),
+        CheckAction::Disable { id } => set_disabled(&id, true),
+        CheckAction::Enable { id } => set_disabled(&id, false),
+        CheckAction::Map {
+            batch_size,
+            threads,
+        } => map(usize::try_from(batch_size)?, usize::try_from(threads)?, mode),
+        CheckAction::Lint => lint(mode),
+        CheckAction::Test {
+            target,
+            on,
+            commits,
+        } => test(&target, &on, commits, mode),
+        CheckAction::VerifyBranch { branch, base } => {
+            let base = match base {
+                Some(base) => base,
+                None => noslop_file::base_ref(&branch)?,
+            };
+            verify_branch(&branch, &base, mode)
+        },
+        CheckAction::Template { name } => template(name.as_deref()),
     }
 }
 
-fn add(target: &str, message: &str, severity: &str) -> anyhow::Result<()> {
+fn add(target: &str, message: &str, severity: Severity) -> anyhow::Result<()> {
     let id = noslop_file::add_check(target, message, severity)?;
 
     println!("Added check to .noslop.toml");
@@ -31,7 +80,26 @@ fn add(target: &str, message: &str, severity: &str) -> anyhow::Result<()> {
     Ok(())
 }
 
-fn list(target: Option<&str>) -> anyhow::Result<()> {
+/// Add the checks of the bundled template `name`, or list the templates
+fn template(name: Option<&str>) -> anyhow::Result<()> {
+    let Some(name) = name else {
+        println!("Bundled templates:");
+        for t in &TEMPLATES {
+            println!("  {:<14} {}", t.name, t.description);
+        }
+        println!("\nAdd one with: noslop check template <name>");
+        return Ok(());
+    };
+    let template = find_template(name).ok_or_else(|| {
+        let names: Vec<&str> = TEMPLATES.iter().map(|t| t.name).collect();
+        anyhow::anyhow!("No template '{name}' (available: {})", names.join(", "))
+    })?;
+    let ids = add_checks(template.checks()?)?;
+    println!("Added the {name} template to .noslop.toml: {}", ids.join(", "));
+    Ok(())
+}
+
+fn list(target: Option<&str>, owner: Option<&str>) -> anyhow::Result<()> {
     let cwd = std::env::current_dir()?;
     let search_path = target.map(|t| cwd.join(t)).unwrap_or_else(|| cwd.clone());
 
@@ -46,13 +114,40 @@ fn list(target: Option<&str>) -> anyhow::Result<()> {
     let mut total = 0;
     for path in &noslop_files {
         let file = noslop_file::load_file(path)?;
-        if file.checks.is_empty() {
+        let checks: Vec<_> = file
+            .checks
+            .iter()
+            .filter(|c| owner.is_none_or(|o| c.owner.as_deref() == Some(o)))
+            .collect();
+        if checks.is_empty() {
             continue;
         }
 
         println!("{}:", path.display());
-        for c in file.checks.iter() {
-            println!("  [{}] {} -> {}", c.severity.to_uppercase(), c.target, c.message);
+        for c in checks {
+            let owned = c.owner.as_ref().map(|o| format!("  ({o})")).unwrap_or_default();
+            let from = c
+                .origin
+                .as_ref()
+                .map(|o| format!("  [from {}]", o.strip_prefix(&cwd).unwrap_or(o).display()))
+                .unwrap_or_default();
+            let target = match c.target_kind {
+                TargetKind::File => c.target.clone(),
+                TargetKind::Commit => c.commit.describe(),
+            };
+            let disabled = if c.disabled { "  (disabled)" } else { "" };
+            println!(
+                "  [{}] {target} -> {}{owned}{from}{disabled}",
+                c.severity.to_string().to_uppercase(),
+                c.message
+            );
+            for o in &c.overrides {
+                println!("      {} in {}", o.severity, o.scope);
+            }
+            let defined_in = c.origin.as_deref().unwrap_or(path);
+            if let Some(phase) = noslop_file::ramp_phase(c, defined_in, "HEAD") {
+                println!("      {phase}");
+            }
             total += 1;
         }
         println!();
@@ -67,6 +162,338 @@ fn list(target: Option<&str>) -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Columns `check list --format` shows unless `--columns` names others
+const DEFAULT_COLUMNS: &[&str] = &["id", "severity", "target", "message"];
+
+/// `check list` as one row per check, in the shared table layouts
+fn list_table(
+    target: Option<&str>,
+    owner: Option<&str>,
+    format: TableFormat,
+    columns: &[String],
+) -> anyhow::Result<()> {
+    let cwd = std::env::current_dir()?;
+    let search_path = target.map(|t| cwd.join(t)).unwrap_or_else(|| cwd.clone());
+
+    let mut table = Table::new(&[
+        "id", "severity", "target", "message", "owner", "group", "tags", "status", "ramp", "file",
+    ]);
+    for path in noslop_file::find_noslop_files(&search_path) {
+        let file = noslop_file::load_file(&path)?;
+        for c in file
+            .checks
+            .iter()
+            .filter(|c| owner.is_none_or(|o| c.owner.as_deref() == Some(o)))
+        {
+            let defined_in = c.origin.as_deref().unwrap_or(&path);
+            table.push(vec![
+                c.id.clone().unwrap_or_default(),
+                c.severity.to_string(),
+                c.describe_target(),
+                c.message.clone(),
+                c.owner.clone().unwrap_or_default(),
+                c.group.clone().unwrap_or_default(),
+                c.tags.join(","),
+                if c.disabled { "disabled" } else { "enabled" }.to_string(),
+                noslop_file::ramp_phase(c, defined_in, "HEAD")
+                    .map(|p| p.to_string())
+                    .unwrap_or_default(),
+                defined_in.strip_prefix(&cwd).unwrap_or(defined_in).display().to_string(),
+            ]);
+        }
+    }
+
+    let wanted: Vec<String> = if columns.is_empty() {
+        DEFAULT_COLUMNS.iter().map(ToString::to_string).collect()
+    } else {
+        columns.to_vec()
+    };
+    print!("{}", table.select(&wanted).map_err(anyhow::Error::msg)?.render(format));
+    Ok(())
+}
+
+/// Coverage of every tracked file, read and matched `batch_size` files at
+/// a time over `threads` threads; progress goes to stderr on a terminal
+fn map(batch_size: usize, threads: usize, mode: OutputMode) -> anyhow::Result<()> {
+    let progress = std::io::stderr().is_terminal();
+    let mut coverage = Coverage::default();
+    git::staged::tracked_files_in_batches(batch_size, |files| {
+        let chunk = files.len().div_ceil(threads);
+        let parts = std::thread::scope(|scope| {
+            let workers: Vec<_> =
+                files.chunks(chunk).map(|part| scope.spawn(|| cover(part))).collect();
+            workers
+                .into_iter()
+                .map(|worker| {
+                    worker.join().unwrap_or_else(|_| anyhow::bail!("map worker panicked"))
+                })
+                .collect::<anyhow::Result<Vec<Coverage>>>()
+        })?;
+        for part in parts {
+            coverage.merge(part);
+        }
+        if progress {
+            eprint!("\rnoslop: mapped {} files", coverage.files());
+        }
+        Ok(())
+    })?;
+    if progress && coverage.files() > 0 {
+        eprintln!();
+    }
+    let map = coverage.finish();
+
+    if mode == OutputMode::Json {
+        println!("{}", serde_json::json!({ "directories": map }));
+        return Ok(());
+    }
+
+    if map.is_empty() {
+        println!("No tracked files.");
+        return Ok(());
+    }
+
+    for row in &map {
+        let label = format!("{}/", row.dir);
+        let detail = if row.is_gap() {
+            "gap: no checks".to_string()
+        } else {
+            let ids: Vec<String> =
+                row.checks.iter().map(|c| format!("{} x{}", c.id, c.files)).collect();
+            let overlap = if row.overlapping > 0 {
+                format!("  ({} overlapping)", row.overlapping)
+            } else {
+                String::new()
+            };
+            format!("{}/{} covered  [{}]{overlap}", row.covered, row.files, ids.join(", "))
+        };
+        println!("{label:<32} {detail}");
+    }
+
+    let gaps = map.iter().filter(|r| r.is_gap()).count();
+    let dirs = if map.len() == 1 {
+        "directory"
+    } else {
+        "directories"
+    };
+    println!("\n{} {dirs}, {gaps} with no covering check.", map.len());
+    Ok(())
+}
+
+/// Every tracked config file's own checks (not included ones), checked for
+/// identical definitions across files
+/// Coverage of `files`, each matched as if modified: the same matching
+/// the gate runs
+fn cover(files: &[String]) -> anyhow::Result<Coverage> {
+    let as_changes: Vec<FileChange> =
+        files.iter().map(|f| FileChange::new(f.as_str(), ChangeKind::Modify)).collect();
+    let mut ids: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for (check, file) in noslop_file::load_checks_for_changes(&as_changes)? {
+        let matched = ids.entry(file).or_default();
+        if !matched.contains(&check.id) {
+            matched.push(check.id);
+        }
+    }
+    let mut coverage = Coverage::default();
+    for file in files {
+        let matched: Vec<&str> = ids
+            .get(file)
+            .map(|ids| ids.iter().map(String::as_str).collect())
+            .unwrap_or_default();
+        coverage.add(file, &matched);
+    }
+    Ok(coverage)
+}
+
+fn lint(mode: OutputMode) -> anyhow::Result<()> {
+    let root = noslop::adapters::git::repo_root_or_cwd();
+    let mut files = Vec::new();
+    for rel in git::staged::tracked_files()? {
+        if rel == ".noslop.toml" || rel.ends_with("/.noslop.toml") {
+            let file = noslop_file::load_file(&root.join(&rel))?;
+            files.push((rel, file));
+        }
+    }
+    let definitions: Vec<Definition<'_>> = files
+        .iter()
+        .flat_map(|(rel, file)| {
+            file.checks
+                .iter()
+                .filter(|c| c.origin.is_none() && c.target_kind == TargetKind::File)
+                .map(move |c| Definition {
+                    source: rel,
+                    target: &c.target,
+                    message: &c.message,
+                    severity: c.severity,
+                })
+        })
+        .collect();
+    let duplicates = find_duplicates(&definitions);
+
+    if mode == OutputMode::Json {
+        println!("{}", serde_json::json!({ "duplicates": duplicates }));
+        return Ok(());
+    }
+
+    if duplicates.is_empty() {
+        println!("No duplicate checks in {} config file(s).", files.len());
+        return Ok(());
+    }
+    for d in &duplicates {
+        println!(
+            "  duplicate  [{}] {} -> {}",
+            d.severity.to_string().to_uppercase(),
+            d.target,
+            d.message
+        );
+        println!("             defined in: {}", d.sources.join(", "));
+    }
+    println!(
+        "\n{} check(s) defined more than once; each copy fires on its own.",
+        duplicates.len()
+    );
+    Ok(())
+}
+
+/// Matched files listed per section before eliding the rest
+const TEST_LIST_LIMIT: usize = 20;
+
+/// Evaluate a candidate target as if it were a check in `./.noslop.toml`:
+/// the tracked files it covers, and the last `commits` commits it would
+/// have fired on
+fn test(target: &str, on: &[ChangeKind], commits: usize, mode: OutputMode) -> anyhow::Result<()> {
+    let cwd = std::env::current_dir()?;
+    let matches = |path: &str| matches_target(target, path, &cwd, &cwd);
+
+    let files: Vec<String> =
+        git::staged::tracked_files()?.into_iter().filter(|f| matches(f)).collect();
+
+    let mut replayed = 0;
+    let mut fired = Vec::new();
+    for (sha, _) in git::staged::log_commits(None)?.into_iter().take(commits) {
+        replayed += 1;
+        let changes = git::staged::commit_changes(&sha, noslop_file::submodule_mode())?;
+        let mut hit: Vec<&str> =
+            changes.iter().flat_map(|c| c.paths_for(on)).filter(|p| matches(p)).collect();
+        hit.dedup();
+        if !hit.is_empty() {
+            fired.push((sha, hit.len()));
+        }
+    }
+
+    if mode == OutputMode::Json {
+        let fired: Vec<serde_json::Value> = fired
+            .iter()
+            .map(|(sha, n)| serde_json::json!({ "commit": sha, "files": n }))
+            .collect();
+        println!(
+            "{}",
+            serde_json::json!({
+                "target": target,
+                "files": files,
+                "commits_replayed": replayed,
+                "commits_fired": fired,
+            })
+        );
+        return Ok(());
+    }
+
+    println!("Candidate target: {target}\n");
+    println!("Working tree: {} tracked file(s) match", files.len());
+    for file in files.iter().take(TEST_LIST_LIMIT) {
+        println!("  {file}");
+    }
+    if files.len() > TEST_LIST_LIMIT {
+        println!("  ... and {} more", files.len() - TEST_LIST_LIMIT);
+    }
+
+    let percent = (fired.len() * 100).checked_div(replayed).unwrap_or(0);
+    println!("\nLast {replayed} commit(s): would have fired on {} ({percent}%)", fired.len());
+    for (sha, n) in fired.iter().take(TEST_LIST_LIMIT) {
+        println!("  {}  {n} file(s)", &sha[..sha.len().min(7)]);
+    }
+    if fired.len() > TEST_LIST_LIMIT {
+        println!("  ... and {} more", fired.len() - TEST_LIST_LIMIT);
+    }
+    Ok(())
+}
+
+/// Checks the branch diff fires, each verified or not by the `Noslop-Ack`
+/// trailers on the branch's own commits
+fn verify_branch(branch: &str, base: &str, mode: OutputMode) -> anyhow::Result<()> {
+    let changes = git::staged::diff_changes_to(base, branch, noslop_file::submodule_mode())?;
+    let store = storage::ack_store();
+    let mut acks = Vec::new();
+    for sha in git::staged::commits_in_range(base, branch)? {
+        for ack in store.parse_from_commit(&sha)? {
+            acks.push((sha.clone(), ack));
+        }
+    }
+    acks.extend(stacked_acks(base, branch, |sha| Ok(store.parse_from_commit(sha)?))?);
+
+    // One row per check, with every file it fired on
+    let mut rows: Vec<(Check, Vec<String>)> = Vec::new();
+    let mut applicable = noslop_file::load_checks_for_changes(&changes)?;
+    let stats = git::staged::range_stats(base, branch)?;
+    applicable.extend(noslop_file::load_commit_checks(&changes, &stats)?);
+    for (check, file) in applicable {
+        match rows.iter_mut().find(|(c, _)| c.id == check.id) {
+            Some((_, files)) if !files.contains(&file) => files.push(file),
+            Some(_) => {},
+            None => rows.push((check, vec![file])),
+        }
+    }
+    let answer = |check: &Check| acks.iter().find(|(_, ack)| check.answered_by(&ack.check_id));
+
+    if mode == OutputMode::Json {
+        let checks: Vec<serde_json::Value> = rows
+            .iter()
+            .map(|(check, files)| {
+                serde_json::json!({
+                    "id": check.id,
+                    "severity": check.severity.to_string(),
+                    "message": check.message,
+                    "files": files,
+                    "verified": answer(check).is_some(),
+                    "ack": answer(check).map(|(sha, ack)| serde_json::json!({
+                        "commit": sha,
+                        "message": ack.message,
+                        "by": ack.acknowledged_by,
+                    })),
+                })
+            })
+            .collect();
+        println!("{}", serde_json::json!({ "branch": branch, "base": base, "checks": checks }));
+        return Ok(());
+    }
+
+    if rows.is_empty() {
+        println!("No checks apply to {branch} since {base}.");
+        return Ok(());
+    }
+
+    println!("Checks on {branch} since {base}:\n");
+    for (check, files) in &rows {
+        let status = if answer(check).is_some() {
+            "verified"
+        } else {
+            "unverified"
+        };
+        println!("  {status:<10}  [{}] {} ({})", check.id, check.message, check.severity);
+        println!("              files: {}", files.join(", "));
+        if let Some((sha, ack)) = answer(check) {
+            println!(
+                "              {}: {} ({})",
+                &sha[..sha.len().min(7)],
+                ack.message,
+                ack.acknowledged_by
+            );
+        }
+    }
+    let unverified = rows.iter().filter(|(c, _)| answer(c).is_none()).count();
+    println!("\n{} check(s), {unverified} unverified.", rows.len());
+    Ok(())
+}
+
 fn remove(id: &str) -> anyhow::Result<()> {
     // ID format: ".noslop.toml:0" or "path/to/.noslop.toml:2"
     let parts: Vec<&str> = id.rsplitn(2, ':').collect();
@@ -86,30 +513,172 @@ fn remove(id: &str) -> anyhow::Result<()> {
         anyhow::bail!("Index {} out of range (file has {} checks)", index, file.checks.len());
     }
 
+    if let Some(origin) = &file.checks[index].origin {
+        anyhow::bail!("Check is defined in {}; remove it there", origin.display());
+    }
     let removed = file.checks.remove(index);
 
     // Rewrite file
-    let content = format_noslop_file(&file);
+    let content = noslop::adapters::toml::format_noslop_file(&file);
     std::fs::write(file_path, content)?;
 
     println!("Removed check: {}", removed.message);
     Ok(())
 }
 
-fn format_noslop_file(file: &noslop_file::NoslopFile) -> String {
-    let mut out = String::new();
-    out.push_str("# noslop checks\n\n");
+/// One change to a check's definition: the commit (`None` for the working
+/// tree), its author and date, and what changed
+#[derive(serde::Serialize)]
+struct Revision {
+    commit: Option<String>,
+    author: Option<String>,
+    date: Option<String>,
+    change: String,
+}
 
-    for entry in &file.checks {
-        out.push_str("[[check]]\n");
-        out.push_str(&format!("target = \"{}\"\n", entry.target));
-        out.push_str(&format!("message = \"{}\"\n", entry.message));
-        out.push_str(&format!("severity = \"{}\"\n", entry.severity));
-        if !entry.tags.is_empty() {
-            out.push_str(&format!("tags = {:?}\n", entry.tags));
+/// Print the check with ID `id` and its provenance, replayed from the git
+/// history of the config file that defines it
+fn show(id: &str, mode: OutputMode) -> anyhow::Result<()> {
+    let cwd = std::env::current_dir()?;
+    let root = noslop::adapters::git::repo_root_or_cwd();
+    let (path, entry) = noslop_file::find_noslop_files(&cwd)
+        .into_iter()
+        .find_map(|path| {
+            let file = noslop_file::load_file(&path).ok()?;
+            let entry = file.checks.into_iter().find(|c| c.id.as_deref() == Some(id))?;
+            Some((entry.origin.clone().unwrap_or(path), entry))
+        })
+        .ok_or_else(|| anyhow::anyhow!("No check with ID '{id}'"))?;
+    let rel = path.strip_prefix(&root).unwrap_or(&path).to_string_lossy().replace('\\', "/");
+
+    let find = |content: &str| {
+        noslop::adapters::toml::parse_file(content)
+            .ok()?
+            .checks
+            .into_iter()
+            .find(|c| c.id.as_deref() == Some(id))
+    };
+    let mut history = Vec::new();
+    let mut previous = None;
+    for (sha, author, date) in git::staged::file_log(&rel)? {
+        let current = git::staged::file_at(&sha, &rel).ok().and_then(|c| find(&c));
+        if let Some(change) = describe_change(previous.as_ref(), current.as_ref()) {
+            history.push(Revision {
+                commit: Some(sha),
+                author: Some(author),
+                date: Some(date),
+                change,
+            });
         }
-        out.push('\n');
+        previous = current;
+    }
+    if let Some(change) = describe_change(previous.as_ref(), Some(&entry)) {
+        history.push(Revision {
+            commit: None,
+            author: None,
+            date: None,
+            change: format!("{change} (uncommitted)"),
+        });
     }
+    let created = history.iter().find(|r| r.change == "added" && r.commit.is_some());
 
-    out
+    if mode == OutputMode::Json {
+        println!(
+            "{}",
+            serde_json::json!({
+                "id": id,
+                "defined_in": rel,
+                "target": entry.describe_target(),
+                "message": entry.message,
+                "severity": entry.severity.to_string(),
+                "disabled": entry.disabled,
+                "created_by": created.and_then(|r| r.author.as_deref()),
+                "created_at": created.and_then(|r| r.date.as_deref()),
+                "history": history,
+            })
+        );
+        return Ok(());
+    }
+
+    let disabled = if entry.disabled { "  (disabled)" } else { "" };
+    println!(
+        "{id}  [{}] {} -> {}{disabled}",
+        entry.severity.to_string().to_uppercase(),
+        entry.describe_target(),
+        entry.message
+    );
+    println!("Defined in: {rel}");
+    match created {
+        Some(r) => println!(
+            "Created:    {} by {} ({})",
+            r.date.as_deref().unwrap_or_default(),
+            r.author.as_deref().unwrap_or_default(),
+            short(r.commit.as_deref().unwrap_or_default())
+        ),
+        None => println!("Created:    not committed yet"),
+    }
+    println!("\nHistory:");
+    for r in &history {
+        let commit = r.commit.as_deref().map_or("working tree", short);
+        let when = r.date.as_deref().map_or("", |d| d.get(..10).unwrap_or(d));
+        println!(
+            "  {commit:<12} {when:<10}  {:<16} {}",
+            r.author.as_deref().unwrap_or(""),
+            r.change
+        );
+    }
+    Ok(())
+}
+
+/// What happened to a check between two versions of its config file
+fn describe_change(
+    before: Option<&noslop_file::CheckEntry>,
+    after: Option<&noslop_file::CheckEntry>,
+) -> Option<String> {
+    match (before, after) {
+        (None, Some(_)) => Some("added".to_string()),
+        (Some(_), None) => Some("removed".to_string()),
+        (Some(old), Some(new)) => {
+            let changes = new.changes_from(old);
+            (!changes.is_empty()).then(|| changes.join("; "))
+        },
+        (None, None) => None,
+    }
+}
+
+fn short(sha: &str) -> &str {
+    &sha[..sha.len().min(7)]
+}
+
+/// Set the `disabled` flag of the check with ID `id` in the nearest config
+/// file that defines it
+fn set_disabled(id: &str, disabled: bool) -> anyhow::Result<()> {
+    let cwd = std::env::current_dir()?;
+    for path in noslop_file::find_noslop_files(&cwd) {
+        let mut file = noslop_file::load_file(&path)?;
+        let Some(entry) = file.checks.iter_mut().find(|c| c.id.as_deref() == Some(id)) else {
+            continue;
+        };
+        if let Some(origin) = &entry.origin {
+            let verb = if disabled { "disable" } else { "enable" };
+            anyhow::bail!("{id} is defined in {}; {verb} it there", origin.display());
+        }
+        if entry.disabled == disabled {
+            let state = if disabled { "disabled" } else { "enabled" };
+            println!("{id} is already {state}.");
+            return Ok(());
+        }
+        entry.disabled = disabled;
+        std::fs::write(&path, noslop::adapters::toml::format_noslop_file(&file))?;
+        if disabled {
+            println!(
+                "Disabled {id} in {}; 'noslop check enable {id}' turns it back on.",
+                path.display()
+            );
+        } else {
+            println!("Enabled {id} in {}.", path.display());
+        }
+        return Ok(());
+    }
+    anyhow::bail!("No check with ID '{id}'")
 }
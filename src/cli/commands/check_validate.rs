@@ -1,13 +1,30 @@
 //! Validate checks for staged changes
 
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+
 use crate::{git, noslop_file};
+use noslop::VersionControl;
+use noslop::adapters::git::state_path;
 use noslop::adapters::remote::{FetchedCheckSet, RemoteCheckSet, load_remote_checks};
-use noslop::adapters::{agent_spend, detect_actor, ledger, telemetry};
-use noslop::core::models::{Actor, Check, CheckFireEvent, Severity};
-use noslop::core::services::{CheckItemResult, check_items, matches_target, merge_checks};
-use noslop::output::{CheckMatch, CheckResult, OutputMode};
+use noslop::adapters::{
+    agent_spend, cache, detect_actor, in_github_actions, ledger, resolve_identity, telemetry,
+    workspace,
+};
+use noslop::core::models::{
+    Acknowledgment, Actor, ChangeClass, Check, CheckFireEvent, CommitStats, FileChange, Severity,
+};
+use noslop::core::services::hunks::{classify_diff, retain_in_scope};
+use noslop::core::services::interpolate::interpolate_messages;
+use noslop::core::services::{
+    CheckItemResult, check_items, check_items_at, matches_target, merge_checks,
+};
+use noslop::output::{Attribution, CheckMatch, CheckMiss, CheckResult, HookRun, OutputMode};
 use noslop::storage;
 
+/// Blocking checks of the last blocked run, for editors and agents
+const PENDING_PATH: &str = ".noslop/pending.md";
+
 /// Validate checks for staged changes (pre-commit hook) or, with
 /// `diff_base`, for everything a branch changed (CI mode).
 ///
@@ -17,48 +34,408 @@ use noslop::storage;
 /// In diff-base mode the file list is the branch diff and acknowledgments
 /// come from the ledger records committed in the branch — so a commit made
 /// with `--no-verify` (no ledger record) fails here.
-pub fn check_validate(ci: bool, diff_base: Option<&str>, mode: OutputMode) -> anyhow::Result<()> {
+///
+/// `explain_misses` additionally reports every local check considered for
+/// each file that did not fire, and why. `prompt` replaces the report with
+/// one instruction per blocking check, for agent commit loops.
+///
+/// A passing pre-commit run records the staged tree it passed, so the
+/// post-commit hook can tell a commit that skipped the gate.
+///
+/// With `[ack] in_message`, a blocked pre-commit run leaves the verdict to
+/// the commit-msg hook, where `[ack ID: message]` lines count.
+pub fn check_validate(
+    ci: bool,
+    diff_base: Option<&str>,
+    explain_misses: bool,
+    prompt: bool,
+    mode: OutputMode,
+) -> anyhow::Result<()> {
+    let outcome = gate(ci, diff_base, explain_misses, prompt, None, mode);
+    if outcome.as_ref().is_err_and(|e| e.is::<Blocked>())
+        && !ci
+        && diff_base.is_none()
+        && defers_to_message()
+    {
+        eprintln!(
+            "noslop: answer in the commit message with '[ack <id>: <message>]'; the commit-msg hook decides"
+        );
+        return Ok(());
+    }
+    outcome
+}
+
+/// Whether this is a pre-commit hook run whose verdict the commit-msg hook
+/// takes over: `[ack] in_message` is on and noslop's commit-msg hook is
+/// installed. Git hands commit hooks `GIT_INDEX_FILE`.
+fn defers_to_message() -> bool {
+    noslop_file::ack_config().in_message
+        && std::env::var_os("GIT_INDEX_FILE").is_some()
+        && noslop::adapters::git::hooks::hook_path("commit-msg")
+            .and_then(|path| Ok(std::fs::read_to_string(path)?))
+            .is_ok_and(|hook| hook.contains("noslop add-trailers"))
+}
+
+/// Validate the staged changes and report what changed since the previous
+/// run (`--compare`): newly blocking and newly satisfied checks, files
+/// that gained or lost matches
+///
+/// The baseline is the verdict cache, which holds the previous run's
+/// result until this run replaces it. Runs that skip the cache (`[hooks]`
+/// scripts or a `[remote]` check set) leave no baseline behind.
+pub fn check_compare(ci: bool, explain_misses: bool, mode: OutputMode) -> anyhow::Result<()> {
+    let previous = cache::latest::<CheckResult>();
+    if previous.is_none() {
+        eprintln!("noslop: no previous check run cached; nothing to compare with yet");
+    }
+    gate(ci, None, explain_misses, false, previous.as_ref(), mode)
+}
+
+/// Validate and render without deferring, recording the staged tree when
+/// a pre-commit run passes. With `previous`, the report says what changed
+/// since that result.
+pub fn gate(
+    ci: bool,
+    diff_base: Option<&str>,
+    explain_misses: bool,
+    prompt: bool,
+    previous: Option<&CheckResult>,
+    mode: OutputMode,
+) -> anyhow::Result<()> {
+    let outcome = validate(ci, diff_base, None, explain_misses, prompt, previous, mode);
+    if outcome.is_ok()
+        && diff_base.is_none()
+        && let Ok(tree_oid) = git::staged::staged_tree_oid()
+    {
+        let _ = telemetry::record_gate(&tree_oid);
+    }
+    outcome
+}
+
+/// Validate checks for an explicit list of changed files instead of the
+/// index or a branch diff
+///
+/// `list` names a file, or `-` for stdin, holding one path or one
+/// `git diff --name-status` line per line. Made for CI systems that work
+/// out the change set themselves (merge queues): no staging detection runs
+/// and no staged tree is recorded. Acks come from the ledger records in the
+/// tree with `ci`, from staged acks otherwise.
+pub fn check_listed(
+    list: &str,
+    ci: bool,
+    explain_misses: bool,
+    prompt: bool,
+    mode: OutputMode,
+) -> anyhow::Result<()> {
+    let text = if list == "-" {
+        std::io::read_to_string(std::io::stdin())?
+    } else {
+        std::fs::read_to_string(list)
+            .map_err(|e| anyhow::anyhow!("Failed to read file list '{list}': {e}"))?
+    };
+    let changes: Vec<FileChange> = text.lines().filter_map(FileChange::from_listing).collect();
+    validate(ci, None, Some(&changes), explain_misses, prompt, None, mode)
+}
+
+/// Run the `[hooks]` scripts around the check pipeline, then render one
+/// report covering both (and, with `previous`, what changed since it)
+fn validate(
+    ci: bool,
+    diff_base: Option<&str>,
+    listed: Option<&[FileChange]>,
+    explain_misses: bool,
+    prompt: bool,
+    previous: Option<&CheckResult>,
+    mode: OutputMode,
+) -> anyhow::Result<()> {
+    let hooks = noslop_file::hooks_config();
+    let mut runs = Vec::new();
+    if let Some(command) = &hooks.pre_check {
+        runs.push(run_hook("pre_check", command, hooks.on_failure, None)?);
+    }
+    // Hook scripts are inputs the verdict cache can't see
+    let use_cache = hooks.pre_check.is_none() && hooks.post_check.is_none();
+    let mut result = evaluate(ci, diff_base, listed, explain_misses, use_cache)?;
+    if diff_base.is_none() && listed.is_none() {
+        escalate_debts(&mut result)?;
+    }
+    result.comparison = previous.map(|previous| result.compare(previous));
+    if let Some(command) = &hooks.post_check {
+        runs.push(run_hook("post_check", command, hooks.on_failure, Some(result.passed))?);
+    }
+
+    let hook_blocked = runs.iter().any(HookRun::blocks);
+    result.hooks = runs;
+    if result.enforced && hook_blocked {
+        result.passed = false;
+    }
+    if prompt {
+        let acks = noslop_file::ack_config();
+        result.render_prompts(mode, |severity| *acks.for_severity(severity));
+    } else {
+        result.render(mode);
+        // Inline on the pull request diff; JSON output stays parseable
+        if mode == OutputMode::Human && in_github_actions() {
+            let lines = match listed {
+                Some(_) => BTreeMap::new(),
+                None => git::staged::first_changed_lines(diff_base).unwrap_or_default(),
+            };
+            print!("{}", result.github_annotations(|file| lines.get(file).copied()));
+        }
+    }
+    if diff_base.is_none() && listed.is_none() && noslop_file::pending_enabled() {
+        update_pending(&result);
+    }
+
+    if result.enforced && (!result.blocking.is_empty() || hook_blocked) {
+        // The hook path already rendered the guidance; only CI adds a message
+        return Err(Blocked { quiet: !ci }.naming(&result));
+    }
+    Ok(())
+}
+
+/// Block the commit on overdue attestation debts no staged ack settles
+///
+/// A debt's check replaces its own warnings on this commit: it blocks on
+/// the files it went unanswered for, whether or not they are staged again.
+/// Debts of checks since removed are skipped (`noslop gc` drops them).
+fn escalate_debts(result: &mut CheckResult) -> anyhow::Result<()> {
+    let debts = telemetry::load_debts()?;
+    if result.files_checked == 0 || debts.is_empty() {
+        return Ok(());
+    }
+    let today = chrono::Utc::now().date_naive();
+    let staged = storage::ack_store().staged()?;
+    let checks = noslop_file::load_all_checks()?;
+    for debt in debts {
+        if !debt.is_overdue(today)
+            || staged.iter().any(|ack| debt.settled_by(&ack.check_id))
+            || !checks.iter().any(|c| c.id == debt.check_id)
+        {
+            continue;
+        }
+        result.warnings.retain(|w| w.id != debt.check_id);
+        let commit = &debt.commit[..debt.commit.len().min(7)];
+        for file in &debt.files {
+            if result.blocking.iter().any(|b| b.id == debt.check_id && &b.file == file) {
+                continue;
+            }
+            result.blocking.push(CheckMatch {
+                id: debt.check_id.clone(),
+                file: file.clone(),
+                target: debt.target.clone(),
+                message: format!(
+                    "{} (unattested since {commit}, was due {})",
+                    debt.message, debt.due
+                ),
+                severity: Severity::Block,
+                group: debt.group.clone(),
+                owner: None,
+                vendored: false,
+                requires_human: false,
+                code_owners: Vec::new(),
+                acknowledged: false,
+                stale_digest: None,
+                introduced_by: Vec::new(),
+                change_classes: Vec::new(),
+            });
+        }
+    }
+    if result.enforced && !result.blocking.is_empty() {
+        result.passed = false;
+    }
+    Ok(())
+}
+
+/// Write `.noslop/pending.md` for a run blocked on checks, remove it
+/// otherwise. Best-effort: the file is a convenience, never the verdict.
+fn update_pending(result: &CheckResult) {
+    let path = state_path(PENDING_PATH);
+    if result.enforced && !result.blocking.is_empty() {
+        if let Some(dir) = path.parent() {
+            let _ = std::fs::create_dir_all(dir);
+        }
+        let _ = std::fs::write(&path, result.pending_markdown());
+    } else if path.exists() {
+        let _ = std::fs::remove_file(&path);
+    }
+}
+
+/// Run one `[hooks]` script through `sh -c` from the repository root,
+/// capturing its output. `post_check` also sees the pipeline verdict as
+/// `NOSLOP_CHECK_PASSED` (1 or 0).
+fn run_hook(
+    stage: &str,
+    command: &str,
+    on_failure: Severity,
+    passed: Option<bool>,
+) -> anyhow::Result<HookRun> {
+    let mut script = std::process::Command::new("sh");
+    script
+        .args(["-c", command])
+        .current_dir(noslop::adapters::git::repo_root_or_cwd());
+    if let Some(passed) = passed {
+        script.env("NOSLOP_CHECK_PASSED", if passed { "1" } else { "0" });
+    }
+    let output = script
+        .output()
+        .map_err(|e| anyhow::anyhow!("Failed to run [hooks] {stage} '{command}': {e}"))?;
+    let mut text = String::from_utf8_lossy(&output.stdout).into_owned();
+    text.push_str(&String::from_utf8_lossy(&output.stderr));
+    Ok(HookRun {
+        stage: stage.to_string(),
+        command: command.to_string(),
+        exit_code: output.status.code(),
+        on_failure,
+        output: text.trim_end().to_string(),
+    })
+}
+
+/// Evaluate the checks for the staged changes, the branch diff, or a
+/// listed change set
+fn evaluate(
+    ci: bool,
+    diff_base: Option<&str>,
+    listed: Option<&[FileChange]>,
+    explain_misses: bool,
+    use_cache: bool,
+) -> anyhow::Result<CheckResult> {
     let actor = detect_actor();
     // Diff-base is the CI source-of-truth pass: always enforced
     let enforced = ci || diff_base.is_some() || actor.is_gated();
 
-    // Files under scrutiny: the branch diff (CI) or the index (pre-commit)
-    let staged = match diff_base {
-        Some(base) => git::staged::diff_files(base)?,
-        None => git::staged::get_staged_files()?,
+    // Changes under scrutiny: a supplied list, the branch diff (CI) or the
+    // index (pre-commit)
+    let changes = match (listed, diff_base) {
+        (Some(listed), _) => listed.to_vec(),
+        (None, Some(base)) => git::staged::diff_changes(base, noslop_file::submodule_mode())?,
+        (None, None) => git::staged::get_staged_changes(noslop_file::submodule_mode())?,
     };
+    // Cloud checks carry no `on` list: they see the default add/modify paths
+    let staged: Vec<String> =
+        changes.iter().flat_map(|c| c.paths_for(&[])).map(String::from).collect();
 
-    if staged.is_empty() {
-        render_empty(0, &actor, enforced, mode, None);
-        return Ok(());
+    if changes.is_empty() {
+        return Ok(empty_result(0, &actor, enforced, None, Vec::new()));
     }
 
+    // Verdict cache: pre-commit runs only, and only without a cloud binding
+    // (the fetched set is an input the key can't see). A hit re-renders
+    // without re-logging fire events: the same tree already logged them.
+    let cache_key = if use_cache
+        && diff_base.is_none()
+        && listed.is_none()
+        && noslop_file::load_remote_config().url.is_none()
+    {
+        verdict_key(&changes, &actor, enforced, explain_misses).ok()
+    } else {
+        None
+    };
+    if let Some(cached) = cache_key.as_deref().and_then(cache::load::<CheckResult>) {
+        return Ok(cached);
+    }
+
+    let misses = if explain_misses {
+        let root = noslop::adapters::git::repo_root_or_cwd();
+        noslop_file::explain_misses(&changes)?
+            .into_iter()
+            .map(|(check, file, reason, defined_in)| CheckMiss {
+                id: check.id,
+                file,
+                target: check.target,
+                reason: reason.to_string(),
+                defined_in: defined_in
+                    .strip_prefix(&root)
+                    .unwrap_or(&defined_in)
+                    .display()
+                    .to_string(),
+            })
+            .collect()
+    } else {
+        Vec::new()
+    };
+
     // Load checks from .noslop.toml files, then merge the org's cloud set
     // (fail-open: a cloud outage degrades to local checks, never a block)
-    let local = noslop_file::load_checks_for_files(&staged)?;
-    let fetched = load_remote_checks(&noslop_file::load_remote_config());
+    let mut local = noslop_file::load_checks_for_changes(&changes)?;
+    // Hunk classes scope `when.change_kind` checks and annotate every
+    // match. A listed change set has no diff, so scoped checks skip it.
+    let classes = match (listed, diff_base) {
+        (Some(_), _) => BTreeMap::new(),
+        (None, Some(base)) => classify_diff(&git::staged::zero_context_diff(&[
+            git::staged::merge_base(base, "HEAD")?.as_str(),
+            "HEAD",
+        ])?),
+        (None, None) => classify_diff(&git::staged::zero_context_diff(&[])?),
+    };
+    retain_in_scope(&mut local, &classes);
+    match (listed, diff_base) {
+        (Some(_), _) => retain_dependency_changes(&mut local, None),
+        (None, Some(base)) => {
+            let fork = git::staged::merge_base(base, "HEAD")?;
+            retain_dependency_changes(&mut local, Some((&fork, "HEAD")));
+        },
+        (None, None) => retain_dependency_changes(&mut local, Some(("HEAD", ""))),
+    }
+    // Disabled checks keep their definition; name the ones this run skipped
+    let mut skipped: Vec<String> = noslop_file::disabled_checks_for_changes(&changes)?
+        .into_iter()
+        .map(|(check, _)| check.id)
+        .collect();
+    skipped.sort();
+    skipped.dedup();
+    if !skipped.is_empty() {
+        eprintln!("noslop: skipped {} disabled check(s): {}", skipped.len(), skipped.join(", "));
+    }
+    // A listed change set carries no line counts or merge state
+    let stats = if listed.is_some() {
+        CommitStats {
+            files: changes.len(),
+            ..CommitStats::default()
+        }
+    } else {
+        git::staged::change_stats(diff_base)?
+    };
+    local.extend(noslop_file::load_commit_checks(&changes, &stats)?);
+    let fetched = load_remote_checks(noslop_file::load_remote_config());
     let remote_set = fetched.as_ref().map(|f| &f.set);
     let (remote_gating, remote_monitor) = partition_remote(remote_set, &staged, &actor);
     let applicable = merge_checks(local, remote_gating);
 
     if applicable.is_empty() && remote_monitor.is_empty() {
-        render_empty(staged.len(), &actor, enforced, mode, fetched.as_ref());
-        return Ok(());
+        return Ok(empty_result(changes.len(), &actor, enforced, fetched.as_ref(), misses));
     }
 
-    // Acknowledgments: committed ledger records (CI) or staged acks (local)
-    let acks = if diff_base.is_some() {
+    // Acknowledgments: committed ledger records (CI), plus those made on a
+    // stacked parent branch since this one forked, or staged acks (local)
+    let acks = if listed.is_some() && ci {
         ledger::load_pending()?
+    } else if let Some(base) = diff_base {
+        let mut acks = ledger::load_pending()?;
+        acks.extend(
+            stacked_acks(base, "HEAD", ledger::added_in_commit)?.into_iter().map(|(_, a)| a),
+        );
+        acks
     } else {
         storage::ack_store().staged()?
     };
 
-    // Core service does the matching; map its result to output types
-    let core_result = check_items(&applicable, &acks, staged.len());
+    // Core service does the matching; map its result to output types. On
+    // the branch diff, acks only answer files whose content they reviewed.
+    let blobs = match (listed, diff_base) {
+        (None, Some(_)) => matched_blobs("HEAD", &applicable)?,
+        _ => BTreeMap::new(),
+    };
+    let mut core_result = check_items_at(&applicable, &acks, changes.len(), &blobs);
+    interpolate_messages(&mut core_result, current_branch().as_deref());
 
     // Gate-time tree oid: joined against ledger tree oids downstream to
     // distinguish action rate from answers that change nothing (see docs/SCHEMA.md).
-    let tree_oid = git::staged::staged_tree_oid().ok();
+    let tree_oid = if listed.is_some() {
+        None
+    } else {
+        git::staged::staged_tree_oid().ok()
+    };
 
     // Telemetry: record every surfaced (unacknowledged) check for stats.
     // Best-effort — a telemetry failure must never block a commit. CI
@@ -91,7 +468,7 @@ pub fn check_validate(ci: bool, diff_base: Option<&str>, mode: OutputMode) -> an
 
     // Monitor-state cloud checks: evaluated for telemetry, never surfaced
     // to the agent and never gating (the Semgrep Monitor trial stage)
-    let monitor_result = check_items(&remote_monitor, &acks, staged.len());
+    let monitor_result = check_items(&remote_monitor, &acks, changes.len());
     let monitor: Vec<CheckMatch> = monitor_result
         .blocking
         .iter()
@@ -99,6 +476,22 @@ pub fn check_validate(ci: bool, diff_base: Option<&str>, mode: OutputMode) -> an
         .map(to_check_match)
         .collect();
 
+    let mut blocking: Vec<CheckMatch> = core_result.blocking.iter().map(to_check_match).collect();
+    let mut warnings: Vec<CheckMatch> = core_result.warnings.iter().map(to_check_match).collect();
+    let mut acknowledged: Vec<CheckMatch> =
+        core_result.acknowledged.iter().map(to_check_match).collect();
+    annotate_classes(&mut [&mut blocking, &mut warnings, &mut acknowledged], &classes);
+    // CI: name the commits (and so the people) behind each surfaced check
+    if let Some(base) = diff_base {
+        for m in blocking.iter_mut().chain(warnings.iter_mut()) {
+            m.introduced_by = git::staged::commits_touching(base, &m.file)
+                .unwrap_or_default()
+                .into_iter()
+                .map(|(sha, author)| Attribution { sha, author })
+                .collect();
+        }
+    }
+
     let result = CheckResult {
         passed: core_result.passed || !enforced,
         files_checked: core_result.files_checked,
@@ -107,22 +500,193 @@ pub fn check_validate(ci: bool, diff_base: Option<&str>, mode: OutputMode) -> an
         tree_oid,
         check_set_version: remote_set.map(|s| s.check_set_version.clone()),
         check_set_age_seconds: fetched.as_ref().map(|f| f.age_seconds),
-        blocking: core_result.blocking.iter().map(to_check_match).collect(),
-        warnings: core_result.warnings.iter().map(to_check_match).collect(),
-        acknowledged: core_result.acknowledged.iter().map(to_check_match).collect(),
+        blocking,
+        warnings,
+        acknowledged,
         monitor,
+        misses,
+        hooks: Vec::new(),
+        comparison: None,
+        range: None,
     };
 
+    if let Some(key) = cache_key {
+        let _ = cache::store(&key, &result);
+    }
+    Ok(result)
+}
+
+/// Judge the commits in `range` (`BASE..HEAD`) from git objects alone
+///
+/// Made for server-side hooks, where there is no work tree and git hands
+/// over `GIT_DIR`: the checks come from HEAD's tree, the changes from the
+/// range diff, and the acks from the range's trailers and ledger records.
+/// Always enforced.
+pub fn check_range(range: &str, mode: OutputMode) -> anyhow::Result<()> {
+    let (base, head) = range
+        .split_once("..")
+        .filter(|(base, head)| !base.is_empty() && !head.is_empty() && !head.starts_with('.'))
+        .ok_or_else(|| anyhow::anyhow!("--against takes BASE..HEAD, got '{range}'"))?;
+    if base.chars().all(|c| c == '0') {
+        anyhow::bail!(
+            "'{base}' is the null object id (a newly created ref); check against the branch it was created from"
+        );
+    }
+
+    let result = range_result(base, head, &[])?;
     result.render(mode);
 
-    if enforced && !core_result.passed {
-        if !ci {
-            std::process::exit(1);
+    if result.blocking.is_empty() {
+        Ok(())
+    } else {
+        Err(Blocked { quiet: false }.naming(&result))
+    }
+}
+
+/// The verdict on `base..head`: the checks HEAD's config raises for the
+/// range diff, answered by the trailers and ledger records of the range's
+/// commits plus `extra`. Always enforced.
+pub fn range_result(
+    base: &str,
+    head: &str,
+    extra: &[Acknowledgment],
+) -> anyhow::Result<CheckResult> {
+    let changes = git::staged::diff_changes_to(base, head, noslop_file::submodule_mode())?;
+    let stats = git::staged::range_stats(base, head)?;
+    let mut applicable = noslop_file::checks_at_commit(head, &changes, &stats)?;
+    let fork = git::staged::merge_base(base, head)?;
+    let classes = classify_diff(&git::staged::zero_context_diff(&[fork.as_str(), head])?);
+    retain_in_scope(&mut applicable, &classes);
+    retain_dependency_changes(&mut applicable, Some((&fork, head)));
+    let store = storage::ack_store();
+    let mut acks = extra.to_vec();
+    for sha in git::staged::commits_in_range(base, head)? {
+        acks.extend(store.parse_from_commit(&sha)?);
+        acks.extend(ledger::added_in_commit(&sha)?);
+    }
+
+    let blobs = matched_blobs(head, &applicable)?;
+    let mut core = check_items_at(&applicable, &acks, changes.len(), &blobs);
+    interpolate_messages(&mut core, Some(head));
+    let mut blocking: Vec<CheckMatch> = core.blocking.iter().map(to_check_match).collect();
+    let mut warnings: Vec<CheckMatch> = core.warnings.iter().map(to_check_match).collect();
+    let mut acknowledged: Vec<CheckMatch> = core.acknowledged.iter().map(to_check_match).collect();
+    annotate_classes(&mut [&mut blocking, &mut warnings, &mut acknowledged], &classes);
+    Ok(CheckResult {
+        passed: core.passed,
+        files_checked: core.files_checked,
+        actor: detect_actor().name().to_string(),
+        enforced: true,
+        tree_oid: None,
+        check_set_version: None,
+        check_set_age_seconds: None,
+        blocking,
+        warnings,
+        acknowledged,
+        monitor: Vec::new(),
+        misses: Vec::new(),
+        hooks: Vec::new(),
+        comparison: None,
+        range: Some(format!("{base}..{head}")),
+    })
+}
+
+/// Blob ids at `rev` of the files `applicable` checks matched, for
+/// [`check_items_at`]
+pub fn matched_blobs(
+    rev: &str,
+    applicable: &[(Check, String)],
+) -> anyhow::Result<BTreeMap<String, String>> {
+    let mut files: Vec<String> = applicable.iter().map(|(_, file)| file.clone()).collect();
+    files.sort();
+    files.dedup();
+    git::staged::blobs_at(rev, &files)
+}
+
+/// A gated run stopped on unacknowledged blocking checks (exit 1)
+#[derive(Debug, thiserror::Error)]
+#[error("Unacknowledged checks")]
+pub struct Blocked {
+    /// Exit without an error line (the result was already rendered)
+    pub quiet: bool,
+}
+
+impl Blocked {
+    /// This block as an error whose message names the unacknowledged
+    /// checks in `result` ([`noslop::Error::CheckBlocked`]); still a
+    /// [`Blocked`] to downcasts
+    fn naming(self, result: &CheckResult) -> anyhow::Error {
+        let blocked = anyhow::Error::new(self);
+        match result.ensure_passed() {
+            Err(checks) => blocked.context(checks),
+            Ok(()) => blocked,
         }
-        anyhow::bail!("Unacknowledged checks");
     }
+}
 
-    Ok(())
+/// Acknowledgments carried forward from the branch `head` is stacked on
+/// (see [`git::staged::stacked_parent`]), as (commit, ack).
+///
+/// Only commits the parent gained after `head` forked from it are read,
+/// with `read_acks`, and only acks answering a check the parent's own
+/// changes since `base` fire are kept: answers given on feature-1 count
+/// for feature-2 without re-acking, but never for checks feature-1 did not
+/// raise.
+pub fn stacked_acks(
+    base: &str,
+    head: &str,
+    read_acks: impl Fn(&str) -> anyhow::Result<Vec<Acknowledgment>>,
+) -> anyhow::Result<Vec<(String, Acknowledgment)>> {
+    let Some(parent) = git::staged::stacked_parent(head, base)? else {
+        return Ok(Vec::new());
+    };
+    let parent_changes =
+        git::staged::diff_changes_to(base, &parent, noslop_file::submodule_mode())?;
+    let parent_checks = noslop_file::load_checks_for_changes(&parent_changes)?;
+    let since_base = git::staged::commits_in_range(base, &parent)?;
+
+    let mut carried = Vec::new();
+    for sha in git::staged::commits_in_range(head, &parent)? {
+        if !since_base.contains(&sha) {
+            continue;
+        }
+        for ack in read_acks(&sha)? {
+            if parent_checks.iter().any(|(check, _)| check.answered_by(&ack.check_id)) {
+                carried.push((sha.clone(), ack));
+            }
+        }
+    }
+    if !carried.is_empty() {
+        eprintln!("noslop: counting {} ack(s) made on stacked parent '{parent}'", carried.len());
+    }
+    Ok(carried)
+}
+
+/// Cache key over every input of a pre-commit verdict: binary version,
+/// staged tree, HEAD, governing config files, CODEOWNERS, workspace
+/// packages, staged acks, actor, and flags
+fn verdict_key(
+    changes: &[FileChange],
+    actor: &Actor,
+    enforced: bool,
+    explain_misses: bool,
+) -> anyhow::Result<String> {
+    // The date is an input: a ramped check changes severity when it ends
+    // So is the identity: `codeowners = ["warn"]` depends on who commits
+    // And HEAD: ramps are dated and stacked acks found from its history
+    let material = format!(
+        "{}\n{}\n{}\n{}\n{}\n{}\n{enforced}\n{explain_misses}\n{}\n{}\n{}",
+        env!("CARGO_PKG_VERSION"),
+        chrono::Utc::now().date_naive(),
+        git::staged::staged_tree_oid()?,
+        git::staged::resolve_rev("HEAD").unwrap_or_default(),
+        actor.name(),
+        resolve_identity(None).map(|i| i.name).unwrap_or_default(),
+        noslop_file::config_fingerprint(changes)?,
+        noslop_file::scope_fingerprint(),
+        serde_json::to_string(&storage::ack_store().staged()?)?,
+    );
+    git::staged::hash_content(&material)
 }
 
 /// Checks paired with the staged file they matched
@@ -168,24 +732,75 @@ fn partition_remote(
     (gating, monitor)
 }
 
-fn to_check_match(item: &CheckItemResult) -> CheckMatch {
+/// The branch HEAD is on, `None` when detached or outside a repository
+pub fn current_branch() -> Option<String> {
+    noslop::adapters::GitVersionControl::default().current_branch().ok().flatten()
+}
+
+/// The output form of a core check item
+pub fn to_check_match(item: &CheckItemResult) -> CheckMatch {
     CheckMatch {
         id: item.id.clone(),
         file: item.file.clone(),
         target: item.target.clone(),
         message: item.message.clone(),
-        severity: item.severity.to_string(),
+        severity: item.severity,
+        group: item.group.clone(),
+        owner: item.owner.clone(),
+        vendored: item.vendored,
+        requires_human: item.requires_human,
+        code_owners: item.code_owners.clone(),
         acknowledged: item.acknowledged,
+        stale_digest: item.stale_digest.clone(),
+        introduced_by: Vec::new(),
+        change_classes: Vec::new(),
+    }
+}
+
+/// Keep the `when.dependency_change` checks whose file's dependencies
+/// changed from `before` to `after` (`""` = the index), naming the added
+/// ones in the message. Without revisions (a listed change set) they skip.
+pub fn retain_dependency_changes(
+    applicable: &mut Vec<(Check, String)>,
+    revs: Option<(&str, &str)>,
+) {
+    applicable.retain_mut(|(check, file)| {
+        if !check.dependency_change {
+            return true;
+        }
+        let Some((before, after)) = revs else {
+            return false;
+        };
+        let at = |rev: &str| git::staged::file_at(rev, file).ok();
+        let Some(change) =
+            workspace::dependency_change(file, at(before).as_deref(), at(after).as_deref())
+        else {
+            return false;
+        };
+        if !change.added.is_empty() {
+            let _ = write!(check.message, " (new dependencies: {})", change.added.join(", "));
+        }
+        true
+    });
+}
+
+/// Give each match the hunk classes of its file
+fn annotate_classes(
+    matches: &mut [&mut Vec<CheckMatch>],
+    classes: &BTreeMap<String, Vec<ChangeClass>>,
+) {
+    for m in matches.iter_mut().flat_map(|list| list.iter_mut()) {
+        m.change_classes = classes.get(&m.file).cloned().unwrap_or_default();
     }
 }
 
-fn render_empty(
+fn empty_result(
     files_checked: usize,
     actor: &Actor,
     enforced: bool,
-    mode: OutputMode,
     fetched: Option<&FetchedCheckSet>,
-) {
+    misses: Vec<CheckMiss>,
+) -> CheckResult {
     CheckResult {
         passed: true,
         files_checked,
@@ -198,6 +813,9 @@ fn render_empty(
         warnings: vec![],
         acknowledged: vec![],
         monitor: vec![],
+        misses,
+        hooks: Vec::new(),
+        comparison: None,
+        range: None,
     }
-    .render(mode);
 }
@@ -0,0 +1,43 @@
+//! CI command - scaffold pipeline configs for server-side enforcement
+
+use crate::cli::app::CiAction;
+use noslop::adapters::git::repo_root_or_cwd;
+use noslop::core::services::ci::{CiOptions, CiProvider, generate};
+
+/// Handle CI subcommands
+pub fn ci(action: CiAction) -> anyhow::Result<()> {
+    match action {
+        CiAction::Generate {
+            provider,
+            base,
+            version,
+            write,
+        } => {
+            let provider: CiProvider = provider.parse().map_err(anyhow::Error::msg)?;
+            let config = generate(
+                provider,
+                &CiOptions {
+                    base_branch: base
+                        .or_else(|| crate::noslop_file::base_branch(None))
+                        .unwrap_or_else(|| "main".to_string()),
+                    version,
+                },
+            );
+            if !write {
+                print!("{config}");
+                return Ok(());
+            }
+
+            let path = repo_root_or_cwd().join(provider.default_path());
+            if path.exists() {
+                anyhow::bail!("{} already exists; not overwriting", path.display());
+            }
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::write(&path, config)?;
+            println!("Wrote {}", provider.default_path());
+            Ok(())
+        },
+    }
+}
@@ -1,23 +1,203 @@
 //! Clear staged acknowledgments
 //!
 //! This command is called by the post-commit hook to remove
-//! staged acknowledgments after they've been added to the commit.
+//! staged acknowledgments once the new commit carries them as trailers.
+//! Acks the commit lacks stay staged for the next commit.
+//! It also notices commits that skipped the pre-commit gate, reminds
+//! about checks a gated commit left unanswered, and keeps the attestation
+//! debts of warnings with a deadline.
 
 use std::path::Path;
 
+use crate::{git, noslop_file};
+use noslop::adapters::{detect_actor, telemetry};
+use noslop::core::models::{Acknowledgment, AttestationDebt, BypassEvent, Check, Severity};
+use noslop::core::services::hunks::{classify_diff, retain_in_scope};
+use noslop::core::services::{CheckItemResult, check_items};
+use noslop::storage;
+
 /// Clear staged acknowledgments
 ///
-/// Called by post-commit hook to delete .noslop/staged-acks.json
-/// after the commit has been created with acknowledgment trailers.
+/// Called by post-commit hook once the commit exists, so its SHA and
+/// trailers are known. Only acknowledgments HEAD carries are removed from
+/// .noslop/staged-acks.json.
 pub fn clear_staged() -> anyhow::Result<()> {
-    clear_staged_in(&noslop::adapters::git::repo_root_or_cwd())
+    // Best-effort: reviewing the commit must never fail the hook
+    let _ = review_commit();
+    let carried = storage::ack_store().parse_from_commit("HEAD")?;
+    clear_staged_in(&noslop::adapters::git::repo_root_or_cwd(), &carried)
 }
 
-/// Clear staged acknowledgments in a specific directory (for testing)
-fn clear_staged_in(base_dir: &Path) -> anyhow::Result<()> {
+/// Settle the staged acknowledgments against the trailers of the commit
+/// just made, in a specific directory (for testing)
+///
+/// The commit-msg hook marks the acks it wrote into the message as
+/// consumed-pending. Those the commit carries are cleared; the rest
+/// (the commit was made with `--no-verify`, or the message was rewritten
+/// after the hook) are restored to staged, as are staged acks that
+/// never reached the message.
+fn clear_staged_in(base_dir: &Path, carried: &[Acknowledgment]) -> anyhow::Result<()> {
     let acks_file = base_dir.join(".noslop/staged-acks.json");
-    if acks_file.exists() {
-        std::fs::remove_file(acks_file)?;
+    let consumed_file = base_dir.join(".noslop/consumed-acks.json");
+
+    let mut kept: Vec<Acknowledgment> = read_acks(&acks_file)?
+        .into_iter()
+        .filter(|ack| !is_carried(ack, carried))
+        .collect();
+    for ack in read_acks(&consumed_file)? {
+        let staged = kept
+            .iter()
+            .any(|k| k.check_id == ack.check_id && k.created_at == ack.created_at);
+        if !staged && !is_carried(&ack, carried) {
+            kept.push(ack);
+        }
+    }
+
+    if kept.is_empty() {
+        if acks_file.exists() {
+            std::fs::remove_file(&acks_file)?;
+        }
+    } else {
+        let mut ids: Vec<&str> = kept.iter().map(|ack| ack.check_id.as_str()).collect();
+        ids.sort_unstable();
+        ids.dedup();
+        eprintln!(
+            "noslop: kept {} staged acknowledgment(s) this commit does not carry ({}); they apply to the next commit",
+            kept.len(),
+            ids.join(", ")
+        );
+        std::fs::write(&acks_file, serde_json::to_string_pretty(&kept)?)?;
+    }
+    if consumed_file.exists() {
+        std::fs::remove_file(consumed_file)?;
+    }
+    Ok(())
+}
+
+fn read_acks(path: &Path) -> anyhow::Result<Vec<Acknowledgment>> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    Ok(serde_json::from_str(&std::fs::read_to_string(path)?)?)
+}
+
+/// Whether a trailer on the commit answers `ack`
+///
+/// Trailers are single `check | message | by` lines, so a message with
+/// its own " | " comes back cut short.
+fn is_carried(ack: &Acknowledgment, carried: &[Acknowledgment]) -> bool {
+    carried.iter().any(|trailer| {
+        trailer.check_id == ack.check_id && ack.message.trim().starts_with(&trailer.message)
+    })
+}
+
+/// Review HEAD for checks its trailers left unanswered.
+///
+/// When HEAD's tree is not the one the gate last passed (`git commit
+/// --no-verify`), record a bypass event. Otherwise the gate let it
+/// through (warnings, or a human's blocking checks): print a reminder
+/// unless `[reminders]` is off.
+///
+/// Either way, debts HEAD's acks answer are settled, and warnings with
+/// `attest_within_days` it leaves unanswered become debts.
+///
+/// Commits git replays (rebase, cherry-pick) never pass through
+/// pre-commit and are skipped.
+fn review_commit() -> anyhow::Result<()> {
+    if noslop::adapters::git::replaying_commits() {
+        return Ok(());
+    }
+    let tree = git::staged::resolve_rev("HEAD^{tree}")?;
+    let gated = telemetry::last_gate().as_deref() == Some(tree.as_str());
+
+    let commit = git::staged::resolve_rev("HEAD")?;
+    let changes = git::staged::commit_changes(&commit, noslop_file::submodule_mode())?;
+    let mut applicable = noslop_file::load_checks_for_changes(&changes)?;
+    let stats = git::staged::commit_stats(&commit)?;
+    applicable.extend(noslop_file::load_commit_checks(&changes, &stats)?);
+    // A root commit has no parent to diff against: its scoped checks skip
+    let diff = git::staged::zero_context_diff(&[&format!("{commit}^"), &commit]);
+    retain_in_scope(&mut applicable, &classify_diff(&diff.unwrap_or_default()));
+    super::check_validate::retain_dependency_changes(
+        &mut applicable,
+        Some((&format!("{commit}^"), &commit)),
+    );
+    let acks = storage::ack_store().parse_from_commit(&commit)?;
+    let result = check_items(&applicable, &acks, changes.len());
+    track_debts(&commit, &applicable, &acks, &result.warnings)?;
+    if gated && !noslop_file::reminders_enabled() {
+        return Ok(());
+    }
+    if result.blocking.is_empty() && result.warnings.is_empty() {
+        return Ok(());
+    }
+
+    let severity = if result.blocking.is_empty() {
+        Severity::Warn
+    } else {
+        Severity::Block
+    };
+    let mut check_ids: Vec<String> = result
+        .blocking
+        .iter()
+        .chain(&result.warnings)
+        .map(|item| item.id.clone())
+        .collect();
+    check_ids.sort();
+    check_ids.dedup();
+
+    if gated {
+        eprintln!(
+            "noslop: {} check(s) left unacknowledged in this commit ({}); run 'noslop ack <id> -m \"...\"' before pushing",
+            check_ids.len(),
+            check_ids.join(", ")
+        );
+        return Ok(());
+    }
+
+    eprintln!(
+        "noslop: commit {} skipped the pre-commit gate with {} check(s) unanswered ({}); recorded, see 'noslop status'",
+        &commit[..commit.len().min(7)],
+        check_ids.len(),
+        check_ids.join(", ")
+    );
+    let event = BypassEvent::new(commit, severity, check_ids, detect_actor().name().to_string());
+    telemetry::append_bypass(&event)
+}
+
+/// Settle the debts `acks` answer, and add one for each check with a
+/// deadline that `warnings` (left unanswered by `commit`) name and no debt
+/// covers yet
+fn track_debts(
+    commit: &str,
+    applicable: &[(Check, String)],
+    acks: &[Acknowledgment],
+    warnings: &[CheckItemResult],
+) -> anyhow::Result<()> {
+    let mut debts = telemetry::load_debts()?;
+    let before = debts.clone();
+    debts.retain(|debt| !acks.iter().any(|ack| debt.settled_by(&ack.check_id)));
+
+    let today = chrono::Utc::now().date_naive();
+    for (check, _) in applicable {
+        if debts.iter().any(|debt| debt.check_id == check.id) {
+            continue;
+        }
+        let files: Vec<String> =
+            warnings.iter().filter(|w| w.id == check.id).map(|w| w.file.clone()).collect();
+        if files.is_empty() {
+            continue;
+        }
+        if let Some(debt) = AttestationDebt::new(check, commit.to_string(), files, today) {
+            eprintln!(
+                "noslop: {} left unanswered; attest it by {} or it blocks the next commit",
+                debt.check_id, debt.due
+            );
+            debts.push(debt);
+        }
+    }
+    if debts != before {
+        telemetry::save_debts(&debts)?;
     }
     Ok(())
 }
@@ -0,0 +1,279 @@
+//! Config command - show the configuration noslop runs with
+//!
+//! `noslop config show` lists the files the configuration is read from.
+//! With `--effective` it prints the resolved configuration itself: the
+//! repo-root settings over built-in defaults, then every check of every
+//! config file (includes merged) and of the org's cloud set, each key
+//! annotated with where it came from. With `--json`, the same as one
+//! object.
+
+use std::fmt::Write;
+use std::path::Path;
+
+use serde::Serialize;
+
+use crate::cli::app::ConfigAction;
+use crate::noslop_file;
+use noslop::adapters::remote::{DEFAULT_TOKEN_ENV, load_remote_checks};
+use noslop::adapters::toml::{NoslopFile, format_check, load_file, parse_file};
+use noslop::output::OutputMode;
+
+/// Origin of a setting the repo-root config leaves unset
+const DEFAULT: &str = "default";
+
+/// Handle config subcommands
+pub fn config(action: ConfigAction, mode: OutputMode) -> anyhow::Result<()> {
+    match action {
+        ConfigAction::Show { effective: false } => sources(mode),
+        ConfigAction::Show { effective: true } => effective(mode),
+    }
+}
+
+/// One resolved setting
+#[derive(Debug, Serialize)]
+struct Setting {
+    /// Dotted key, e.g. `project.prefix`
+    key: String,
+    value: toml::Value,
+    /// The file that sets it, or `default`
+    origin: String,
+}
+
+/// One check in effect
+#[derive(Debug, Serialize)]
+struct EffectiveCheck {
+    /// Config file (or included file) defining it, or the cloud set
+    origin: String,
+    /// Directory its target is relative to (empty = the repo root)
+    scope: String,
+    /// The check as a `[[check]]` table
+    check: toml::Table,
+    /// The same as TOML text, for printing
+    #[serde(skip)]
+    text: String,
+}
+
+/// List the config files in the order they are read
+fn sources(mode: OutputMode) -> anyhow::Result<()> {
+    let root = noslop::adapters::git::repo_root_or_cwd();
+    let mut files = Vec::new();
+    for path in noslop_file::repo_config_paths()? {
+        let file = load_file(&path)?;
+        let mut includes: Vec<String> = file
+            .checks
+            .iter()
+            .filter_map(|c| c.origin.as_deref())
+            .map(|p| relative(p, &root))
+            .collect();
+        includes.dedup();
+        let own = file.checks.iter().filter(|c| c.origin.is_none()).count();
+        files.push(serde_json::json!({
+            "path": relative(&path, &root),
+            "checks": own,
+            "includes": includes,
+        }));
+    }
+    let remote = &noslop_file::load_remote_config().url;
+
+    if mode == OutputMode::Json {
+        let out = serde_json::json!({ "files": files, "remote": remote });
+        println!("{}", serde_json::to_string_pretty(&out)?);
+        return Ok(());
+    }
+    if files.is_empty() {
+        println!("No .noslop.toml files; built-in defaults apply");
+    } else {
+        println!("Config files (settings from the repo-root one, checks from all):");
+        for file in &files {
+            println!("  {} ({} check(s))", file["path"].as_str().unwrap_or(""), file["checks"]);
+            for include in file["includes"].as_array().into_iter().flatten() {
+                println!("    includes {}", include.as_str().unwrap_or(""));
+            }
+        }
+    }
+    if let Some(url) = remote {
+        println!("Cloud check set: {url}");
+    }
+    println!("\nResolved configuration: noslop config show --effective");
+    Ok(())
+}
+
+/// Print the resolved configuration with each key's origin
+fn effective(mode: OutputMode) -> anyhow::Result<()> {
+    let root = noslop::adapters::git::repo_root_or_cwd();
+    let root_config = root.join(".noslop.toml");
+    let (file, raw) = if root_config.exists() {
+        let raw = toml::from_str(&std::fs::read_to_string(&root_config)?)?;
+        (load_file(&root_config)?, raw)
+    } else {
+        (parse_file("")?, toml::Table::new())
+    };
+    let settings = settings(&file, &raw)?;
+    let checks = checks(&file, &root)?;
+
+    if mode == OutputMode::Json {
+        let out = serde_json::json!({ "settings": settings, "checks": checks });
+        println!("{}", serde_json::to_string_pretty(&out)?);
+    } else {
+        print!("{}", render(&settings, &checks));
+    }
+    Ok(())
+}
+
+/// The repo-root settings over the built-in defaults, one per key
+fn settings(file: &NoslopFile, raw: &toml::Table) -> anyhow::Result<Vec<Setting>> {
+    let origin = |set: bool| if set { ".noslop.toml" } else { DEFAULT }.to_string();
+    let mut settings = vec![
+        Setting {
+            key: "root".to_string(),
+            value: toml::Value::Boolean(file.root),
+            origin: origin(raw.contains_key("root")),
+        },
+        Setting {
+            key: "vendored".to_string(),
+            value: toml::Value::try_from(&file.vendored)?,
+            origin: origin(raw.contains_key("vendored")),
+        },
+    ];
+    let sections = [
+        ("project", toml::Value::try_from(&file.project)?),
+        ("discover", toml::Value::try_from(&file.discover)?),
+        ("remote", toml::Value::try_from(&file.remote)?),
+        ("metrics", toml::Value::try_from(file.metrics)?),
+        ("reminders", toml::Value::try_from(file.reminders)?),
+        ("pending", toml::Value::try_from(file.pending)?),
+        ("git", toml::Value::try_from(&file.git)?),
+        ("ack", toml::Value::try_from(file.ack)?),
+        ("hooks", toml::Value::try_from(&file.hooks)?),
+        ("alias", toml::Value::try_from(&file.alias)?),
+        ("reviews", toml::Value::try_from(&file.reviews)?),
+    ];
+    for (section, value) in sections {
+        let toml::Value::Table(table) = value else {
+            continue;
+        };
+        let written = raw.get(section).and_then(toml::Value::as_table);
+        for (key, value) in table {
+            let set = written.is_some_and(|t| t.contains_key(&key));
+            settings.push(Setting {
+                key: format!("{section}.{key}"),
+                value,
+                origin: origin(set),
+            });
+        }
+    }
+
+    // The cloud token is the one setting read from the environment
+    if file.remote.url.is_some() {
+        let var = file.remote.token_env.as_deref().unwrap_or(DEFAULT_TOKEN_ENV);
+        let state = if std::env::var_os(var).is_some() {
+            "set"
+        } else {
+            "not set"
+        };
+        if let Some(url) = settings.iter_mut().find(|s| s.key == "remote.url") {
+            let _ = write!(url.origin, "; token from ${var} ({state})");
+        }
+    }
+    Ok(settings)
+}
+
+/// Every check: each config file's (includes merged), then the cloud set
+fn checks(file: &NoslopFile, root: &Path) -> anyhow::Result<Vec<EffectiveCheck>> {
+    let mut checks = Vec::new();
+    for path in noslop_file::repo_config_paths()? {
+        let config = relative(&path, root);
+        let scope = config.trim_end_matches(".noslop.toml").to_string();
+        for mut entry in load_file(&path)?.checks {
+            // Acks answer an ID-less check by the one derived from it
+            let id = noslop_file::check_id(&entry);
+            entry.id.get_or_insert(id);
+            let origin =
+                entry.origin.as_deref().map_or_else(|| config.clone(), |p| relative(p, root));
+            checks.push(EffectiveCheck::new(origin, scope.clone(), format_check(&entry))?);
+        }
+    }
+
+    if let Some(fetched) = load_remote_checks(&file.remote) {
+        for remote in &fetched.set.checks {
+            let mut text = String::from("[[check]]\n");
+            for (key, value) in [
+                ("id", &remote.id),
+                ("target", &remote.target),
+                ("message", &remote.message),
+                ("severity", &remote.severity),
+                ("owner", &remote.owner),
+            ] {
+                let _ = writeln!(text, "{key} = {}", toml::Value::String(value.clone()));
+            }
+            text.push('\n');
+            let origin = format!("cloud set {} ({})", fetched.set.check_set_version, remote.state);
+            checks.push(EffectiveCheck::new(origin, String::new(), text)?);
+        }
+    }
+    Ok(checks)
+}
+
+impl EffectiveCheck {
+    fn new(origin: String, scope: String, text: String) -> anyhow::Result<Self> {
+        let mut parsed: toml::Table = toml::from_str(&text)?;
+        let check = match parsed.remove("check") {
+            Some(toml::Value::Array(mut tables)) if !tables.is_empty() => match tables.remove(0) {
+                toml::Value::Table(check) => check,
+                _ => toml::Table::new(),
+            },
+            _ => toml::Table::new(),
+        };
+        Ok(Self {
+            origin,
+            scope,
+            check,
+            text,
+        })
+    }
+}
+
+/// The effective configuration as `.noslop.toml` text, origins as comments
+fn render(settings: &[Setting], checks: &[EffectiveCheck]) -> String {
+    let mut out = String::from(
+        "# Effective noslop configuration\n\
+         # Settings: the repo-root .noslop.toml over built-in defaults\n\
+         # Checks: every config file (includes merged), then the cloud set\n\n",
+    );
+    let mut section = None;
+    for setting in settings {
+        let (table, key) = match setting.key.split_once('.') {
+            Some((table, key)) => (Some(table), key),
+            None => (None, setting.key.as_str()),
+        };
+        if table != section {
+            let _ = write!(out, "\n[{}]\n", table.unwrap_or_default());
+            section = table;
+        }
+        let _ = writeln!(out, "{} = {}  # {}", quoted(key), setting.value, setting.origin);
+    }
+    for check in checks {
+        let scope = if check.scope.is_empty() {
+            String::new()
+        } else {
+            format!(", targets under {}", check.scope)
+        };
+        let _ = write!(out, "\n# {}{scope}\n{}", check.origin, check.text.trim_end());
+        out.push('\n');
+    }
+    out
+}
+
+/// A key as TOML writes it: bare when it can be, quoted otherwise
+fn quoted(key: &str) -> String {
+    if !key.is_empty() && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+        key.to_string()
+    } else {
+        format!("{key:?}")
+    }
+}
+
+/// `path` relative to the repo root, with forward slashes
+fn relative(path: &Path, root: &Path) -> String {
+    path.strip_prefix(root).unwrap_or(path).to_string_lossy().replace('\\', "/")
+}
@@ -243,7 +243,7 @@ fn review_proposals() -> anyhow::Result<()> {
         let choice = lines.next().transpose()?.unwrap_or_else(|| "q".to_string());
         match choice.trim().to_lowercase().as_str() {
             "a" => {
-                let id = noslop_file::add_check(&p.target, &p.message, &p.severity.to_string())?;
+                let id = noslop_file::add_check(&p.target, &p.message, p.severity)?;
                 println!("  Added as {id}");
                 accepted += 1;
             },
@@ -251,7 +251,8 @@ fn review_proposals() -> anyhow::Result<()> {
                 let target = prompt_default(&mut lines, "target", &p.target)?;
                 let message = prompt_default(&mut lines, "message", &p.message)?;
                 let severity = prompt_default(&mut lines, "severity", &p.severity.to_string())?;
-                let id = noslop_file::add_check(&target, &message, &severity)?;
+                let severity = severity.parse().map_err(anyhow::Error::msg)?;
+                let id = noslop_file::add_check(&target, &message, severity)?;
                 println!("  Added as {id}");
                 accepted += 1;
             },
@@ -306,7 +307,7 @@ fn existing_check_keys(root: &std::path::Path) -> Vec<String> {
                 let p = Proposal {
                     target: entry.target.clone(),
                     message: entry.message.clone(),
-                    severity: entry.severity.parse().unwrap_or_default(),
+                    severity: entry.severity,
                     source: String::new(),
                 };
                 keys.push(p.dedupe_key());
@@ -0,0 +1,177 @@
+//! Doctor command - verify this clone is set up for noslop
+//!
+//! Checks what has to hold for the gate to run at commit time: the config
+//! loads, the hooks are installed and executable, the `noslop` the hooks
+//! call is on PATH, per-clone state is kept out of commits, and every
+//! acknowledgment backend in the clone is readable.
+
+use std::process::Command;
+
+use crate::noslop_file;
+use noslop::adapters::git::hook_path;
+use noslop::output::OutputMode;
+use noslop::storage;
+
+/// One verified requirement
+#[derive(Debug, serde::Serialize)]
+pub struct Finding {
+    /// What was checked
+    pub what: String,
+    /// Whether it holds
+    pub ok: bool,
+    /// How to fix it, when it does not
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fix: Option<String>,
+}
+
+impl Finding {
+    fn pass(what: impl Into<String>) -> Self {
+        Self {
+            what: what.into(),
+            ok: true,
+            fix: None,
+        }
+    }
+
+    fn fail(what: impl Into<String>, fix: impl Into<String>) -> Self {
+        Self {
+            what: what.into(),
+            ok: false,
+            fix: Some(fix.into()),
+        }
+    }
+}
+
+/// Report the setup checks; fails when any of them does not hold
+pub fn doctor(mode: OutputMode) -> anyhow::Result<()> {
+    let findings = diagnose();
+    render(&findings, mode);
+    let problems = findings.iter().filter(|f| !f.ok).count();
+    if problems > 0 {
+        anyhow::bail!("{problems} problem(s) found");
+    }
+    Ok(())
+}
+
+/// Print findings, one per line, with the fix under each failure
+pub fn render(findings: &[Finding], mode: OutputMode) {
+    if mode == OutputMode::Json {
+        let ok = findings.iter().all(|f| f.ok);
+        println!("{}", serde_json::json!({ "ok": ok, "findings": findings }));
+        return;
+    }
+    for f in findings {
+        println!("  {} {}", if f.ok { "ok  " } else { "FAIL" }, f.what);
+        if let Some(fix) = &f.fix {
+            println!("       fix: {fix}");
+        }
+    }
+}
+
+/// Run every setup check
+pub fn diagnose() -> Vec<Finding> {
+    let root = noslop::adapters::git::repo_root_or_cwd();
+    let mut findings = Vec::new();
+
+    if !root.join(".noslop.toml").exists() {
+        findings.push(Finding::fail("no .noslop.toml at the repository root", "noslop init"));
+    } else {
+        match noslop_file::load_all_checks() {
+            Ok(checks) => findings
+                .push(Finding::pass(format!("config loads ({} check(s) in scope)", checks.len()))),
+            Err(e) => findings.push(Finding::fail(
+                format!("config does not load: {e}"),
+                "correct the .noslop.toml it names",
+            )),
+        }
+    }
+
+    for hook in ["pre-commit", "commit-msg", "post-commit"] {
+        findings.push(if hook_installed(hook) {
+            Finding::pass(format!("{hook} hook installed"))
+        } else {
+            Finding::fail(format!("{hook} hook missing or not executable"), "noslop hooks install")
+        });
+    }
+
+    let on_path = Command::new("noslop").arg("version").output().is_ok_and(|o| o.status.success());
+    findings.push(if on_path {
+        Finding::pass("noslop is on PATH (the hooks call it by name)")
+    } else {
+        Finding::fail(
+            "noslop is not on PATH; the hooks cannot run it",
+            "install noslop somewhere on PATH",
+        )
+    });
+
+    let ignored = std::fs::read_to_string(root.join(".gitignore"))
+        .is_ok_and(|g| g.lines().any(|l| l.trim() == ".noslop/staged-acks.json"));
+    findings.push(if ignored {
+        Finding::pass(".gitignore keeps per-clone .noslop/ state out of commits")
+    } else {
+        Finding::fail(".gitignore does not cover .noslop/staged-acks.json", "noslop init")
+    });
+
+    findings.extend(storage_findings());
+    findings
+}
+
+/// Which backend is active, what the others hold, and whether all of it reads
+fn storage_findings() -> Vec<Finding> {
+    let report = match storage::report() {
+        Ok(report) => report,
+        Err(e) => {
+            return vec![Finding::fail(
+                format!("acknowledgment storage cannot be read: {e}"),
+                "check the permissions on .noslop/",
+            )];
+        },
+    };
+    let mut findings = Vec::new();
+    let populated = report.populated();
+    findings.push(Finding::pass(if populated.is_empty() {
+        format!("acks are stored in {}; no other backend holds records", report.active)
+    } else {
+        format!(
+            "acks are stored in {}; records also in: {}",
+            report.active,
+            populated.join(", ")
+        )
+    }));
+    for backend in report.backends.iter().filter(|b| b.unreadable > 0) {
+        findings.push(Finding::fail(
+            format!("{} unreadable record(s) in {}", backend.unreadable, backend.location),
+            "fix or remove the damaged file; other records are unaffected",
+        ));
+    }
+    if report.newer_schema > 0 {
+        findings.push(Finding::fail(
+            format!("{} ledger record(s) come from a newer noslop", report.newer_schema),
+            "upgrade noslop",
+        ));
+    }
+    if report.pending_compaction > 0 {
+        findings.push(Finding::pass(format!(
+            "{} ledger record(s) pending compaction ('noslop compact' folds them at merge)",
+            report.pending_compaction
+        )));
+    }
+    findings
+}
+
+/// The hook exists, calls noslop, and (on unix) is executable
+fn hook_installed(name: &str) -> bool {
+    let Ok(path) = hook_path(name) else {
+        return false;
+    };
+    let calls_noslop = std::fs::read_to_string(&path).is_ok_and(|s| s.contains("noslop"));
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        calls_noslop && std::fs::metadata(&path).is_ok_and(|m| m.permissions().mode() & 0o111 != 0)
+    }
+    #[cfg(not(unix))]
+    {
+        calls_noslop
+    }
+}
@@ -58,13 +58,15 @@ pub fn envelope(
     Ok(())
 }
 
-/// Check ids present in the payload's blocking/warnings/acknowledged arrays.
+/// Check ids present in the payload's blocking/warnings/acknowledged
+/// arrays, plus their group names (a group ack's record carries the group).
 fn touched_check_ids(check: &serde_json::Value) -> HashSet<String> {
     ["blocking", "warnings", "acknowledged"]
         .iter()
         .filter_map(|key| check.get(key)?.as_array())
         .flatten()
-        .filter_map(|item| item.get("id")?.as_str())
+        .flat_map(|item| ["id", "group"].map(|field| item.get(field).and_then(|v| v.as_str())))
+        .flatten()
         .map(ToString::to_string)
         .collect()
 }
@@ -0,0 +1,227 @@
+//! Gc command - clean up state noslop leaves behind
+//!
+//! Per-clone state accumulates: staged acks and attestation debts for
+//! checks since removed, evidence copied for acks that were never committed, caches nothing
+//! reads any more, and sandbox worktrees of runs that were killed. `gc`
+//! finds each, removes it (or, with `--dry-run`, only reports it), and
+//! tells how much space that reclaimed.
+
+use std::fs;
+use std::path::Path;
+
+use serde::Serialize;
+
+use crate::noslop_file;
+use noslop::adapters::git::{sandbox, state_path};
+use noslop::adapters::{cache, ledger, remote, telemetry};
+use noslop::output::OutputMode;
+use noslop::storage::FileStore;
+
+/// Something `gc` removes
+#[derive(Debug, Serialize)]
+struct Garbage {
+    /// What kind of state it is
+    kind: &'static str,
+    /// The file, directory, or ack it concerns
+    item: String,
+    /// Why it is garbage
+    reason: String,
+    /// Bytes on disk it takes
+    bytes: u64,
+}
+
+/// Find stale state and remove it, unless `dry_run`
+pub fn gc(dry_run: bool, mode: OutputMode) -> anyhow::Result<()> {
+    let mut found = Vec::new();
+    found.extend(stale_acks(dry_run)?);
+    found.extend(stale_debts(dry_run)?);
+    found.extend(orphaned_evidence(dry_run)?);
+    found.extend(stale_caches(dry_run)?);
+    found.extend(dead_sandboxes(dry_run)?);
+
+    let bytes: u64 = found.iter().map(|g| g.bytes).sum();
+    if mode == OutputMode::Json {
+        println!(
+            "{}",
+            serde_json::json!({
+                "dry_run": dry_run,
+                "removed": found,
+                "reclaimed_bytes": bytes,
+            })
+        );
+        return Ok(());
+    }
+    if found.is_empty() {
+        println!("Nothing to clean up.");
+        return Ok(());
+    }
+    println!("{}", if dry_run { "Would remove:" } else { "Removed:" });
+    for g in &found {
+        println!("  {:<10} {}: {}", g.kind, g.item, g.reason);
+    }
+    let verb = if dry_run {
+        "Would reclaim"
+    } else {
+        "Reclaimed"
+    };
+    println!("\n{verb} {} in {} item(s).", human_bytes(bytes), found.len());
+    Ok(())
+}
+
+/// Staged acks naming neither a check nor a group: they would never answer
+/// anything, yet ride along into the next commit's trailers
+fn stale_acks(dry_run: bool) -> anyhow::Result<Vec<Garbage>> {
+    let staged = FileStore::load_staged_acks()?;
+    if staged.is_empty() {
+        return Ok(Vec::new());
+    }
+    let checks = noslop_file::load_all_checks()?;
+    let known = |id: &str| checks.iter().any(|c| c.id == id || c.group.as_deref() == Some(id));
+    let (kept, stale): (Vec<_>, Vec<_>) = staged.into_iter().partition(|a| known(&a.check_id));
+    if !stale.is_empty() && !dry_run {
+        FileStore::save_staged_acks(&kept)?;
+    }
+    Ok(stale
+        .into_iter()
+        .map(|ack| Garbage {
+            kind: "ack",
+            item: format!("staged ack for {}", ack.check_id),
+            reason: "no check or group has this ID".to_string(),
+            bytes: serde_json::to_string_pretty(&ack).map_or(0, |s| s.len() as u64),
+        })
+        .collect())
+}
+
+/// Attestation debts of checks since removed: no ack can settle them
+fn stale_debts(dry_run: bool) -> anyhow::Result<Vec<Garbage>> {
+    let debts = telemetry::load_debts()?;
+    if debts.is_empty() {
+        return Ok(Vec::new());
+    }
+    let checks = noslop_file::load_all_checks()?;
+    let (kept, stale): (Vec<_>, Vec<_>) =
+        debts.into_iter().partition(|d| checks.iter().any(|c| c.id == d.check_id));
+    if !stale.is_empty() && !dry_run {
+        telemetry::save_debts(&kept)?;
+    }
+    Ok(stale
+        .into_iter()
+        .map(|debt| Garbage {
+            kind: "debt",
+            item: format!("attestation debt for {}", debt.check_id),
+            reason: "no check has this ID".to_string(),
+            bytes: serde_json::to_string_pretty(&debt).map_or(0, |s| s.len() as u64),
+        })
+        .collect())
+}
+
+/// Evidence files no ack record or staged ack attaches
+fn orphaned_evidence(dry_run: bool) -> anyhow::Result<Vec<Garbage>> {
+    let dir = state_path(ledger::EVIDENCE_DIR);
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+    let mut referenced: Vec<String> = ledger::load_all()?
+        .into_iter()
+        .chain(FileStore::load_staged_acks()?)
+        .flat_map(|ack| ack.attachments.into_iter().map(|a| a.blob))
+        .collect();
+    referenced.sort();
+
+    let mut found = Vec::new();
+    for entry in fs::read_dir(&dir)? {
+        let path = entry?.path();
+        let name = path.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+        if referenced.binary_search(&name).is_ok() || name.starts_with('.') {
+            continue;
+        }
+        let bytes = size_of(&path);
+        if !dry_run {
+            ledger::remove_evidence(&path)?;
+        }
+        found.push(Garbage {
+            kind: "evidence",
+            item: format!("{}/{name}", ledger::EVIDENCE_DIR),
+            reason: "no acknowledgment attaches it".to_string(),
+            bytes,
+        });
+    }
+    Ok(found)
+}
+
+/// Caches nothing will read: verdicts (rebuilt on the next check), the
+/// remote check set once `[remote]` is gone, and `pending.md` once
+/// `[pending]` is off
+fn stale_caches(dry_run: bool) -> anyhow::Result<Vec<Garbage>> {
+    let mut candidates = vec![(cache::CHECK_CACHE_DIR, "verdict cache; rebuilt on the next check")];
+    if noslop_file::load_remote_config().url.is_none() {
+        candidates.push((remote::CACHE_FILE, "no [remote] is configured"));
+    }
+    if !noslop_file::pending_enabled() {
+        candidates.push((".noslop/pending.md", "[pending] is off"));
+    }
+
+    let mut found = Vec::new();
+    for (rel, reason) in candidates {
+        let path = state_path(rel);
+        if !path.exists() {
+            continue;
+        }
+        let bytes = size_of(&path);
+        if !dry_run {
+            if path.is_dir() {
+                fs::remove_dir_all(&path)?;
+            } else {
+                fs::remove_file(&path)?;
+            }
+        }
+        found.push(Garbage {
+            kind: "cache",
+            item: rel.to_string(),
+            reason: reason.to_string(),
+            bytes,
+        });
+    }
+    Ok(found)
+}
+
+/// Sandbox worktrees whose `noslop sandbox` process is gone, or whose
+/// directory was deleted (those are pruned from git's records)
+fn dead_sandboxes(dry_run: bool) -> anyhow::Result<Vec<Garbage>> {
+    let mut found = Vec::new();
+    for worktree in sandbox::worktrees().into_iter().filter(sandbox::SandboxWorktree::abandoned) {
+        let bytes = size_of(&worktree.path);
+        if !dry_run {
+            worktree.remove()?;
+        }
+        found.push(Garbage {
+            kind: "worktree",
+            item: worktree.path.display().to_string(),
+            reason: format!("sandbox process {} is gone", worktree.pid),
+            bytes,
+        });
+    }
+    Ok(found)
+}
+
+/// Bytes under `path`, files and directories alike; 0 when unreadable
+fn size_of(path: &Path) -> u64 {
+    let Ok(meta) = fs::symlink_metadata(path) else {
+        return 0;
+    };
+    if !meta.is_dir() {
+        return meta.len();
+    }
+    fs::read_dir(path)
+        .map_or(0, |entries| entries.filter_map(Result::ok).map(|e| size_of(&e.path())).sum())
+}
+
+/// `bytes` in B, KiB or MiB, to one decimal
+fn human_bytes(bytes: u64) -> String {
+    let (unit, name) = match bytes {
+        0..1024 => return format!("{bytes} B"),
+        1024..1_048_576 => (1024, "KiB"),
+        _ => (1_048_576, "MiB"),
+    };
+    format!("{}.{} {name}", bytes / unit, bytes % unit * 10 / unit)
+}
@@ -0,0 +1,126 @@
+//! Long-form guides built into the binary
+//!
+//! The guides under `docs/guides/` are embedded at compile time so they can
+//! be read offline, from any directory, with the version of noslop that is
+//! actually installed.
+
+use std::io::{IsTerminal, Write};
+use std::process::{Command, Stdio};
+
+use noslop::output::OutputMode;
+
+/// Embedded guides: (topic, summary, markdown source)
+const TOPICS: &[(&str, &str, &str)] = &[
+    (
+        "checks",
+        "Defining checks: targets, severities, overrides",
+        include_str!("../../../docs/guides/checks.md"),
+    ),
+    (
+        "acks",
+        "The acknowledgment workflow, end to end",
+        include_str!("../../../docs/guides/acks.md"),
+    ),
+    (
+        "trailers",
+        "The Noslop-Ack trailer format",
+        include_str!("../../../docs/guides/trailers.md"),
+    ),
+    (
+        "agents",
+        "Agent mode: who is gated and how detection works",
+        include_str!("../../../docs/guides/agents.md"),
+    ),
+];
+
+/// Print a guide, or the list of guides when no subject is given
+pub fn help_topic(
+    subject: Option<&str>,
+    format: &str,
+    output_mode: OutputMode,
+) -> anyhow::Result<()> {
+    let markdown = match format {
+        "text" => false,
+        "markdown" => true,
+        other => anyhow::bail!("Unknown format '{other}' (expected text or markdown)"),
+    };
+
+    let Some(subject) = subject else {
+        return list_topics(output_mode);
+    };
+    let Some(&(topic, _, source)) = TOPICS.iter().find(|(name, ..)| *name == subject) else {
+        let names: Vec<&str> = TOPICS.iter().map(|(name, ..)| *name).collect();
+        anyhow::bail!("No help topic '{subject}'. Available: {}", names.join(", "));
+    };
+
+    if output_mode == OutputMode::Json {
+        println!("{}", serde_json::json!({ "topic": topic, "markdown": source }));
+        return Ok(());
+    }
+    let body = if markdown {
+        source.to_string()
+    } else {
+        render_text(source)
+    };
+    page(&body)
+}
+
+fn list_topics(output_mode: OutputMode) -> anyhow::Result<()> {
+    if output_mode == OutputMode::Json {
+        let topics: Vec<_> = TOPICS
+            .iter()
+            .map(|(name, summary, _)| serde_json::json!({ "topic": name, "summary": summary }))
+            .collect();
+        println!("{}", serde_json::json!({ "topics": topics }));
+        return Ok(());
+    }
+    println!("Help topics:");
+    for (name, summary, _) in TOPICS {
+        println!("  {name:<10} {summary}");
+    }
+    println!("\nRun 'noslop help-topic <topic>' to read one.");
+    Ok(())
+}
+
+/// Plain-text rendering: headings underlined, fences and inline code
+/// markers dropped
+fn render_text(source: &str) -> String {
+    let mut out = String::new();
+    for line in source.lines() {
+        if line.starts_with("```") {
+            continue;
+        }
+        if let Some(heading) = line.strip_prefix("# ").or_else(|| line.strip_prefix("## ")) {
+            let underline = if line.starts_with("# ") { '=' } else { '-' };
+            out.push_str(heading);
+            out.push('\n');
+            out.push_str(&underline.to_string().repeat(heading.chars().count()));
+            out.push('\n');
+            continue;
+        }
+        out.push_str(&line.replace('`', ""));
+        out.push('\n');
+    }
+    out
+}
+
+/// Write through `$PAGER` (default `less -FR`) when stdout is a terminal;
+/// print directly otherwise, or when the pager cannot be started
+fn page(body: &str) -> anyhow::Result<()> {
+    let pager = std::env::var("PAGER").unwrap_or_else(|_| "less -FR".to_string());
+    if !std::io::stdout().is_terminal() || pager.trim().is_empty() {
+        print!("{body}");
+        return Ok(());
+    }
+    let Ok(mut child) = Command::new("sh").args(["-c", &pager]).stdin(Stdio::piped()).spawn()
+    else {
+        print!("{body}");
+        return Ok(());
+    };
+    if let Some(mut stdin) = child.stdin.take() {
+        // The reader may quit the pager early; a broken pipe is not an error
+        let _ = stdin.write_all(body.as_bytes());
+    }
+    child.wait()?;
+    Ok(())
+}
@@ -0,0 +1,116 @@
+//! History command - export acknowledgment history for data teams
+//!
+//! Walks commits from HEAD and collects every acknowledgment each one
+//! carries: its `Noslop-Ack` trailers and the ledger records it added.
+//! A record and the trailer for the same ack collapse into one row.
+
+use crate::cli::app::HistoryAction;
+use crate::{git, noslop_file};
+use noslop::adapters::ledger;
+use noslop::core::models::Acknowledgment;
+use noslop::core::services::export::{ExportFormat, ExportRow, encode};
+use noslop::output::{Table, TableFormat};
+use noslop::storage;
+
+/// Handle history subcommands
+pub fn history(action: HistoryAction) -> anyhow::Result<()> {
+    match action {
+        HistoryAction::Export {
+            format,
+            since,
+            columns,
+        } => export(&format, since.as_deref(), &columns),
+    }
+}
+
+/// Warehouse encodings, or the shared table layouts for reading in a
+/// terminal or piping to other tools
+enum Layout {
+    Export(ExportFormat),
+    Table(TableFormat),
+}
+
+fn export(format: &str, since: Option<&str>, columns: &[String]) -> anyhow::Result<()> {
+    let layout = match format.to_lowercase().as_str() {
+        "table" => Layout::Table(TableFormat::Table),
+        "tsv" => Layout::Table(TableFormat::Tsv),
+        "csv" | "jsonl" | "parquet" => Layout::Export(format.parse().map_err(anyhow::Error::msg)?),
+        _ => anyhow::bail!("Invalid format: {format}. Use: csv, jsonl, table, tsv"),
+    };
+    if !columns.is_empty() && matches!(layout, Layout::Export(_)) {
+        anyhow::bail!("--columns applies to --format table or tsv");
+    }
+    if let Some(since) = since {
+        chrono::NaiveDate::parse_from_str(since, "%Y-%m-%d")
+            .map_err(|_| anyhow::anyhow!("Invalid --since date: {since} (expected YYYY-MM-DD)"))?;
+    }
+
+    let store = storage::ack_store();
+    let mut rows = Vec::new();
+    for (sha, committed_at) in git::staged::log_commits(since)? {
+        let records = ledger::added_in_commit(&sha)?;
+        let mut acks: Vec<(Acknowledgment, bool)> =
+            records.into_iter().map(|ack| (ack, true)).collect();
+        for trailer in store.parse_from_commit(&sha)? {
+            // Trailers carry the message with '|' replaced
+            let recorded = acks.iter().any(|(a, _)| {
+                a.check_id == trailer.check_id && a.message.replace('|', "-") == trailer.message
+            });
+            if !recorded {
+                acks.push((trailer, false));
+            }
+        }
+        if acks.is_empty() {
+            continue;
+        }
+
+        let files: Vec<String> = git::staged::commit_changes(&sha, noslop_file::submodule_mode())?
+            .into_iter()
+            .map(|c| c.path)
+            .filter(|p| !p.starts_with(".noslop/"))
+            .collect();
+        for (ack, from_ledger) in acks {
+            rows.push(ExportRow {
+                commit: sha.clone(),
+                check_id: ack.check_id,
+                acknowledged_by: ack.acknowledged_by,
+                timestamp: if from_ledger {
+                    ack.created_at
+                } else {
+                    committed_at.clone()
+                },
+                files: files.clone(),
+                message: ack.message,
+            });
+        }
+    }
+
+    match layout {
+        Layout::Export(format) => print!("{}", encode(&rows, format)),
+        Layout::Table(format) => {
+            let mut table = Table::new(&[
+                "commit",
+                "check_id",
+                "acknowledged_by",
+                "timestamp",
+                "files",
+                "message",
+            ]);
+            for row in rows {
+                table.push(vec![
+                    row.commit,
+                    row.check_id,
+                    row.acknowledged_by,
+                    row.timestamp,
+                    row.files.join(","),
+                    row.message,
+                ]);
+            }
+            if !columns.is_empty() {
+                table = table.select(columns).map_err(anyhow::Error::msg)?;
+            }
+            print!("{}", table.render(format));
+        },
+    }
+    Ok(())
+}
@@ -0,0 +1,172 @@
+//! Hooks command - install or remove noslop's git hooks, or run one by hand
+//!
+//! Installing never overwrites a hook noslop did not write: its script is
+//! chained after the existing one, and each hook reports the file it
+//! touched.
+//!
+//! `run` executes the hook script exactly as installed, from the
+//! repository root with the index git would point it at, and narrates each
+//! step, so hook behaviour can be debugged without crafting throwaway
+//! commits.
+
+use std::path::Path;
+use std::process::Command;
+
+use super::Blocked;
+use crate::cli::app::HooksAction;
+use crate::git;
+use crate::git::hooks::{HookInstall, HookRemoval};
+use noslop::adapters::git::{git_path, hook_path, repo_root_or_cwd};
+
+/// Handle hooks subcommands
+pub fn hooks(action: HooksAction) -> anyhow::Result<()> {
+    match action {
+        HooksAction::Install {
+            commit_template,
+            only,
+        } => install(commit_template, &only),
+        HooksAction::Uninstall { hooks } => uninstall(&hooks),
+        HooksAction::Run {
+            hook,
+            message_file,
+            args,
+        } => run(&hook, message_file.as_deref(), &args),
+    }
+}
+
+/// Install the pre-commit, commit-msg and post-commit hooks (or the
+/// `only` ones), plus prepare-commit-msg with `commit_template`
+fn install(commit_template: bool, only: &[String]) -> anyhow::Result<()> {
+    let mut names: Vec<&str> = if only.is_empty() {
+        git::hooks::DEFAULT_HOOKS.to_vec()
+    } else {
+        only.iter().map(|h| h.trim()).collect()
+    };
+    if commit_template && !names.contains(&"prepare-commit-msg") {
+        names.push("prepare-commit-msg");
+    }
+    install_each(&names, "")
+}
+
+/// Install each hook in `names`, printing what was written where
+pub fn install_each(names: &[&str], indent: &str) -> anyhow::Result<()> {
+    for name in names {
+        let outcome = git::hooks::install(name)?;
+        let path = hook_path(name)?;
+        let path = path.display();
+        match outcome {
+            HookInstall::Created => println!("{indent}Installed {name} hook: wrote {path}"),
+            HookInstall::Chained => println!(
+                "{indent}Installed {name} hook: appended to the existing {path} (its commands run first)"
+            ),
+            HookInstall::AlreadyInstalled => {
+                println!("{indent}{name} hook already calls noslop: {path} unchanged");
+            },
+        }
+    }
+    Ok(())
+}
+
+/// Remove noslop from each hook in `names`
+fn uninstall(names: &[String]) -> anyhow::Result<()> {
+    for name in names {
+        let outcome = git::hooks::uninstall(name)?;
+        let path = hook_path(name)?;
+        let path = path.display();
+        match outcome {
+            HookRemoval::Removed => println!("Removed {name} hook: deleted {path}"),
+            HookRemoval::Unchained => {
+                println!("Removed noslop from {name} hook: {path} keeps its other commands");
+            },
+            HookRemoval::NotInstalled => {
+                println!("{name} hook does not call noslop: {path} unchanged")
+            },
+        }
+    }
+    Ok(())
+}
+
+/// Run the installed `hook` with git's arguments for it
+fn run(hook: &str, message_file: Option<&str>, extra: &[String]) -> anyhow::Result<()> {
+    let path = hook_path(hook)?;
+    if !path.is_file() {
+        anyhow::bail!(
+            "No {hook} hook installed at {}; 'noslop init' installs noslop's hooks",
+            path.display()
+        );
+    }
+    let root = repo_root_or_cwd();
+    let script = std::fs::read_to_string(&path).unwrap_or_default();
+
+    // commit-msg gets the message file as $1, like git passes it
+    let message_file = match (hook, message_file) {
+        (_, Some(file)) => Some(std::path::absolute(file)?),
+        ("commit-msg", None) => {
+            anyhow::bail!("commit-msg takes the commit message file: --message-file <path>")
+        },
+        _ => None,
+    };
+    let mut args: Vec<String> =
+        message_file.iter().map(|f| f.to_string_lossy().to_string()).collect();
+    args.extend(extra.iter().cloned());
+
+    println!("Hook:      {}", path.display());
+    println!(
+        "noslop:    {}",
+        if script.contains("noslop") {
+            "installed in this hook"
+        } else {
+            "not in this hook (only other tooling runs)"
+        }
+    );
+    println!("Directory: {}", root.display());
+    println!(
+        "Arguments: {}",
+        if args.is_empty() {
+            "(none)".to_string()
+        } else {
+            args.join(" ")
+        }
+    );
+    // The index file git hands commit hooks (`GIT_INDEX_FILE`)
+    let index = git_path("index");
+    if let Some(index) = &index {
+        println!("Index:     {}", index.display());
+    }
+    if let Some(file) = &message_file {
+        print_message("Message before:", file);
+    }
+
+    println!("\n--- {hook} output ---");
+    let mut command = Command::new(&path);
+    command.args(&args).current_dir(&root);
+    if let Some(index) = &index {
+        command.env("GIT_INDEX_FILE", index);
+    }
+    let status = command.status()?;
+    println!("--- end of {hook} output ---\n");
+
+    if let Some(file) = &message_file {
+        print_message("Message after:", file);
+    }
+
+    let code = status.code().map_or_else(|| "signal".to_string(), |c| c.to_string());
+    if status.success() {
+        println!("Exit: {code} (git would continue)");
+        Ok(())
+    } else if hook.starts_with("post-") {
+        println!("Exit: {code} (git ignores {hook} failures)");
+        Ok(())
+    } else {
+        println!("Exit: {code} (git would abort)");
+        Err(Blocked { quiet: true }.into())
+    }
+}
+
+/// Print a commit message file, indented
+fn print_message(label: &str, file: &Path) {
+    println!("{label}");
+    for line in std::fs::read_to_string(file).unwrap_or_default().lines() {
+        println!("    {line}");
+    }
+}
@@ -0,0 +1,118 @@
+//! Import-reviews command - count approvals given elsewhere as acks
+//!
+//! Where reviews already happen in GitHub or Gerrit, asking the reviewer
+//! to ack the same checks again is double work. `[reviews.labels]` maps
+//! approvals (a reviewer, a team, a review label) to the checks they
+//! answer; `noslop import-reviews --from github-review 123` stages an ack
+//! for each, linking the review and naming the reviewer, to be committed
+//! like any other.
+//!
+//! The acks are signed by whoever runs the import, never as a human on the
+//! reviewer's behalf: an approval does not satisfy `requires_human` by
+//! itself. Only approvals given on the work being committed count: the
+//! reviewed commit must be HEAD or have the staged tree.
+
+use serde::Serialize;
+
+use super::ack::answered_checks;
+use crate::noslop_file;
+use noslop::adapters::{detect_actor, gerrit, gh, ledger};
+use noslop::core::models::Acknowledgment;
+use noslop::core::services::reviews::{ExternalApproval, imported_reviews};
+use noslop::output::OutputMode;
+use noslop::storage;
+
+/// An ack staged from a review
+#[derive(Debug, Serialize)]
+struct Imported {
+    check: String,
+    reviewer: String,
+    label: String,
+    url: String,
+}
+
+/// Stage acks for the checks the approvals on `reference` answer
+pub fn import_reviews(from: &str, reference: &str, mode: OutputMode) -> anyhow::Result<()> {
+    let config = noslop_file::reviews_config();
+    if config.labels.is_empty() {
+        anyhow::bail!(
+            "No [reviews.labels] in .noslop.toml: map approvals to the checks they answer first"
+        );
+    }
+
+    let mut approvals: Vec<ExternalApproval> = match from {
+        "github-review" => gh::pr_approvals(&gh::repo_slug()?, reference)?,
+        "gerrit" => {
+            let Some(url) = config.gerrit_url.as_deref() else {
+                anyhow::bail!("Set gerrit_url under [reviews] to import Gerrit reviews");
+            };
+            gerrit::change_approvals(url, reference)?
+        },
+        other => anyhow::bail!("Unknown review system '{other}' (use github-review or gerrit)"),
+    };
+    // Gerrit groups are not resolved: its approvals match reviewers and labels
+    let in_team =
+        |team: &str, login: &str| from == "github-review" && gh::is_team_member(team, login);
+
+    let tree_oid = crate::git::staged::staged_tree_oid().ok();
+    let head = crate::git::staged::resolve_rev("HEAD").ok();
+    approvals.retain(|approval| {
+        let reviewed = approval.revision.as_deref().is_some_and(|rev| {
+            head.as_deref() == Some(rev)
+                || (tree_oid.is_some()
+                    && crate::git::staged::resolve_rev(&format!("{rev}^{{tree}}")).ok() == tree_oid)
+        });
+        if !reviewed {
+            let rev = approval
+                .revision
+                .as_deref()
+                .map_or("an unknown revision", |rev| rev.get(..7).unwrap_or(rev));
+            eprintln!(
+                "noslop: ignoring {}'s approval: {rev} is neither HEAD nor the staged tree",
+                approval.reviewer
+            );
+        }
+        reviewed
+    });
+
+    let actor = detect_actor();
+    let store = storage::ack_store();
+    let mut imported = Vec::new();
+    for review in imported_reviews(&approvals, &config.labels, in_team) {
+        let approval = review.approval;
+        let message = format!(
+            "Approved in review by {} ({}): {}",
+            approval.reviewer, review.label, approval.url
+        );
+        if let Err(e) = answered_checks(&review.check, &message) {
+            eprintln!("noslop: not importing {}: {e}", review.check);
+            continue;
+        }
+        let ack = Acknowledgment::by_actor(review.check.clone(), message, &actor)
+            .with_identity(Some(approval.reviewer.clone()))
+            .with_tree_oid(tree_oid.clone());
+        store.stage(&ack)?;
+        ledger::record(&ack)?;
+        imported.push(Imported {
+            check: review.check,
+            reviewer: approval.reviewer.clone(),
+            label: review.label,
+            url: approval.url.clone(),
+        });
+    }
+
+    if mode == OutputMode::Json {
+        println!("{}", serde_json::to_string_pretty(&imported)?);
+        return Ok(());
+    }
+    if imported.is_empty() {
+        println!("No approval on {reference} answers a check in [reviews.labels].");
+        return Ok(());
+    }
+    println!("Staged {} acknowledgment(s) from {from} {reference}:", imported.len());
+    for i in &imported {
+        println!("  {}  approved by {} ({})", i.check, i.reviewer, i.label);
+    }
+    println!("\nRun 'git commit' to finalize.");
+    Ok(())
+}
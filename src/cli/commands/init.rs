@@ -12,9 +12,21 @@ use noslop::output::OutputMode;
 /// `--force`). Hooks and `.noslop/` are per-clone and are ALWAYS ensured —
 /// a fresh clone of an initialized repo must still get its hooks.
 pub fn init(force: bool, _mode: OutputMode) -> anyhow::Result<()> {
-    let noslop_path = Path::new(".noslop.toml");
-
     println!("Initializing noslop...\n");
+    setup(force)?;
+
+    println!("\nnoslop initialized!");
+    println!("\nNext steps:");
+    println!("  noslop check add <target> -m \"message\"");
+    println!("  git commit  # checks will be validated");
+
+    Ok(())
+}
+
+/// Write the config (when absent, or `force`), per-clone state, and hooks,
+/// printing one line per step
+pub fn setup(force: bool) -> anyhow::Result<()> {
+    let noslop_path = Path::new(".noslop.toml");
 
     if noslop_path.exists() && !force {
         println!("  Kept existing .noslop.toml (use --force to overwrite)");
@@ -44,7 +56,11 @@ prefix = "{prefix}"
         fs::write(noslop_path, noslop_toml)?;
         println!("  Created .noslop.toml");
     }
+    setup_clone()
+}
 
+/// Per-clone state and hooks, printing one line per step
+pub fn setup_clone() -> anyhow::Result<()> {
     // Create .noslop/ for acknowledgments (pending until committed)
     fs::create_dir_all(".noslop")?;
     fs::write(".noslop/.gitkeep", "")?;
@@ -53,7 +69,14 @@ prefix = "{prefix}"
     // Staged acks and fire telemetry are per-clone state; the ledger and
     // history are tracked
     ensure_line(".gitignore", ".noslop/staged-acks.json")?;
+    ensure_line(".gitignore", ".noslop/consumed-acks.json")?;
     ensure_line(".gitignore", ".noslop/events.jsonl")?;
+    ensure_line(".gitignore", ".noslop/metrics.jsonl")?;
+    ensure_line(".gitignore", ".noslop/cache/")?;
+    ensure_line(".gitignore", ".noslop/last-gate")?;
+    ensure_line(".gitignore", ".noslop/bypasses.jsonl")?;
+    ensure_line(".gitignore", ".noslop/identity")?;
+    ensure_line(".gitignore", ".noslop/pending.md")?;
     println!("  Ensured .gitignore covers per-clone .noslop/ state");
 
     // Parallel branches both append to history.jsonl; union merge never conflicts
@@ -61,17 +84,7 @@ prefix = "{prefix}"
     println!("  Ensured .gitattributes union-merges .noslop/history.jsonl");
 
     // Install git hooks
-    git::hooks::install_pre_commit()?;
-    println!("  Installed pre-commit hook");
-    git::hooks::install_commit_msg()?;
-    println!("  Installed commit-msg hook");
-    git::hooks::install_post_commit()?;
-    println!("  Installed post-commit hook");
-
-    println!("\nnoslop initialized!");
-    println!("\nNext steps:");
-    println!("  noslop check add <target> -m \"message\"");
-    println!("  git commit  # checks will be validated");
+    super::hooks::install_each(&git::hooks::DEFAULT_HOOKS, "  ")?;
 
     Ok(())
 }
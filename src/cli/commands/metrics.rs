@@ -0,0 +1,48 @@
+//! Metrics command - friction report from opt-in local command metrics
+//!
+//! Reads `.noslop/metrics.jsonl`, which is only written when the repo sets
+//! `[metrics] enabled = true`. Nothing is sent anywhere.
+
+use crate::cli::app::MetricsAction;
+use crate::noslop_file;
+use noslop::adapters::telemetry;
+use noslop::core::services::metrics::summarize;
+use noslop::output::OutputMode;
+
+/// Handle metrics subcommands
+pub fn metrics(action: MetricsAction, mode: OutputMode) -> anyhow::Result<()> {
+    match action {
+        MetricsAction::Report => report(mode),
+    }
+}
+
+fn report(mode: OutputMode) -> anyhow::Result<()> {
+    let rows = summarize(&telemetry::load_metrics()?);
+
+    if mode == OutputMode::Json {
+        println!("{}", serde_json::json!({ "commands": rows }));
+        return Ok(());
+    }
+
+    if rows.is_empty() {
+        if noslop_file::metrics_enabled() {
+            println!("No metrics recorded yet.");
+        } else {
+            println!("Metrics are off. Opt in by adding to .noslop.toml:");
+            println!("\n[metrics]\nenabled = true");
+        }
+        return Ok(());
+    }
+
+    println!(
+        "{:<14} {:>6} {:>8} {:>7} {:>10} {:>8}",
+        "COMMAND", "RUNS", "BLOCKED", "ERRORS", "MEDIAN_MS", "MAX_MS"
+    );
+    for r in &rows {
+        println!(
+            "{:<14} {:>6} {:>8} {:>7} {:>10} {:>8}",
+            r.command, r.runs, r.blocked, r.errors, r.median_ms, r.max_ms
+        );
+    }
+    Ok(())
+}
@@ -2,24 +2,72 @@
 
 mod ack;
 mod add_trailers;
+mod agent;
+mod alias;
+mod approve;
 mod check_manage;
 mod check_validate;
+mod ci;
 mod clear_staged;
 mod compact;
+mod config;
 mod curate;
 mod discover;
+mod doctor;
 mod envelope;
+mod gc;
+mod help_topic;
+mod history;
+mod hooks;
+mod import_reviews;
 mod init;
+mod metrics;
+mod onboard;
+mod prepare_message;
+mod quarantine;
+mod sandbox;
+mod scaffold;
+mod search;
+mod self_update;
+mod snapshot;
+mod staged;
 mod stats;
+mod status;
+mod verify_commit;
+mod whoami;
 
 pub use ack::ack;
 pub use add_trailers::add_trailers;
+pub use agent::agent;
+pub use alias::alias;
+pub use approve::approve;
 pub use check_manage::check_manage;
-pub use check_validate::check_validate;
+pub use check_validate::{Blocked, check_compare, check_listed, check_range, check_validate};
+pub use ci::ci;
 pub use clear_staged::clear_staged;
 pub use compact::compact;
+pub use config::config;
 pub use curate::curate;
 pub use discover::discover;
+pub use doctor::doctor;
 pub use envelope::envelope;
+pub use gc::gc;
+pub use help_topic::help_topic;
+pub use history::history;
+pub use hooks::hooks;
+pub use import_reviews::import_reviews;
 pub use init::init;
+pub use metrics::metrics;
+pub use onboard::onboard;
+pub use prepare_message::prepare_message;
+pub use quarantine::quarantine;
+pub use sandbox::sandbox;
+pub use scaffold::scaffold;
+pub use search::search;
+pub use self_update::self_update;
+pub use snapshot::snapshot;
+pub use staged::staged;
 pub use stats::stats;
+pub use status::status;
+pub use verify_commit::verify_commit;
+pub use whoami::whoami;
@@ -0,0 +1,148 @@
+//! Onboard command - guided first-time setup
+//!
+//! Walks a new user through what `init`, a first check, and a first ack
+//! look like in their own repository: it inspects the repo, proposes a
+//! starter check, writes the config and hooks, shows the commit an agent
+//! would be stopped at, and ends with the `doctor` checks.
+
+use std::collections::BTreeMap;
+use std::io::{BufRead, Write};
+
+use super::check_validate::{current_branch, to_check_match};
+use super::doctor;
+use crate::{git, noslop_file};
+use noslop::adapters::rules::find_rules_files;
+use noslop::core::models::{ChangeKind, FileChange, Severity};
+use noslop::core::services::check_items;
+use noslop::core::services::interpolate::interpolate_messages;
+use noslop::output::{CheckResult, OutputMode};
+
+/// Extensions that are rarely where conventions bite
+const NON_SOURCE: &[&str] = &["md", "txt", "json", "lock", "toml", "yml", "yaml", "cfg", "ini"];
+
+/// Run the guided setup; `yes` accepts every default without asking
+pub fn onboard(yes: bool) -> anyhow::Result<()> {
+    let stdin = std::io::stdin();
+    let mut lines = stdin.lock().lines();
+    let mut confirm = |question: &str| -> anyhow::Result<bool> {
+        print!("{question} [Y/n] ");
+        if yes {
+            println!("y");
+            return Ok(true);
+        }
+        std::io::stdout().flush()?;
+        let answer = lines.next().transpose()?.unwrap_or_default();
+        Ok(matches!(answer.trim().to_lowercase().as_str(), "" | "y" | "yes"))
+    };
+
+    println!("Step 1/4: looking at this repository\n");
+    let root = noslop::adapters::git::repo_root_or_cwd();
+    let tracked = git::staged::tracked_files()?;
+    println!("  {} tracked file(s)", tracked.len());
+    let rules = find_rules_files(&root)?;
+    if !rules.is_empty() {
+        let names: Vec<&str> = rules.iter().map(|r| r.name.as_str()).collect();
+        println!("  Rules files: {} (import them later with 'noslop discover')", names.join(", "));
+    }
+    let has_config = root.join(".noslop.toml").exists();
+    let starter = if has_config {
+        println!("  Existing .noslop.toml kept as is");
+        None
+    } else {
+        let target = main_extension(&tracked).map_or_else(|| "*".to_string(), |e| format!("*.{e}"));
+        println!("\n  Starter check:");
+        println!("    target   = \"{target}\"");
+        println!("    message  = \"Tests cover the change to {{file}}?\"");
+        println!("    severity = \"block\"");
+        Some(target)
+    };
+
+    println!("\nStep 2/4: config and hooks\n");
+    if !confirm("Write the config and install the git hooks?")? {
+        println!("\nStopped; nothing was changed. Run 'noslop onboard' again any time.");
+        return Ok(());
+    }
+    println!();
+    super::init::setup(false)?;
+    if let Some(target) = &starter {
+        let id =
+            noslop_file::add_check(target, "Tests cover the change to {file}?", Severity::Block)?;
+        println!("  Added starter check {id}");
+    }
+
+    println!("\nStep 3/4: what an agent sees\n");
+    demo_blocked_commit(&tracked)?;
+
+    println!("\nStep 4/4: verifying the setup\n");
+    let findings = doctor::diagnose();
+    doctor::render(&findings, OutputMode::Human);
+    if findings.iter().all(|f| f.ok) {
+        println!("\nAll set. Commit as usual; 'noslop help-topic acks' explains the workflow.");
+    } else {
+        println!("\nFix the items above, then run 'noslop doctor'.");
+    }
+    Ok(())
+}
+
+/// Most common source extension among tracked files
+fn main_extension(files: &[String]) -> Option<String> {
+    let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+    for file in files {
+        let name = file.rsplit('/').next().unwrap_or(file);
+        if let Some((stem, ext)) = name.rsplit_once('.')
+            && !stem.is_empty()
+            && !NON_SOURCE.contains(&ext)
+        {
+            *counts.entry(ext).or_default() += 1;
+        }
+    }
+    counts.into_iter().max_by_key(|(_, n)| *n).map(|(ext, _)| ext.to_string())
+}
+
+/// Render the check report an agent would get for changing the first
+/// tracked file a check covers, and how it answers
+fn demo_blocked_commit(tracked: &[String]) -> anyhow::Result<()> {
+    let mut fired = None;
+    for file in tracked {
+        let change = FileChange::new(file.as_str(), ChangeKind::Modify);
+        if let Some(hit) = noslop_file::load_checks_for_changes(&[change])?.into_iter().next() {
+            fired = Some(hit);
+            break;
+        }
+    }
+    let Some((check, file)) = fired else {
+        println!("  No tracked file matches a check yet; add one with 'noslop check add'.");
+        return Ok(());
+    };
+
+    println!("  If an agent committed a change to {file}, the pre-commit hook would print:\n");
+    let mut core = check_items(&[(check, file)], &[], 1);
+    interpolate_messages(&mut core, current_branch().as_deref());
+    let report = CheckResult {
+        passed: core.passed,
+        files_checked: 1,
+        actor: "claude-code".to_string(),
+        enforced: true,
+        tree_oid: None,
+        check_set_version: None,
+        check_set_age_seconds: None,
+        blocking: core.blocking.iter().map(to_check_match).collect(),
+        warnings: core.warnings.iter().map(to_check_match).collect(),
+        acknowledged: Vec::new(),
+        monitor: Vec::new(),
+        misses: Vec::new(),
+        hooks: Vec::new(),
+        comparison: None,
+        range: None,
+    };
+    report.render(OutputMode::Human);
+
+    if let Some(item) = core.blocking.first().or_else(|| core.warnings.first()) {
+        println!("\n  The agent checks the change, then answers:\n");
+        println!("    noslop ack {} -m \"added tests for the new branch\"", item.id);
+        println!("\n  and the commit goes through carrying the receipt:\n");
+        println!("    Noslop-Ack: {} | added tests for the new branch | claude-code", item.id);
+        println!("\n  A person committing sees the same check as guidance and is not blocked.");
+    }
+    Ok(())
+}
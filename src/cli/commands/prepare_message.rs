@@ -0,0 +1,78 @@
+//! Prepare the commit message editor
+//!
+//! This command is called by the prepare-commit-msg hook (installed with
+//! `noslop hooks install --commit-template`) to list the checks on the
+//! staged changes as comments in the message, so they are in view while
+//! the message is written.
+
+use std::fs;
+use std::path::Path;
+
+use super::check_validate::current_branch;
+use crate::{git, noslop_file};
+use noslop::core::services::interpolate::interpolate_messages;
+use noslop::core::services::{CheckItemResult, check_items};
+use noslop::storage;
+
+/// Append the pending checks as comments to the commit message file
+///
+/// Only for messages written in the editor: git passes no `source` for a
+/// plain `git commit` and `template` with `commit.template`. Messages from
+/// `-m`, merges, squashes and amends are left alone. Best-effort: a
+/// failure never stops the commit.
+pub fn prepare_message(commit_msg_file: &str, source: Option<&str>) -> anyhow::Result<()> {
+    if !matches!(source, None | Some("template")) {
+        return Ok(());
+    }
+    let Ok(summary) = pending_summary() else {
+        return Ok(());
+    };
+    if summary.is_empty() {
+        return Ok(());
+    }
+
+    let path = Path::new(commit_msg_file);
+    let mut msg = fs::read_to_string(path)?;
+    if !msg.is_empty() && !msg.ends_with('\n') {
+        msg.push('\n');
+    }
+    msg.push_str(&summary);
+    fs::write(path, msg)?;
+    Ok(())
+}
+
+/// Commented list of the checks the staged changes fire, or empty when
+/// none do
+fn pending_summary() -> anyhow::Result<String> {
+    let changes = git::staged::get_staged_changes(noslop_file::submodule_mode())?;
+    if changes.is_empty() {
+        return Ok(String::new());
+    }
+    let mut applicable = noslop_file::load_checks_for_changes(&changes)?;
+    let stats = git::staged::change_stats(None)?;
+    applicable.extend(noslop_file::load_commit_checks(&changes, &stats)?);
+    let acks = storage::ack_store().staged()?;
+    let mut result = check_items(&applicable, &acks, changes.len());
+    interpolate_messages(&mut result, current_branch().as_deref());
+
+    let open: Vec<&CheckItemResult> = result.blocking.iter().chain(&result.warnings).collect();
+    if open.is_empty() && result.acknowledged.is_empty() {
+        return Ok(String::new());
+    }
+
+    let mut out = String::from("#\n# noslop: checks on the staged changes\n");
+    for item in &open {
+        out.push_str(&format!(
+            "#   {:<5}  {}  {}: {}\n",
+            item.severity, item.id, item.file, item.message
+        ));
+    }
+    for item in &result.acknowledged {
+        out.push_str(&format!("#   acked  {}  {}\n", item.id, item.file));
+    }
+    if !open.is_empty() {
+        out.push_str("# Answer with 'noslop ack <id> -m \"...\"' before committing,\n");
+        out.push_str("# or with a line '[ack <id>: <message>]' in this message.\n");
+    }
+    Ok(out)
+}
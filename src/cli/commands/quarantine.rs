@@ -0,0 +1,197 @@
+//! Quarantine command - ship past blocking checks, answer for it later
+//!
+//! `quarantine commit` commits the staged changes to a new
+//! `quarantine/<branch>-<sha>` branch without running the gate, and leaves
+//! HEAD on it. The commit carries a `Noslop-Quarantine: <branch>` trailer
+//! naming the branch it came from, so the record of what is pending review
+//! travels with the commit. `quarantine promote` merges the branch back
+//! once every blocking check its changes raise has an acknowledgment.
+
+use serde::Serialize;
+
+use super::check_validate::{Blocked, range_result};
+use crate::cli::app::QuarantineAction;
+use crate::{git, noslop_file};
+use noslop::core::services::check_items;
+use noslop::output::{CheckResult, OutputMode};
+use noslop::storage;
+
+/// Trailer naming the branch a quarantined commit was taken from
+const QUARANTINE_TRAILER: &str = "Noslop-Quarantine";
+
+/// Prefix of the branches `quarantine commit` creates
+const BRANCH_PREFIX: &str = "quarantine/";
+
+/// Handle quarantine subcommands
+pub fn quarantine(action: QuarantineAction, mode: OutputMode) -> anyhow::Result<()> {
+    match action {
+        QuarantineAction::Commit { message } => commit(&message),
+        QuarantineAction::List => list(mode),
+        QuarantineAction::Promote { branch } => promote(branch.as_deref(), mode),
+    }
+}
+
+/// A quarantine branch and what still keeps it from being promoted
+#[derive(Debug, Serialize)]
+struct Quarantined {
+    branch: String,
+    /// The branch it was taken from, and is promoted into
+    origin: String,
+    /// Blocking checks without an acknowledgment yet
+    pending: Vec<String>,
+}
+
+fn commit(message: &str) -> anyhow::Result<()> {
+    let Some(origin) = super::check_validate::current_branch() else {
+        anyhow::bail!("HEAD is detached; quarantine commits are taken from a branch");
+    };
+    let changes = git::staged::get_staged_changes(noslop_file::submodule_mode())?;
+    if changes.is_empty() {
+        anyhow::bail!("No staged changes to quarantine");
+    }
+    let store = storage::ack_store();
+    let acks = store.staged()?;
+    let mut applicable = noslop_file::load_checks_for_changes(&changes)?;
+    let stats = git::staged::change_stats(None)?;
+    applicable.extend(noslop_file::load_commit_checks(&changes, &stats)?);
+    let result = check_items(&applicable, &acks, changes.len());
+    let mut pending: Vec<String> = result.blocking.iter().map(|item| item.id.clone()).collect();
+    pending.sort();
+    pending.dedup();
+    if pending.is_empty() {
+        anyhow::bail!("No blocking check is left unanswered; commit normally");
+    }
+
+    // Commit the index as it stands, without hooks: the gate would block it
+    let mut trailers = format!("{QUARANTINE_TRAILER}: {origin}");
+    let answered = store.format_trailers(&acks);
+    if !answered.is_empty() {
+        trailers = format!("{trailers}\n{answered}");
+    }
+    let tree = git::staged::staged_tree_oid()?;
+    let sha = git::staged::commit_tree(&tree, "HEAD", message, &trailers)?;
+    let branch = format!("{BRANCH_PREFIX}{origin}-{}", &sha[..sha.len().min(7)]);
+    git::staged::create_branch(&branch, &sha)?;
+    // The index already holds the new commit's tree: moving HEAD is enough
+    git::staged::point_head_at(&branch)?;
+    store.clear_staged()?;
+
+    println!(
+        "Committed {} to {branch}, pending review of {} check(s): {}",
+        &sha[..7],
+        pending.len(),
+        pending.join(", ")
+    );
+    println!(
+        "HEAD is now on {branch}. Acknowledge them with 'noslop ack', then run 'noslop quarantine promote'."
+    );
+    Ok(())
+}
+
+fn list(mode: OutputMode) -> anyhow::Result<()> {
+    let mut quarantined = Vec::new();
+    for branch in git::staged::branches_with_prefix(BRANCH_PREFIX)? {
+        let Some(origin) = origin_of(&branch)? else {
+            continue;
+        };
+        let result = verdict(&origin, &branch)?;
+        quarantined.push(Quarantined {
+            branch,
+            origin,
+            pending: blocking_ids(&result),
+        });
+    }
+
+    if mode == OutputMode::Json {
+        println!("{}", serde_json::to_string_pretty(&quarantined)?);
+        return Ok(());
+    }
+    if quarantined.is_empty() {
+        println!("No quarantined commits.");
+        return Ok(());
+    }
+    println!("Quarantined ({}):", quarantined.len());
+    for q in &quarantined {
+        let state = if q.pending.is_empty() {
+            "ready to promote".to_string()
+        } else {
+            format!("needs {}", q.pending.join(", "))
+        };
+        println!("  {}  (from {})  {state}", q.branch, q.origin);
+    }
+    Ok(())
+}
+
+/// Commit the staged acks on the quarantine branch, then merge it into
+/// its origin branch and delete it
+///
+/// Nothing is committed or merged while a blocking check is unanswered;
+/// the verdict is rendered as `noslop check` would and the run exits 1.
+fn promote(branch: Option<&str>, mode: OutputMode) -> anyhow::Result<()> {
+    let current = super::check_validate::current_branch();
+    let Some(branch) = branch.map(String::from).or_else(|| current.clone()) else {
+        anyhow::bail!("HEAD is detached; name the quarantine branch to promote");
+    };
+    let Some(origin) = origin_of(&branch)? else {
+        anyhow::bail!("'{branch}' is not a quarantine branch (no {QUARANTINE_TRAILER} trailer)");
+    };
+
+    // Ack records under .noslop/ are the only changes promote commits
+    if git::staged::tracked_changes_outside(".noslop")? {
+        anyhow::bail!("Commit or stash local changes before promoting {branch}");
+    }
+
+    let store = storage::ack_store();
+    let acks = store.staged()?;
+    if !acks.is_empty() {
+        if current.as_deref() != Some(branch.as_str()) {
+            anyhow::bail!(
+                "{} acknowledgment(s) are staged; switch to {branch} to promote with them",
+                acks.len()
+            );
+        }
+        let base = git::staged::merge_base(&origin, &branch)?;
+        let result = range_result(&base, &branch, &acks)?;
+        if !result.blocking.is_empty() {
+            result.render(mode);
+            return Err(Blocked { quiet: false }.into());
+        }
+        // The ack records `noslop ack` staged go in with the trailers
+        git::staged::commit_unverified(
+            &format!("Acknowledge checks quarantined on {branch}"),
+            &store.format_trailers(&acks),
+        )?;
+        store.clear_staged()?;
+    }
+
+    let result = verdict(&origin, &branch)?;
+    if !result.blocking.is_empty() {
+        result.render(mode);
+        return Err(Blocked { quiet: false }.into());
+    }
+    git::staged::switch_branch(&origin)?;
+    git::staged::merge_no_ff(&branch, &format!("Promote quarantined {branch} into {origin}"))?;
+    git::staged::delete_branch(&branch)?;
+    let sha = git::staged::resolve_rev("HEAD")?;
+    println!("Promoted {branch} into {origin} ({})", &sha[..7]);
+    Ok(())
+}
+
+/// The branch a quarantine branch was taken from: the newest
+/// `Noslop-Quarantine` trailer in its history
+fn origin_of(branch: &str) -> anyhow::Result<Option<String>> {
+    Ok(git::staged::trailer_values(branch, QUARANTINE_TRAILER)?.into_iter().next())
+}
+
+/// The verdict on what `branch` changed since it left `origin`
+fn verdict(origin: &str, branch: &str) -> anyhow::Result<CheckResult> {
+    let base = git::staged::merge_base(origin, branch)?;
+    range_result(&base, branch, &[])
+}
+
+fn blocking_ids(result: &CheckResult) -> Vec<String> {
+    let mut ids: Vec<String> = result.blocking.iter().map(|m| m.id.clone()).collect();
+    ids.sort();
+    ids.dedup();
+    ids
+}
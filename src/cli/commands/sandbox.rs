@@ -0,0 +1,101 @@
+//! Sandbox command - dry-run the hook pipeline on the staged changes
+//!
+//! Runs what the installed hooks would run (pre-commit gate, commit-msg
+//! trailers, the commit, post-commit cleanup) in a throwaway worktree, so
+//! hook interactions can be debugged without touching the real index,
+//! staged acks, or history.
+
+use std::path::Path;
+use std::process::Command;
+
+use noslop::adapters::git::sandbox::Sandbox;
+use noslop::output::OutputMode;
+
+/// One pipeline step and what it did
+struct Step {
+    name: &'static str,
+    ok: bool,
+    output: String,
+}
+
+/// Simulate `git commit -m <message>` with the noslop hooks, and report
+pub fn sandbox(message: &str, mode: OutputMode) -> anyhow::Result<()> {
+    if crate::git::get_staged_files()?.is_empty() {
+        println!("No staged changes; nothing to simulate.");
+        return Ok(());
+    }
+
+    let sandbox = Sandbox::create()?;
+    let exe = std::env::current_exe()?;
+    let msg_file = std::env::temp_dir().join(format!("noslop-sandbox-msg-{}", std::process::id()));
+    std::fs::write(&msg_file, format!("{message}\n"))?;
+    let msg_arg = msg_file.to_string_lossy().to_string();
+
+    let mut steps = Vec::new();
+    let pre_commit = run(sandbox.path(), "pre-commit", Command::new(&exe).arg("check"))?;
+    let committed = pre_commit.ok;
+    steps.push(pre_commit);
+    if committed {
+        steps.push(run(
+            sandbox.path(),
+            "commit-msg",
+            Command::new(&exe).args(["add-trailers", &msg_arg]),
+        )?);
+        steps.push(run(
+            sandbox.path(),
+            "commit",
+            Command::new("git").args(["commit", "--no-verify", "--quiet", "-F", &msg_arg]),
+        )?);
+        steps.push(run(sandbox.path(), "post-commit", Command::new(&exe).arg("clear-staged"))?);
+    }
+    let final_message = std::fs::read_to_string(&msg_file).unwrap_or_default();
+    let _ = std::fs::remove_file(&msg_file);
+    let would_commit = steps.iter().all(|s| s.ok);
+
+    if mode == OutputMode::Json {
+        let steps: Vec<serde_json::Value> = steps
+            .iter()
+            .map(|s| serde_json::json!({ "step": s.name, "ok": s.ok, "output": s.output }))
+            .collect();
+        println!(
+            "{}",
+            serde_json::json!({
+                "would_commit": would_commit,
+                "message": would_commit.then_some(final_message),
+                "steps": steps,
+            })
+        );
+        return Ok(());
+    }
+
+    println!("Sandbox run (nothing in this repository was changed):\n");
+    for step in &steps {
+        println!("{} {}", if step.ok { "ok  " } else { "FAIL" }, step.name);
+        for line in step.output.lines() {
+            println!("       {line}");
+        }
+    }
+    if would_commit {
+        println!("\nThe commit would be created with this message:\n");
+        for line in final_message.trim_end().lines() {
+            println!("    {line}");
+        }
+    } else if !committed {
+        println!("\nThe pre-commit hook would stop this commit.");
+    } else {
+        println!("\nThe commit would fail; see the failing step above.");
+    }
+    Ok(())
+}
+
+/// Run one step in the sandbox, capturing its combined output
+fn run(dir: &Path, name: &'static str, command: &mut Command) -> anyhow::Result<Step> {
+    let output = command.current_dir(dir).output()?;
+    let mut text = String::from_utf8_lossy(&output.stdout).into_owned();
+    text.push_str(&String::from_utf8_lossy(&output.stderr));
+    Ok(Step {
+        name,
+        ok: output.status.success(),
+        output: text.trim_end().to_string(),
+    })
+}
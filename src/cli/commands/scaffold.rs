@@ -0,0 +1,120 @@
+//! Scaffold command - start new services with their gates in place
+//!
+//! `noslop scaffold service payments-api` creates `payments-api/` with a
+//! `.noslop.toml`: the checks of an org pack (`--pack`, any file of
+//! `[[check]]` entries), renumbered under the service's own prefix, plus
+//! starter checks for the README and tests. Inside a repository the config
+//! nests under the repo's own, whose hooks already gate the new directory;
+//! elsewhere the directory becomes a repository of its own, initialized
+//! like `noslop init` with hooks installed.
+
+use std::fs;
+use std::path::Path;
+
+use crate::cli::app::ScaffoldAction;
+use crate::git;
+use noslop::VersionControl;
+use noslop::adapters::GitVersionControl;
+use noslop::adapters::git::init_repo;
+use noslop::adapters::toml::{
+    CheckEntry, When, format_noslop_file, generate_prefix_from_repo, load_file, parse_file,
+};
+use noslop::core::models::{CommitCondition, Severity, TargetKind};
+use noslop::output::OutputMode;
+
+/// Starter checks every service begins with: target and message
+const STARTER_CHECKS: [(&str, &str); 2] = [
+    (
+        "README.md",
+        "README says what this service does, how to run it, and who owns it?",
+    ),
+    ("src/", "Tests added or updated for this change?"),
+];
+
+/// Handle scaffold subcommands
+pub fn scaffold(action: ScaffoldAction, _mode: OutputMode) -> anyhow::Result<()> {
+    match action {
+        ScaffoldAction::Service { name, pack } => service(&name, pack.as_deref()),
+    }
+}
+
+fn service(name: &str, pack: Option<&str>) -> anyhow::Result<()> {
+    let dir = Path::new(name);
+    if dir.exists() && fs::read_dir(dir)?.next().is_some() {
+        anyhow::bail!("{name} already exists and is not empty");
+    }
+    let service = dir
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .ok_or_else(|| anyhow::anyhow!("Invalid service name '{name}'"))?;
+    let prefix = generate_prefix_from_repo(&service);
+    let in_repo = GitVersionControl::default().repo_root().is_ok();
+
+    let mut file = parse_file("")?;
+    file.root = !in_repo;
+    file.project.prefix.clone_from(&prefix);
+    if let Some(pack) = pack {
+        let pack = load_file(Path::new(pack)).map_err(|e| anyhow::anyhow!("Pack {pack}: {e}"))?;
+        file.checks.extend(pack.checks.into_iter().filter(|c| !c.disabled));
+    }
+    for (target, message) in STARTER_CHECKS {
+        if file.checks.iter().all(|c| c.target != target) {
+            file.checks.push(starter(target, message));
+        }
+    }
+    renumber(&mut file.checks, &prefix);
+
+    fs::create_dir_all(dir)?;
+    println!("Scaffolding {service} in {}...\n", dir.display());
+    if in_repo {
+        fs::write(dir.join(".noslop.toml"), format_noslop_file(&file))?;
+        println!("  Created {name}/.noslop.toml ({} checks, prefix {prefix})", file.checks.len());
+        // The repo's hooks gate the new directory; make sure they are in
+        super::hooks::install_each(&git::hooks::DEFAULT_HOOKS, "  ")?;
+    } else {
+        init_repo(dir)?;
+        println!("  Initialized a git repository");
+        fs::write(dir.join(".noslop.toml"), format_noslop_file(&file))?;
+        println!("  Created .noslop.toml ({} checks, prefix {prefix})", file.checks.len());
+        std::env::set_current_dir(dir)?;
+        super::init::setup_clone()?;
+    }
+
+    println!("\nStarter tasks:");
+    println!("  - Write README.md (a starter check asks what it should cover)");
+    println!("  - Add CI: noslop ci generate --provider github --write");
+    println!("  - Add service checks: noslop check add <target> -m \"message\"");
+    Ok(())
+}
+
+fn starter(target: &str, message: &str) -> CheckEntry {
+    CheckEntry {
+        id: None,
+        target: target.to_string(),
+        target_kind: TargetKind::File,
+        commit: CommitCondition::default(),
+        message: message.to_string(),
+        severity: Severity::Warn,
+        tags: Vec::new(),
+        on: Vec::new(),
+        requires_human: false,
+        group: None,
+        owner: None,
+        overrides: Vec::new(),
+        disabled: false,
+        include_vendored: false,
+        codeowners: Vec::new(),
+        ramp: None,
+        must_match: None,
+        attest_within_days: None,
+        when: When::default(),
+        origin: None,
+    }
+}
+
+/// Give every check a fresh `PREFIX-N` ID, in order
+fn renumber(checks: &mut [CheckEntry], prefix: &str) {
+    for (n, check) in checks.iter_mut().enumerate() {
+        check.id = Some(format!("{prefix}-{}", n + 1));
+    }
+}
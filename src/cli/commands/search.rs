@@ -0,0 +1,37 @@
+//! Search command - find where a decision was written down
+//!
+//! Searches check ids, targets, and messages in `.noslop.toml` files and
+//! acknowledgment messages in the ledger.
+
+use crate::noslop_file;
+use noslop::adapters::ledger;
+use noslop::core::services::search::{HitKind, search as rank};
+use noslop::output::OutputMode;
+
+/// Print ranked search results.
+pub fn search(query: &str, mode: OutputMode) -> anyhow::Result<()> {
+    let checks = noslop_file::load_all_checks()?;
+    let acks = ledger::load_all()?;
+    let hits = rank(query, &checks, &acks);
+
+    if mode == OutputMode::Json {
+        println!("{}", serde_json::json!({ "query": query, "hits": hits }));
+        return Ok(());
+    }
+
+    if hits.is_empty() {
+        println!("No matches for \"{query}\".");
+        return Ok(());
+    }
+
+    for h in &hits {
+        let kind = match h.kind {
+            HitKind::Check => "check",
+            HitKind::Ack => "ack  ",
+        };
+        println!("{kind} {:<10} {}", h.id, h.text);
+        println!("      {:<10} {}", "", h.context);
+    }
+    println!("\n{} match(es).", hits.len());
+    Ok(())
+}
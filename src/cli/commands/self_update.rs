@@ -0,0 +1,66 @@
+//! Self-update command - replace this binary with the latest release
+//!
+//! For curl installs; `cargo install` users update through cargo.
+
+use noslop::adapters::release;
+use noslop::core::services::update::{Channel, asset_name, is_newer, pick_release};
+use noslop::output::OutputMode;
+
+/// Check the release feed and, unless `check_only`, install a newer release.
+///
+/// `--check-only` is offline-friendly: an unreachable feed is reported,
+/// not an error.
+pub fn self_update(channel: &str, check_only: bool, mode: OutputMode) -> anyhow::Result<()> {
+    let channel: Channel = channel.parse().map_err(anyhow::Error::msg)?;
+    let current = env!("CARGO_PKG_VERSION");
+
+    let releases = match release::fetch_releases() {
+        Ok(releases) => releases,
+        Err(e) if check_only => {
+            println!("Could not reach the release feed ({e}); running v{current}.");
+            return Ok(());
+        },
+        Err(e) => return Err(e),
+    };
+    let latest = pick_release(&releases, channel).map(|r| r.tag_name.clone());
+    let available = latest.as_deref().is_some_and(|tag| is_newer(current, tag));
+
+    if mode == OutputMode::Json {
+        println!(
+            "{}",
+            serde_json::json!({
+                "current": current,
+                "latest": latest,
+                "update_available": available,
+            })
+        );
+    }
+
+    let Some(tag) = latest.filter(|_| available) else {
+        if mode == OutputMode::Human {
+            println!("noslop v{current} is up to date.");
+        }
+        return Ok(());
+    };
+
+    if check_only {
+        if mode == OutputMode::Human {
+            println!("Update available: v{current} -> {tag}");
+            println!("Run 'noslop self-update' to install it.");
+        }
+        return Ok(());
+    }
+
+    let Some(asset) = asset_name(std::env::consts::OS, std::env::consts::ARCH) else {
+        anyhow::bail!(
+            "No release binary for {}-{}; use scripts/install.sh or cargo install",
+            std::env::consts::OS,
+            std::env::consts::ARCH
+        );
+    };
+    let path = release::install(&tag, &asset)?;
+    if mode == OutputMode::Human {
+        println!("Updated {} to {tag} (sha256 verified).", path.display());
+    }
+    Ok(())
+}
@@ -0,0 +1,157 @@
+//! Snapshot command - freeze the effective policy for a release
+//!
+//! `noslop snapshot v1.2.0` records every file the policy at a revision is
+//! read from (each `.noslop.toml`, the files they include, and CODEOWNERS)
+//! into `.noslop/snapshots/v1.2.0.json`, with a summary of the checks they
+//! define. Committed alongside the release tag, it lets
+//! `noslop verify-commit --policy-snapshot v1.2.0` audit later commits
+//! against the policy exactly as it stood at the release.
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{git, noslop_file};
+use noslop::output::OutputMode;
+
+/// Snapshot file format version
+const SNAPSHOT_SCHEMA: u32 = 1;
+
+/// Where snapshots live, relative to the repo root
+const SNAPSHOT_DIR: &str = ".noslop/snapshots";
+
+/// The effective policy at one revision
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PolicySnapshot {
+    /// File format version
+    pub schema: u32,
+    /// Release the snapshot was taken for
+    pub version: String,
+    /// Commit the policy was read from
+    pub commit: String,
+    /// When the snapshot was taken (RFC 3339)
+    pub created_at: String,
+    /// The checks the policy defines, for reading; `files` is authoritative
+    pub checks: Vec<SnapshotCheck>,
+    /// Every policy file, repo-root path to content
+    pub files: BTreeMap<String, String>,
+}
+
+/// One check as the snapshot recorded it
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SnapshotCheck {
+    /// Check ID, when it has one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    /// Target pattern, relative to `defined_in`'s directory
+    pub target: String,
+    /// Severity before overrides and ramps
+    pub severity: String,
+    /// What the check asks
+    pub message: String,
+    /// Ack group, when it has one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub group: Option<String>,
+    /// Config file defining the check
+    pub defined_in: String,
+}
+
+/// Write the snapshot for `version`, reading the policy at `rev`
+pub fn snapshot(version: &str, rev: &str, force: bool, mode: OutputMode) -> anyhow::Result<()> {
+    let path = snapshot_path(version)?;
+    if path.exists() && !force {
+        anyhow::bail!("{} already exists; pass --force to replace it", path.display());
+    }
+    let commit = git::staged::resolve_rev(&format!("{rev}^{{commit}}"))
+        .map_err(|_| anyhow::anyhow!("Not a commit: '{rev}'"))?;
+    let files = noslop_file::policy_files_at(&commit)?;
+    if !files.keys().any(|path| path.ends_with(".noslop.toml")) {
+        anyhow::bail!("No .noslop.toml at {rev}; nothing to snapshot");
+    }
+
+    let mut checks = Vec::new();
+    for (dir, file) in noslop_file::configs_in(&files)? {
+        let config = format!("{dir}.noslop.toml");
+        for entry in file.checks.iter().filter(|entry| !entry.disabled) {
+            let defined_in = entry
+                .origin
+                .as_ref()
+                .map_or_else(|| config.clone(), |p| p.to_string_lossy().replace('\\', "/"));
+            checks.push(SnapshotCheck {
+                id: entry.id.clone(),
+                target: entry.target.clone(),
+                severity: entry.severity.to_string(),
+                message: entry.message.clone(),
+                group: entry.group.clone(),
+                defined_in,
+            });
+        }
+    }
+
+    let snapshot = PolicySnapshot {
+        schema: SNAPSHOT_SCHEMA,
+        version: version.to_string(),
+        commit,
+        created_at: chrono::Utc::now().to_rfc3339(),
+        checks,
+        files,
+    };
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(&path, serde_json::to_string_pretty(&snapshot)? + "\n")?;
+
+    let relative = format!("{SNAPSHOT_DIR}/{version}.json");
+    if mode == OutputMode::Json {
+        println!(
+            "{}",
+            serde_json::json!({
+                "path": relative,
+                "version": snapshot.version,
+                "commit": snapshot.commit,
+                "checks": snapshot.checks.len(),
+                "files": snapshot.files.keys().collect::<Vec<_>>(),
+            })
+        );
+    } else {
+        println!(
+            "Wrote {relative}: {} check(s) from {} file(s) at {}",
+            snapshot.checks.len(),
+            snapshot.files.len(),
+            &snapshot.commit[..snapshot.commit.len().min(12)]
+        );
+        println!("Commit it before tagging {version}.");
+    }
+    Ok(())
+}
+
+/// Load the snapshot for `version`: from the working tree, else from the
+/// tree of the `version` tag it was committed with
+pub fn load(version: &str) -> anyhow::Result<PolicySnapshot> {
+    let path = snapshot_path(version)?;
+    let content = if path.exists() {
+        std::fs::read_to_string(&path)?
+    } else {
+        git::staged::file_at(version, &format!("{SNAPSHOT_DIR}/{version}.json"))
+            .map_err(|_| anyhow::anyhow!("No policy snapshot for {version}"))?
+    };
+    let snapshot: PolicySnapshot = serde_json::from_str(&content)
+        .map_err(|e| anyhow::anyhow!("Invalid policy snapshot for {version}: {e}"))?;
+    if snapshot.schema > SNAPSHOT_SCHEMA {
+        anyhow::bail!(
+            "The {version} snapshot uses schema {}; this noslop reads up to {SNAPSHOT_SCHEMA}",
+            snapshot.schema
+        );
+    }
+    Ok(snapshot)
+}
+
+fn snapshot_path(version: &str) -> anyhow::Result<PathBuf> {
+    if version.is_empty() || version.split('/').any(|part| part.is_empty() || part == "..") {
+        anyhow::bail!("Invalid snapshot version '{version}'");
+    }
+    Ok(noslop::adapters::git::repo_root_or_cwd()
+        .join(SNAPSHOT_DIR)
+        .join(format!("{version}.json")))
+}
@@ -0,0 +1,102 @@
+//! Staged command - acknowledgments waiting for the next commit
+//!
+//! `noslop ack` stages an acknowledgment in `.noslop/staged-acks.json`
+//! until the commit-msg hook writes it into the commit. `staged show`
+//! lists them with the check each one answers, so an editor or agent can
+//! show a check as acknowledged, pending commit, before the commit exists.
+
+use serde::Serialize;
+
+use crate::cli::app::StagedAction;
+use crate::noslop_file;
+use noslop::core::models::{Acknowledgment, Check, Severity};
+use noslop::output::OutputMode;
+use noslop::storage;
+
+/// Handle staged subcommands
+pub fn staged(action: StagedAction, mode: OutputMode) -> anyhow::Result<()> {
+    match action {
+        StagedAction::Show { id } => show(id.as_deref(), mode),
+    }
+}
+
+/// A staged acknowledgment and the checks it answers
+#[derive(Debug, Serialize)]
+struct StagedAck {
+    #[serde(flatten)]
+    ack: Acknowledgment,
+    /// The check it names, or every member of the group it names (empty
+    /// when no config defines it)
+    checks: Vec<CheckRef>,
+}
+
+/// The parts of a check an acknowledgment display needs
+#[derive(Debug, Serialize)]
+struct CheckRef {
+    id: String,
+    target: String,
+    message: String,
+    severity: Severity,
+}
+
+fn show(id: Option<&str>, mode: OutputMode) -> anyhow::Result<()> {
+    let checks = noslop_file::load_repo_checks()?;
+    let answered = |ack: &Acknowledgment| -> Vec<&Check> {
+        checks.iter().filter(|c| c.answered_by(&ack.check_id)).collect()
+    };
+
+    let staged: Vec<StagedAck> = storage::ack_store()
+        .staged()?
+        .into_iter()
+        .filter(|ack| {
+            id.is_none_or(|id| ack.check_id == id || answered(ack).iter().any(|c| c.id == id))
+        })
+        .map(|ack| StagedAck {
+            checks: answered(&ack)
+                .into_iter()
+                .map(|c| CheckRef {
+                    id: c.id.clone(),
+                    target: c.target.clone(),
+                    message: c.message.clone(),
+                    severity: c.severity,
+                })
+                .collect(),
+            ack,
+        })
+        .collect();
+
+    if mode == OutputMode::Json {
+        println!("{}", serde_json::to_string_pretty(&staged)?);
+        return Ok(());
+    }
+
+    if staged.is_empty() {
+        match id {
+            Some(id) => println!("No staged acknowledgment for {id}."),
+            None => println!("No staged acknowledgments."),
+        }
+        return Ok(());
+    }
+    println!("Staged acknowledgments, pending commit ({}):", staged.len());
+    for s in &staged {
+        println!("\n  {}", s.ack.check_id);
+        for c in &s.checks {
+            println!(
+                "    check:   [{}] {} {} -> {}",
+                c.severity.to_string().to_uppercase(),
+                c.id,
+                c.target,
+                c.message
+            );
+        }
+        if s.checks.is_empty() {
+            println!("    check:   (not defined in any config)");
+        }
+        println!("    by:      {} at {}", s.ack.acknowledged_by, s.ack.created_at);
+        if let Some(approver) = &s.ack.approved_by {
+            println!("    approved by {approver}");
+        }
+        println!("    message: {}", s.ack.message);
+    }
+    Ok(())
+}
@@ -0,0 +1,80 @@
+//! Status command - staged acks, attestation debts, and commits that
+//! skipped the gate
+//!
+//! Bypasses are recorded by the post-commit hook into
+//! `.noslop/bypasses.jsonl` (per-clone). `--no-verify` cannot be
+//! prevented, but it should never go unnoticed. Debts (warnings left
+//! unanswered past `attest_within_days`) come from `.noslop/debts.json`.
+
+use noslop::adapters::telemetry;
+use noslop::output::OutputMode;
+use noslop::storage;
+
+/// Print staged acknowledgments, attestation debts and recorded gate
+/// bypasses
+pub fn status(mode: OutputMode) -> anyhow::Result<()> {
+    let staged = storage::ack_store().staged()?;
+    let debts = telemetry::load_debts()?;
+    let mut bypasses = telemetry::load_bypasses()?;
+    bypasses.reverse();
+
+    if mode == OutputMode::Json {
+        println!(
+            "{}",
+            serde_json::json!({
+                "staged_acks": staged,
+                "debts": debts,
+                "bypasses": bypasses,
+                "storage": storage::report()?,
+            })
+        );
+        return Ok(());
+    }
+
+    if staged.is_empty() {
+        println!("No staged acknowledgments.");
+    } else {
+        println!("Staged acknowledgments ({}):", staged.len());
+        for ack in &staged {
+            println!("  [{}] {}", ack.check_id, ack.message);
+        }
+    }
+
+    if !debts.is_empty() {
+        let today = chrono::Utc::now().date_naive();
+        println!("\nUnattested warnings ({}):", debts.len());
+        for debt in &debts {
+            let state = if debt.is_overdue(today) {
+                "OVERDUE, blocks the next commit"
+            } else {
+                "due"
+            };
+            println!(
+                "  [{}] {state} {}  (left unanswered in {})",
+                debt.check_id,
+                debt.due,
+                &debt.commit[..debt.commit.len().min(7)]
+            );
+        }
+        println!("Answer them with: noslop ack <id> -m \"...\"\n");
+    }
+
+    if bypasses.is_empty() {
+        println!("No commits skipped the gate.");
+        return Ok(());
+    }
+    println!("\nCommits that skipped the gate ({}):", bypasses.len());
+    for b in &bypasses {
+        println!(
+            "  {}  {:<5}  {:<12}  {}",
+            &b.commit[..b.commit.len().min(7)],
+            b.severity,
+            b.actor,
+            b.check_ids.join(", ")
+        );
+    }
+    println!(
+        "\nAnswer them before merging: the diff-base gate fails on checks without ledger records."
+    );
+    Ok(())
+}
@@ -0,0 +1,149 @@
+//! Verify-commit command - audit one commit against its obligations
+//!
+//! Rebuilds the check set from the commit's own tree, matches it against
+//! the files the commit changed, and answers the checks with the commit's
+//! `Noslop-Ack` trailers and the ledger records it added. Evidence files
+//! attached to those acks must be in the commit's tree, unaltered.
+//!
+//! With `--policy-snapshot`, the check set comes from a release's policy
+//! snapshot instead (see the snapshot command).
+
+use crate::{git, noslop_file};
+use noslop::adapters::ledger;
+use noslop::core::models::Acknowledgment;
+use noslop::core::services::{CheckItemResult, check_items_at};
+use noslop::output::OutputMode;
+use noslop::storage;
+
+use super::Blocked;
+
+/// Print the commit's changed files, the checks it had to answer, and
+/// the verdict. Fails (exit 1) when a blocking check went unanswered.
+pub fn verify_commit(rev: &str, policy: Option<&str>, mode: OutputMode) -> anyhow::Result<()> {
+    let sha = git::staged::resolve_rev(&format!("{rev}^{{commit}}"))
+        .map_err(|_| anyhow::anyhow!("Not a commit: '{rev}'"))?;
+    let changes = git::staged::commit_changes(&sha, noslop_file::submodule_mode())?;
+    let stats = git::staged::commit_stats(&sha)?;
+    let snapshot = policy.map(super::snapshot::load).transpose()?;
+    let applicable = match &snapshot {
+        Some(snapshot) => noslop_file::checks_in_policy(
+            &snapshot.files,
+            &snapshot.commit,
+            &sha,
+            &changes,
+            &stats,
+        )?,
+        None => noslop_file::checks_at_commit(&sha, &changes, &stats)?,
+    };
+
+    let mut acks = storage::ack_store().parse_from_commit(&sha)?;
+    acks.extend(ledger::added_in_commit(&sha)?);
+    let blobs = super::check_validate::matched_blobs(&sha, &applicable)?;
+    let result = check_items_at(&applicable, &acks, changes.len(), &blobs);
+    let mut evidence_problems = Vec::new();
+    for ack in &acks {
+        for problem in ledger::check_attachments(&sha, ack)? {
+            if !evidence_problems.contains(&problem) {
+                evidence_problems.push(problem);
+            }
+        }
+    }
+    let passed = result.passed && evidence_problems.is_empty();
+
+    let required: Vec<&CheckItemResult> = result
+        .blocking
+        .iter()
+        .chain(&result.warnings)
+        .chain(&result.acknowledged)
+        .collect();
+    let answer = |item: &CheckItemResult| -> Option<&Acknowledgment> {
+        let (check, _) = applicable.iter().find(|(c, _)| c.id == item.id)?;
+        acks.iter().find(|ack| check.answered_by(&ack.check_id))
+    };
+
+    if mode == OutputMode::Json {
+        let checks: Vec<serde_json::Value> = required
+            .iter()
+            .map(|item| {
+                serde_json::json!({
+                    "id": item.id,
+                    "file": item.file,
+                    "severity": item.severity,
+                    "message": item.message,
+                    "acknowledged": item.acknowledged,
+                    "ack": answer(item).map(|ack| serde_json::json!({
+                        "message": ack.message,
+                        "by": ack.acknowledged_by,
+                        "evidence": ack.attachments,
+                    })),
+                })
+            })
+            .collect();
+        let files: Vec<&str> = changes.iter().map(|c| c.path.as_str()).collect();
+        println!(
+            "{}",
+            serde_json::json!({
+                "commit": sha,
+                "policy_snapshot": snapshot.as_ref().map(|s| &s.version),
+                "passed": passed,
+                "files": files,
+                "checks": checks,
+                "evidence_problems": evidence_problems,
+            })
+        );
+    } else {
+        println!("Commit {}", &sha[..sha.len().min(12)]);
+        if let Some(snapshot) = &snapshot {
+            println!(
+                "Policy: snapshot {} (taken at {})",
+                snapshot.version,
+                &snapshot.commit[..snapshot.commit.len().min(12)]
+            );
+        }
+        println!("\nFiles changed ({}):", changes.len());
+        for change in &changes {
+            println!("  {:<6} {}", change.kind.to_string(), change.path);
+        }
+
+        if required.is_empty() {
+            println!("\nNo checks applied to this commit.");
+        } else {
+            println!("\nChecks ({}):", required.len());
+            for item in &required {
+                let status = if item.acknowledged {
+                    "answered  "
+                } else {
+                    "unanswered"
+                };
+                println!("  {status}  [{}] {} ({})", item.id, item.file, item.severity);
+                if let Some(ack) = answer(item) {
+                    println!("              {} ({})", ack.message, ack.acknowledged_by);
+                    for attachment in &ack.attachments {
+                        println!("              evidence: {}", attachment.name);
+                    }
+                }
+            }
+        }
+
+        if !evidence_problems.is_empty() {
+            println!("\nEvidence problems ({}):", evidence_problems.len());
+            for problem in &evidence_problems {
+                println!("  {problem}");
+            }
+        }
+
+        if passed {
+            println!("\nPASS: every blocking check was answered.");
+        } else if !result.passed {
+            println!("\nFAIL: {} blocking check(s) unanswered.", result.blocking.len());
+        } else {
+            println!("\nFAIL: attached evidence does not match its recorded hash.");
+        }
+    }
+
+    if passed {
+        Ok(())
+    } else {
+        Err(Blocked { quiet: true }.into())
+    }
+}
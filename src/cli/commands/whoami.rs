@@ -0,0 +1,55 @@
+//! Whoami command - the actor and identity noslop records
+
+use noslop::adapters::env::IDENTITY_PATH;
+use noslop::adapters::git::state_path;
+use noslop::adapters::{detect_actor, resolve_identity};
+use noslop::output::OutputMode;
+
+/// Show the detected actor and resolved identity, or with `set` store a
+/// per-clone identity in `.noslop/identity`
+pub fn whoami(identity: Option<&str>, set: Option<&str>, mode: OutputMode) -> anyhow::Result<()> {
+    if let Some(name) = set {
+        let name = name.trim();
+        if name.is_empty() {
+            anyhow::bail!("Identity must not be empty");
+        }
+        let path = state_path(IDENTITY_PATH);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&path, format!("{name}\n"))?;
+        println!("Identity set to {name} (in {IDENTITY_PATH})");
+        return Ok(());
+    }
+
+    let actor = detect_actor();
+    let resolved = resolve_identity(identity);
+    if mode == OutputMode::Json {
+        println!(
+            "{}",
+            serde_json::json!({
+                "actor": actor.name(),
+                "gated": actor.is_gated(),
+                "identity": resolved,
+            })
+        );
+        return Ok(());
+    }
+
+    let kind = if actor.is_gated() {
+        "agent, blocking checks gate its commits"
+    } else {
+        "human, blocking checks are guidance"
+    };
+    println!("Actor:    {} ({kind})", actor.name());
+    match resolved {
+        Some(identity) => println!("Identity: {} (from {})", identity.name, identity.source),
+        None => {
+            println!("Identity: none");
+            println!(
+                "Set one with 'noslop whoami --set <email>', NOSLOP_IDENTITY, or git config user.email"
+            );
+        },
+    }
+    Ok(())
+}
@@ -3,6 +3,8 @@
 //! An acknowledgment proves that a check was considered.
 //! It's attached to the commit that addresses the check.
 
+use std::collections::BTreeMap;
+
 use serde::{Deserialize, Serialize};
 
 /// An acknowledgment - proof that a check was considered
@@ -59,6 +61,36 @@ pub struct Acknowledgment {
     /// (additive, schema 1)
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub model: Option<String>,
+
+    /// Human who co-signed an agent's ack on a `requires_human` check
+    /// (additive, schema 1; absent until `noslop approve`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub approved_by: Option<String>,
+
+    /// Person or account behind the acknowledging actor, e.g. an email
+    /// (additive, schema 1; see `noslop whoami`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub identity: Option<String>,
+
+    /// Evidence files attached with `noslop ack --evidence` (additive,
+    /// schema 1): each is stored in the tree under its git blob id
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub attachments: Vec<Attachment>,
+
+    /// Git blob id of each matched file as staged when acknowledged
+    /// (additive, schema 1): after a rebase, only files whose content has
+    /// changed since need a fresh ack
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub file_digests: BTreeMap<String, String>,
+}
+
+/// An evidence file attached to an acknowledgment
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Attachment {
+    /// File name as attached, e.g. `pentest.pdf`
+    pub name: String,
+    /// Git blob id of the file content (`git hash-object`)
+    pub blob: String,
 }
 
 impl Acknowledgment {
@@ -76,6 +108,10 @@ impl Acknowledgment {
             tokens_to_answer: None,
             cached_tokens_to_answer: None,
             model: None,
+            approved_by: None,
+            identity: None,
+            attachments: Vec::new(),
+            file_digests: BTreeMap::new(),
         }
     }
 
@@ -114,4 +150,39 @@ impl Acknowledgment {
         self.model = model;
         self
     }
+
+    /// Record the content (git blob ids) of the files the ack reviewed
+    #[must_use]
+    pub fn with_file_digests(mut self, file_digests: BTreeMap<String, String>) -> Self {
+        self.file_digests = file_digests;
+        self
+    }
+
+    /// Whether the ack reviewed `file` with content other than `blob`;
+    /// `false` when either digest is unknown
+    #[must_use]
+    pub fn reviewed_other_content(&self, file: &str, blob: Option<&str>) -> bool {
+        matches!((self.file_digests.get(file), blob), (Some(reviewed), Some(blob)) if reviewed != blob)
+    }
+
+    /// Attach the identity behind the acknowledging actor
+    #[must_use]
+    pub fn with_identity(mut self, identity: Option<String>) -> Self {
+        self.identity = identity;
+        self
+    }
+
+    /// Attach evidence files
+    #[must_use]
+    pub fn with_attachments(mut self, attachments: Vec<Attachment>) -> Self {
+        self.attachments = attachments;
+        self
+    }
+
+    /// Whether a human stands behind this ack: a human made it, or a
+    /// human approved it
+    #[must_use]
+    pub fn is_human_signed(&self) -> bool {
+        self.acknowledged_by == super::Actor::Human.name() || self.approved_by.is_some()
+    }
 }
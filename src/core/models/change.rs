@@ -0,0 +1,248 @@
+//! File change model
+//!
+//! What happened to a staged (or branch-diffed) file: added, modified,
+//! deleted, or renamed. Checks declare which kinds they fire on, so
+//! "ack when a file covered by NOS-9 is deleted" is expressible.
+//!
+//! Within a file, each hunk of the diff has a class too (a new function,
+//! a changed signature, ...), which checks can scope to with
+//! `when.change_kind`.
+
+use serde::{Deserialize, Serialize};
+
+/// Kind of change a file underwent
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeKind {
+    /// File was created
+    Add,
+    /// File content changed in place
+    Modify,
+    /// File was removed
+    Delete,
+    /// File moved to a new path (possibly with edits)
+    Rename,
+}
+
+impl ChangeKind {
+    /// Kinds a check fires on when it declares no `on` list
+    pub const DEFAULT: [Self; 2] = [Self::Add, Self::Modify];
+}
+
+impl std::fmt::Display for ChangeKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Add => write!(f, "add"),
+            Self::Modify => write!(f, "modify"),
+            Self::Delete => write!(f, "delete"),
+            Self::Rename => write!(f, "rename"),
+        }
+    }
+}
+
+impl std::str::FromStr for ChangeKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "add" => Ok(Self::Add),
+            "modify" => Ok(Self::Modify),
+            "delete" => Ok(Self::Delete),
+            "rename" => Ok(Self::Rename),
+            _ => Err(format!("Invalid change kind: {s}. Use: add, modify, delete, rename")),
+        }
+    }
+}
+
+/// What a hunk of a file's diff does, as far as its lines tell
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ChangeClass {
+    /// Adds a function that was not there before
+    NewFunction,
+    /// Changes the signature of an existing function
+    SignatureChange,
+    /// Changes a version in a dependency manifest
+    DependencyBump,
+    /// Touches comments (or blank lines) only
+    CommentOnly,
+    /// Touches a test file
+    TestOnly,
+}
+
+impl std::fmt::Display for ChangeClass {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NewFunction => write!(f, "new-function"),
+            Self::SignatureChange => write!(f, "signature-change"),
+            Self::DependencyBump => write!(f, "dependency-bump"),
+            Self::CommentOnly => write!(f, "comment-only"),
+            Self::TestOnly => write!(f, "test-only"),
+        }
+    }
+}
+
+/// How a submodule pointer (gitlink) change is presented to checks
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SubmoduleMode {
+    /// The submodule path is one changed file (a pointer bump)
+    #[default]
+    Path,
+    /// Submodule changes are invisible to checks
+    Skip,
+    /// The files changed between the old and new submodule commits,
+    /// prefixed with the submodule path
+    Recurse,
+}
+
+impl std::fmt::Display for SubmoduleMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Path => write!(f, "path"),
+            Self::Skip => write!(f, "skip"),
+            Self::Recurse => write!(f, "recurse"),
+        }
+    }
+}
+
+/// A changed file with its change kind
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileChange {
+    /// Current path (for deletions, the path that was removed)
+    pub path: String,
+
+    /// What happened to the file
+    pub kind: ChangeKind,
+
+    /// Previous path, for renames only
+    pub old_path: Option<String>,
+}
+
+impl FileChange {
+    /// A non-rename change to `path`
+    #[must_use]
+    pub fn new(path: impl Into<String>, kind: ChangeKind) -> Self {
+        Self {
+            path: path.into(),
+            kind,
+            old_path: None,
+        }
+    }
+
+    /// A rename from `old_path` to `path`
+    #[must_use]
+    pub fn renamed(old_path: impl Into<String>, path: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            kind: ChangeKind::Rename,
+            old_path: Some(old_path.into()),
+        }
+    }
+
+    /// Parse one line of a changed-file listing
+    ///
+    /// Accepts a bare path (a modification) or a `git diff --name-status`
+    /// line: `A\tpath`, `M\tpath`, `D\tpath`, `R100\told\tnew`. Blank
+    /// lines yield `None`.
+    #[must_use]
+    pub fn from_listing(line: &str) -> Option<Self> {
+        let line = line.trim_end_matches(['\r', '\n']);
+        if line.trim().is_empty() {
+            return None;
+        }
+        let mut fields = line.split('\t');
+        let first = fields.next()?;
+        let Some(path) = fields.next() else {
+            return Some(Self::new(line.trim(), ChangeKind::Modify));
+        };
+        Some(match first.chars().next()? {
+            'A' => Self::new(path, ChangeKind::Add),
+            'D' => Self::new(path, ChangeKind::Delete),
+            'R' => Self::renamed(path, fields.next()?),
+            'C' => Self::new(fields.next()?, ChangeKind::Add),
+            _ => Self::new(path, ChangeKind::Modify),
+        })
+    }
+
+    /// Paths a check firing on `on` should be matched against
+    ///
+    /// An empty `on` means [`ChangeKind::DEFAULT`]. A rename the check
+    /// doesn't list explicitly decomposes into delete(old) + add(new), so
+    /// default checks keep firing on the new path exactly as before.
+    #[must_use]
+    pub fn paths_for(&self, on: &[ChangeKind]) -> Vec<&str> {
+        let on = if on.is_empty() {
+            &ChangeKind::DEFAULT[..]
+        } else {
+            on
+        };
+        let fires = |kind| on.contains(&kind);
+
+        match (self.kind, self.old_path.as_deref()) {
+            (ChangeKind::Rename, Some(old)) if fires(ChangeKind::Rename) => vec![old, &self.path],
+            (ChangeKind::Rename, old) => {
+                let mut paths = Vec::new();
+                if let Some(old) = old
+                    && fires(ChangeKind::Delete)
+                {
+                    paths.push(old);
+                }
+                if fires(ChangeKind::Add) {
+                    paths.push(self.path.as_str());
+                }
+                paths
+            },
+            (kind, _) if fires(kind) => vec![&self.path],
+            _ => Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_fires_on_add_and_modify_only() {
+        assert_eq!(FileChange::new("a.rs", ChangeKind::Add).paths_for(&[]), vec!["a.rs"]);
+        assert_eq!(FileChange::new("a.rs", ChangeKind::Modify).paths_for(&[]), vec!["a.rs"]);
+        assert!(FileChange::new("a.rs", ChangeKind::Delete).paths_for(&[]).is_empty());
+    }
+
+    #[test]
+    fn delete_fires_when_declared() {
+        let change = FileChange::new("a.rs", ChangeKind::Delete);
+        assert_eq!(change.paths_for(&[ChangeKind::Delete]), vec!["a.rs"]);
+    }
+
+    #[test]
+    fn rename_decomposes_without_explicit_rename() {
+        let change = FileChange::renamed("old.rs", "new.rs");
+        assert_eq!(change.paths_for(&[]), vec!["new.rs"]);
+        assert_eq!(change.paths_for(&[ChangeKind::Delete]), vec!["old.rs"]);
+    }
+
+    #[test]
+    fn listing_accepts_bare_paths_and_name_status() {
+        assert_eq!(
+            FileChange::from_listing("src/a.rs\n"),
+            Some(FileChange::new("src/a.rs", ChangeKind::Modify))
+        );
+        assert_eq!(
+            FileChange::from_listing("D\tgone.rs"),
+            Some(FileChange::new("gone.rs", ChangeKind::Delete))
+        );
+        assert_eq!(
+            FileChange::from_listing("R087\told.rs\tnew.rs"),
+            Some(FileChange::renamed("old.rs", "new.rs"))
+        );
+        assert_eq!(FileChange::from_listing("  "), None);
+    }
+
+    #[test]
+    fn explicit_rename_matches_both_paths() {
+        let change = FileChange::renamed("old.rs", "new.rs");
+        assert_eq!(change.paths_for(&[ChangeKind::Rename]), vec!["old.rs", "new.rs"]);
+    }
+}
@@ -5,7 +5,7 @@
 
 use serde::{Deserialize, Serialize};
 
-use super::Severity;
+use super::{ChangeClass, ChangeKind, CommitCondition, Severity};
 
 /// A check attached to a file or pattern
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -27,6 +27,52 @@ pub struct Check {
 
     /// When this check was created
     pub created_at: String,
+
+    /// Change kinds the check fires on (empty = add and modify)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub on: Vec<ChangeKind>,
+
+    /// Agent acks only count once a human approves them (`noslop approve`)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub requires_human: bool,
+
+    /// Group whose single ack satisfies every member
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub group: Option<String>,
+
+    /// Who to contact when the check blocks (e.g. "@team-payments")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub owner: Option<String>,
+
+    /// Commit checks: when the change set as a whole fires the check
+    /// (`None` for file checks)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub commit: Option<CommitCondition>,
+
+    /// Matched a vendored or generated file, so it fires as a warning at
+    /// most
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub vendored: bool,
+
+    /// CODEOWNERS owners of the matched file, set for `codeowners = ["ack"]`:
+    /// only an ack (or approval) by one of them counts. Empty = anyone's.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub code_owners: Vec<String>,
+
+    /// Days a warning left unanswered in a commit may stay unattested
+    /// before it blocks (`None` = it never escalates)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub attest_within_days: Option<u32>,
+
+    /// Hunk classes the check is scoped to (`when.change_kind`): it fires
+    /// only on a file whose diff has one of them. Empty = any change.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub scoped_to: Vec<ChangeClass>,
+
+    /// Fire only when the matched file's dependencies changed
+    /// (`when.dependency_change`)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub dependency_change: bool,
 }
 
 impl Check {
@@ -45,8 +91,81 @@ impl Check {
             severity,
             introduced_by: None,
             created_at: chrono::Utc::now().to_rfc3339(),
+            on: Vec::new(),
+            requires_human: false,
+            group: None,
+            owner: None,
+            commit: None,
+            vendored: false,
+            code_owners: Vec::new(),
+            attest_within_days: None,
+            scoped_to: Vec::new(),
+            dependency_change: false,
         }
     }
+
+    /// Set the change kinds this check fires on
+    #[must_use]
+    pub fn with_on(mut self, on: Vec<ChangeKind>) -> Self {
+        self.on = on;
+        self
+    }
+
+    /// Require a human co-sign on agent acknowledgments
+    #[must_use]
+    pub const fn with_requires_human(mut self, requires_human: bool) -> Self {
+        self.requires_human = requires_human;
+        self
+    }
+
+    /// Put the check in an ack group
+    #[must_use]
+    pub fn with_group(mut self, group: Option<String>) -> Self {
+        self.group = group;
+        self
+    }
+
+    /// Set who owns the check
+    #[must_use]
+    pub fn with_owner(mut self, owner: Option<String>) -> Self {
+        self.owner = owner;
+        self
+    }
+
+    /// Make this a commit check that fires on `condition`
+    #[must_use]
+    pub const fn with_commit(mut self, condition: Option<CommitCondition>) -> Self {
+        self.commit = condition;
+        self
+    }
+
+    /// Set how long an unanswered warning may stay unattested
+    #[must_use]
+    pub const fn with_attest_within_days(mut self, days: Option<u32>) -> Self {
+        self.attest_within_days = days;
+        self
+    }
+
+    /// Scope the check to hunks of these classes
+    #[must_use]
+    pub fn with_scoped_to(mut self, classes: Vec<ChangeClass>) -> Self {
+        self.scoped_to = classes;
+        self
+    }
+
+    /// Scope the check to dependency changes
+    #[must_use]
+    pub const fn with_dependency_change(mut self, dependency_change: bool) -> Self {
+        self.dependency_change = dependency_change;
+        self
+    }
+
+    /// Whether `ack_ref` (an ack's check ID) answers this check: its own ID
+    /// or its group's name
+    #[must_use]
+    pub fn answered_by(&self, ack_ref: &str) -> bool {
+        self.id == ack_ref || self.group.as_deref() == Some(ack_ref)
+    }
 }
 
 /// Stable content-derived fallback ID (FNV-1a over target + message)
@@ -0,0 +1,122 @@
+//! Commit-level check model
+//!
+//! Most checks match files. A commit check instead matches the shape of
+//! the change set as a whole: how many files it touches, how many lines it
+//! adds, whether it is a merge ("commits over 1000 added lines need a
+//! design-doc link").
+
+use serde::{Deserialize, Serialize};
+
+/// What a check's target refers to
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TargetKind {
+    /// A file path or pattern (the default)
+    #[default]
+    File,
+    /// The commit as a whole, judged by its [`CommitCondition`]
+    Commit,
+}
+
+/// Size and shape of a change set (staged changes, or a branch diff)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CommitStats {
+    /// Files changed
+    pub files: usize,
+    /// Lines added (binary files count none)
+    pub added_lines: usize,
+    /// Whether the change set includes a merge commit
+    pub merge: bool,
+}
+
+/// When a commit check fires; every condition set must hold
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CommitCondition {
+    /// Fire when at least this many files changed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_files: Option<usize>,
+    /// Fire when at least this many lines were added
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_added_lines: Option<usize>,
+    /// Fire only on merges (`true`) or only on non-merges (`false`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub merge: Option<bool>,
+}
+
+impl CommitCondition {
+    /// No condition is set (such a check would fire on every commit)
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.min_files.is_none() && self.min_added_lines.is_none() && self.merge.is_none()
+    }
+
+    /// Whether a change set with `stats` meets every condition
+    #[must_use]
+    pub fn matches(&self, stats: &CommitStats) -> bool {
+        self.min_files.is_none_or(|min| stats.files >= min)
+            && self.min_added_lines.is_none_or(|min| stats.added_lines >= min)
+            && self.merge.is_none_or(|merge| stats.merge == merge)
+    }
+
+    /// Human-readable form, e.g. `commit(files >= 20, merge)`
+    #[must_use]
+    pub fn describe(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some(min) = self.min_files {
+            parts.push(format!("files >= {min}"));
+        }
+        if let Some(min) = self.min_added_lines {
+            parts.push(format!("added lines >= {min}"));
+        }
+        match self.merge {
+            Some(true) => parts.push("merge".to_string()),
+            Some(false) => parts.push("not a merge".to_string()),
+            None => {},
+        }
+        format!("commit({})", parts.join(", "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_set_condition_must_hold() {
+        let big_merge = CommitCondition {
+            min_files: Some(20),
+            merge: Some(true),
+            ..CommitCondition::default()
+        };
+        let stats = CommitStats {
+            files: 25,
+            added_lines: 10,
+            merge: true,
+        };
+        assert!(big_merge.matches(&stats));
+        assert!(!big_merge.matches(&CommitStats {
+            merge: false,
+            ..stats
+        }));
+        assert!(!big_merge.matches(&CommitStats { files: 3, ..stats }));
+        assert_eq!(big_merge.describe(), "commit(files >= 20, merge)");
+    }
+
+    #[test]
+    fn added_lines_threshold_is_inclusive() {
+        let large = CommitCondition {
+            min_added_lines: Some(1000),
+            ..CommitCondition::default()
+        };
+        assert!(large.matches(&CommitStats {
+            added_lines: 1000,
+            ..CommitStats::default()
+        }));
+        assert!(!large.matches(&CommitStats {
+            added_lines: 999,
+            ..CommitStats::default()
+        }));
+        assert!(CommitCondition::default().is_empty());
+    }
+}
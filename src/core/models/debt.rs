@@ -0,0 +1,99 @@
+//! Attestation debt model
+//!
+//! A warning with `attest_within_days` that a commit leaves unanswered is
+//! not forgotten: it becomes a debt, due a number of days after that
+//! commit. An ack for the check in a later commit settles it. Past its due
+//! date, the debt blocks the next commit until answered.
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use super::Check;
+
+/// An unanswered warning that must be attested by a date
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AttestationDebt {
+    /// The check left unanswered
+    pub check_id: String,
+
+    /// The check's ack group, whose ack settles it too
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub group: Option<String>,
+
+    /// The check's target, as it fired
+    pub target: String,
+
+    /// The check message
+    pub message: String,
+
+    /// The commit that left it unanswered
+    pub commit: String,
+
+    /// Files the check matched in that commit
+    pub files: Vec<String>,
+
+    /// Last day to attest; the debt blocks from the day after
+    pub due: NaiveDate,
+
+    /// When the debt was recorded (RFC 3339)
+    pub created_at: String,
+}
+
+impl AttestationDebt {
+    /// The debt `check` incurs when `commit` leaves it unanswered on
+    /// `today`, `None` for a check without `attest_within_days`
+    #[must_use]
+    pub fn new(
+        check: &Check,
+        commit: String,
+        files: Vec<String>,
+        today: NaiveDate,
+    ) -> Option<Self> {
+        let days = check.attest_within_days?;
+        Some(Self {
+            check_id: check.id.clone(),
+            group: check.group.clone(),
+            target: check.target.clone(),
+            message: check.message.clone(),
+            commit,
+            files,
+            due: today + chrono::Days::new(u64::from(days)),
+            created_at: chrono::Utc::now().to_rfc3339(),
+        })
+    }
+
+    /// Whether the due date has passed on `today`
+    #[must_use]
+    pub fn is_overdue(&self, today: NaiveDate) -> bool {
+        today > self.due
+    }
+
+    /// Whether `ack_ref` (an ack's check ID) settles the debt: the check's
+    /// own ID or its group's name
+    #[must_use]
+    pub fn settled_by(&self, ack_ref: &str) -> bool {
+        self.check_id == ack_ref || self.group.as_deref() == Some(ack_ref)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::models::Severity;
+
+    #[test]
+    fn debt_falls_due_after_the_configured_days() {
+        let today = NaiveDate::from_ymd_opt(2025, 3, 1).unwrap();
+        let plain =
+            Check::new(Some("LOG-1".into()), "src/".into(), "Logged?".into(), Severity::Warn);
+        assert!(AttestationDebt::new(&plain, "abc".into(), vec![], today).is_none());
+
+        let check = plain.with_attest_within_days(Some(3)).with_group(Some("obs".into()));
+        let debt =
+            AttestationDebt::new(&check, "abc".into(), vec!["src/a.rs".into()], today).unwrap();
+        assert_eq!(debt.due, NaiveDate::from_ymd_opt(2025, 3, 4).unwrap());
+        assert!(!debt.is_overdue(debt.due));
+        assert!(debt.is_overdue(debt.due + chrono::Days::new(1)));
+        assert!(debt.settled_by("LOG-1") && debt.settled_by("obs") && !debt.settled_by("LOG-2"));
+    }
+}
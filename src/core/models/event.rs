@@ -4,6 +4,9 @@
 //! staged change. Events are the raw material for `noslop stats`: joined
 //! with acknowledgments by check id and staged-tree fingerprint, they
 //! distinguish action rate from answers that change nothing.
+//!
+//! A bypass event records the opposite: a commit that skipped the gate
+//! (`git commit --no-verify`) while checks on its files went unanswered.
 
 use serde::{Deserialize, Serialize};
 
@@ -78,3 +81,40 @@ impl CheckFireEvent {
         self
     }
 }
+
+/// A commit that skipped the pre-commit gate with checks unanswered
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BypassEvent {
+    /// Event format version
+    pub schema: u32,
+
+    /// The commit that bypassed the gate
+    pub commit: String,
+
+    /// Strictest severity among the unanswered checks
+    pub severity: Severity,
+
+    /// Checks that would have fired, unanswered in the commit
+    pub check_ids: Vec<String>,
+
+    /// Who committed (detected actor)
+    pub actor: String,
+
+    /// When the bypass was detected (RFC 3339)
+    pub created_at: String,
+}
+
+impl BypassEvent {
+    /// Create a new bypass event stamped with the current time
+    #[must_use]
+    pub fn new(commit: String, severity: Severity, check_ids: Vec<String>, actor: String) -> Self {
+        Self {
+            schema: EVENT_SCHEMA_VERSION,
+            commit,
+            severity,
+            check_ids,
+            actor,
+            created_at: chrono::Utc::now().to_rfc3339(),
+        }
+    }
+}
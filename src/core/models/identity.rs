@@ -0,0 +1,43 @@
+//! Identity model - the person or account behind an actor
+//!
+//! [`Actor`](super::Actor) says *what kind* of committer is acting (a
+//! human, or which agent) and drives gating. An identity says *who*: the
+//! email or handle recorded on acks and approvals for accountability.
+
+use std::fmt;
+
+use serde::Serialize;
+
+/// Where a resolved identity came from, highest precedence first
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum IdentitySource {
+    /// The `--identity` command-line flag
+    Flag,
+    /// The `NOSLOP_IDENTITY` environment variable
+    Env,
+    /// The per-clone `.noslop/identity` file
+    File,
+    /// `git config user.email`
+    GitConfig,
+}
+
+impl fmt::Display for IdentitySource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Flag => write!(f, "--identity flag"),
+            Self::Env => write!(f, "NOSLOP_IDENTITY"),
+            Self::File => write!(f, ".noslop/identity"),
+            Self::GitConfig => write!(f, "git config user.email"),
+        }
+    }
+}
+
+/// A resolved identity and its source
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Identity {
+    /// Email or handle, e.g. "dev@example.com"
+    pub name: String,
+    /// Where it was resolved from
+    pub source: IdentitySource,
+}
@@ -0,0 +1,48 @@
+//! Command metric model
+//!
+//! One line per noslop invocation, written only when a repo opts in with
+//! `[metrics] enabled = true`. Local working state: nothing is sent
+//! anywhere, and `noslop metrics report` is the only reader.
+
+use serde::{Deserialize, Serialize};
+
+/// How an invocation ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Outcome {
+    /// Completed normally (a check run that passed, or any other command)
+    Ok,
+    /// A check run stopped the commit on unacknowledged checks
+    Blocked,
+    /// Failed with an error
+    Error,
+}
+
+/// One recorded invocation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommandMetric {
+    /// Subcommand name (e.g. "check", "ack")
+    pub command: String,
+    /// Wall-clock duration in milliseconds
+    pub duration_ms: u64,
+    /// How it ended
+    pub outcome: Outcome,
+    /// Who ran it (detected actor)
+    pub actor: String,
+    /// When it finished (RFC 3339)
+    pub created_at: String,
+}
+
+impl CommandMetric {
+    /// Create a metric stamped with the current time
+    #[must_use]
+    pub fn new(command: String, duration_ms: u64, outcome: Outcome, actor: String) -> Self {
+        Self {
+            command,
+            duration_ms,
+            outcome,
+            actor,
+            created_at: chrono::Utc::now().to_rfc3339(),
+        }
+    }
+}
@@ -4,22 +4,36 @@
 //!
 //! - [`Check`] - "When this code changes, verify this"
 //! - [`Acknowledgment`] - "I verified this because..."
+//! - [`AttestationDebt`] - A warning left unanswered, due to be attested
+//! - [`CommitCondition`] - When a commit-level check fires (size, merges)
+//! - [`FileChange`] - A changed file and how it changed (add/modify/delete/rename)
 //! - [`Actor`] - Who is committing or acknowledging (human or agent)
+//! - [`Identity`] - The person or account behind an actor, and its source
 //! - [`Severity`] - How strictly a check is enforced
 //! - [`Target`] - A reference to code (path, glob, or fragment)
 
 mod acknowledgment;
 mod actor;
+mod change;
 mod check;
+mod commit;
+mod debt;
 mod event;
+mod identity;
+mod metric;
 mod proposal;
 mod severity;
 mod target;
 
-pub use acknowledgment::Acknowledgment;
+pub use acknowledgment::{Acknowledgment, Attachment};
 pub use actor::Actor;
+pub use change::{ChangeClass, ChangeKind, FileChange, SubmoduleMode};
 pub use check::Check;
-pub use event::{CheckFireEvent, EVENT_SCHEMA_VERSION};
+pub use commit::{CommitCondition, CommitStats, TargetKind};
+pub use debt::AttestationDebt;
+pub use event::{BypassEvent, CheckFireEvent, EVENT_SCHEMA_VERSION};
+pub use identity::{Identity, IdentitySource};
+pub use metric::{CommandMetric, Outcome};
 pub use proposal::Proposal;
 pub use severity::Severity;
 pub use target::{Fragment, GlobPattern, ParseError, PathSpec, Target};
@@ -2,10 +2,13 @@
 //!
 //! Defines how strictly a check should be enforced.
 
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
 
 /// Check severity levels
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+///
+/// Serialized lowercase; deserialized through [`FromStr`](std::str::FromStr),
+/// so any case is accepted and unknown values are rejected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
 #[serde(rename_all = "lowercase")]
 pub enum Severity {
     /// Informational - shown but doesn't block
@@ -39,3 +42,22 @@ impl std::str::FromStr for Severity {
         }
     }
 }
+
+impl<'de> Deserialize<'de> for Severity {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deserializes_any_case_and_rejects_unknown() {
+        let parsed: Severity = serde_json::from_str("\"Warn\"").unwrap();
+        assert_eq!(parsed, Severity::Warn);
+        assert!(serde_json::from_str::<Severity>("\"blokc\"").is_err());
+        assert_eq!(serde_json::to_string(&Severity::Block).unwrap(), "\"block\"");
+    }
+}
@@ -13,13 +13,13 @@ pub trait AcknowledgmentStore: Send + Sync {
     /// Stage an acknowledgment (pending until commit)
     ///
     /// Staged acknowledgments are temporary and will be cleared after commit.
-    fn stage(&self, ack: &Acknowledgment) -> anyhow::Result<()>;
+    fn stage(&self, ack: &Acknowledgment) -> crate::Result<()>;
 
     /// Get all staged acknowledgments
-    fn staged(&self) -> anyhow::Result<Vec<Acknowledgment>>;
+    fn staged(&self) -> crate::Result<Vec<Acknowledgment>>;
 
     /// Clear staged acknowledgments (called after commit succeeds)
-    fn clear_staged(&self) -> anyhow::Result<()>;
+    fn clear_staged(&self) -> crate::Result<()>;
 
     /// Format acknowledgments for commit message trailer
     ///
@@ -29,5 +29,5 @@ pub trait AcknowledgmentStore: Send + Sync {
     /// Parse acknowledgments from a commit message
     ///
     /// Used to retrieve acknowledgment history from past commits.
-    fn parse_from_commit(&self, commit_sha: &str) -> anyhow::Result<Vec<Acknowledgment>>;
+    fn parse_from_commit(&self, commit_sha: &str) -> crate::Result<Vec<Acknowledgment>>;
 }
@@ -0,0 +1,87 @@
+//! Ack message service - acks written inline in a commit message
+//!
+//! A line of the form `[ack NOS-3: reviewed the migration plan]` answers
+//! check `NOS-3` without a separate `noslop ack` run. This module only
+//! finds those lines; the commit-msg hook validates the IDs and turns them
+//! into trailers.
+
+/// An ack written inline in a commit message
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MessageAck {
+    /// Check ID or group name
+    pub check_ref: String,
+    /// The ack message
+    pub message: String,
+}
+
+/// Split `[ack ID: message]` lines out of a commit message.
+///
+/// Returns the message without those lines and the acks they hold, in
+/// order. A line must consist of the shorthand alone; mentions inside
+/// prose are left as written.
+#[must_use]
+pub fn extract_acks(message: &str) -> (String, Vec<MessageAck>) {
+    let mut kept = Vec::new();
+    let mut acks = Vec::new();
+    for line in message.lines() {
+        match parse_line(line) {
+            Some(ack) => acks.push(ack),
+            None => kept.push(line),
+        }
+    }
+    if acks.is_empty() {
+        return (message.to_string(), acks);
+    }
+    let mut rest = kept.join("\n").trim_end().to_string();
+    rest.push('\n');
+    (rest, acks)
+}
+
+/// Parse one `[ack ID: message]` line
+fn parse_line(line: &str) -> Option<MessageAck> {
+    let inner = line.trim().strip_prefix("[ack ")?.strip_suffix(']')?;
+    let (check_ref, message) = inner.split_once(':')?;
+    let (check_ref, message) = (check_ref.trim(), message.trim());
+    if check_ref.is_empty() || check_ref.contains(char::is_whitespace) || message.is_empty() {
+        return None;
+    }
+    Some(MessageAck {
+        check_ref: check_ref.to_string(),
+        message: message.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extracts_shorthand_lines_and_keeps_the_rest() {
+        let (rest, acks) = extract_acks(
+            "Add migration\n\nSee [ack X-1: prose] inline.\n[ack NOS-3: reviewed migration plan]\n  [ack NOS-4:ran it locally]  \n",
+        );
+        assert_eq!(rest, "Add migration\n\nSee [ack X-1: prose] inline.\n");
+        assert_eq!(
+            acks,
+            vec![
+                MessageAck {
+                    check_ref: "NOS-3".to_string(),
+                    message: "reviewed migration plan".to_string(),
+                },
+                MessageAck {
+                    check_ref: "NOS-4".to_string(),
+                    message: "ran it locally".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn ignores_malformed_shorthand() {
+        for line in ["[ack NOS-3]", "[ack : msg]", "[ack NOS-3: ]", "[ack two words: msg]"] {
+            let (rest, acks) = extract_acks(line);
+            assert!(acks.is_empty(), "{line}");
+            assert_eq!(rest, line);
+        }
+    }
+}
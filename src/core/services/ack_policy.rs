@@ -0,0 +1,82 @@
+//! Ack policy service - per-severity requirements on ack messages
+//!
+//! A blocking check deserves more than "done". Repos can require a minimum
+//! message length and a reference (URL or ticket) per severity; this
+//! module only judges a message against a requirement.
+
+use serde::{Deserialize, Serialize};
+
+/// What an ack message must contain (all constraints off by default)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct AckRequirement {
+    /// Minimum message length in characters (after trimming)
+    pub min_length: usize,
+    /// Require a URL or ticket reference (`ABC-123`, `#123`)
+    pub require_reference: bool,
+}
+
+/// Reasons `message` fails `req`; empty when it complies.
+#[must_use]
+pub fn violations(message: &str, req: &AckRequirement) -> Vec<String> {
+    let mut problems = Vec::new();
+    let length = message.trim().chars().count();
+    if length < req.min_length {
+        problems
+            .push(format!("message is {length} characters; at least {} required", req.min_length));
+    }
+    if req.require_reference && !has_reference(message) {
+        problems.push("message needs a URL or ticket reference (e.g. ABC-123, #123)".to_string());
+    }
+    problems
+}
+
+/// Whether `message` contains a URL or a ticket reference
+fn has_reference(message: &str) -> bool {
+    message.split_whitespace().any(|word| {
+        let word = word.trim_matches(|c: char| !c.is_alphanumeric() && c != '#' && c != '/');
+        word.starts_with("http://") || word.starts_with("https://") || is_ticket(word)
+    })
+}
+
+/// `#123` or `ABC-123` (uppercase project key, digits)
+fn is_ticket(word: &str) -> bool {
+    let all_digits = |s: &str| !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit());
+    if let Some(number) = word.strip_prefix('#') {
+        return all_digits(number);
+    }
+    word.split_once('-').is_some_and(|(key, number)| {
+        key.starts_with(|c: char| c.is_ascii_uppercase())
+            && key.bytes().all(|b| b.is_ascii_uppercase() || b.is_ascii_digit())
+            && all_digits(number)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const STRICT: AckRequirement = AckRequirement {
+        min_length: 20,
+        require_reference: true,
+    };
+
+    #[test]
+    fn default_requirement_accepts_anything() {
+        assert!(violations("ok", &AckRequirement::default()).is_empty());
+    }
+
+    #[test]
+    fn reports_each_unmet_constraint() {
+        assert_eq!(violations("done", &STRICT).len(), 2);
+        assert!(violations("Rate limit added, see PLAT-42", &STRICT).is_empty());
+        assert!(violations("Fixed in https://github.com/o/r/pull/9", &STRICT).is_empty());
+        assert!(violations("Tracked in issue (#812) upstream", &STRICT).is_empty());
+    }
+
+    #[test]
+    fn rejects_lookalike_references() {
+        assert!(!has_reference("bumped to utf-8 and re-ran"));
+        assert!(!has_reference("see section #a"));
+    }
+}
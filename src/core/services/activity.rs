@@ -0,0 +1,161 @@
+//! Activity service - what an actor did, in order
+//!
+//! Pure merge of the two append-only records noslop already keeps: fire
+//! events (a check surfaced for the actor) and ledger acknowledgments (the
+//! actor answered one). No separate journal is written; this is a view.
+
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+
+use crate::core::models::{Acknowledgment, CheckFireEvent};
+
+/// What happened
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ActivityKind {
+    /// A check surfaced on the actor's staged change
+    Fired,
+    /// The actor acknowledged a check
+    Acked,
+}
+
+/// One line of the activity log
+#[derive(Debug, Clone, Serialize)]
+pub struct ActivityEntry {
+    /// When it happened (RFC 3339)
+    pub at: String,
+    /// Who did it
+    pub actor: String,
+    /// What happened
+    pub kind: ActivityKind,
+    /// Check involved
+    pub check_id: String,
+    /// The matched file (fires) or the ack message (acks)
+    pub detail: String,
+}
+
+/// Merge events and acks into a chronological log.
+///
+/// `actor` keeps only that actor's entries; `since` drops anything older.
+/// Records with unparseable timestamps are kept unless `since` is set.
+#[must_use]
+pub fn activity_log(
+    events: &[CheckFireEvent],
+    acks: &[Acknowledgment],
+    actor: Option<&str>,
+    since: Option<DateTime<Utc>>,
+) -> Vec<ActivityEntry> {
+    let fired = events.iter().map(|e| ActivityEntry {
+        at: e.created_at.clone(),
+        actor: e.actor.clone(),
+        kind: ActivityKind::Fired,
+        check_id: e.check_id.clone(),
+        detail: e.file.clone(),
+    });
+    let acked = acks.iter().map(|a| ActivityEntry {
+        at: a.created_at.clone(),
+        actor: a.acknowledged_by.clone(),
+        kind: ActivityKind::Acked,
+        check_id: a.check_id.clone(),
+        detail: a.message.clone(),
+    });
+
+    let mut log: Vec<ActivityEntry> = fired
+        .chain(acked)
+        .filter(|e| actor.is_none_or(|name| e.actor == name))
+        .filter(|e| {
+            since.is_none_or(|cutoff| {
+                DateTime::parse_from_rfc3339(&e.at).is_ok_and(|at| at.with_timezone(&Utc) >= cutoff)
+            })
+        })
+        .collect();
+    log.sort_by(|a, b| a.at.cmp(&b.at));
+    log
+}
+
+/// Parse a relative window like `30m`, `12h`, `1d`, or `2w`.
+///
+/// # Errors
+///
+/// Returns an error for an empty value, a non-numeric amount, or an
+/// unknown unit.
+pub fn parse_since(value: &str) -> Result<Duration, String> {
+    let value = value.trim();
+    let invalid = || format!("Invalid --since '{value}'. Use e.g. 30m, 12h, 1d, 2w");
+    let unit = value.chars().last().ok_or_else(invalid)?;
+    let amount: i64 = value[..value.len() - unit.len_utf8()].parse().map_err(|_| invalid())?;
+    let window = match unit {
+        'm' => Duration::try_minutes(amount),
+        'h' => Duration::try_hours(amount),
+        'd' => Duration::try_days(amount),
+        'w' => Duration::try_weeks(amount),
+        _ => return Err(format!("Invalid --since unit in '{value}'. Use m, h, d, or w")),
+    };
+    window.ok_or_else(|| format!("--since '{value}' is too large"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::models::Severity;
+
+    fn event(actor: &str, at: &str) -> CheckFireEvent {
+        let mut e = CheckFireEvent::new(
+            "NOS-1".into(),
+            "src/lib.rs".into(),
+            Severity::Block,
+            actor.into(),
+            "tree".into(),
+        );
+        e.created_at = at.into();
+        e
+    }
+
+    fn ack(actor: &str, at: &str) -> Acknowledgment {
+        let mut a = Acknowledgment::new("NOS-1".into(), "verified".into(), actor.into());
+        a.created_at = at.into();
+        a
+    }
+
+    #[test]
+    fn merges_chronologically() {
+        let events = [event("claude-code", "2026-01-02T00:00:00+00:00")];
+        let acks = [ack("claude-code", "2026-01-03T00:00:00+00:00")];
+        let log = activity_log(&events, &acks, None, None);
+        assert_eq!(log.len(), 2);
+        assert_eq!(log[0].kind, ActivityKind::Fired);
+        assert_eq!(log[1].kind, ActivityKind::Acked);
+    }
+
+    #[test]
+    fn filters_by_actor_and_since() {
+        let events = [
+            event("claude-code", "2026-01-01T00:00:00+00:00"),
+            event("cursor", "2026-01-05T00:00:00+00:00"),
+        ];
+        let acks = [ack("claude-code", "2026-01-05T00:00:00+00:00")];
+        let cutoff = DateTime::parse_from_rfc3339("2026-01-04T00:00:00+00:00")
+            .unwrap()
+            .with_timezone(&Utc);
+
+        let log = activity_log(&events, &acks, Some("claude-code"), Some(cutoff));
+        assert_eq!(log.len(), 1);
+        assert_eq!(log[0].kind, ActivityKind::Acked);
+    }
+
+    #[test]
+    fn parses_relative_windows() {
+        assert_eq!(parse_since("1d"), Ok(Duration::days(1)));
+        assert_eq!(parse_since("12h"), Ok(Duration::hours(12)));
+        assert!(parse_since("soon").is_err());
+        assert!(parse_since("").is_err());
+    }
+
+    #[test]
+    fn rejects_non_ascii_units_and_oversized_windows() {
+        assert!(parse_since("1д").is_err());
+        assert!(parse_since("д").is_err());
+        assert!(parse_since("99999999999999d").is_err());
+        assert!(parse_since("9223372036854775807w").is_err());
+    }
+}
@@ -3,6 +3,9 @@
 //! This service contains the pure business logic for checking checks
 //! against staged files and acknowledgments.
 
+use std::collections::BTreeMap;
+
+use super::codeowners;
 use crate::core::models::{Acknowledgment, Check, Severity};
 
 /// Result of a check operation
@@ -33,8 +36,21 @@ pub struct CheckItemResult {
     pub message: String,
     /// Severity level
     pub severity: Severity,
+    /// Ack group the check belongs to
+    pub group: Option<String>,
+    /// Who to contact when the check blocks
+    pub owner: Option<String>,
+    /// The matched file is vendored or generated
+    pub vendored: bool,
+    /// Agent acks count only once a human approves them
+    pub requires_human: bool,
+    /// Owners whose ack alone counts (`codeowners = ["ack"]`; empty = anyone's)
+    pub code_owners: Vec<String>,
     /// Whether this check was acknowledged
     pub acknowledged: bool,
+    /// Blob id an ack recorded for this file, when the file has changed
+    /// since: that ack answers the check elsewhere, not here
+    pub stale_digest: Option<String>,
 }
 
 impl CheckResult {
@@ -81,13 +97,35 @@ pub fn check_items(
     applicable: &[(Check, String)],
     acks: &[Acknowledgment],
     files_checked: usize,
+) -> CheckResult {
+    check_items_at(applicable, acks, files_checked, &BTreeMap::new())
+}
+
+/// [`check_items`] for a tree whose file contents are known
+///
+/// `blobs` maps matched files to their git blob ids. An ack that recorded
+/// a different blob for a file does not answer its check there; the item
+/// carries [`CheckItemResult::stale_digest`]. Files missing from `blobs`,
+/// or from an ack's digests, are judged as by [`check_items`].
+#[must_use]
+pub fn check_items_at(
+    applicable: &[(Check, String)],
+    acks: &[Acknowledgment],
+    files_checked: usize,
+    blobs: &BTreeMap<String, String>,
 ) -> CheckResult {
     let mut blocking = Vec::new();
     let mut warnings = Vec::new();
     let mut acknowledged_list = Vec::new();
 
     for (check, file) in applicable {
-        let is_acknowledged = is_check_acknowledged(check, acks);
+        let blob = blobs.get(file).map(String::as_str);
+        let answering: Vec<&Acknowledgment> = acks.iter().filter(|a| answers(check, a)).collect();
+        let is_acknowledged = answering.iter().any(|a| !a.reviewed_other_content(file, blob));
+        let stale_digest = answering
+            .first()
+            .filter(|_| !is_acknowledged)
+            .and_then(|a| a.file_digests.get(file).cloned());
 
         let result = CheckItemResult {
             id: check.id.clone(),
@@ -95,7 +133,13 @@ pub fn check_items(
             target: check.target.clone(),
             message: check.message.clone(),
             severity: check.severity,
+            group: check.group.clone(),
+            owner: check.owner.clone(),
+            vendored: check.vendored,
+            requires_human: check.requires_human,
+            code_owners: check.code_owners.clone(),
             acknowledged: is_acknowledged,
+            stale_digest,
         };
 
         match check.severity {
@@ -123,12 +167,28 @@ pub fn check_items(
     }
 }
 
-/// Check if a check has been acknowledged
+/// Whether `ack` answers `check`
+///
+/// Only an exact ID match counts, or the exact name of the check's group:
+/// fuzzy matching would let one answer cover unrelated checks. On a
+/// `requires_human` check an agent's ack is pending until a human approves
+/// it. On a `codeowners = ["ack"]` check only an ack made or approved by
+/// one of the file's code owners counts.
+fn answers(check: &Check, ack: &Acknowledgment) -> bool {
+    check.answered_by(&ack.check_id)
+        && (!check.requires_human || ack.is_human_signed())
+        && (check.code_owners.is_empty() || signed_by_owner(&check.code_owners, ack))
+}
+
+/// Whether `ack` names an owner as its maker or approver
 ///
-/// Only an exact ID match counts: fuzzy matching would let one answer
-/// cover unrelated checks.
-fn is_check_acknowledged(check: &Check, acks: &[Acknowledgment]) -> bool {
-    acks.iter().any(|a| a.check_id == check.id)
+/// Honor-based: identities are whatever the acknowledging side asserted
+/// (`--identity`, `NOSLOP_IDENTITY`, git config), never authenticated.
+fn signed_by_owner(owners: &[String], ack: &Acknowledgment) -> bool {
+    [&ack.identity, &ack.approved_by]
+        .into_iter()
+        .flatten()
+        .any(|who| codeowners::is_owner(owners, who))
 }
 
 #[cfg(test)]
@@ -190,4 +250,80 @@ mod tests {
         assert!(result.passed);
         assert_eq!(result.warnings.len(), 1);
     }
+
+    #[test]
+    fn test_requires_human_ignores_unapproved_agent_ack() {
+        let check =
+            make_check("CHK-1", "*.rs", "Review Rust", Severity::Block).with_requires_human(true);
+        let applicable = vec![(check, "src/main.rs".to_string())];
+        let mut agent_ack =
+            Acknowledgment::new("CHK-1".to_string(), "Reviewed".to_string(), "claude-code".into());
+
+        assert!(!check_items(&applicable, std::slice::from_ref(&agent_ack), 1).passed);
+
+        agent_ack.approved_by = Some("human".to_string());
+        assert!(check_items(&applicable, &[agent_ack], 1).passed);
+        assert!(check_items(&applicable, &[make_ack("CHK-1", "Reviewed")], 1).passed);
+    }
+
+    #[test]
+    fn test_owner_ack_needs_an_owner_identity_or_approval() {
+        let mut check = make_check("CHK-1", "*.rs", "Review Rust", Severity::Block);
+        check.code_owners = vec!["@alice".to_string()];
+        let applicable = vec![(check, "src/main.rs".to_string())];
+        let mut ack = make_ack("CHK-1", "Reviewed");
+        ack.identity = Some("bob@example.com".to_string());
+
+        assert!(!check_items(&applicable, std::slice::from_ref(&ack), 1).passed);
+        ack.approved_by = Some("alice".to_string());
+        assert!(check_items(&applicable, std::slice::from_ref(&ack), 1).passed);
+        ack.approved_by = None;
+        ack.identity = Some("@alice".to_string());
+        assert!(check_items(&applicable, &[ack], 1).passed);
+    }
+
+    #[test]
+    fn test_ack_answers_only_files_it_reviewed_unchanged() {
+        let check = make_check("CHK-1", "*.rs", "Review Rust", Severity::Block);
+        let applicable = vec![(check.clone(), "a.rs".to_string()), (check, "b.rs".to_string())];
+        let digests = [("a.rs", "a1"), ("b.rs", "b1")]
+            .into_iter()
+            .map(|(file, blob)| (file.to_string(), blob.to_string()))
+            .collect();
+        let ack = make_ack("CHK-1", "Reviewed").with_file_digests(digests);
+        let blobs = [("a.rs", "a1"), ("b.rs", "b2")]
+            .into_iter()
+            .map(|(file, blob)| (file.to_string(), blob.to_string()))
+            .collect();
+
+        let result = check_items_at(&applicable, std::slice::from_ref(&ack), 2, &blobs);
+        assert_eq!(result.blocking.len(), 1);
+        assert_eq!(result.blocking[0].file, "b.rs");
+        assert_eq!(result.blocking[0].stale_digest.as_deref(), Some("b1"));
+        assert_eq!(result.acknowledged[0].file, "a.rs");
+        // Contents unknown: the ack answers every file, as before
+        assert!(check_items(&applicable, &[ack], 2).passed);
+    }
+
+    #[test]
+    fn test_group_ack_satisfies_every_member() {
+        let group = Some("security-review".to_string());
+        let applicable = vec![
+            (
+                make_check("SEC-1", "auth.rs", "Tokens?", Severity::Block)
+                    .with_group(group.clone()),
+                "auth.rs".to_string(),
+            ),
+            (
+                make_check("SEC-2", "auth.rs", "Logging?", Severity::Block).with_group(group),
+                "auth.rs".to_string(),
+            ),
+            (make_check("RS-1", "*.rs", "Tests?", Severity::Block), "auth.rs".to_string()),
+        ];
+
+        let result = check_items(&applicable, &[make_ack("security-review", "Reviewed")], 1);
+        assert_eq!(result.acknowledged.len(), 2);
+        assert_eq!(result.blocking.len(), 1);
+        assert_eq!(result.blocking[0].id, "RS-1");
+    }
 }
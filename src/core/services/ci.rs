@@ -0,0 +1,222 @@
+//! CI scaffolding service - pipeline snippets that run the diff-base gate
+//!
+//! Pure rendering: each provider gets a job that installs noslop, fetches
+//! the base ref, and runs `noslop check --ci --diff-base <base>`. GitLab and
+//! `CircleCI` cache the binary between runs. GitHub uses the published Action,
+//! pinned to a release tag, which installs noslop itself and also writes the
+//! ack table to the job summary.
+
+/// Supported CI providers
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CiProvider {
+    /// GitHub Actions workflow
+    Github,
+    /// GitLab CI job
+    Gitlab,
+    /// `CircleCI` config
+    Circleci,
+}
+
+impl CiProvider {
+    /// Conventional path for the generated file
+    #[must_use]
+    pub const fn default_path(self) -> &'static str {
+        match self {
+            Self::Github => ".github/workflows/noslop.yml",
+            Self::Gitlab => ".gitlab-ci.yml",
+            Self::Circleci => ".circleci/config.yml",
+        }
+    }
+}
+
+impl std::str::FromStr for CiProvider {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "github" => Ok(Self::Github),
+            "gitlab" => Ok(Self::Gitlab),
+            "circleci" => Ok(Self::Circleci),
+            _ => Err(format!("Unknown CI provider: {s}. Use: github, gitlab, circleci")),
+        }
+    }
+}
+
+/// Options for a generated pipeline
+#[derive(Debug, Clone)]
+pub struct CiOptions {
+    /// Base branch the pull request diff is computed against (e.g. `main`)
+    pub base_branch: String,
+    /// noslop version to install (`latest` for the newest release)
+    pub version: String,
+}
+
+/// Render the pipeline snippet for `provider`.
+#[must_use]
+pub fn generate(provider: CiProvider, opts: &CiOptions) -> String {
+    match provider {
+        CiProvider::Github => github(opts),
+        CiProvider::Gitlab => gitlab(opts),
+        CiProvider::Circleci => circleci(opts),
+    }
+}
+
+fn install_cmd(version: &str) -> String {
+    if version == "latest" {
+        "cargo install noslop --locked".to_string()
+    } else {
+        format!("cargo install noslop --version {version} --locked")
+    }
+}
+
+/// Release tag of the Action: the pinned version, or the release that
+/// generated the workflow for `latest`.
+fn action_tag(version: &str) -> String {
+    let version = if version == "latest" {
+        env!("CARGO_PKG_VERSION")
+    } else {
+        version
+    };
+    format!("v{}", version.trim_start_matches('v'))
+}
+
+fn github(opts: &CiOptions) -> String {
+    format!(
+        "# noslop: recompute checks on the PR diff and reconcile the ack ledger
+name: noslop
+
+on:
+  pull_request:
+    branches: [{base}]
+
+jobs:
+  check:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/checkout@v4
+        with:
+          fetch-depth: 0
+      - uses: dtolnay/rust-toolchain@stable
+      - uses: noslop-sh/noslop@{tag}
+        with:
+          base-ref: origin/{base}
+          version: {version}
+",
+        base = opts.base_branch,
+        version = opts.version,
+        tag = action_tag(&opts.version),
+    )
+}
+
+fn gitlab(opts: &CiOptions) -> String {
+    format!(
+        "# noslop: recompute checks on the MR diff and reconcile the ack ledger
+noslop:
+  image: rust:latest
+  rules:
+    - if: $CI_PIPELINE_SOURCE == \"merge_request_event\"
+  variables:
+    GIT_DEPTH: 0
+    CARGO_HOME: $CI_PROJECT_DIR/.cargo
+  cache:
+    key: noslop-{version}
+    paths:
+      - .cargo/bin/
+  script:
+    - command -v noslop || {install}
+    - git fetch --no-tags origin \"${{CI_MERGE_REQUEST_TARGET_BRANCH_NAME:-{base}}}\"
+    - NOSLOP_ACTOR=ci noslop check --ci --diff-base \"origin/${{CI_MERGE_REQUEST_TARGET_BRANCH_NAME:-{base}}}\"
+",
+        base = opts.base_branch,
+        version = opts.version,
+        install = install_cmd(&opts.version),
+    )
+}
+
+fn circleci(opts: &CiOptions) -> String {
+    format!(
+        "# noslop: recompute checks on the branch diff and reconcile the ack ledger
+version: 2.1
+
+jobs:
+  noslop:
+    docker:
+      - image: cimg/rust:1.85
+    steps:
+      - checkout
+      - restore_cache:
+          keys:
+            - noslop-{version}
+      - run: command -v noslop || {install}
+      - save_cache:
+          key: noslop-{version}
+          paths:
+            - ~/.cargo/bin/noslop
+      - run: git fetch --no-tags origin {base}
+      - run: NOSLOP_ACTOR=ci noslop check --ci --diff-base origin/{base}
+
+workflows:
+  noslop:
+    jobs:
+      - noslop
+",
+        base = opts.base_branch,
+        version = opts.version,
+        install = install_cmd(&opts.version),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn opts() -> CiOptions {
+        CiOptions {
+            base_branch: "develop".into(),
+            version: "latest".into(),
+        }
+    }
+
+    #[test]
+    fn every_provider_gates_on_the_base_branch() {
+        for provider in [CiProvider::Github, CiProvider::Gitlab, CiProvider::Circleci] {
+            let out = generate(provider, &opts());
+            assert!(out.contains("develop"), "{provider:?} ignores the base branch");
+        }
+        assert!(generate(CiProvider::Gitlab, &opts()).contains("noslop check --ci --diff-base"));
+        assert!(generate(CiProvider::Circleci, &opts()).contains("noslop check --ci --diff-base"));
+    }
+
+    #[test]
+    fn github_pins_the_action_to_a_release_tag() {
+        let out = generate(CiProvider::Github, &opts());
+        let tag = format!("uses: noslop-sh/noslop@v{}", env!("CARGO_PKG_VERSION"));
+        assert!(out.contains(&tag));
+        assert!(!out.contains("@main"));
+        // The Action always installs, so a cache step would only go stale
+        assert!(!out.contains("actions/cache"));
+
+        let pinned = CiOptions {
+            version: "0.2.0".into(),
+            ..opts()
+        };
+        assert!(generate(CiProvider::Github, &pinned).contains("uses: noslop-sh/noslop@v0.2.0"));
+    }
+
+    #[test]
+    fn pinned_version_is_installed_and_cached() {
+        let pinned = CiOptions {
+            version: "0.2.0".into(),
+            ..opts()
+        };
+        let out = generate(CiProvider::Circleci, &pinned);
+        assert!(out.contains("cargo install noslop --version 0.2.0 --locked"));
+        assert!(out.contains("noslop-0.2.0"));
+    }
+
+    #[test]
+    fn parses_providers() {
+        assert_eq!("GitHub".parse::<CiProvider>(), Ok(CiProvider::Github));
+        assert!("jenkins".parse::<CiProvider>().is_err());
+    }
+}
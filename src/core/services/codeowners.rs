@@ -0,0 +1,185 @@
+//! CODEOWNERS resolution
+//!
+//! Parses a GitHub/GitLab `CODEOWNERS` file and answers who owns a path.
+//! Patterns follow the gitignore subset CODEOWNERS documents: a leading
+//! `/` or an inner `/` anchors to the repository root, a trailing `/`
+//! matches directories only, `*` stays within a path segment and `**`
+//! spans segments. The last matching rule wins.
+
+/// Where GitHub looks for the file, in precedence order
+pub const LOCATIONS: [&str; 3] = [".github/CODEOWNERS", "CODEOWNERS", "docs/CODEOWNERS"];
+
+/// One `pattern owner...` line
+#[derive(Debug, Clone)]
+struct Rule {
+    segments: Vec<String>,
+    dir_only: bool,
+    owners: Vec<String>,
+}
+
+/// Parsed ownership rules
+#[derive(Debug, Clone, Default)]
+pub struct CodeOwners {
+    rules: Vec<Rule>,
+}
+
+impl CodeOwners {
+    /// Parse CODEOWNERS text; comments, blank lines and GitLab `[Section]`
+    /// headers are skipped
+    #[must_use]
+    pub fn parse(text: &str) -> Self {
+        let mut rules = Vec::new();
+        for line in text.lines() {
+            let line = line.split_once(" #").map_or(line, |(rule, _)| rule).trim();
+            if line.is_empty() || line.starts_with('#') || line.starts_with('[') {
+                continue;
+            }
+            let mut words = line.split_whitespace();
+            let Some(pattern) = words.next() else {
+                continue;
+            };
+            let dir_only = pattern.ends_with('/');
+            let trimmed = pattern.trim_end_matches('/');
+            let anchored = trimmed.starts_with('/') || trimmed.contains('/');
+            let mut segments: Vec<String> = trimmed
+                .trim_start_matches('/')
+                .split('/')
+                .filter(|s| !s.is_empty())
+                .map(String::from)
+                .collect();
+            if !anchored {
+                segments.insert(0, "**".to_string());
+            }
+            rules.push(Rule {
+                segments,
+                dir_only,
+                owners: words.map(String::from).collect(),
+            });
+        }
+        Self { rules }
+    }
+
+    /// Owners of `path` (repo-root relative), empty when no rule matches or
+    /// the last match names nobody
+    #[must_use]
+    pub fn owners_of(&self, path: &str) -> &[String] {
+        let parts: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+        self.rules
+            .iter()
+            .rev()
+            .find(|rule| {
+                // The pattern names the file itself, or a directory above it
+                (1..=parts.len()).any(|len| {
+                    (len < parts.len() || !rule.dir_only)
+                        && segments_match(&rule.segments, &parts[..len])
+                })
+            })
+            .map_or(&[], |rule| rule.owners.as_slice())
+    }
+}
+
+/// Whether `identity` is one of `owners`
+///
+/// Emails compare case-insensitively; an `@handle` owner also matches the
+/// identity `handle`. Teams (`@org/team`) only match an identity that
+/// names the team itself: membership is not known offline.
+#[must_use]
+pub fn is_owner(owners: &[String], identity: &str) -> bool {
+    let identity = identity.trim();
+    owners.iter().any(|owner| {
+        owner.eq_ignore_ascii_case(identity)
+            || owner
+                .strip_prefix('@')
+                .is_some_and(|handle| handle.eq_ignore_ascii_case(identity))
+    })
+}
+
+fn segments_match(pattern: &[String], path: &[&str]) -> bool {
+    match pattern.split_first() {
+        None => path.is_empty(),
+        Some((first, rest)) if first == "**" => {
+            (0..=path.len()).any(|skip| segments_match(rest, &path[skip..]))
+        },
+        Some((first, rest)) => path
+            .split_first()
+            .is_some_and(|(part, tail)| wildcard(first, part) && segments_match(rest, tail)),
+    }
+}
+
+/// `*` and `?` within one path segment
+fn wildcard(pattern: &str, text: &str) -> bool {
+    let (p, t): (Vec<char>, Vec<char>) = (pattern.chars().collect(), text.chars().collect());
+    let (mut pi, mut ti) = (0, 0);
+    let mut star: Option<(usize, usize)> = None;
+    while ti < t.len() {
+        if pi < p.len() && (p[pi] == '?' || p[pi] == t[ti]) {
+            pi += 1;
+            ti += 1;
+        } else if pi < p.len() && p[pi] == '*' {
+            star = Some((pi, ti));
+            pi += 1;
+        } else if let Some((sp, st)) = star {
+            pi = sp + 1;
+            ti = st + 1;
+            star = Some((sp, st + 1));
+        } else {
+            return false;
+        }
+    }
+    p[pi..].iter().all(|&c| c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FILE: &str = "\
+# Default owners
+*                 @org/everyone
+*.rs              @rustaceans
+/docs/            docs@example.com
+apps/**/api.ts    @api-team
+build/            @release   # trailing comment
+/src/payments/    @alice @org/payments
+/src/payments/generated/
+";
+
+    #[test]
+    fn test_last_matching_rule_wins() {
+        let owners = CodeOwners::parse(FILE);
+        assert_eq!(owners.owners_of("README.md"), ["@org/everyone"]);
+        assert_eq!(owners.owners_of("src/lib.rs"), ["@rustaceans"]);
+        assert_eq!(owners.owners_of("src/payments/charge.rs"), ["@alice", "@org/payments"]);
+        // A rule naming nobody clears ownership
+        assert!(owners.owners_of("src/payments/generated/x.rs").is_empty());
+    }
+
+    #[test]
+    fn test_anchoring_and_directories() {
+        let owners = CodeOwners::parse(FILE);
+        assert_eq!(owners.owners_of("docs/guide/intro.md"), ["docs@example.com"]);
+        // Anchored: a nested docs/ is not the root one
+        assert_eq!(owners.owners_of("lib/docs/intro.md"), ["@org/everyone"]);
+        // Unanchored directory: build/ anywhere, but not a file named build
+        assert_eq!(owners.owners_of("tools/build/out.txt"), ["@release"]);
+        assert_eq!(owners.owners_of("tools/build"), ["@org/everyone"]);
+        assert_eq!(owners.owners_of("apps/web/v2/api.ts"), ["@api-team"]);
+        assert_eq!(owners.owners_of("apps/api.ts"), ["@api-team"]);
+    }
+
+    #[test]
+    fn test_no_rules_means_no_owners() {
+        assert!(CodeOwners::parse("").owners_of("src/main.rs").is_empty());
+        assert!(CodeOwners::default().owners_of("x").is_empty());
+    }
+
+    #[test]
+    fn test_is_owner_matches_emails_and_handles() {
+        let owners = vec!["@alice".to_string(), "Bob@Example.com".to_string()];
+        assert!(is_owner(&owners, "alice"));
+        assert!(is_owner(&owners, "@alice"));
+        assert!(is_owner(&owners, "bob@example.com"));
+        assert!(!is_owner(&owners, "carol@example.com"));
+        assert!(!is_owner(&["@org/team".to_string()], "alice"));
+    }
+}
@@ -0,0 +1,199 @@
+//! Coverage service - which checks cover which directories
+//!
+//! Folds (check, file) matches over the tracked file list into one row per
+//! directory: how many of its files any check covers, how many more than
+//! one check covers (overlap), and which checks concentrate there. A
+//! directory with files and no covering check is a gap.
+
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+
+/// Coverage of the files directly inside one directory
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DirCoverage {
+    /// Directory relative to the repo (`.` for the root)
+    pub dir: String,
+    /// Files directly in this directory
+    pub files: usize,
+    /// Files matched by at least one check
+    pub covered: usize,
+    /// Files matched by more than one check
+    pub overlapping: usize,
+    /// Checks matching files here, most files first
+    pub checks: Vec<CheckCoverage>,
+}
+
+impl DirCoverage {
+    /// No check covers any file here
+    #[must_use]
+    pub const fn is_gap(&self) -> bool {
+        self.covered == 0
+    }
+}
+
+/// How many files in a directory one check matches
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CheckCoverage {
+    /// The check ID
+    pub id: String,
+    /// Files it matches in the directory
+    pub files: usize,
+}
+
+/// Build the per-directory coverage map, sorted by directory.
+///
+/// `matches` pairs a check ID with a file it matches; duplicates count
+/// once.
+#[must_use]
+pub fn coverage_map(files: &[String], matches: &[(String, String)]) -> Vec<DirCoverage> {
+    let mut per_file: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+    for (id, file) in matches {
+        let ids = per_file.entry(file.as_str()).or_default();
+        if !ids.contains(&id.as_str()) {
+            ids.push(id);
+        }
+    }
+
+    let mut coverage = Coverage::default();
+    for file in files {
+        coverage.add(file, per_file.get(file.as_str()).map_or(&[][..], Vec::as_slice));
+    }
+    coverage.finish()
+}
+
+/// Coverage folded in one file at a time, for file lists too large to hold
+///
+/// Memory grows with the number of directories, not files: batches of
+/// files can be added as they are read, on several threads, and the
+/// partial results merged.
+#[derive(Debug, Default)]
+pub struct Coverage {
+    dirs: BTreeMap<String, DirCoverage>,
+    counts: BTreeMap<String, BTreeMap<String, usize>>,
+}
+
+impl Coverage {
+    /// Count `file`, matched by the checks `ids` (distinct)
+    pub fn add(&mut self, file: &str, ids: &[&str]) {
+        let dir = file.rsplit_once('/').map_or(".", |(dir, _)| dir);
+        let row = self.dirs.entry(dir.to_string()).or_insert_with(|| DirCoverage {
+            dir: dir.to_string(),
+            files: 0,
+            covered: 0,
+            overlapping: 0,
+            checks: Vec::new(),
+        });
+        row.files += 1;
+        if !ids.is_empty() {
+            row.covered += 1;
+        }
+        if ids.len() > 1 {
+            row.overlapping += 1;
+        }
+        if ids.is_empty() {
+            return;
+        }
+        let counts = self.counts.entry(dir.to_string()).or_default();
+        for id in ids {
+            *counts.entry((*id).to_string()).or_default() += 1;
+        }
+    }
+
+    /// Fold in coverage counted over other files
+    pub fn merge(&mut self, other: Self) {
+        for (dir, theirs) in other.dirs {
+            let row = self.dirs.entry(dir).or_insert_with(|| DirCoverage {
+                files: 0,
+                covered: 0,
+                overlapping: 0,
+                ..theirs.clone()
+            });
+            row.files += theirs.files;
+            row.covered += theirs.covered;
+            row.overlapping += theirs.overlapping;
+        }
+        for (dir, theirs) in other.counts {
+            let counts = self.counts.entry(dir).or_default();
+            for (id, files) in theirs {
+                *counts.entry(id).or_default() += files;
+            }
+        }
+    }
+
+    /// Files counted so far
+    #[must_use]
+    pub fn files(&self) -> usize {
+        self.dirs.values().map(|row| row.files).sum()
+    }
+
+    /// The per-directory map, sorted by directory
+    #[must_use]
+    pub fn finish(mut self) -> Vec<DirCoverage> {
+        self.dirs
+            .into_iter()
+            .map(|(dir, mut row)| {
+                row.checks = self
+                    .counts
+                    .remove(&dir)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|(id, files)| CheckCoverage { id, files })
+                    .collect();
+                row.checks.sort_by_key(|c| std::cmp::Reverse(c.files));
+                row
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn s(v: &[&str]) -> Vec<String> {
+        v.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn groups_by_directory_with_gaps_and_overlaps() {
+        let files = s(&["README.md", "src/a.rs", "src/b.rs", "docs/x.md"]);
+        let matches = vec![
+            ("RS-1".to_string(), "src/a.rs".to_string()),
+            ("RS-1".to_string(), "src/b.rs".to_string()),
+            ("SEC-1".to_string(), "src/a.rs".to_string()),
+            ("MD-1".to_string(), "README.md".to_string()),
+        ];
+        let map = coverage_map(&files, &matches);
+        let dirs: Vec<&str> = map.iter().map(|d| d.dir.as_str()).collect();
+        assert_eq!(dirs, vec![".", "docs", "src"]);
+
+        let docs = &map[1];
+        assert!(docs.is_gap());
+
+        let src = &map[2];
+        assert_eq!((src.files, src.covered, src.overlapping), (2, 2, 1));
+        assert_eq!(src.checks[0].id, "RS-1");
+        assert_eq!(src.checks[0].files, 2);
+    }
+
+    #[test]
+    fn merged_batches_match_one_pass() {
+        let mut first = Coverage::default();
+        first.add("src/a.rs", &["RS-1", "SEC-1"]);
+        first.add("README.md", &[]);
+        let mut second = Coverage::default();
+        second.add("src/b.rs", &["RS-1"]);
+        second.add("docs/x.md", &[]);
+        first.merge(second);
+        assert_eq!(first.files(), 4);
+
+        let files = s(&["src/a.rs", "README.md", "src/b.rs", "docs/x.md"]);
+        let matches = vec![
+            ("RS-1".to_string(), "src/a.rs".to_string()),
+            ("SEC-1".to_string(), "src/a.rs".to_string()),
+            ("RS-1".to_string(), "src/b.rs".to_string()),
+        ];
+        assert_eq!(first.finish(), coverage_map(&files, &matches));
+    }
+}
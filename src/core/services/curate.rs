@@ -75,6 +75,7 @@ pub fn recommend(stats: &[CheckStats]) -> Vec<Recommendation> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::models::Severity;
 
     fn stat(
         id: &str,
@@ -87,7 +88,7 @@ mod tests {
         CheckStats {
             id: id.to_string(),
             target: "src/**/*.rs".to_string(),
-            severity: "block".to_string(),
+            severity: Severity::Block,
             fires,
             acks,
             acted,
@@ -0,0 +1,75 @@
+//! Diff excerpt service - keep a review-sized slice of a unified diff
+//!
+//! A long diff buries the part that matters. Truncation is per file, so a
+//! huge generated file can't crowd out the small hand-written change next
+//! to it: every file header survives, and each file keeps its first lines.
+
+use std::fmt::Write;
+
+/// Truncate a unified diff to at most `per_file` body lines per file.
+///
+/// File headers (`diff --git` through `+++`) are always kept; elided lines
+/// are replaced by a `... (N more lines)` marker.
+#[must_use]
+pub fn excerpt(diff: &str, per_file: usize) -> String {
+    let mut out = String::new();
+    let mut body = 0;
+    let mut elided = 0;
+
+    let flush = |out: &mut String, elided: &mut usize| {
+        if *elided > 0 {
+            let _ = writeln!(out, "... ({elided} more lines)");
+            *elided = 0;
+        }
+    };
+
+    for line in diff.lines() {
+        if line.starts_with("diff --git ") {
+            flush(&mut out, &mut elided);
+            body = 0;
+        }
+        let is_header = line.starts_with("diff --git ")
+            || line.starts_with("index ")
+            || line.starts_with("--- ")
+            || line.starts_with("+++ ")
+            || line.starts_with("new file mode")
+            || line.starts_with("deleted file mode")
+            || line.starts_with("similarity index")
+            || line.starts_with("rename from")
+            || line.starts_with("rename to");
+        if is_header && body == 0 {
+            out.push_str(line);
+            out.push('\n');
+            continue;
+        }
+        if body < per_file {
+            out.push_str(line);
+            out.push('\n');
+            body += 1;
+        } else {
+            elided += 1;
+        }
+    }
+    flush(&mut out, &mut elided);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DIFF: &str = "diff --git a/a.rs b/a.rs\nindex 1..2 100644\n--- a/a.rs\n+++ b/a.rs\n@@ -1,3 +1,3 @@\n-one\n+uno\n two\ndiff --git a/b.rs b/b.rs\n--- a/b.rs\n+++ b/b.rs\n@@ -1 +1 @@\n-x\n+y\n";
+
+    #[test]
+    fn keeps_short_diffs_intact() {
+        assert_eq!(excerpt(DIFF, 100), DIFF);
+    }
+
+    #[test]
+    fn truncates_each_file_and_keeps_every_header() {
+        let out = excerpt(DIFF, 2);
+        assert!(out.contains("+++ b/a.rs\n@@ -1,3 +1,3 @@\n-one\n... (2 more lines)\n"));
+        assert!(out.contains("diff --git a/b.rs b/b.rs"));
+        assert!(out.ends_with("-x\n... (1 more lines)\n"));
+    }
+}
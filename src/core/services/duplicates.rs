@@ -0,0 +1,107 @@
+//! Duplicates service - checks defined identically in several config files
+//!
+//! Nested configs often copy a rule from a parent ("*.rs needs review").
+//! Each copy fires on its own, so one change surfaces the same question
+//! several times. A check is a duplicate when another config file defines
+//! the same target, message, and severity; targets are compared as written.
+
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+
+use crate::core::models::Severity;
+
+/// One check definition, as written in a config file
+#[derive(Debug, Clone, Copy)]
+pub struct Definition<'a> {
+    /// Config file defining it, relative to the repo root
+    pub source: &'a str,
+    /// Target pattern as written
+    pub target: &'a str,
+    /// The check message
+    pub message: &'a str,
+    /// Severity
+    pub severity: Severity,
+}
+
+/// A check defined identically in more than one config file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Duplicate {
+    /// Target pattern
+    pub target: String,
+    /// The check message
+    pub message: String,
+    /// Severity
+    pub severity: Severity,
+    /// Config files defining it, sorted
+    pub sources: Vec<String>,
+}
+
+/// Find checks defined identically in two or more config files, sorted by
+/// target then message. Repeats within one file count once.
+#[must_use]
+pub fn find_duplicates(definitions: &[Definition<'_>]) -> Vec<Duplicate> {
+    let mut by_key: BTreeMap<(&str, &str, String), (Severity, Vec<&str>)> = BTreeMap::new();
+    for def in definitions {
+        let key = (def.target, def.message, def.severity.to_string());
+        let (_, sources) = by_key.entry(key).or_insert_with(|| (def.severity, Vec::new()));
+        if !sources.contains(&def.source) {
+            sources.push(def.source);
+        }
+    }
+    by_key
+        .into_iter()
+        .filter(|(_, (_, sources))| sources.len() > 1)
+        .map(|((target, message, _), (severity, mut sources))| {
+            sources.sort_unstable();
+            Duplicate {
+                target: target.to_string(),
+                message: message.to_string(),
+                severity,
+                sources: sources.into_iter().map(str::to_string).collect(),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const fn def<'a>(source: &'a str, target: &'a str, severity: Severity) -> Definition<'a> {
+        Definition {
+            source,
+            target,
+            message: "Needs review",
+            severity,
+        }
+    }
+
+    #[test]
+    fn flags_identical_checks_across_files() {
+        let found = find_duplicates(&[
+            def("b/.noslop.toml", "*.rs", Severity::Block),
+            def(".noslop.toml", "*.rs", Severity::Block),
+            def("c/.noslop.toml", "*.rs", Severity::Warn),
+            def("d/.noslop.toml", "*.py", Severity::Block),
+        ]);
+        assert_eq!(
+            found,
+            vec![Duplicate {
+                target: "*.rs".to_string(),
+                message: "Needs review".to_string(),
+                severity: Severity::Block,
+                sources: vec![".noslop.toml".to_string(), "b/.noslop.toml".to_string()],
+            }]
+        );
+    }
+
+    #[test]
+    fn repeats_within_one_file_are_not_duplicates() {
+        let found = find_duplicates(&[
+            def(".noslop.toml", "*.rs", Severity::Block),
+            def(".noslop.toml", "*.rs", Severity::Block),
+        ]);
+        assert!(found.is_empty());
+    }
+}
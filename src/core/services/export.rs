@@ -0,0 +1,122 @@
+//! Export service - ack history as rows for data warehouses
+//!
+//! One row per acknowledgment found while walking commits. This module
+//! only shapes and encodes rows; the walk itself is git I/O.
+
+use serde::Serialize;
+
+/// One acknowledgment in the exported history
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ExportRow {
+    /// Commit that carried the acknowledgment
+    pub commit: String,
+    /// Check (or group) acknowledged
+    pub check_id: String,
+    /// Who acknowledged
+    pub acknowledged_by: String,
+    /// When: the ledger record's time, else the commit date (RFC 3339)
+    pub timestamp: String,
+    /// Files the commit changed
+    pub files: Vec<String>,
+    /// The acknowledgment message
+    pub message: String,
+}
+
+/// Export encodings
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    /// Comma-separated values with a header row
+    Csv,
+    /// One JSON object per line
+    Jsonl,
+}
+
+impl std::str::FromStr for ExportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "csv" => Ok(Self::Csv),
+            "jsonl" => Ok(Self::Jsonl),
+            "parquet" => {
+                Err("parquet is not built in; export csv or jsonl and convert (e.g. with duckdb)"
+                    .to_string())
+            },
+            _ => Err(format!("Invalid format: {s}. Use: csv, jsonl")),
+        }
+    }
+}
+
+const CSV_HEADER: &str = "commit,check_id,acknowledged_by,timestamp,files,message";
+
+/// Encode rows in `format`; files are `;`-joined in CSV
+#[must_use]
+pub fn encode(rows: &[ExportRow], format: ExportFormat) -> String {
+    let mut out = String::new();
+    match format {
+        ExportFormat::Csv => {
+            out.push_str(CSV_HEADER);
+            out.push('\n');
+            for row in rows {
+                let fields = [
+                    row.commit.as_str(),
+                    &row.check_id,
+                    &row.acknowledged_by,
+                    &row.timestamp,
+                    &row.files.join(";"),
+                    &row.message,
+                ];
+                let quoted: Vec<String> = fields.iter().map(|f| csv_field(f)).collect();
+                out.push_str(&quoted.join(","));
+                out.push('\n');
+            }
+        },
+        ExportFormat::Jsonl => {
+            for row in rows {
+                out.push_str(&serde_json::to_string(row).unwrap_or_default());
+                out.push('\n');
+            }
+        },
+    }
+    out
+}
+
+/// RFC 4180 quoting: only fields with a comma, quote, or line break
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(message: &str) -> ExportRow {
+        ExportRow {
+            commit: "abc".to_string(),
+            check_id: "SEC-1".to_string(),
+            acknowledged_by: "human".to_string(),
+            timestamp: "2024-01-02T00:00:00+00:00".to_string(),
+            files: vec!["a.rs".to_string(), "b.rs".to_string()],
+            message: message.to_string(),
+        }
+    }
+
+    #[test]
+    fn csv_quotes_only_when_needed() {
+        let csv = encode(&[row("plain"), row("said \"ok\", then, left")], ExportFormat::Csv);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], CSV_HEADER);
+        assert_eq!(lines[1], "abc,SEC-1,human,2024-01-02T00:00:00+00:00,a.rs;b.rs,plain");
+        assert!(lines[2].ends_with(",\"said \"\"ok\"\", then, left\""));
+    }
+
+    #[test]
+    fn parquet_is_refused_with_a_pointer() {
+        assert!("parquet".parse::<ExportFormat>().unwrap_err().contains("csv or jsonl"));
+        assert_eq!("JSONL".parse::<ExportFormat>(), Ok(ExportFormat::Jsonl));
+    }
+}
@@ -0,0 +1,256 @@
+//! Hunk classification - what kind of change each hunk of a diff makes
+//!
+//! A line-level pass over a unified diff, not a parse: each language in
+//! [`LANGUAGES`] says how its comments start and what a function
+//! definition line looks like, and a hunk is classed from its added and
+//! removed lines. Classes are hints for reviewers and a way to scope checks
+//! (`when.change_kind`), not a verdict on the code.
+
+use std::collections::BTreeMap;
+
+use regex::Regex;
+
+use crate::core::models::{ChangeClass, Check};
+
+/// How one language marks comments and function definitions
+struct Language {
+    /// File extensions, without the dot
+    extensions: &'static [&'static str],
+    /// Prefixes of a comment line, after indentation
+    comments: &'static [&'static str],
+    /// A function definition line, where one is recognized; the first
+    /// capture is the name
+    function: Option<&'static str>,
+}
+
+/// Languages hunks are classified in; other files only get the
+/// language-independent classes
+const LANGUAGES: [Language; 8] = [
+    Language {
+        extensions: &["rs"],
+        comments: &["//", "/*", "*"],
+        function: Some(
+            r"^\s*(?:pub(?:\([^)]*\))?\s+)?(?:const\s+)?(?:async\s+)?(?:unsafe\s+)?(?:extern\s+\S+\s+)?fn\s+(\w+)",
+        ),
+    },
+    Language {
+        extensions: &["py"],
+        comments: &["#"],
+        function: Some(r"^\s*(?:async\s+)?def\s+(\w+)"),
+    },
+    Language {
+        extensions: &["go"],
+        comments: &["//", "/*", "*"],
+        function: Some(r"^func\s+(?:\([^)]*\)\s*)?(\w+)"),
+    },
+    Language {
+        extensions: &["js", "jsx", "mjs", "cjs", "ts", "tsx"],
+        comments: &["//", "/*", "*"],
+        function: Some(r"^\s*(?:export\s+)?(?:default\s+)?(?:async\s+)?function\s*\*?\s*(\w+)"),
+    },
+    Language {
+        extensions: &["rb"],
+        comments: &["#"],
+        function: Some(r"^\s*def\s+(?:self\.)?(\w+[?!]?)"),
+    },
+    Language {
+        extensions: &["sh", "bash"],
+        comments: &["#"],
+        function: Some(r"^\s*(?:function\s+)?(\w+)\s*\(\)"),
+    },
+    Language {
+        extensions: &["c", "h", "cc", "cpp", "hpp", "java", "kt", "cs", "swift"],
+        comments: &["//", "/*", "*"],
+        function: None,
+    },
+    Language {
+        extensions: &["toml", "yaml", "yml"],
+        comments: &["#"],
+        function: None,
+    },
+];
+
+/// Dependency manifests, by file name: a changed version there is a bump
+const MANIFESTS: [&str; 8] = [
+    "Cargo.toml",
+    "package.json",
+    "go.mod",
+    "pyproject.toml",
+    "Gemfile",
+    "pom.xml",
+    "build.gradle",
+    "build.gradle.kts",
+];
+
+/// Classes of the hunks in a unified diff, per file (new path, or the old
+/// one for deletions), each list sorted and deduplicated
+///
+/// Made for `git diff -U0`: context lines are ignored either way.
+#[must_use]
+pub fn classify_diff(diff: &str) -> BTreeMap<String, Vec<ChangeClass>> {
+    let mut classes: BTreeMap<String, Vec<ChangeClass>> = BTreeMap::new();
+    let mut file: Option<String> = None;
+    let mut in_header = false;
+    let mut hunk = Hunk::default();
+
+    let mut flush = |file: Option<&String>, hunk: &mut Hunk| {
+        if let Some(file) = file {
+            let found = hunk.classify(file);
+            let entry = classes.entry(file.clone()).or_default();
+            entry.extend(found);
+            entry.sort();
+            entry.dedup();
+        }
+        *hunk = Hunk::default();
+    };
+
+    for line in diff.lines() {
+        if line.starts_with("diff --git ") {
+            flush(file.as_ref(), &mut hunk);
+            file = None;
+            in_header = true;
+        } else if line.starts_with("@@") {
+            flush(file.as_ref(), &mut hunk);
+            in_header = false;
+        } else if in_header {
+            if let Some(path) = line.strip_prefix("+++ b/") {
+                file = Some(path.to_string());
+            } else if let Some(path) = line.strip_prefix("--- a/") {
+                file = Some(path.to_string());
+            }
+        } else if let Some(added) = line.strip_prefix('+') {
+            hunk.added.push(added.to_string());
+        } else if let Some(removed) = line.strip_prefix('-') {
+            hunk.removed.push(removed.to_string());
+        }
+    }
+    flush(file.as_ref(), &mut hunk);
+    classes
+}
+
+/// Keep the checks whose `when.change_kind` scope, if any, meets a class
+/// of the file they matched
+pub fn retain_in_scope(
+    applicable: &mut Vec<(Check, String)>,
+    classes: &BTreeMap<String, Vec<ChangeClass>>,
+) {
+    applicable.retain(|(check, file)| {
+        check.scoped_to.is_empty()
+            || classes.get(file).is_some_and(|c| c.iter().any(|k| check.scoped_to.contains(k)))
+    });
+}
+
+/// The changed lines of one hunk
+#[derive(Default)]
+struct Hunk {
+    added: Vec<String>,
+    removed: Vec<String>,
+}
+
+impl Hunk {
+    fn classify(&self, path: &str) -> Vec<ChangeClass> {
+        if self.added.is_empty() && self.removed.is_empty() {
+            return Vec::new();
+        }
+        if is_test_path(path) {
+            return vec![ChangeClass::TestOnly];
+        }
+        let language =
+            extension(path).and_then(|ext| LANGUAGES.iter().find(|l| l.extensions.contains(&ext)));
+        let mut lines = self.added.iter().chain(&self.removed).map(|l| l.trim());
+        if let Some(language) = language
+            && lines.all(|l| l.is_empty() || language.comments.iter().any(|c| l.starts_with(c)))
+        {
+            return vec![ChangeClass::CommentOnly];
+        }
+
+        let mut found = Vec::new();
+        let name = path.rsplit('/').next().unwrap_or(path);
+        let versioned =
+            |lines: &[String]| lines.iter().any(|l| l.chars().any(|c| c.is_ascii_digit()));
+        let manifest = MANIFESTS.contains(&name)
+            || (name.starts_with("requirements") && extension(name) == Some("txt"));
+        if manifest && versioned(&self.added) && versioned(&self.removed) {
+            found.push(ChangeClass::DependencyBump);
+        }
+        if let Some(function) = language.and_then(|l| Regex::new(l.function?).ok()) {
+            let definitions = |lines: &[String]| -> BTreeMap<String, String> {
+                lines
+                    .iter()
+                    .filter_map(|l| {
+                        Some((function.captures(l)?[1].to_string(), l.trim().to_string()))
+                    })
+                    .collect()
+            };
+            let before = definitions(&self.removed);
+            for (name, line) in definitions(&self.added) {
+                match before.get(&name) {
+                    None => found.push(ChangeClass::NewFunction),
+                    Some(old) if *old != line => found.push(ChangeClass::SignatureChange),
+                    Some(_) => {},
+                }
+            }
+        }
+        found
+    }
+}
+
+/// Whether `path` is a test file, by the usual layouts and names
+fn is_test_path(path: &str) -> bool {
+    let (dirs, name) = path.rsplit_once('/').unwrap_or(("", path));
+    let stem = name.split('.').next().unwrap_or(name);
+    dirs.split('/').any(|d| matches!(d, "test" | "tests" | "__tests__" | "spec"))
+        || stem.starts_with("test_")
+        || stem.ends_with("_test")
+        || stem.ends_with("_spec")
+        || name.contains(".test.")
+        || name.contains(".spec.")
+}
+
+fn extension(path: &str) -> Option<&str> {
+    let name = path.rsplit('/').next()?;
+    name.rsplit_once('.').map(|(_, ext)| ext)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify_diff_tells_hunks_apart() {
+        let diff = "\
+diff --git a/src/api.rs b/src/api.rs
+--- a/src/api.rs
++++ b/src/api.rs
+@@ -3 +3 @@
+-pub fn get(id: u32) -> User {
++pub fn get(id: u64) -> User {
+@@ -10,0 +11,3 @@
++pub async fn list() -> Vec<User> {
++    Vec::new()
++}
+diff --git a/src/lib.rs b/src/lib.rs
+--- a/src/lib.rs
++++ b/src/lib.rs
+@@ -1 +1 @@
+-// Users
++// Users and their sessions
+diff --git a/Cargo.toml b/Cargo.toml
+--- a/Cargo.toml
++++ b/Cargo.toml
+@@ -8 +8 @@
+-serde = \"1.0.100\"
++serde = \"1.0.200\"
+diff --git a/tests/api_test.rs b/tests/api_test.rs
+--- a/tests/api_test.rs
++++ b/tests/api_test.rs
+@@ -0,0 +1 @@
++fn test_get() {}
+";
+        let classes = classify_diff(diff);
+        assert_eq!(classes["src/api.rs"], [ChangeClass::NewFunction, ChangeClass::SignatureChange]);
+        assert_eq!(classes["src/lib.rs"], [ChangeClass::CommentOnly]);
+        assert_eq!(classes["Cargo.toml"], [ChangeClass::DependencyBump]);
+        assert_eq!(classes["tests/api_test.rs"], [ChangeClass::TestOnly]);
+    }
+}
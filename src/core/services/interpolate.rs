@@ -0,0 +1,90 @@
+//! Interpolation service - check-time placeholders in check messages
+//!
+//! A message can name what it is about at the moment it fires:
+//!
+//! - `{file}` - the file this match is for
+//! - `{files}` - every file the check matched in this run
+//! - `{branch}` - the branch being committed to
+//!
+//! Other braces are left as written (`{package}` is resolved earlier, per
+//! package, when the check is matched).
+
+use std::collections::HashMap;
+
+use super::checker::CheckResult;
+
+/// Placeholder for the file this match is for
+pub const FILE_VAR: &str = "{file}";
+/// Placeholder for every file the check matched
+pub const FILES_VAR: &str = "{files}";
+/// Placeholder for the branch being committed to
+pub const BRANCH_VAR: &str = "{branch}";
+
+/// Replace the check-time placeholders in every message of `result`.
+///
+/// `branch` is `None` on a detached HEAD, where `{branch}` reads
+/// `detached HEAD`.
+pub fn interpolate_messages(result: &mut CheckResult, branch: Option<&str>) {
+    let mut files: HashMap<String, Vec<String>> = HashMap::new();
+    for item in result.blocking.iter().chain(&result.warnings).chain(&result.acknowledged) {
+        let list = files.entry(item.id.clone()).or_default();
+        if !list.contains(&item.file) {
+            list.push(item.file.clone());
+        }
+    }
+
+    let branch = branch.unwrap_or("detached HEAD");
+    let items = result
+        .blocking
+        .iter_mut()
+        .chain(&mut result.warnings)
+        .chain(&mut result.acknowledged);
+    for item in items {
+        if !item.message.contains('{') {
+            continue;
+        }
+        let all = files.get(&item.id).map_or_else(|| item.file.clone(), |f| f.join(", "));
+        item.message = render(&item.message, &item.file, &all, branch);
+    }
+}
+
+fn render(message: &str, file: &str, files: &str, branch: &str) -> String {
+    message
+        .replace(FILES_VAR, files)
+        .replace(FILE_VAR, file)
+        .replace(BRANCH_VAR, branch)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::models::{Check, Severity};
+    use crate::core::services::check_items;
+
+    #[test]
+    fn fills_file_files_and_branch() {
+        let check = Check::new(
+            Some("RS-1".to_string()),
+            "*.rs".to_string(),
+            "Review {files} on {branch} ({file}, {task})".to_string(),
+            Severity::Block,
+        );
+        let applicable = vec![(check.clone(), "a.rs".to_string()), (check, "b.rs".to_string())];
+        let mut result = check_items(&applicable, &[], 2);
+
+        interpolate_messages(&mut result, Some("feature"));
+        assert_eq!(result.blocking[0].message, "Review a.rs, b.rs on feature (a.rs, {task})");
+        assert_eq!(result.blocking[1].message, "Review a.rs, b.rs on feature (b.rs, {task})");
+
+        interpolate_messages(&mut result, None);
+        assert!(result.blocking[0].message.starts_with("Review a.rs, b.rs on feature"));
+    }
+
+    #[test]
+    fn detached_head_is_named() {
+        let check = Check::new(None, "*".to_string(), "On {branch}".to_string(), Severity::Warn);
+        let mut result = check_items(&[(check, "x".to_string())], &[], 1);
+        interpolate_messages(&mut result, None);
+        assert_eq!(result.warnings[0].message, "On detached HEAD");
+    }
+}
@@ -4,6 +4,32 @@
 
 use std::path::Path;
 
+use crate::core::models::{ChangeKind, FileChange};
+
+/// Why a check considered for a changed file did not fire
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MissReason {
+    /// The target pattern matches none of the change's paths
+    TargetMismatch,
+    /// The target matches, but the check's `on` list excludes this kind
+    ChangeKind(ChangeKind),
+    /// The check is disabled (`disabled = true`)
+    Disabled,
+    /// The file already has the content the check's `must_match` asks for
+    ContentPresent,
+}
+
+impl std::fmt::Display for MissReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::TargetMismatch => write!(f, "target does not match"),
+            Self::ChangeKind(kind) => write!(f, "'{kind}' changes are not in its `on` list"),
+            Self::Disabled => write!(f, "check is disabled"),
+            Self::ContentPresent => write!(f, "file already has what `must_match` asks for"),
+        }
+    }
+}
+
 /// Check if a target pattern matches a file path
 ///
 /// Supports multiple pattern types:
@@ -12,6 +38,7 @@ use std::path::Path;
 /// - `dir/*.ext` - matches files in directory with extension
 /// - `dir/**/*.ext` - matches files recursively with extension
 /// - Exact paths and prefix matches
+/// - A list (`["src/**", "!src/generated/**"]`, see [`composite_patterns`])
 ///
 /// # Arguments
 ///
@@ -25,6 +52,10 @@ use std::path::Path;
 /// `true` if the target pattern matches the file
 #[must_use]
 pub fn matches_target(target: &str, file: &str, base_dir: &Path, cwd: &Path) -> bool {
+    if let Some(patterns) = composite_patterns(target) {
+        return matches_composite(&patterns, file, base_dir, cwd);
+    }
+
     // Get relative path from base_dir
     let file_abs = cwd.join(file);
     let file_rel = file_abs
@@ -84,6 +115,35 @@ pub fn matches_target(target: &str, file: &str, base_dir: &Path, cwd: &Path) ->
     file_rel == target || file_rel.starts_with(target) || file.contains(target)
 }
 
+/// The patterns of a composite target, `None` for a single pattern
+///
+/// A composite target is a list, kept as its JSON array text (which is also
+/// a TOML array): `["src/**", "!src/generated/**", "proto/**"]`.
+#[must_use]
+pub fn composite_patterns(target: &str) -> Option<Vec<String>> {
+    let trimmed = target.trim();
+    if !(trimmed.starts_with('[') && trimmed.ends_with(']')) {
+        return None;
+    }
+    serde_json::from_str(trimmed).ok()
+}
+
+/// Match a composite target: as in `.gitignore`, the last pattern that
+/// matches decides, and a `!` pattern excludes
+///
+/// A list opening with an exclusion starts from every file included.
+fn matches_composite(patterns: &[String], file: &str, base_dir: &Path, cwd: &Path) -> bool {
+    let mut hit = patterns.first().is_some_and(|p| p.starts_with('!'));
+    for pattern in patterns {
+        match pattern.strip_prefix('!') {
+            Some(excluded) if matches_target(excluded, file, base_dir, cwd) => hit = false,
+            None if matches_target(pattern, file, base_dir, cwd) => hit = true,
+            _ => {},
+        }
+    }
+    hit
+}
+
 /// Match a recursive glob pattern (dir/**/*.ext)
 fn matches_recursive_glob(target: &str, file_rel: &str, file: &str) -> bool {
     if let Some(doublestar_pos) = target.find("/**") {
@@ -98,6 +158,27 @@ fn matches_recursive_glob(target: &str, file_rel: &str, file: &str) -> bool {
     false
 }
 
+/// The path of `change` a check fires on, or why it does not fire
+///
+/// `hits` says whether the check's target matches a path. The check fires
+/// on the first path [`FileChange::paths_for`] selects that it hits; one
+/// hitting only paths its `on` list leaves out misses for the change kind.
+pub fn match_change<'c>(
+    on: &[ChangeKind],
+    change: &'c FileChange,
+    hits: impl Fn(&str) -> bool,
+) -> Result<&'c str, MissReason> {
+    if let Some(path) = change.paths_for(on).into_iter().find(|path| hits(path)) {
+        return Ok(path);
+    }
+    let any_path = std::iter::once(change.path.as_str()).chain(change.old_path.as_deref());
+    if any_path.into_iter().any(hits) {
+        Err(MissReason::ChangeKind(change.kind))
+    } else {
+        Err(MissReason::TargetMismatch)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -148,4 +229,40 @@ mod tests {
         assert!(test_match("src/", "src/main.rs"));
         assert!(test_match("src/", "src/sub/lib.rs"));
     }
+
+    #[test]
+    fn test_composite_target_last_match_wins() {
+        let target = r#"["src/**", "!src/generated/**", "src/generated/keep.rs", "proto/"]"#;
+        assert!(test_match(target, "src/lib.rs"));
+        assert!(test_match(target, "proto/api.proto"));
+        assert!(!test_match(target, "src/generated/out.rs"));
+        assert!(test_match(target, "src/generated/keep.rs"));
+        assert!(!test_match(target, "docs/guide.md"));
+        // Opening with an exclusion: everything else is in
+        assert!(test_match(r#"["!*.md"]"#, "src/lib.rs"));
+        assert!(!test_match(r#"["!*.md"]"#, "README.md"));
+        assert_eq!(composite_patterns("src/[ab].rs"), None);
+    }
+
+    #[test]
+    fn test_match_change() {
+        let repo = PathBuf::from("/repo");
+        let explain = |target: &str, on: &[ChangeKind], change: &FileChange| {
+            match_change(on, change, |path| matches_target(target, path, &repo, &repo)).err()
+        };
+        let deleted = FileChange::new("src/old.rs", ChangeKind::Delete);
+
+        assert_eq!(explain("*.py", &[], &deleted), Some(MissReason::TargetMismatch));
+        assert_eq!(
+            explain("*.rs", &[], &deleted),
+            Some(MissReason::ChangeKind(ChangeKind::Delete))
+        );
+        assert_eq!(explain("*.rs", &[ChangeKind::Delete], &deleted), None);
+        // Default checks still fire on a rename's new path
+        let renamed = FileChange::renamed("lib/a.rs", "src/a.rs");
+        assert_eq!(explain("src/", &[], &renamed), None);
+        // A rename the check follows fires on its old path first
+        let fired = match_change(&[ChangeKind::Rename], &renamed, |path| test_match("*.rs", path));
+        assert_eq!(fired, Ok("lib/a.rs"));
+    }
 }
@@ -0,0 +1,86 @@
+//! Metrics service - aggregate local command metrics into a friction report
+//!
+//! Pure logic over [`CommandMetric`] lines: per command, how often it ran,
+//! how long it took, and how often it blocked or failed. The block rate
+//! of `check` is the headline: how often commits are stopped.
+
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+
+use crate::core::models::{CommandMetric, Outcome};
+
+/// Aggregate for one command
+#[derive(Debug, Clone, Serialize)]
+pub struct CommandSummary {
+    /// Subcommand name
+    pub command: String,
+    /// Invocations recorded
+    pub runs: usize,
+    /// Invocations that blocked a commit
+    pub blocked: usize,
+    /// Invocations that failed with an error
+    pub errors: usize,
+    /// Median duration in milliseconds
+    pub median_ms: u64,
+    /// Slowest duration in milliseconds
+    pub max_ms: u64,
+}
+
+/// Summarize metrics per command, most-run first.
+#[must_use]
+pub fn summarize(metrics: &[CommandMetric]) -> Vec<CommandSummary> {
+    let mut by_command: BTreeMap<&str, Vec<&CommandMetric>> = BTreeMap::new();
+    for m in metrics {
+        by_command.entry(&m.command).or_default().push(m);
+    }
+
+    let mut rows: Vec<CommandSummary> = by_command
+        .into_iter()
+        .map(|(command, runs)| {
+            let mut durations: Vec<u64> = runs.iter().map(|m| m.duration_ms).collect();
+            durations.sort_unstable();
+            CommandSummary {
+                command: command.to_string(),
+                runs: runs.len(),
+                blocked: runs.iter().filter(|m| m.outcome == Outcome::Blocked).count(),
+                errors: runs.iter().filter(|m| m.outcome == Outcome::Error).count(),
+                median_ms: durations[durations.len() / 2],
+                max_ms: durations[durations.len() - 1],
+            }
+        })
+        .collect();
+    rows.sort_by_key(|r| std::cmp::Reverse(r.runs));
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn metric(command: &str, ms: u64, outcome: Outcome) -> CommandMetric {
+        CommandMetric::new(command.into(), ms, outcome, "claude-code".into())
+    }
+
+    #[test]
+    fn summarizes_runs_blocks_and_durations() {
+        let metrics = [
+            metric("check", 10, Outcome::Blocked),
+            metric("check", 30, Outcome::Ok),
+            metric("check", 20, Outcome::Ok),
+            metric("ack", 5, Outcome::Error),
+        ];
+        let rows = summarize(&metrics);
+        assert_eq!(rows[0].command, "check");
+        assert_eq!(rows[0].runs, 3);
+        assert_eq!(rows[0].blocked, 1);
+        assert_eq!(rows[0].median_ms, 20);
+        assert_eq!(rows[0].max_ms, 30);
+        assert_eq!(rows[1].errors, 1);
+    }
+
+    #[test]
+    fn empty_input_yields_no_rows() {
+        assert!(summarize(&[]).is_empty());
+    }
+}
@@ -4,16 +4,42 @@
 //! These services have no I/O dependencies - they operate on
 //! data passed in and return results.
 //!
+//! - [`ack_message`] - Find `[ack ID: message]` lines in commit messages
 //! - [`checker`] - Check checks against acknowledgments
+//! - [`codeowners`] - Resolve path owners from a CODEOWNERS file
+//! - [`duplicates`] - Find checks defined identically in several configs
+//! - [`hunks`] - Classify diff hunks (new function, signature change, ...)
+//! - [`interpolate`] - Fill `{file}`, `{files}`, `{branch}` in messages
 //! - [`matcher`] - Match target patterns to file paths
+//! - [`packages`] - Map files to workspace packages for `{package}` checks
+//! - [`reviews`] - Map external review approvals to the checks they answer
+//! - [`search`] - Rank checks and acks against a free-text query
+//! - [`vendored`] - Recognize third-party and generated files
 
+pub mod ack_message;
+pub mod ack_policy;
+pub mod activity;
 pub mod checker;
+pub mod ci;
+pub mod codeowners;
+pub mod coverage;
 pub mod curate;
+pub mod diff;
 pub mod discovery;
+pub mod duplicates;
+pub mod export;
+pub mod hunks;
+pub mod interpolate;
 pub mod matcher;
 pub mod merge;
+pub mod metrics;
+pub mod packages;
+pub mod reviews;
+pub mod search;
 pub mod stats;
+pub mod update;
+pub mod vendored;
 
-pub use checker::{CheckItemResult, CheckResult, check_items};
-pub use matcher::matches_target;
+pub use checker::{CheckItemResult, CheckResult, check_items, check_items_at};
+pub use matcher::{MissReason, composite_patterns, match_change, matches_target};
 pub use merge::merge_checks;
@@ -0,0 +1,87 @@
+//! Package service - which workspace package a file belongs to
+//!
+//! Monorepos hold many packages (Cargo crates, npm packages, Go modules).
+//! A check whose target or message mentions `{package}` is evaluated per
+//! package: in the target it stands for the package directory, in the
+//! message for the package name.
+
+use std::borrow::Cow;
+
+/// The placeholder substituted per package
+pub const PACKAGE_VAR: &str = "{package}";
+
+/// A workspace package: its name and its directory relative to the repo
+/// root (`""` for a package at the root)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Package {
+    /// Name from the manifest (crate name, npm name, Go module path)
+    pub name: String,
+    /// Directory holding the manifest, without a trailing slash
+    pub dir: String,
+}
+
+/// The innermost package containing `file`
+#[must_use]
+pub fn package_for<'a>(packages: &'a [Package], file: &str) -> Option<&'a Package> {
+    packages
+        .iter()
+        .filter(|p| {
+            p.dir.is_empty() || file.strip_prefix(&p.dir).is_some_and(|r| r.starts_with('/'))
+        })
+        .max_by_key(|p| p.dir.len())
+}
+
+/// `text` with `{package}` replaced by `value`; borrowed when it has no
+/// placeholder
+#[must_use]
+pub fn substitute<'a>(text: &'a str, value: &str) -> Cow<'a, str> {
+    if text.contains(PACKAGE_VAR) {
+        Cow::Owned(text.replace(PACKAGE_VAR, value))
+    } else {
+        Cow::Borrowed(text)
+    }
+}
+
+/// A package target for `package`: `{package}` becomes its directory.
+/// `"{package}/src/**"` for a root package becomes `"src/**"`.
+#[must_use]
+pub fn package_target(target: &str, package: &Package) -> String {
+    if package.dir.is_empty() {
+        target.replace(&format!("{PACKAGE_VAR}/"), "").replace(PACKAGE_VAR, "")
+    } else {
+        target.replace(PACKAGE_VAR, &package.dir)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pkg(name: &str, dir: &str) -> Package {
+        Package {
+            name: name.to_string(),
+            dir: dir.to_string(),
+        }
+    }
+
+    #[test]
+    fn innermost_package_wins() {
+        let packages =
+            vec![pkg("root", ""), pkg("api", "crates/api"), pkg("api-v2", "crates/api/v2")];
+        assert_eq!(package_for(&packages, "crates/api/v2/lib.rs").unwrap().name, "api-v2");
+        assert_eq!(package_for(&packages, "crates/api/lib.rs").unwrap().name, "api");
+        assert_eq!(package_for(&packages, "crates/apix/lib.rs").unwrap().name, "root");
+        assert!(package_for(&packages[1..], "README.md").is_none());
+    }
+
+    #[test]
+    fn targets_and_messages_substitute_per_package() {
+        assert_eq!(
+            package_target("{package}/src/**", &pkg("api", "crates/api")),
+            "crates/api/src/**"
+        );
+        assert_eq!(package_target("{package}/src/**", &pkg("root", "")), "src/**");
+        assert_eq!(substitute("Owner of {package} reviewed?", "api"), "Owner of api reviewed?");
+        assert!(matches!(substitute("plain", "api"), Cow::Borrowed(_)));
+    }
+}
@@ -0,0 +1,140 @@
+//! Review import - map approvals from external review systems to checks
+//!
+//! `[reviews.labels]` in the repo-root config names which approvals answer
+//! which checks. A key is matched against each approval:
+//!
+//! - `@login`: approved by that reviewer
+//! - `@org/team`: approved by a member of that team (membership is asked
+//!   of the review system, through `in_team`)
+//! - anything else: a label on the approval, e.g. a Gerrit
+//!   `Security-Review` vote or a label on the GitHub pull request. A label
+//!   only counts from a reviewer some `@` key maps too, so a label plus a
+//!   drive-by approval answers nothing.
+
+use std::collections::BTreeMap;
+
+use crate::core::services::codeowners::is_owner;
+
+/// One approval in an external review system
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExternalApproval {
+    /// Who approved (GitHub login, Gerrit username or email)
+    pub reviewer: String,
+    /// Labels the approval carries
+    pub labels: Vec<String>,
+    /// Link to the review
+    pub url: String,
+    /// Commit the approval was given on, when the review system says
+    pub revision: Option<String>,
+}
+
+/// A check answered by an approval
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportedReview<'a> {
+    /// Check ID or group name, as the mapping names it
+    pub check: String,
+    /// The mapping key the approval matched
+    pub label: String,
+    /// The approval answering the check
+    pub approval: &'a ExternalApproval,
+}
+
+/// The checks `approvals` answer under `labels`, one entry per check
+///
+/// Keys are tried in order, and each check is answered by the first
+/// approval matching a key that names it.
+#[must_use]
+pub fn imported_reviews<'a>(
+    approvals: &'a [ExternalApproval],
+    labels: &BTreeMap<String, Vec<String>>,
+    in_team: impl Fn(&str, &str) -> bool,
+) -> Vec<ImportedReview<'a>> {
+    let mapped = |approval: &ExternalApproval| {
+        labels
+            .keys()
+            .any(|key| key.starts_with('@') && approves(key, approval, &in_team))
+    };
+    let mut imported: Vec<ImportedReview<'a>> = Vec::new();
+    for (label, checks) in labels {
+        let Some(approval) = approvals
+            .iter()
+            .find(|a| approves(label, a, &in_team) && (label.starts_with('@') || mapped(a)))
+        else {
+            continue;
+        };
+        for check in checks {
+            if imported.iter().all(|i| &i.check != check) {
+                imported.push(ImportedReview {
+                    check: check.clone(),
+                    label: label.clone(),
+                    approval,
+                });
+            }
+        }
+    }
+    imported
+}
+
+fn approves(
+    label: &str,
+    approval: &ExternalApproval,
+    in_team: impl Fn(&str, &str) -> bool,
+) -> bool {
+    match label.strip_prefix('@') {
+        Some(team) if team.contains('/') => in_team(team, &approval.reviewer),
+        Some(_) => is_owner(&[label.to_string()], &approval.reviewer),
+        None => approval.labels.iter().any(|l| l.eq_ignore_ascii_case(label)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn approval(reviewer: &str, labels: &[&str]) -> ExternalApproval {
+        ExternalApproval {
+            reviewer: reviewer.to_string(),
+            labels: labels.iter().map(ToString::to_string).collect(),
+            url: format!("https://example.com/{reviewer}"),
+            revision: None,
+        }
+    }
+
+    #[test]
+    fn test_imported_reviews_match_reviewers_teams_and_labels() {
+        // mallory carries the label but no `@` key maps her
+        let approvals = vec![
+            approval("alice", &[]),
+            approval("mallory", &["Security-Review"]),
+            approval("bob", &["Security-Review"]),
+        ];
+        let labels: BTreeMap<String, Vec<String>> = [
+            ("@alice", vec!["DOC-1"]),
+            ("@org/security", vec!["SEC-1", "SEC-2"]),
+            ("security-review", vec!["SEC-2", "SEC-3"]),
+            ("@carol", vec!["API-1"]),
+        ]
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), v.into_iter().map(String::from).collect()))
+        .collect();
+        let in_team = |team: &str, user: &str| team == "org/security" && user == "bob";
+
+        let unmapped = imported_reviews(&approvals[1..2], &labels, in_team);
+        assert!(unmapped.is_empty());
+
+        let imported = imported_reviews(&approvals, &labels, in_team);
+        let summary: Vec<(&str, &str, &str)> = imported
+            .iter()
+            .map(|i| (i.check.as_str(), i.label.as_str(), i.approval.reviewer.as_str()))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                ("DOC-1", "@alice", "alice"),
+                ("SEC-1", "@org/security", "bob"),
+                ("SEC-2", "@org/security", "bob"),
+                ("SEC-3", "security-review", "bob"),
+            ]
+        );
+    }
+}
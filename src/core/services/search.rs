@@ -0,0 +1,139 @@
+//! Search service - find checks and acknowledgments by free text
+//!
+//! Pure ranking over loaded checks and ledger acks. Every query term must
+//! appear somewhere in an item; ids score highest, then targets, then
+//! message text, so `noslop search "jwt rotation"` surfaces the check that
+//! guards the code before the acks that answered it.
+
+use serde::Serialize;
+
+use crate::core::models::{Acknowledgment, Check};
+
+/// What kind of record a hit came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HitKind {
+    /// A check from `.noslop.toml`
+    Check,
+    /// An acknowledgment from the ledger
+    Ack,
+}
+
+/// One ranked search result
+#[derive(Debug, Clone, Serialize)]
+pub struct SearchHit {
+    /// Record type tag
+    pub kind: HitKind,
+    /// Check id (for acks, the acknowledged check)
+    pub id: String,
+    /// Matched text: the check message or the ack message
+    pub text: String,
+    /// Context: the check target, or who acknowledged and when
+    pub context: String,
+    /// Relevance score (higher is better)
+    pub score: u32,
+}
+
+const ID_WEIGHT: u32 = 5;
+const TARGET_WEIGHT: u32 = 3;
+const TEXT_WEIGHT: u32 = 1;
+
+/// Rank checks and acks against `query`, best first.
+///
+/// Matching is case-insensitive; an item must contain every whitespace-
+/// separated term. An empty query matches nothing.
+#[must_use]
+pub fn search(query: &str, checks: &[Check], acks: &[Acknowledgment]) -> Vec<SearchHit> {
+    let terms: Vec<String> = query.split_whitespace().map(str::to_lowercase).collect();
+    if terms.is_empty() {
+        return Vec::new();
+    }
+
+    let mut hits = Vec::new();
+    for c in checks {
+        if let Some(score) = score(
+            &terms,
+            &[(&c.id, ID_WEIGHT), (&c.target, TARGET_WEIGHT), (&c.message, TEXT_WEIGHT)],
+        ) {
+            hits.push(SearchHit {
+                kind: HitKind::Check,
+                id: c.id.clone(),
+                text: c.message.clone(),
+                context: c.target.clone(),
+                score,
+            });
+        }
+    }
+    for a in acks {
+        if let Some(score) = score(&terms, &[(&a.check_id, ID_WEIGHT), (&a.message, TEXT_WEIGHT)]) {
+            hits.push(SearchHit {
+                kind: HitKind::Ack,
+                id: a.check_id.clone(),
+                text: a.message.clone(),
+                context: format!("{} at {}", a.acknowledged_by, a.created_at),
+                score,
+            });
+        }
+    }
+
+    // Stable sort keeps checks ahead of acks on ties
+    hits.sort_by_key(|h| std::cmp::Reverse(h.score));
+    hits
+}
+
+/// Sum the weights of every field each term appears in; `None` if any
+/// term appears nowhere.
+fn score(terms: &[String], fields: &[(&str, u32)]) -> Option<u32> {
+    let fields: Vec<(String, u32)> = fields.iter().map(|(f, w)| (f.to_lowercase(), *w)).collect();
+    let mut total = 0;
+    for term in terms {
+        let term_score: u32 =
+            fields.iter().filter(|(f, _)| f.contains(term.as_str())).map(|(_, w)| w).sum();
+        if term_score == 0 {
+            return None;
+        }
+        total += term_score;
+    }
+    Some(total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::models::Severity;
+
+    fn check(id: &str, target: &str, message: &str) -> Check {
+        Check::new(Some(id.into()), target.into(), message.into(), Severity::Block)
+    }
+
+    #[test]
+    fn requires_every_term() {
+        let checks = [check("SEC-1", "src/auth/*.rs", "JWT rotation schedule documented?")];
+        assert_eq!(search("jwt rotation", &checks, &[]).len(), 1);
+        assert!(search("jwt billing", &checks, &[]).is_empty());
+        assert!(search("   ", &checks, &[]).is_empty());
+    }
+
+    #[test]
+    fn ranks_id_and_target_above_message() {
+        let checks = [
+            check("DOC-1", "docs/", "Mentions auth flow?"),
+            check("SEC-1", "src/auth/*.rs", "Tokens rotated?"),
+        ];
+        let hits = search("auth", &checks, &[]);
+        assert_eq!(hits[0].id, "SEC-1");
+        assert_eq!(hits[1].id, "DOC-1");
+    }
+
+    #[test]
+    fn finds_acks_with_type_tag() {
+        let acks = [Acknowledgment::new(
+            "SEC-1".into(),
+            "Rotation moved to 24h per JWT decision".into(),
+            "human".into(),
+        )];
+        let hits = search("jwt", &[], &acks);
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].kind, HitKind::Ack);
+    }
+}
@@ -11,7 +11,7 @@
 use std::collections::{BTreeMap, BTreeSet};
 use std::path::Path;
 
-use crate::core::models::{Acknowledgment, Check, CheckFireEvent};
+use crate::core::models::{Acknowledgment, Check, CheckFireEvent, Severity};
 use crate::core::services::matcher::matches_target;
 
 /// Per-check metrics
@@ -22,7 +22,7 @@ pub struct CheckStats {
     /// Target glob
     pub target: String,
     /// Severity as configured
-    pub severity: String,
+    pub severity: Severity,
     /// Distinct staged states in which the check fired
     pub fires: usize,
     /// Total acknowledgments recorded
@@ -53,16 +53,14 @@ pub fn compute(
     for e in events {
         events_by_check.entry(&e.check_id).or_default().push(e);
     }
-    let mut acks_by_check: BTreeMap<&str, Vec<&Acknowledgment>> = BTreeMap::new();
-    for a in acks {
-        acks_by_check.entry(&a.check_id).or_default().push(a);
-    }
 
     checks
         .iter()
         .map(|check| {
             let check_events = events_by_check.get(check.id.as_str());
-            let check_acks = acks_by_check.get(check.id.as_str());
+            // A group ack answers every member
+            let check_acks: Vec<&Acknowledgment> =
+                acks.iter().filter(|a| check.answered_by(&a.check_id)).collect();
 
             // Distinct staged states, not raw invocations: re-running
             // `noslop check` on the same index must not inflate fires.
@@ -78,7 +76,7 @@ pub fn compute(
 
             let mut acted = 0;
             let mut no_action = 0;
-            for ack in check_acks.into_iter().flatten() {
+            for ack in &check_acks {
                 // Only categorizable when both sides carry a fingerprint
                 if let Some(oid) = &ack.tree_oid {
                     if fire_oids.is_empty() {
@@ -99,9 +97,9 @@ pub fn compute(
             CheckStats {
                 id: check.id.clone(),
                 target: check.target.clone(),
-                severity: check.severity.to_string(),
+                severity: check.severity,
                 fires: fire_oids.len(),
-                acks: check_acks.map_or(0, Vec::len),
+                acks: check_acks.len(),
                 acted,
                 no_action,
                 last_fired,
@@ -0,0 +1,129 @@
+//! Update service - pick the release a `self-update` should install
+//!
+//! Pure logic over the release feed: channel selection, version
+//! comparison, and the platform asset name (mirrors `scripts/install.sh`).
+//! Fetching, checksum verification, and the binary swap live in
+//! `adapters::release`.
+
+use serde::Deserialize;
+
+/// Release channel
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Channel {
+    /// Published, non-prerelease versions only
+    Stable,
+    /// Newest release, prereleases included
+    Nightly,
+}
+
+impl std::str::FromStr for Channel {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "stable" => Ok(Self::Stable),
+            "nightly" => Ok(Self::Nightly),
+            _ => Err(format!("Unknown channel: {s}. Use: stable, nightly")),
+        }
+    }
+}
+
+/// One entry of the release feed (GitHub releases API shape)
+#[derive(Debug, Clone, Deserialize)]
+pub struct Release {
+    /// Tag, e.g. `v0.3.0`
+    pub tag_name: String,
+    /// Marked as a prerelease
+    #[serde(default)]
+    pub prerelease: bool,
+    /// Unpublished draft
+    #[serde(default)]
+    pub draft: bool,
+}
+
+/// Newest release on `channel` (feed order is newest first).
+#[must_use]
+pub fn pick_release(releases: &[Release], channel: Channel) -> Option<&Release> {
+    releases
+        .iter()
+        .filter(|r| !r.draft)
+        .find(|r| channel == Channel::Nightly || !r.prerelease)
+}
+
+/// Whether `tag` is a newer version than `current` (both `[v]X.Y.Z[-pre]`).
+///
+/// A prerelease sorts below the release with the same numbers. Unparseable
+/// versions are never considered newer.
+#[must_use]
+pub fn is_newer(current: &str, tag: &str) -> bool {
+    match (parse_version(current), parse_version(tag)) {
+        (Some(cur), Some(new)) => new > cur,
+        _ => false,
+    }
+}
+
+/// `(major, minor, patch, is_release)`: the release flag makes `1.0.0`
+/// sort above `1.0.0-rc1`
+fn parse_version(v: &str) -> Option<(u64, u64, u64, bool)> {
+    let v = v.trim().trim_start_matches('v');
+    let (numbers, pre) = v.split_once('-').map_or((v, None), |(n, p)| (n, Some(p)));
+    let mut parts = numbers.split('.').map(str::parse::<u64>);
+    let major = parts.next()?.ok()?;
+    let minor = parts.next()?.ok()?;
+    let patch = parts.next()?.ok()?;
+    Some((major, minor, patch, pre.is_none()))
+}
+
+/// Release asset for this platform, e.g. `noslop-linux-x86_64.tar.gz`.
+///
+/// `None` for platforms without a published tarball: the release workflow
+/// builds Linux only for `x86_64`, and self-update does not handle the
+/// Windows zip (use the install script or `cargo install` there).
+#[must_use]
+pub fn asset_name(os: &str, arch: &str) -> Option<String> {
+    match (os, arch) {
+        ("linux", "x86_64") | ("macos", "x86_64" | "aarch64") => {
+            Some(format!("noslop-{os}-{arch}.tar.gz"))
+        },
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn release(tag: &str, prerelease: bool) -> Release {
+        Release {
+            tag_name: tag.into(),
+            prerelease,
+            draft: false,
+        }
+    }
+
+    #[test]
+    fn stable_skips_prereleases_nightly_does_not() {
+        let feed = [release("v0.4.0-rc1", true), release("v0.3.0", false)];
+        assert_eq!(pick_release(&feed, Channel::Stable).unwrap().tag_name, "v0.3.0");
+        assert_eq!(pick_release(&feed, Channel::Nightly).unwrap().tag_name, "v0.4.0-rc1");
+    }
+
+    #[test]
+    fn compares_versions_numerically() {
+        assert!(is_newer("0.2.0", "v0.10.0"));
+        assert!(!is_newer("0.2.0", "v0.2.0"));
+        assert!(!is_newer("0.3.0", "v0.2.9"));
+        assert!(is_newer("0.3.0-rc1", "v0.3.0"));
+        assert!(!is_newer("0.3.0", "v0.3.0-rc1"));
+        assert!(!is_newer("0.2.0", "nightly"));
+    }
+
+    #[test]
+    fn asset_names_match_install_script() {
+        assert_eq!(asset_name("linux", "x86_64").as_deref(), Some("noslop-linux-x86_64.tar.gz"));
+        assert_eq!(asset_name("macos", "aarch64").as_deref(), Some("noslop-macos-aarch64.tar.gz"));
+        assert!(asset_name("windows", "x86_64").is_none());
+        // Not built by the release workflow
+        assert!(asset_name("linux", "aarch64").is_none());
+    }
+}
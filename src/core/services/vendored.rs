@@ -0,0 +1,65 @@
+//! Vendored-code detection
+//!
+//! Third-party and generated files change in bulk and are reviewed where
+//! they come from (or regenerated), so a check firing on them should not
+//! hold a commit for an ack. These heuristics catch the common layouts;
+//! a repo adds its own paths with `vendored = [...]`.
+
+/// Directories whose contents are third-party code, at any depth
+const VENDOR_DIRS: &[&str] = &["vendor", "third_party", "third-party", "node_modules"];
+
+/// Lockfiles package managers regenerate
+const LOCKFILES: &[&str] = &[
+    "Cargo.lock",
+    "package-lock.json",
+    "npm-shrinkwrap.json",
+    "yarn.lock",
+    "pnpm-lock.yaml",
+    "poetry.lock",
+    "Pipfile.lock",
+    "uv.lock",
+    "Gemfile.lock",
+    "composer.lock",
+    "go.sum",
+];
+
+/// File-name suffixes of generated code
+const GENERATED_SUFFIXES: &[&str] =
+    &["_generated.go", ".pb.go", "_pb2.py", "_pb2_grpc.py", ".g.dart", ".generated.ts"];
+
+/// Why `path` (repo-relative, `/`-separated) looks vendored, `None` when
+/// no built-in heuristic matches
+#[must_use]
+pub fn builtin_reason(path: &str) -> Option<&'static str> {
+    let (dirs, name) = path.rsplit_once('/').unwrap_or(("", path));
+    if dirs.split('/').any(|d| VENDOR_DIRS.contains(&d)) {
+        Some("vendored directory")
+    } else if LOCKFILES.contains(&name) {
+        Some("lockfile")
+    } else if GENERATED_SUFFIXES.iter().any(|s| name.ends_with(s)) {
+        Some("generated file")
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recognizes_vendor_dirs_lockfiles_and_generated_code() {
+        assert_eq!(builtin_reason("vendor/github.com/x/y.go"), Some("vendored directory"));
+        assert_eq!(builtin_reason("web/node_modules/a/index.js"), Some("vendored directory"));
+        assert_eq!(builtin_reason("crates/x/Cargo.lock"), Some("lockfile"));
+        assert_eq!(builtin_reason("api/user_generated.go"), Some("generated file"));
+        assert_eq!(builtin_reason("proto/user_pb2.py"), Some("generated file"));
+    }
+
+    #[test]
+    fn ordinary_paths_are_not_vendored() {
+        assert_eq!(builtin_reason("src/vendor.rs"), None);
+        assert_eq!(builtin_reason("src/vendoring/mod.rs"), None);
+        assert_eq!(builtin_reason("Cargo.toml"), None);
+    }
+}
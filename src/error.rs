@@ -0,0 +1,53 @@
+//! Library error type
+//!
+//! The port traits and the adapters implementing them return [`Result`],
+//! so library consumers can match on what went wrong. Code that only ever
+//! reports to a person (the CLI, best-effort telemetry) keeps `anyhow`;
+//! [`Error`] converts into it with `?`.
+
+use std::fmt::Display;
+
+/// Errors surfaced by the library's public APIs
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum Error {
+    /// A `.noslop.toml` is missing, unreadable, or defines something invalid
+    #[error("config: {0}")]
+    Config(String),
+    /// A git command failed, or the directory is not a repository
+    #[error("git: {0}")]
+    Git(String),
+    /// Reading or writing noslop's state under `.noslop/` failed
+    #[error("storage: {0}")]
+    Storage(#[from] std::io::Error),
+    /// Stored data (staged acks, records) could not be parsed
+    #[error("parse: {0}")]
+    Parse(String),
+    /// Blocking checks are left unacknowledged
+    #[error("{} unacknowledged check(s): {}", ids.len(), ids.join(", "))]
+    CheckBlocked {
+        /// IDs of the unanswered blocking checks, without repeats
+        ids: Vec<String>,
+    },
+}
+
+impl Error {
+    /// A [`Error::Config`] from any displayable cause
+    pub fn config(cause: impl Display) -> Self {
+        Self::Config(cause.to_string())
+    }
+
+    /// A [`Error::Git`] from any displayable cause
+    pub fn git(cause: impl Display) -> Self {
+        Self::Git(cause.to_string())
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(e: serde_json::Error) -> Self {
+        Self::Parse(e.to_string())
+    }
+}
+
+/// Result alias for the library's public APIs
+pub type Result<T> = std::result::Result<T, Error>;
@@ -18,13 +18,20 @@ pub use noslop::adapters::git::{
 pub mod hooks {
     //! Git hooks re-exports
     pub use noslop::adapters::git::hooks::{
-        install_commit_msg, install_post_commit, install_pre_commit,
+        DEFAULT_HOOKS, HookInstall, HookRemoval, install, uninstall,
     };
 }
 
 pub mod staged {
     //! Staged files re-exports
     pub use noslop::adapters::git::staging::{
-        diff_files, get_staged_files, staged_tree_oid, tracked_files,
+        author_email, base_ref, blobs_at, branches_with_prefix, change_stats, commit_changes,
+        commit_stats, commit_tree, commit_unverified, commits_in_range, commits_touching,
+        create_branch, default_branch, delete_branch, diff_changes, diff_changes_to, file_at,
+        file_log, files_at, first_added, first_changed_lines, get_staged_changes, hash_content,
+        log_commits, merge_base, merge_no_ff, point_head_at, range_stats, resolve_rev,
+        stacked_parent, staged_blobs, staged_diff, staged_tree_oid, switch_branch,
+        tracked_changes_outside, tracked_files, tracked_files_in_batches, trailer_values,
+        zero_context_diff,
     };
 }
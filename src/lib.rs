@@ -20,6 +20,9 @@
 //!   - [`adapters::trailer`] - Commit trailer storage for acknowledgments
 //!   - [`adapters::file`] - JSON file storage for staging
 //!
+//! - [`error`] - Typed [`Error`] returned by the port traits, so consumers
+//!   can match on config, git, storage, parse, and blocked-check failures
+//!
 //! # Example
 //!
 //! ```rust,ignore
@@ -58,6 +61,9 @@
 /// Library version
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
 
+// Typed errors for the public APIs
+pub mod error;
+
 // Core hexagonal architecture modules
 pub mod adapters;
 pub mod core;
@@ -72,3 +78,4 @@ pub mod storage;
 pub use core::models::{Acknowledgment, Check, Severity};
 pub use core::ports::{AcknowledgmentStore, CheckRepository, VersionControl};
 pub use core::services::{CheckResult, check_items, matches_target};
+pub use error::{Error, Result};
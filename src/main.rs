@@ -6,7 +6,10 @@ mod noslop_file;
 
 fn main() {
     if let Err(e) = cli::run() {
-        eprintln!("Error: {e}");
+        let quiet = e.downcast_ref::<cli::commands::Blocked>().is_some_and(|b| b.quiet);
+        if !quiet {
+            eprintln!("Error: {e}");
+        }
         std::process::exit(1);
     }
 }
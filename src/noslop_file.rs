@@ -5,49 +5,198 @@
 //!
 //! This module delegates to `noslop::adapters::toml` for the actual implementation.
 
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+
+use chrono::NaiveDate;
+
+use noslop::VersionControl;
 use noslop::adapters::toml::add_check as adapter_add_check;
 use noslop::adapters::toml::generate_prefix_from_repo as adapter_generate_prefix;
-use noslop::core::models::{Check, Severity};
-use noslop::core::services::matches_target;
+use noslop::adapters::toml::{OwnerRule, RampPhase};
+use noslop::adapters::workspace::discover_packages;
+use noslop::core::models::{Check, CommitStats, FileChange, Severity, SubmoduleMode, TargetKind};
+use noslop::core::services::codeowners::{self, CodeOwners};
+use noslop::core::services::packages::{
+    PACKAGE_VAR, Package, package_for, package_target, substitute,
+};
+use noslop::core::services::{MissReason, match_change, matches_target, vendored};
 
 // Re-export types for backwards compatibility (some may be unused but kept for external use)
 #[allow(unused_imports)]
 pub use noslop::adapters::toml::{
-    CheckEntry, NoslopFile, ProjectConfig, find_noslop_files, load_file,
+    CheckEntry, ConfigDiscovery, NoslopFile, ProjectConfig, find_noslop_files,
+    find_noslop_files_within, load_file, load_file_with,
 };
 
+/// The repo-root `.noslop.toml`, loaded once per process
+///
+/// A missing or unparseable file reads as the defaults, so every setting
+/// below falls back to its documented default.
+fn root_config() -> &'static NoslopFile {
+    static CONFIG: OnceLock<NoslopFile> = OnceLock::new();
+    CONFIG.get_or_init(|| {
+        let path = noslop::adapters::git::repo_root_or_cwd().join(".noslop.toml");
+        if !path.exists() {
+            return NoslopFile::default();
+        }
+        load_file(&path).unwrap_or_default()
+    })
+}
+
 /// Load the repo-root `[remote]` binding, defaulting to local-only
-pub fn load_remote_config() -> noslop::adapters::toml::RemoteConfig {
-    let path = std::path::Path::new(".noslop.toml");
-    if !path.exists() {
-        return noslop::adapters::toml::RemoteConfig::default();
-    }
-    load_file(path).map(|f| f.remote).unwrap_or_default()
+pub fn load_remote_config() -> &'static noslop::adapters::toml::RemoteConfig {
+    &root_config().remote
+}
+
+/// Paths the repo-root config declares vendored (`vendored = [...]`),
+/// on top of the built-in heuristics
+pub fn vendored_patterns() -> &'static [String] {
+    &root_config().vendored
+}
+
+/// How submodule pointer changes are presented to checks (`[git]`)
+pub fn submodule_mode() -> SubmoduleMode {
+    root_config().git.submodules
+}
+
+/// Base branch for `branch` (the current branch when `None`): the longest
+/// `[git.base_branches]` glob it matches, else `[git] base_branch`, else
+/// the repository's default branch
+pub fn base_branch(branch: Option<&str>) -> Option<String> {
+    let git = &root_config().git;
+    let branch = branch
+        .map(String::from)
+        .or_else(|| noslop::adapters::GitVersionControl::default().current_branch().ok().flatten());
+    let overridden = branch.as_deref().and_then(|branch| {
+        git.base_branches
+            .iter()
+            .filter(|(pattern, _)| glob::Pattern::new(pattern).is_ok_and(|p| p.matches(branch)))
+            .max_by_key(|(pattern, _)| pattern.len())
+            .map(|(_, base)| base.clone())
+    });
+    overridden
+        .or_else(|| git.base_branch.clone())
+        .or_else(crate::git::staged::default_branch)
+}
+
+/// The ref `branch` is compared against: its [`base_branch`], resolved to
+/// the local branch or its remote-tracking copy
+pub fn base_ref(branch: &str) -> anyhow::Result<String> {
+    let name = if branch == "HEAD" { None } else { Some(branch) };
+    let Some(base) = base_branch(name) else {
+        anyhow::bail!(
+            "No base branch found: set [git] base_branch in .noslop.toml, or pass --base"
+        );
+    };
+    crate::git::staged::base_ref(&base)
+}
+
+/// Ack message requirements per severity (`[ack.<severity>]`)
+pub fn ack_config() -> &'static noslop::adapters::toml::AckConfig {
+    &root_config().ack
+}
+
+/// Scripts to run around the check pipeline (`[hooks]`)
+pub fn hooks_config() -> &'static noslop::adapters::toml::HooksConfig {
+    &root_config().hooks
+}
+
+/// Review approvals that answer checks (`[reviews]`)
+pub fn reviews_config() -> &'static noslop::adapters::toml::ReviewsConfig {
+    &root_config().reviews
+}
+
+/// Whether post-commit reminders are on (`[reminders]`, default on)
+pub fn reminders_enabled() -> bool {
+    root_config().reminders.enabled
+}
+
+/// Command aliases from the repo-root config (`[alias]`)
+pub fn aliases() -> &'static BTreeMap<String, Vec<String>> {
+    &root_config().alias
 }
 
-/// Load all checks applicable to a set of files
-pub fn load_checks_for_files(files: &[String]) -> anyhow::Result<Vec<(Check, String)>> {
+/// Whether the repo opted into `.noslop/pending.md` (`[pending]`)
+pub fn pending_enabled() -> bool {
+    root_config().pending.enabled
+}
+
+/// Whether the repo opted into local command metrics (`[metrics]`)
+pub fn metrics_enabled() -> bool {
+    root_config().metrics.enabled
+}
+
+/// Load all checks applicable to a set of changed files
+///
+/// Each check is matched against the paths its `on` list selects (see
+/// [`FileChange::paths_for`]), so deletions and renames only fire checks
+/// that ask for them.
+///
+/// A check mentioning `{package}` fires per workspace package (see
+/// [`PackageScope`]). Disabled checks never fire.
+pub fn load_checks_for_changes(changes: &[FileChange]) -> anyhow::Result<Vec<(Check, String)>> {
+    checks_for_changes(changes, false)
+}
+
+/// Disabled checks that would have fired on `changes`, for the gate's
+/// note that they were skipped
+pub fn disabled_checks_for_changes(changes: &[FileChange]) -> anyhow::Result<Vec<(Check, String)>> {
+    checks_for_changes(changes, true)
+}
+
+/// Checks matching `changes` whose `disabled` flag equals `disabled`
+fn checks_for_changes(
+    changes: &[FileChange],
+    disabled: bool,
+) -> anyhow::Result<Vec<(Check, String)>> {
     let mut result = Vec::new();
     let cwd = std::env::current_dir()?;
+    let root = noslop::adapters::git::repo_root_or_cwd();
+    let packages = PackageScope::new(&root);
+    let mut configs = ConfigDiscovery::new(&root, &config_source);
+    let vendored = vendored_patterns();
+    let ownership = Ownership::new(|| {
+        let rules = codeowners_text(&root);
+        let committer = noslop::adapters::resolve_identity(None).map(|i| i.name);
+        (rules.map(|text| CodeOwners::parse(&text)).unwrap_or_default(), committer)
+    });
 
-    for file in files {
-        let file_path = cwd.join(file);
-        let noslop_files = find_noslop_files(&file_path);
-
-        for noslop_path in noslop_files {
-            let noslop_file = load_file(&noslop_path)?;
+    for change in changes {
+        for noslop_path in configs_for_change(&mut configs, &cwd, change) {
+            let noslop_file = configs.load(&noslop_path)?;
             let noslop_dir = noslop_path.parent().unwrap_or(&cwd);
 
-            for entry in &noslop_file.checks {
-                if matches_target(&entry.target, file, noslop_dir, &cwd) {
-                    let check = Check::new(
-                        entry.id.clone(),
-                        entry.target.clone(),
-                        entry.message.clone(),
-                        entry.severity.parse().unwrap_or(Severity::Block),
-                    );
-                    result.push((check, file.clone()));
+            let entries = noslop_file
+                .checks
+                .iter()
+                .filter(|e| e.target_kind == TargetKind::File && e.disabled == disabled);
+            for entry in entries {
+                let Some(Ok((file, message))) =
+                    match_entry(entry, change, &packages, noslop_dir, &root, &cwd)
+                else {
+                    continue;
+                };
+                let mut check = to_check(entry);
+                if let Some(message) = message {
+                    check.message = message;
+                }
+                if !lacks_required(&mut check, entry, || staged_content(file)) {
+                    continue;
                 }
+                let defined_in = entry.origin.as_deref().unwrap_or(&noslop_path);
+                let base = if packages.applies(entry) {
+                    root.as_path()
+                } else {
+                    noslop_dir
+                };
+                check.severity =
+                    ramped(entry, entry.severity_for(file, base, &cwd), defined_in, "HEAD");
+                mark_vendored(&mut check, entry, file, vendored, &root, &cwd);
+                ownership.apply(&mut check, entry, file);
+                result.push((check, file.to_string()));
             }
         }
     }
@@ -59,6 +208,497 @@ pub fn load_checks_for_files(files: &[String]) -> anyhow::Result<Vec<(Check, Str
     Ok(result)
 }
 
+/// Checks applicable to `changes` as the config stood at `rev`
+///
+/// Reads every `.noslop.toml` from the commit's tree rather than the
+/// working tree, so a past commit is judged by the checks it was made
+/// under. Change paths are repo-root relative; commit checks are judged
+/// by `stats`, the size and shape of the same change set.
+pub fn checks_at_commit(
+    rev: &str,
+    changes: &[FileChange],
+    stats: &CommitStats,
+) -> anyhow::Result<Vec<(Check, String)>> {
+    let read_at_rev =
+        |path: &Path| crate::git::staged::file_at(rev, &path.to_string_lossy().replace('\\', "/"));
+    let mut configs = Vec::new();
+    for path in crate::git::staged::files_at(rev)? {
+        if let Some(dir) = config_dir(&path) {
+            configs.push((dir.to_string(), load_file_with(Path::new(&path), &read_at_rev)?));
+        }
+    }
+    let codeowners = || {
+        codeowners::LOCATIONS
+            .iter()
+            .find_map(|loc| crate::git::staged::file_at(rev, loc).ok())
+    };
+    let mut checks = checks_in_configs(&configs, codeowners, rev, rev, changes);
+    checks.extend(commit_checks_in_configs(&configs, changes, stats, rev));
+    Ok(checks)
+}
+
+/// Every file the policy at `rev` is read from, path to content: its
+/// `.noslop.toml` files, the files they include, and CODEOWNERS
+pub fn policy_files_at(rev: &str) -> anyhow::Result<BTreeMap<String, String>> {
+    let files = std::cell::RefCell::new(BTreeMap::new());
+    let record = |path: &Path| {
+        let path = path.to_string_lossy().replace('\\', "/");
+        let content = crate::git::staged::file_at(rev, &path)?;
+        files.borrow_mut().insert(path, content.clone());
+        Ok(content)
+    };
+    for path in crate::git::staged::files_at(rev)? {
+        if config_dir(&path).is_some() {
+            load_file_with(Path::new(&path), &record)?;
+        }
+    }
+    if let Some(loc) = codeowners::LOCATIONS
+        .iter()
+        .find(|loc| crate::git::staged::file_at(rev, loc).is_ok())
+    {
+        record(Path::new(loc))?;
+    }
+    Ok(files.into_inner())
+}
+
+/// The config files among recorded policy `files`, loaded with their
+/// includes, as (directory, file)
+pub fn configs_in(files: &BTreeMap<String, String>) -> anyhow::Result<Vec<(String, NoslopFile)>> {
+    let read = |path: &Path| {
+        let path = path.to_string_lossy().replace('\\', "/");
+        files
+            .get(&path)
+            .cloned()
+            .ok_or_else(|| anyhow::anyhow!("{path} is not recorded"))
+    };
+    let mut configs = Vec::new();
+    for path in files.keys() {
+        if let Some(dir) = config_dir(path) {
+            configs.push((dir.to_string(), load_file_with(Path::new(path), &read)?));
+        }
+    }
+    Ok(configs)
+}
+
+/// Checks `changes` (with `stats`) raise under recorded policy `files`
+/// (see [`policy_files_at`]) taken at `policy_rev`, judged as a commit by
+/// the author of `rev`
+pub fn checks_in_policy(
+    files: &BTreeMap<String, String>,
+    policy_rev: &str,
+    rev: &str,
+    changes: &[FileChange],
+    stats: &CommitStats,
+) -> anyhow::Result<Vec<(Check, String)>> {
+    let configs = configs_in(files)?;
+    let codeowners = || codeowners::LOCATIONS.iter().find_map(|loc| files.get(*loc).cloned());
+    let mut checks = checks_in_configs(&configs, codeowners, policy_rev, rev, changes);
+    checks.extend(commit_checks_in_configs(&configs, changes, stats, policy_rev));
+    Ok(checks)
+}
+
+/// The directory a `.noslop.toml` path governs, `None` for other paths
+fn config_dir(path: &str) -> Option<&str> {
+    path.strip_suffix(".noslop.toml")
+        .filter(|dir| dir.is_empty() || dir.ends_with('/'))
+}
+
+/// File checks from `configs` (directory, file) that `changes` raise;
+/// ramps are dated from `policy_rev`, CODEOWNERS rules from `codeowners`
+/// apply to the author of `rev`, and `{package}` checks resolve against
+/// the packages in `rev`'s tree
+fn checks_in_configs(
+    configs: &[(String, NoslopFile)],
+    codeowners: impl Fn() -> Option<String>,
+    policy_rev: &str,
+    rev: &str,
+    changes: &[FileChange],
+) -> Vec<(Check, String)> {
+    let root = noslop::adapters::git::repo_root_or_cwd();
+    let packages = PackageScope::at(&root, rev);
+    let vendored = configs.iter().find(|(dir, _)| dir.is_empty()).map(|(_, f)| f.vendored.clone());
+    let vendored = vendored.unwrap_or_default();
+    let ownership = Ownership::new(|| {
+        let rules = codeowners().map(|text| CodeOwners::parse(&text)).unwrap_or_default();
+        (rules, crate::git::staged::author_email(rev))
+    });
+
+    let mut result = Vec::new();
+    for change in changes {
+        for (dir, noslop_file) in configs {
+            let within = |path: &str| path.starts_with(dir.as_str());
+            if !within(&change.path) && !change.old_path.as_deref().is_some_and(within) {
+                continue;
+            }
+            let noslop_dir = root.join(dir);
+            let config_path = PathBuf::from(format!("{dir}.noslop.toml"));
+            let entries = noslop_file
+                .checks
+                .iter()
+                .filter(|e| e.target_kind == TargetKind::File && !e.disabled);
+            for entry in entries {
+                let Some(Ok((file, message))) =
+                    match_entry(entry, change, &packages, &noslop_dir, &root, &root)
+                else {
+                    continue;
+                };
+                let mut check = to_check(entry);
+                if let Some(message) = message {
+                    check.message = message;
+                }
+                let content = || crate::git::staged::file_at(rev, file).ok();
+                if !lacks_required(&mut check, entry, content) {
+                    continue;
+                }
+                let defined_in = entry.origin.as_deref().unwrap_or(&config_path);
+                let base = if packages.applies(entry) {
+                    root.as_path()
+                } else {
+                    noslop_dir.as_path()
+                };
+                let severity = entry.severity_for(file, base, &root);
+                check.severity = ramped(entry, severity, defined_in, policy_rev);
+                mark_vendored(&mut check, entry, file, &vendored, &root, &root);
+                ownership.apply(&mut check, entry, file);
+                result.push((check, file.to_string()));
+            }
+        }
+    }
+
+    result.sort_by(|a, b| (&a.0.message, &a.1).cmp(&(&b.0.message, &b.1)));
+    result.dedup_by(|a, b| a.0.message == b.0.message && a.1 == b.1);
+    result
+}
+
+/// Commit checks from `configs` (directory, file) that a change set with
+/// `stats` meets: those of the repo-root config and of every directory
+/// `changes` touch, each once; ramps are dated from `policy_rev`
+fn commit_checks_in_configs(
+    configs: &[(String, NoslopFile)],
+    changes: &[FileChange],
+    stats: &CommitStats,
+    policy_rev: &str,
+) -> Vec<(Check, String)> {
+    let governs = |dir: &str| {
+        dir.is_empty()
+            || changes.iter().any(|change| {
+                change.path.starts_with(dir)
+                    || change.old_path.as_deref().is_some_and(|old| old.starts_with(dir))
+            })
+    };
+    let mut result: Vec<(Check, String)> = Vec::new();
+    for (dir, noslop_file) in configs.iter().filter(|(dir, _)| governs(dir)) {
+        let config_path = PathBuf::from(format!("{dir}.noslop.toml"));
+        for entry in &noslop_file.checks {
+            if entry.target_kind == TargetKind::Commit
+                && !entry.disabled
+                && entry.commit.matches(stats)
+                && !result.iter().any(|(c, _)| entry.id.as_ref() == Some(&c.id))
+            {
+                let mut check = to_check(entry);
+                let defined_in = entry.origin.as_deref().unwrap_or(&config_path);
+                check.severity = ramped(entry, entry.severity, defined_in, policy_rev);
+                result.push((check, COMMIT_LABEL.to_string()));
+            }
+        }
+    }
+    result
+}
+
+/// Label commit checks carry in place of a matched file
+pub const COMMIT_LABEL: &str = "(commit)";
+
+/// Commit checks whose conditions a change set with `stats` meets
+///
+/// Read from every config file governing `changes` plus the repo-root
+/// config; each fires once, labelled [`COMMIT_LABEL`].
+pub fn load_commit_checks(
+    changes: &[FileChange],
+    stats: &CommitStats,
+) -> anyhow::Result<Vec<(Check, String)>> {
+    let cwd = std::env::current_dir()?;
+    let root = noslop::adapters::git::repo_root_or_cwd();
+    let mut configs = ConfigDiscovery::new(&root, &config_source);
+    let mut paths: BTreeSet<PathBuf> = configs.files_for(&root).into_iter().collect();
+    for change in changes {
+        paths.extend(configs.files_for(&cwd.join(&change.path)));
+    }
+
+    let mut result: Vec<(Check, String)> = Vec::new();
+    for path in &paths {
+        for entry in &configs.load(path)?.checks {
+            if entry.target_kind == TargetKind::Commit
+                && !entry.disabled
+                && entry.commit.matches(stats)
+                && !result.iter().any(|(c, _)| entry.id.as_ref() == Some(&c.id))
+            {
+                let mut check = to_check(entry);
+                check.severity =
+                    ramped(entry, entry.severity, entry.origin.as_ref().unwrap_or(path), "HEAD");
+                result.push((check, COMMIT_LABEL.to_string()));
+            }
+        }
+    }
+    Ok(result)
+}
+
+/// Paths and contents of every config file governing `changes`, plus the
+/// repo-root config (it also holds `[git]` and `[ack]`)
+pub fn config_fingerprint(changes: &[FileChange]) -> anyhow::Result<String> {
+    let cwd = std::env::current_dir()?;
+    let root = noslop::adapters::git::repo_root_or_cwd();
+    let mut configs = ConfigDiscovery::new(&root, &config_source);
+    let mut paths: BTreeSet<PathBuf> = configs.files_for(&root).into_iter().collect();
+    for change in changes {
+        paths.extend(configs.files_for(&cwd.join(&change.path)));
+    }
+
+    let mut fingerprint = String::new();
+    // Included files govern too
+    let mut included = Vec::new();
+    for path in &paths {
+        if let Ok(file) = configs.load(path) {
+            included.extend(file.checks.iter().filter_map(|c| c.origin.clone()));
+        }
+    }
+    paths.extend(included);
+    for path in paths {
+        let content = read_worktree_or_index(&path).unwrap_or_default();
+        fingerprint.push_str(&format!("{}\n{content}\n", path.display()));
+    }
+    Ok(fingerprint)
+}
+
+/// CODEOWNERS and the workspace packages as the gate reads them: inputs
+/// to a file check's severity and `{package}` scope that live outside
+/// the config files
+pub fn scope_fingerprint() -> String {
+    let root = noslop::adapters::git::repo_root_or_cwd();
+    let packages: Vec<String> = worktree_packages(&root)
+        .into_iter()
+        .map(|package| format!("{} {}", package.dir, package.name))
+        .collect();
+    format!("{}\n{}", codeowners_text(&root).unwrap_or_default(), packages.join("\n"))
+}
+
+/// The working tree's CODEOWNERS file, from the first location that has one
+fn codeowners_text(root: &Path) -> Option<String> {
+    codeowners::LOCATIONS
+        .iter()
+        .find_map(|loc| std::fs::read_to_string(root.join(loc)).ok())
+}
+
+/// Packages defined by the tracked manifests, read from the working tree
+/// (or the index, where it is sparse)
+fn worktree_packages(root: &Path) -> Vec<Package> {
+    let files = crate::git::staged::tracked_files().unwrap_or_default();
+    discover_packages(&files, |file| read_worktree_or_index(&root.join(file)).ok())
+}
+
+/// A check that did not fire: the check, the changed file, why, and the
+/// config file that defines the check
+pub type Miss = (Check, String, MissReason, PathBuf);
+
+/// Every check considered for a changed file that did not fire, with why
+///
+/// Mirrors [`load_checks_for_changes`]: the same files are scanned in the
+/// same order, so a check missing here either matched or was never in
+/// scope for that file.
+pub fn explain_misses(changes: &[FileChange]) -> anyhow::Result<Vec<Miss>> {
+    let mut result = Vec::new();
+    let cwd = std::env::current_dir()?;
+    let root = noslop::adapters::git::repo_root_or_cwd();
+    let packages = PackageScope::new(&root);
+    let mut configs = ConfigDiscovery::new(&root, &config_source);
+
+    for change in changes {
+        for noslop_path in configs_for_change(&mut configs, &cwd, change) {
+            let noslop_file = configs.load(&noslop_path)?;
+            let noslop_dir = noslop_path.parent().unwrap_or(&cwd);
+
+            for entry in noslop_file.checks.iter().filter(|e| e.target_kind == TargetKind::File) {
+                let reason = if entry.disabled {
+                    Some(MissReason::Disabled)
+                } else {
+                    match match_entry(entry, change, &packages, noslop_dir, &root, &cwd) {
+                        // Outside every package: the check is not in scope
+                        None => continue,
+                        Some(Err(reason)) => Some(reason),
+                        Some(Ok((file, _))) => entry
+                            .content_satisfies(staged_content(file).as_deref())
+                            .then_some(MissReason::ContentPresent),
+                    }
+                };
+                if let Some(reason) = reason {
+                    let defined_in = entry.origin.clone().unwrap_or_else(|| noslop_path.clone());
+                    result.push((to_check(entry), change.path.clone(), reason, defined_in));
+                }
+            }
+        }
+    }
+
+    Ok(result)
+}
+
+/// Config files governing `change`; a rename or move out of a directory
+/// still answers to that directory's configs
+fn configs_for_change(
+    configs: &mut ConfigDiscovery<'_>,
+    cwd: &Path,
+    change: &FileChange,
+) -> Vec<PathBuf> {
+    let mut paths = configs.files_for(&cwd.join(&change.path));
+    if let Some(old) = &change.old_path {
+        for path in configs.files_for(&cwd.join(old)) {
+            if !paths.contains(&path) {
+                paths.push(path);
+            }
+        }
+    }
+    paths
+}
+
+/// The path of `change` that file check `entry` fires on, or why it does
+/// not fire; `None` when `entry` is per package and `change` lies outside
+/// every package
+///
+/// The one matching rule the gate, commit audits, and `--explain-misses`
+/// share. A `{package}` check also yields its message for the package.
+fn match_entry<'c>(
+    entry: &CheckEntry,
+    change: &'c FileChange,
+    packages: &PackageScope<'_>,
+    noslop_dir: &Path,
+    root: &Path,
+    cwd: &Path,
+) -> Option<Result<(&'c str, Option<String>), MissReason>> {
+    if !packages.applies(entry) {
+        let hits = |path: &str| matches_target(&entry.target, path, noslop_dir, cwd);
+        return Some(match_change(&entry.on, change, hits).map(|path| (path, None)));
+    }
+    // Package targets are relative to the repo root
+    let mut paths = std::iter::once(change.path.as_str()).chain(change.old_path.as_deref());
+    if paths.all(|path| packages.resolve(entry, path).is_none()) {
+        return None;
+    }
+    let hits = |path: &str| {
+        packages
+            .resolve(entry, path)
+            .is_some_and(|(target, _)| matches_target(&target, path, root, cwd))
+    };
+    let fired = match_change(&entry.on, change, hits);
+    Some(fired.map(|path| (path, packages.resolve(entry, path).map(|(_, message)| message))))
+}
+
+/// Workspace packages for checks that mention `{package}`, discovered
+/// from tracked manifests (or those of a revision's tree) on first use
+///
+/// In such a check's target `{package}` is the package directory,
+/// relative to the repo root; in its message, the package name. Files
+/// outside every package do not fire it.
+struct PackageScope<'a> {
+    root: &'a Path,
+    /// Revision whose tree the manifests are read from; `None` = the
+    /// working tree (or the index, where it is sparse)
+    rev: Option<&'a str>,
+    packages: std::cell::OnceCell<Vec<Package>>,
+}
+
+impl<'a> PackageScope<'a> {
+    const fn new(root: &'a Path) -> Self {
+        Self {
+            root,
+            rev: None,
+            packages: std::cell::OnceCell::new(),
+        }
+    }
+
+    /// Packages as defined in the tree of `rev`
+    const fn at(root: &'a Path, rev: &'a str) -> Self {
+        Self {
+            root,
+            rev: Some(rev),
+            packages: std::cell::OnceCell::new(),
+        }
+    }
+
+    /// Whether `entry` is evaluated per package
+    fn applies(&self, entry: &CheckEntry) -> bool {
+        entry.target.contains(PACKAGE_VAR) || entry.message.contains(PACKAGE_VAR)
+    }
+
+    /// `entry`'s target and message for the package containing `path`
+    fn resolve(&self, entry: &CheckEntry, path: &str) -> Option<(String, String)> {
+        let packages = self.packages.get_or_init(|| {
+            if let Some(rev) = self.rev {
+                let files = crate::git::staged::files_at(rev).unwrap_or_default();
+                return discover_packages(&files, |file| {
+                    crate::git::staged::file_at(rev, file).ok()
+                });
+            }
+            worktree_packages(self.root)
+        });
+        let package = package_for(packages, path)?;
+        Some((
+            package_target(&entry.target, package),
+            substitute(&entry.message, &package.name).into_owned(),
+        ))
+    }
+}
+
+/// Config files tracked in the index of a sparse checkout, as absolute
+/// paths; `None` for a full checkout, where the working tree is complete
+///
+/// Outside the sparse cone a `.noslop.toml` exists only in the index, so
+/// on a sparse checkout config discovery and reads fall back to it.
+fn sparse_configs() -> Option<&'static BTreeSet<PathBuf>> {
+    static CONFIGS: OnceLock<Option<BTreeSet<PathBuf>>> = OnceLock::new();
+    CONFIGS
+        .get_or_init(|| {
+            if !noslop::adapters::git::sparse_checkout() {
+                return None;
+            }
+            let root = noslop::adapters::git::repo_root_or_cwd();
+            let files = crate::git::staged::tracked_files().ok()?;
+            Some(
+                files
+                    .iter()
+                    .filter(|f| *f == ".noslop.toml" || f.ends_with("/.noslop.toml"))
+                    .map(|f| root.join(f))
+                    .collect(),
+            )
+        })
+        .as_ref()
+}
+
+/// Whether `path` is a config file only the sparse index holds
+fn indexed(path: &Path) -> bool {
+    sparse_configs().is_some_and(|configs| configs.contains(path))
+}
+
+/// Reader for [`ConfigDiscovery`]: config files (and their includes) from
+/// disk, or from the index when a sparse checkout left them out
+fn config_source(path: &Path) -> anyhow::Result<String> {
+    if path.file_name().is_some_and(|name| name == ".noslop.toml")
+        && !path.is_file()
+        && !indexed(path)
+    {
+        anyhow::bail!("No {}", path.display());
+    }
+    read_worktree_or_index(path)
+}
+
+/// A file's working-tree content, or its staged content when it is not
+/// checked out
+fn read_worktree_or_index(path: &Path) -> anyhow::Result<String> {
+    if path.exists() || sparse_configs().is_none() {
+        return Ok(std::fs::read_to_string(path)?);
+    }
+    let root = noslop::adapters::git::repo_root_or_cwd();
+    let rel = path.strip_prefix(&root).unwrap_or(path);
+    crate::git::staged::file_at("", &rel.to_string_lossy().replace('\\', "/"))
+}
+
 /// Load every check defined in .noslop.toml files reachable from the cwd
 pub fn load_all_checks() -> anyhow::Result<Vec<Check>> {
     let cwd = std::env::current_dir()?;
@@ -67,25 +707,211 @@ pub fn load_all_checks() -> anyhow::Result<Vec<Check>> {
     for noslop_path in find_noslop_files(&cwd) {
         let noslop_file = load_file(&noslop_path)?;
         for entry in &noslop_file.checks {
-            checks.push(Check::new(
-                entry.id.clone(),
-                entry.target.clone(),
-                entry.message.clone(),
-                entry.severity.parse().unwrap_or(Severity::Block),
-            ));
+            checks.push(to_check(entry));
         }
     }
 
     Ok(checks)
 }
 
-/// Find a check by exact ID
-pub fn find_check_by_id(id: &str) -> anyhow::Result<Option<Check>> {
-    Ok(load_all_checks()?.into_iter().find(|c| c.id == id))
+/// Every config file in the repository: tracked ones plus those on the
+/// path from the cwd up to the root, sorted
+pub fn repo_config_paths() -> anyhow::Result<Vec<PathBuf>> {
+    let cwd = std::env::current_dir()?;
+    let root = noslop::adapters::git::repo_root_or_cwd();
+    let mut paths: BTreeSet<PathBuf> = find_noslop_files(&cwd).into_iter().collect();
+    for rel in crate::git::staged::tracked_files()? {
+        if rel == ".noslop.toml" || rel.ends_with("/.noslop.toml") {
+            paths.insert(root.join(rel));
+        }
+    }
+    Ok(paths.into_iter().collect())
+}
+
+/// Load every check defined anywhere in the repository (see
+/// [`repo_config_paths`])
+pub fn load_repo_checks() -> anyhow::Result<Vec<Check>> {
+    let mut checks: Vec<Check> = Vec::new();
+    for path in repo_config_paths()? {
+        for entry in &load_file(&path)?.checks {
+            let check = to_check(entry);
+            if !checks.iter().any(|c| c.id == check.id) {
+                checks.push(check);
+            }
+        }
+    }
+    Ok(checks)
+}
+
+/// Where `entry`'s ramp stands today, `None` without one. `defined_in` is
+/// the config file holding the entry; `warn_days` count from the first
+/// commit reachable from `rev` that added its ID there.
+pub fn ramp_phase(entry: &CheckEntry, defined_in: &Path, rev: &str) -> Option<RampPhase> {
+    entry
+        .ramp?
+        .phase(introduced(entry, defined_in, rev), chrono::Utc::now().date_naive())
+}
+
+/// `severity` after `entry`'s ramp: a block warns while the ramp runs
+fn ramped(entry: &CheckEntry, severity: Severity, defined_in: &Path, rev: &str) -> Severity {
+    match entry.ramp {
+        Some(ramp) => ramp.apply(
+            severity,
+            introduced(entry, defined_in, rev),
+            chrono::Utc::now().date_naive(),
+        ),
+        None => severity,
+    }
+}
+
+/// Date the entry was first committed, looked up only for a `warn_days`
+/// ramp (a fixed `warn_until` needs no history)
+///
+/// Looked up once per (revision, config file, entry) and process: a check
+/// matching many files would otherwise walk the history once per file.
+fn introduced(entry: &CheckEntry, defined_in: &Path, rev: &str) -> Option<NaiveDate> {
+    type Key = (String, PathBuf, String);
+    static INTRODUCED: OnceLock<Mutex<BTreeMap<Key, Option<NaiveDate>>>> = OnceLock::new();
+
+    let ramp = entry.ramp?;
+    if ramp.warn_until.is_some() || ramp.warn_days.is_none() {
+        return None;
+    }
+    let needle = entry
+        .id
+        .as_ref()
+        .map_or_else(|| entry.message.clone(), |id| format!("\"{id}\""));
+    let key = (rev.to_string(), defined_in.to_path_buf(), needle);
+    let dates = INTRODUCED.get_or_init(Mutex::default);
+    if let Some(date) = dates.lock().ok()?.get(&key) {
+        return *date;
+    }
+
+    let root = noslop::adapters::git::repo_root_or_cwd();
+    let path = defined_in.strip_prefix(&root).unwrap_or(defined_in);
+    let date =
+        crate::git::staged::first_added(rev, &path.to_string_lossy().replace('\\', "/"), &key.2)
+            .ok()
+            .flatten()
+            .and_then(|date| NaiveDate::parse_from_str(&date, "%Y-%m-%d").ok());
+    dates.lock().ok()?.insert(key, date);
+    date
+}
+
+/// CODEOWNERS rules and the committer's identity, loaded on first use by
+/// a check with `codeowners` rules
+struct Ownership<F: Fn() -> (CodeOwners, Option<String>)> {
+    load: F,
+    loaded: std::cell::OnceCell<(CodeOwners, Option<String>)>,
+}
+
+impl<F: Fn() -> (CodeOwners, Option<String>)> Ownership<F> {
+    const fn new(load: F) -> Self {
+        Self {
+            load,
+            loaded: std::cell::OnceCell::new(),
+        }
+    }
+
+    /// Record the owners of `file` on a check with the `ack` rule, and cap
+    /// one with the `warn` rule at a warning when the committer is one of
+    /// them.
+    /// A file no rule assigns leaves the check as it is.
+    fn apply(&self, check: &mut Check, entry: &CheckEntry, file: &str) {
+        if entry.codeowners.is_empty() {
+            return;
+        }
+        let (rules, committer) = self.loaded.get_or_init(&self.load);
+        let owners = rules.owners_of(file);
+        if owners.is_empty() {
+            return;
+        }
+        if entry.codeowners.contains(&OwnerRule::Ack) {
+            check.code_owners = owners.to_vec();
+        }
+        if entry.codeowners.contains(&OwnerRule::Warn)
+            && check.severity == Severity::Block
+            && committer.as_deref().is_some_and(|c| codeowners::is_owner(owners, c))
+        {
+            check.severity = Severity::Warn;
+        }
+    }
+}
+
+/// Apply `must_match`: `false` when the matched file's `content` already
+/// has the match, so the check does not fire; otherwise the message names
+/// what is missing
+fn lacks_required(
+    check: &mut Check,
+    entry: &CheckEntry,
+    content: impl FnOnce() -> Option<String>,
+) -> bool {
+    let Some(pattern) = &entry.must_match else {
+        return true;
+    };
+    if entry.content_satisfies(content().as_deref()) {
+        return false;
+    }
+    let _ = write!(check.message, " (missing `{pattern}`)");
+    true
+}
+
+/// Staged content of `file` (repo-root relative), if it has any
+fn staged_content(file: &str) -> Option<String> {
+    crate::git::staged::file_at("", file).ok()
+}
+
+/// Cap `check` at a warning when `file` is vendored (built-in heuristics
+/// or `patterns`). A check whose own target names vendored paths is about
+/// them and keeps its severity, as does one with `include_vendored`.
+fn mark_vendored(
+    check: &mut Check,
+    entry: &CheckEntry,
+    file: &str,
+    patterns: &[String],
+    root: &Path,
+    cwd: &Path,
+) {
+    let is_vendored = |path: &str| {
+        vendored::builtin_reason(path).is_some()
+            || patterns.iter().any(|p| matches_target(p, path, root, cwd))
+    };
+    if entry.include_vendored || is_vendored(&entry.target) {
+        return;
+    }
+    if is_vendored(file) {
+        check.vendored = true;
+        if check.severity == Severity::Block {
+            check.severity = Severity::Warn;
+        }
+    }
+}
+
+/// The ID acks answer `entry` by: its own, or the one derived from its
+/// content
+pub fn check_id(entry: &CheckEntry) -> String {
+    to_check(entry).id
+}
+
+/// Build the domain check for a `.noslop.toml` entry
+fn to_check(entry: &CheckEntry) -> Check {
+    let (target, commit) = match entry.target_kind {
+        TargetKind::File => (entry.target.clone(), None),
+        TargetKind::Commit => (entry.commit.describe(), Some(entry.commit)),
+    };
+    Check::new(entry.id.clone(), target, entry.message.clone(), entry.severity)
+        .with_commit(commit)
+        .with_on(entry.on.clone())
+        .with_requires_human(entry.requires_human)
+        .with_group(entry.group.clone())
+        .with_owner(entry.owner.clone())
+        .with_attest_within_days(entry.attest_within_days)
+        .with_scoped_to(entry.when.change_kind.clone())
+        .with_dependency_change(entry.when.dependency_change)
 }
 
 /// Create or update a .noslop.toml file with a new check
-pub fn add_check(target: &str, message: &str, severity: &str) -> anyhow::Result<String> {
+pub fn add_check(target: &str, message: &str, severity: Severity) -> anyhow::Result<String> {
     adapter_add_check(target, message, severity)
 }
 
@@ -3,7 +3,14 @@
 //! This module provides structured output that can be rendered either as
 //! human-readable text or machine-parseable JSON.
 
-use serde::Serialize;
+use std::collections::BTreeSet;
+use std::fmt::Write;
+
+use serde::{Deserialize, Serialize};
+
+use crate::core::models::{ChangeClass, Severity};
+use crate::core::services::ack_policy::AckRequirement;
+use crate::error::Error;
 
 /// Output mode for the CLI
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -16,7 +23,7 @@ pub enum OutputMode {
 }
 
 /// Result of a check operation
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct CheckResult {
     /// Whether the check passed
     pub passed: bool,
@@ -53,6 +60,115 @@ pub struct CheckResult {
     /// schema 1; omitted when empty)
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub monitor: Vec<CheckMatch>,
+    /// Checks considered but not fired, with the reason (only with
+    /// `--explain-misses`; additive within schema 1, omitted when empty)
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub misses: Vec<CheckMiss>,
+    /// `[hooks]` scripts run around the pipeline, in order (additive
+    /// within schema 1; omitted when none are configured)
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub hooks: Vec<HookRun>,
+    /// What changed since the previous run (only with `--compare`;
+    /// additive within schema 1, omitted otherwise)
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub comparison: Option<CheckComparison>,
+    /// Commit range judged instead of the staged changes (`check
+    /// --against`; additive within schema 1, omitted otherwise)
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub range: Option<String>,
+}
+
+/// How a run differs from the one before it (`check --compare`)
+#[derive(Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CheckComparison {
+    /// Blocking now, not before
+    pub newly_blocking: Vec<MatchRef>,
+    /// Blocking or warning before, now answered or no longer firing
+    pub newly_satisfied: Vec<MatchRef>,
+    /// Files some check matches now, none did before
+    pub files_added: Vec<String>,
+    /// Files some check matched before, none does now
+    pub files_removed: Vec<String>,
+}
+
+/// A check on a file
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct MatchRef {
+    /// Check ID
+    pub id: String,
+    /// Matched file
+    pub file: String,
+}
+
+impl CheckComparison {
+    /// Whether nothing changed
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+
+    fn render(&self) {
+        if self.is_empty() {
+            println!("\nSince the last check: no change.");
+            return;
+        }
+        println!("\nSince the last check:");
+        for (label, refs) in [
+            ("newly blocking", &self.newly_blocking),
+            ("newly satisfied", &self.newly_satisfied),
+        ] {
+            for r in refs {
+                println!("  {label:<16} [{}] {}", r.id, r.file);
+            }
+        }
+        for file in &self.files_added {
+            println!("  {:<16} {file}", "now matched");
+        }
+        for file in &self.files_removed {
+            println!("  {:<16} {file}", "no longer matched");
+        }
+    }
+}
+
+/// A `[hooks]` script run: what ran, how it exited, what it printed
+#[derive(Debug, Serialize, Deserialize)]
+pub struct HookRun {
+    /// `pre_check` or `post_check`
+    pub stage: String,
+    /// The configured shell command
+    pub command: String,
+    /// Exit code (`None` when killed by a signal)
+    pub exit_code: Option<i32>,
+    /// What a failure means: info, warn, or block
+    pub on_failure: Severity,
+    /// Captured stdout and stderr
+    pub output: String,
+}
+
+impl HookRun {
+    /// The script failed
+    #[must_use]
+    pub const fn failed(&self) -> bool {
+        !matches!(self.exit_code, Some(0))
+    }
+
+    /// The script failed and its failures block
+    #[must_use]
+    pub fn blocks(&self) -> bool {
+        self.failed() && self.on_failure == Severity::Block
+    }
+
+    fn render(&self) {
+        let status = match self.exit_code {
+            Some(0) => "ok".to_string(),
+            Some(code) => format!("exit {code} ({})", self.on_failure),
+            None => format!("killed ({})", self.on_failure),
+        };
+        println!("Hook {}: {}  [{status}]", self.stage, self.command);
+        for line in self.output.lines() {
+            println!("    {line}");
+        }
+    }
 }
 
 /// Version of the upload envelope format (see `docs/SCHEMA.md`)
@@ -89,7 +205,7 @@ pub struct UploadEnvelope {
 }
 
 /// A check matched to a file
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct CheckMatch {
     /// The check ID (e.g., "NSL-1")
     pub id: String,
@@ -100,9 +216,163 @@ pub struct CheckMatch {
     /// The check message
     pub message: String,
     /// Severity level
-    pub severity: String,
+    pub severity: Severity,
+    /// Ack group the check belongs to (additive within schema 1; omitted
+    /// for ungrouped checks)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub group: Option<String>,
+    /// Who to contact when the check blocks (additive within schema 1;
+    /// omitted when the check names no owner)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub owner: Option<String>,
+    /// The matched file is vendored or generated, so the check only warns
+    /// (additive within schema 1; omitted when false)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub vendored: bool,
+    /// Agent acks count only once a human approves them (additive within
+    /// schema 1; omitted when false)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub requires_human: bool,
+    /// CODEOWNERS owners whose ack alone counts, for checks with `codeowners = ["ack"]`
+    /// (additive within schema 1; omitted when anyone's ack counts)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub code_owners: Vec<String>,
     /// Whether this check was acknowledged
     pub acknowledged: bool,
+    /// Blob id an ack recorded for this file, which has changed since and
+    /// needs a fresh ack (additive within schema 1; omitted otherwise)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stale_digest: Option<String>,
+    /// Commits in the checked range that touched the file, newest first
+    /// (diff-base runs only; additive within schema 1, omitted when empty)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub introduced_by: Vec<Attribution>,
+    /// Classes of the file's diff hunks (new function, signature change,
+    /// ...), as review hints (additive within schema 1, omitted when empty)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub change_classes: Vec<ChangeClass>,
+}
+
+/// A commit that touched a matched file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Attribution {
+    /// Abbreviated commit SHA
+    pub sha: String,
+    /// Commit author name
+    pub author: String,
+}
+
+impl CheckMatch {
+    /// `[ID] file`, plus the ack group when the check has one and a
+    /// label for vendored files
+    fn heading(&self) -> String {
+        let mut heading = format!("[{}] {}", self.id, self.file);
+        if let Some(group) = &self.group {
+            let _ = write!(heading, "  (group: {group})");
+        }
+        if self.vendored {
+            heading.push_str("  (vendored)");
+        }
+        heading
+    }
+
+    /// Heading, message, owner, and who introduced the change (when known)
+    fn render_item(&self) {
+        println!("  {}", self.heading());
+        println!("          {}", self.message);
+        if !self.code_owners.is_empty() {
+            println!("          ack by an owner: {}", self.code_owners.join(", "));
+        }
+        if self.stale_digest.is_some() {
+            println!("          changed since it was acknowledged: re-ack for this file");
+        }
+        if let Some(owner) = &self.owner {
+            println!("          contact {owner}");
+        }
+        if !self.introduced_by.is_empty() {
+            let commits: Vec<String> =
+                self.introduced_by.iter().map(|a| format!("{} ({})", a.sha, a.author)).collect();
+            println!("          introduced by: {}", commits.join(", "));
+        }
+        println!();
+    }
+
+    /// Render matches sharing a message and severity as one block: the
+    /// check IDs and files collapsed into the heading, the message once
+    fn render_group(group: &[&Self]) {
+        let [first, rest @ ..] = group else {
+            return;
+        };
+        if rest.is_empty() {
+            first.render_item();
+            return;
+        }
+        let ids = distinct(group.iter().map(|m| m.id.as_str()));
+        let files = distinct(group.iter().map(|m| m.file.as_str()));
+        let vendored = if group.iter().all(|m| m.vendored) {
+            "  (vendored)"
+        } else {
+            ""
+        };
+        println!("  [{}] {}{vendored}", ids.join(", "), files.join(", "));
+        println!("          {}", first.message);
+        let owners = distinct(group.iter().filter_map(|m| m.owner.as_deref()));
+        if !owners.is_empty() {
+            println!("          contact {}", owners.join(", "));
+        }
+        let commits = distinct(
+            group
+                .iter()
+                .flat_map(|m| &m.introduced_by)
+                .map(|a| format!("{} ({})", a.sha, a.author)),
+        );
+        if !commits.is_empty() {
+            println!("          introduced by: {}", commits.join(", "));
+        }
+        println!();
+    }
+}
+
+/// Group matches by (message, severity), in order of first appearance
+fn group_by_message(items: &[CheckMatch]) -> Vec<Vec<&CheckMatch>> {
+    let mut groups: Vec<Vec<&CheckMatch>> = Vec::new();
+    for item in items {
+        match groups
+            .iter_mut()
+            .find(|g| g[0].message == item.message && g[0].severity == item.severity)
+        {
+            Some(group) => group.push(item),
+            None => groups.push(vec![item]),
+        }
+    }
+    groups
+}
+
+/// Values in order of first appearance, without repeats
+fn distinct<T: PartialEq>(values: impl IntoIterator<Item = T>) -> Vec<T> {
+    let mut out = Vec::new();
+    for value in values {
+        if !out.contains(&value) {
+            out.push(value);
+        }
+    }
+    out
+}
+
+/// A check that was considered for a file but did not fire
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CheckMiss {
+    /// The check ID
+    pub id: String,
+    /// The file it was considered for
+    pub file: String,
+    /// The check target pattern
+    pub target: String,
+    /// Why it did not fire
+    pub reason: String,
+    /// Config file defining the check, relative to the repo root
+    #[serde(default)]
+    pub defined_in: String,
 }
 
 /// Result of a check list operation
@@ -122,7 +392,7 @@ pub struct CheckInfo {
     /// Check message
     pub message: String,
     /// Severity level
-    pub severity: String,
+    pub severity: Severity,
     /// Source file containing this check
     pub source_file: String,
 }
@@ -148,6 +418,55 @@ pub struct OperationResult {
 }
 
 impl CheckResult {
+    /// `Ok` unless unacknowledged blocking checks fail the run, otherwise
+    /// [`Error::CheckBlocked`] naming each of them once
+    pub fn ensure_passed(&self) -> crate::Result<()> {
+        if self.passed || self.blocking.is_empty() {
+            return Ok(());
+        }
+        let mut ids: Vec<String> = Vec::new();
+        for item in &self.blocking {
+            if !ids.contains(&item.id) {
+                ids.push(item.id.clone());
+            }
+        }
+        Err(Error::CheckBlocked { ids })
+    }
+
+    /// What changed from `previous` to this result
+    #[must_use]
+    pub fn compare(&self, previous: &Self) -> CheckComparison {
+        let refs = |lists: &[&Vec<CheckMatch>]| -> BTreeSet<MatchRef> {
+            lists
+                .iter()
+                .flat_map(|list| list.iter())
+                .map(|m| MatchRef {
+                    id: m.id.clone(),
+                    file: m.file.clone(),
+                })
+                .collect()
+        };
+        let files = |result: &Self| -> BTreeSet<String> {
+            refs(&[&result.blocking, &result.warnings, &result.acknowledged])
+                .into_iter()
+                .map(|r| r.file)
+                .collect()
+        };
+        let (before, now) = (files(previous), files(self));
+        CheckComparison {
+            newly_blocking: refs(&[&self.blocking])
+                .difference(&refs(&[&previous.blocking]))
+                .cloned()
+                .collect(),
+            newly_satisfied: refs(&[&previous.blocking, &previous.warnings])
+                .difference(&refs(&[&self.blocking, &self.warnings]))
+                .cloned()
+                .collect(),
+            files_added: now.difference(&before).cloned().collect(),
+            files_removed: before.difference(&now).cloned().collect(),
+        }
+    }
+
     /// Render the result based on output mode
     pub fn render(&self, mode: OutputMode) {
         match mode {
@@ -157,35 +476,83 @@ impl CheckResult {
     }
 
     fn render_human(&self) {
+        for run in self.hooks.iter().filter(|r| r.stage == "pre_check") {
+            run.render();
+            println!();
+        }
+        self.render_summary();
+        for run in self.hooks.iter().filter(|r| r.stage != "pre_check") {
+            println!();
+            run.render();
+        }
+        if self.enforced && self.hooks.iter().any(HookRun::blocks) {
+            println!("\nBLOCKED: a [hooks] script failed with on_failure = \"block\"");
+        }
+
+        if let Some(comparison) = &self.comparison {
+            comparison.render();
+        }
+
+        if !self.misses.is_empty() {
+            println!("\nConsidered but not fired:");
+            let mut file = "";
+            for m in &self.misses {
+                if m.file != file {
+                    file = &m.file;
+                    println!("  {file}");
+                }
+                println!("    [{}] {} - {} ({})", m.id, m.target, m.reason, m.defined_in);
+            }
+        }
+    }
+
+    fn render_summary(&self) {
+        let range = self.range.as_deref();
         if self.files_checked == 0 {
-            println!("No staged changes.");
+            println!(
+                "{}",
+                range.map_or_else(
+                    || "No staged changes.".to_string(),
+                    |r| format!("No changes in {r}.")
+                )
+            );
             return;
         }
+        let subject = range
+            .map_or_else(|| "staged file(s)".to_string(), |r| format!("file(s) changed in {r}"));
+        let proceed = if range.is_some() {
+            "Range may proceed."
+        } else {
+            "Commit may proceed."
+        };
 
-        println!("Checking {} staged file(s)...\n", self.files_checked);
+        println!("Checking {} {subject}...\n", self.files_checked);
 
         if self.blocking.is_empty() && self.warnings.is_empty() && self.acknowledged.is_empty() {
-            println!("No checks apply. Commit may proceed.");
+            println!("No checks apply. {proceed}");
             return;
         }
 
         if !self.warnings.is_empty() {
             println!("Warnings:");
-            for m in &self.warnings {
-                println!("  [{}] {}", m.id, m.file);
-                println!("          {}\n", m.message);
+            for group in group_by_message(&self.warnings) {
+                CheckMatch::render_group(&group);
             }
         }
 
         if self.blocking.is_empty() {
-            println!("All checks acknowledged. Commit may proceed.");
+            println!("All checks acknowledged. {proceed}");
         } else if self.enforced {
             println!("Needs answers:");
-            for m in &self.blocking {
-                println!("  [{}] {}", m.id, m.file);
-                println!("          {}\n", m.message);
+            for group in group_by_message(&self.blocking) {
+                CheckMatch::render_group(&group);
             }
-            println!("NEEDS ANSWERS: {} check(s) on this commit\n", self.blocking.len());
+            let scope = if self.range.is_some() {
+                "in this range"
+            } else {
+                "on this commit"
+            };
+            println!("NEEDS ANSWERS: {} check(s) {scope}\n", self.blocking.len());
             println!("To answer:      noslop ack <check-id> -m \"your acknowledgment\"");
             println!(
                 "Example:        noslop ack {} -m \"reviewed and verified\"",
@@ -193,9 +560,8 @@ impl CheckResult {
             );
         } else {
             println!("Guidance (an agent would pause here):");
-            for m in &self.blocking {
-                println!("  [{}] {}", m.id, m.file);
-                println!("          {}\n", m.message);
+            for group in group_by_message(&self.blocking) {
+                CheckMatch::render_group(&group);
             }
             println!("Human committer - proceeding without acknowledgment.");
         }
@@ -204,6 +570,183 @@ impl CheckResult {
     fn render_json(&self) {
         println!("{}", serde_json::to_string_pretty(self).unwrap_or_default());
     }
+
+    /// Markdown summary of the blocking checks for `.noslop/pending.md`:
+    /// per check, the files it matched, what to verify, and how to answer
+    #[must_use]
+    pub fn pending_markdown(&self) -> String {
+        let mut out = String::from("# Pending noslop checks\n\n");
+        out.push_str("This commit needs answers before it can proceed. This file is\n");
+        out.push_str("removed once `noslop check` passes.\n");
+
+        let mut ids: Vec<&str> = self.blocking.iter().map(|m| m.id.as_str()).collect();
+        ids.dedup();
+        for id in ids {
+            let items: Vec<&CheckMatch> = self.blocking.iter().filter(|m| m.id == id).collect();
+            let first = items[0];
+            let _ = writeln!(out, "\n## {id}\n\nVerify: {}\n\nFiles:", first.message);
+            for m in &items {
+                let _ = writeln!(out, "- `{}`", m.file);
+            }
+            if let Some(owner) = &first.owner {
+                let _ = writeln!(out, "\nContact: {owner}");
+            }
+            let answer = first.group.as_deref().unwrap_or(id);
+            let _ = writeln!(
+                out,
+                "\nAnswer once verified:\n\n```sh\nnoslop ack {answer} -m \"<what you checked>\"\n```"
+            );
+        }
+        out
+    }
+
+    /// One instruction per blocking check for an agent's commit loop
+    /// (`check --prompt`): what to verify, where, and the exact ack command,
+    /// with the message `requirement` for its severity and who must sign
+    #[must_use]
+    pub fn prompts(&self, requirement: impl Fn(Severity) -> AckRequirement) -> Vec<CheckPrompt> {
+        let mut ids: Vec<&str> = Vec::new();
+        for m in &self.blocking {
+            if !ids.contains(&m.id.as_str()) {
+                ids.push(&m.id);
+            }
+        }
+        ids.into_iter()
+            .map(|id| {
+                let items: Vec<&CheckMatch> = self.blocking.iter().filter(|m| m.id == id).collect();
+                let first = items[0];
+                let files: Vec<String> = items.iter().map(|m| m.file.clone()).collect();
+                let answer = first.group.as_deref().unwrap_or(id);
+                let req = requirement(first.severity);
+                let placeholder = if req.require_reference {
+                    "<what you verified, citing a URL or ticket>"
+                } else {
+                    "<what you verified>"
+                };
+                let command = format!("noslop ack {answer} -m \"{placeholder}\"");
+
+                let verify = first.message.trim();
+                let stop = if verify.ends_with(['.', '?', '!']) {
+                    ""
+                } else {
+                    "."
+                };
+                let mut prompt = format!(
+                    "Check {id} blocks this commit. Verify: {verify}{stop} Files: {}. \
+                     Once verified, run `{command}`.",
+                    files.join(", ")
+                );
+                if req.min_length > 0 {
+                    let _ = write!(
+                        prompt,
+                        " The message needs at least {} characters.",
+                        req.min_length
+                    );
+                }
+                if req.require_reference {
+                    prompt.push_str(" It must cite a URL or ticket (e.g. ABC-123, #123).");
+                }
+                if !first.code_owners.is_empty() {
+                    let _ = write!(
+                        prompt,
+                        " Only an ack made or approved by a code owner counts: {}.",
+                        first.code_owners.join(", ")
+                    );
+                }
+                if first.requires_human {
+                    let _ = write!(
+                        prompt,
+                        " An agent's ack counts only after a human runs `noslop approve {id}`."
+                    );
+                }
+                CheckPrompt {
+                    id: id.to_string(),
+                    files,
+                    prompt,
+                    command,
+                }
+            })
+            .collect()
+    }
+
+    /// GitHub Actions workflow commands, one per blocking or warning item,
+    /// so each shows inline on the pull request diff
+    ///
+    /// Blocking items are errors when enforced, warnings otherwise.
+    /// `line_of` gives the first changed line of a file, when known; the
+    /// annotation sits on the file alone without it.
+    #[must_use]
+    pub fn github_annotations(&self, line_of: impl Fn(&str) -> Option<usize>) -> String {
+        let level = if self.enforced { "error" } else { "warning" };
+        let items = self
+            .blocking
+            .iter()
+            .map(|m| (level, m))
+            .chain(self.warnings.iter().map(|m| ("warning", m)));
+        let mut out = String::new();
+        for (level, m) in items {
+            let mut properties = format!("file={}", workflow_escape(&m.file, true));
+            if let Some(line) = line_of(&m.file) {
+                let _ = write!(properties, ",line={line}");
+            }
+            let title = format!("noslop {}", m.id);
+            let answer = m.group.as_deref().unwrap_or(&m.id);
+            let mut message =
+                format!("{}\nAcknowledge with: noslop ack {answer} -m \"...\"", m.message);
+            if m.stale_digest.is_some() {
+                message.push_str("\nThe file changed since it was acknowledged.");
+            }
+            let _ = writeln!(
+                out,
+                "::{level} {properties},title={}::{}",
+                workflow_escape(&title, true),
+                workflow_escape(&message, false)
+            );
+        }
+        out
+    }
+
+    /// Render [`Self::prompts`]: the prompt text, or JSON with a `prompt`
+    /// string per blocking check
+    pub fn render_prompts(
+        &self,
+        mode: OutputMode,
+        requirement: impl Fn(Severity) -> AckRequirement,
+    ) {
+        let prompts = self.prompts(requirement);
+        if mode == OutputMode::Json {
+            let report = serde_json::json!({
+                "passed": self.passed,
+                "enforced": self.enforced,
+                "prompts": prompts,
+            });
+            println!("{}", serde_json::to_string_pretty(&report).unwrap_or_default());
+            return;
+        }
+        if prompts.is_empty() {
+            println!("Nothing blocks this commit; no acknowledgment is needed.");
+            return;
+        }
+        for (i, p) in prompts.iter().enumerate() {
+            if i > 0 {
+                println!();
+            }
+            println!("{}", p.prompt);
+        }
+    }
+}
+
+/// A blocking check phrased as an instruction (`check --prompt`)
+#[derive(Debug, Clone, Serialize)]
+pub struct CheckPrompt {
+    /// Check ID
+    pub id: String,
+    /// Files the check matched
+    pub files: Vec<String>,
+    /// What to verify and how to answer, as one paragraph
+    pub prompt: String,
+    /// The acknowledgment command to run once verified
+    pub command: String,
 }
 
 impl CheckListResult {
@@ -223,7 +766,7 @@ impl CheckListResult {
 
         println!("Checks:\n");
         for c in &self.checks {
-            println!("  [{}] {}", c.severity.to_uppercase(), c.target);
+            println!("  [{}] {}", c.severity.to_string().to_uppercase(), c.target);
             println!("  ID: {}", c.id);
             println!("  {}\n", c.message);
         }
@@ -268,3 +811,150 @@ impl OperationResult {
         }
     }
 }
+
+/// Layouts for a [`Table`] listing
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TableFormat {
+    /// Columns padded to line up, with a header row
+    #[default]
+    Table,
+    /// An array of objects keyed by column name
+    Json,
+    /// Tab-separated values with a header row
+    Tsv,
+}
+
+impl std::str::FromStr for TableFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "table" => Ok(Self::Table),
+            "json" => Ok(Self::Json),
+            "tsv" => Ok(Self::Tsv),
+            _ => Err(format!("Invalid format: {s}. Use: table, json, tsv")),
+        }
+    }
+}
+
+/// Rows of named columns, shared by the list-style commands so each one
+/// offers the same `--format` and `--columns`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Table {
+    columns: Vec<String>,
+    rows: Vec<Vec<String>>,
+}
+
+impl Table {
+    /// An empty table with these columns
+    #[must_use]
+    pub fn new(columns: &[&str]) -> Self {
+        Self {
+            columns: columns.iter().map(ToString::to_string).collect(),
+            rows: Vec::new(),
+        }
+    }
+
+    /// Append a row, one value per column in order
+    pub fn push(&mut self, row: Vec<String>) {
+        self.rows.push(row);
+    }
+
+    /// Keep only `wanted`, in that order
+    ///
+    /// # Errors
+    ///
+    /// Returns an error naming the available columns if one is unknown.
+    pub fn select(self, wanted: &[String]) -> Result<Self, String> {
+        let mut indices = Vec::new();
+        for name in wanted {
+            let index = self.columns.iter().position(|c| c == name).ok_or_else(|| {
+                format!("Unknown column: {name}. Use: {}", self.columns.join(", "))
+            })?;
+            indices.push(index);
+        }
+        Ok(Self {
+            columns: indices.iter().map(|&i| self.columns[i].clone()).collect(),
+            rows: self
+                .rows
+                .iter()
+                .map(|row| {
+                    indices.iter().map(|&i| row.get(i).cloned().unwrap_or_default()).collect()
+                })
+                .collect(),
+        })
+    }
+
+    /// Render in `format`, ending with a newline
+    #[must_use]
+    pub fn render(&self, format: TableFormat) -> String {
+        match format {
+            TableFormat::Table => self.render_table(),
+            TableFormat::Json => {
+                let rows: Vec<serde_json::Map<String, serde_json::Value>> = self
+                    .rows
+                    .iter()
+                    .map(|row| {
+                        self.columns
+                            .iter()
+                            .zip(row)
+                            .map(|(c, v)| (c.clone(), serde_json::Value::from(v.as_str())))
+                            .collect()
+                    })
+                    .collect();
+                format!("{}\n", serde_json::to_string_pretty(&rows).unwrap_or_default())
+            },
+            TableFormat::Tsv => {
+                // Tabs and line breaks inside a value would split the record
+                let clean = |v: &String| v.replace(['\t', '\n', '\r'], " ");
+                let mut out = self.columns.join("\t");
+                out.push('\n');
+                for row in &self.rows {
+                    out.push_str(&row.iter().map(clean).collect::<Vec<_>>().join("\t"));
+                    out.push('\n');
+                }
+                out
+            },
+        }
+    }
+
+    fn render_table(&self) -> String {
+        let mut widths: Vec<usize> = self.columns.iter().map(|c| c.chars().count()).collect();
+        for row in &self.rows {
+            for (width, value) in widths.iter_mut().zip(row) {
+                *width = (*width).max(value.chars().count());
+            }
+        }
+        let mut out = String::new();
+        let header: Vec<String> = self.columns.iter().map(|c| c.to_uppercase()).collect();
+        for row in std::iter::once(&header).chain(&self.rows) {
+            let mut line = String::new();
+            for (i, value) in row.iter().enumerate() {
+                if i + 1 == row.len() {
+                    line.push_str(value);
+                } else {
+                    let _ = write!(line, "{value:<width$}  ", width = widths[i]);
+                }
+            }
+            let _ = writeln!(out, "{}", line.trim_end());
+        }
+        out
+    }
+}
+
+/// Escape a workflow command value: `%`, CR and LF always, and `:` and
+/// `,` inside a property
+fn workflow_escape(value: &str, property: bool) -> String {
+    let mut out = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '%' => out.push_str("%25"),
+            '\r' => out.push_str("%0D"),
+            '\n' => out.push_str("%0A"),
+            ':' if property => out.push_str("%3A"),
+            ',' if property => out.push_str("%2C"),
+            c => out.push(c),
+        }
+    }
+    out
+}
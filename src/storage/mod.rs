@@ -15,3 +15,86 @@ pub use crate::adapters::trailer::{TrailerAckStore, append_trailers};
 pub fn ack_store() -> Box<dyn AcknowledgmentStore> {
     Box::new(TrailerAckStore::new())
 }
+
+/// Name of the backend [`ack_store`] persists acknowledgments to
+pub const ACTIVE_BACKEND: &str = "trailers";
+
+/// One place acknowledgments are kept, as found in this clone
+#[derive(Debug, Clone, Copy, serde::Serialize)]
+pub struct BackendStatus {
+    /// Backend name
+    pub name: &'static str,
+    /// Where it keeps its data
+    pub location: &'static str,
+    /// Whether [`ack_store`] writes to it
+    pub active: bool,
+    /// Records it currently holds, when countable without a history walk
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub records: Option<usize>,
+    /// Records that could not be read
+    pub unreadable: usize,
+}
+
+/// Every storage backend plus the work still pending between them
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct StorageReport {
+    /// Name of the active backend
+    pub active: &'static str,
+    /// Each backend with data or a role in this clone
+    pub backends: Vec<BackendStatus>,
+    /// Ledger records waiting for `noslop compact` to fold them into history
+    pub pending_compaction: usize,
+    /// Ledger records written by a newer noslop than this one
+    pub newer_schema: usize,
+}
+
+impl StorageReport {
+    /// Names of the backends that hold countable records in this clone
+    #[must_use]
+    pub fn populated(&self) -> Vec<&'static str> {
+        self.backends
+            .iter()
+            .filter(|b| b.records.unwrap_or(0) > 0)
+            .map(|b| b.name)
+            .collect()
+    }
+}
+
+/// Inspect every acknowledgment backend in this clone
+///
+/// # Errors
+///
+/// Returns an error if an existing ledger file cannot be read.
+pub fn report() -> anyhow::Result<StorageReport> {
+    let (staged, staged_unreadable) =
+        FileStore::load_staged_acks().map_or((0, 1), |acks| (acks.len(), 0));
+    let ledger = crate::adapters::ledger::inventory()?;
+    Ok(StorageReport {
+        active: ACTIVE_BACKEND,
+        backends: vec![
+            BackendStatus {
+                name: "staged",
+                location: ".noslop/staged-acks.json",
+                active: false,
+                records: Some(staged),
+                unreadable: staged_unreadable,
+            },
+            BackendStatus {
+                name: ACTIVE_BACKEND,
+                location: "Noslop-Ack commit trailers",
+                active: true,
+                records: None,
+                unreadable: 0,
+            },
+            BackendStatus {
+                name: "ledger",
+                location: ".noslop/acks, .noslop/history.jsonl",
+                active: false,
+                records: Some(ledger.pending + ledger.history),
+                unreadable: ledger.unreadable,
+            },
+        ],
+        pending_compaction: ledger.pending,
+        newer_schema: ledger.newer_schema,
+    })
+}
@@ -0,0 +1,27 @@
+//! Tests for the git version control adapter
+
+use noslop::adapters::GitVersionControl;
+use noslop::core::ports::VersionControl;
+use tempfile::TempDir;
+
+#[test]
+fn test_bare_repository_root_is_the_git_dir() {
+    let temp = TempDir::new().unwrap();
+    let bare = temp.path().join("repo.git");
+    std::process::Command::new("git")
+        .args(["init", "--bare", "-b", "trunk"])
+        .arg(&bare)
+        .output()
+        .unwrap();
+
+    let vcs = GitVersionControl::new(bare.clone());
+    assert!(vcs.is_bare());
+    assert_eq!(vcs.repo_root().unwrap().canonicalize().unwrap(), bare.canonicalize().unwrap());
+    assert_eq!(vcs.current_branch().unwrap().as_deref(), Some("trunk"));
+
+    let work = temp.path().join("work");
+    std::process::Command::new("git").args(["init"]).arg(&work).output().unwrap();
+    let vcs = GitVersionControl::new(work.clone());
+    assert!(!vcs.is_bare());
+    assert_eq!(vcs.repo_root().unwrap().canonicalize().unwrap(), work.canonicalize().unwrap());
+}
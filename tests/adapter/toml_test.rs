@@ -1,6 +1,10 @@
 //! Tests for TOML adapter (check repository)
 
-use noslop::adapters::toml::{CheckEntry, find_noslop_files, load_file};
+use noslop::adapters::toml::{
+    CheckEntry, RampPhase, When, find_noslop_files, find_noslop_files_within, find_template,
+    format_noslop_file, load_file, parse_file,
+};
+use noslop::core::models::{ChangeClass, CommitCondition, Severity, TargetKind};
 use std::fs;
 use tempfile::TempDir;
 
@@ -37,7 +41,101 @@ severity = "block"
     assert_eq!(file.checks.len(), 1);
     assert_eq!(file.checks[0].target, "*.rs");
     assert_eq!(file.checks[0].message, "Rust code must be reviewed");
-    assert_eq!(file.checks[0].severity, "block");
+    assert_eq!(file.checks[0].severity, Severity::Block);
+}
+
+#[test]
+fn test_parse_commit_check() {
+    let temp = TempDir::new().unwrap();
+    let path = temp.path().join(".noslop.toml");
+    fs::write(
+        &path,
+        "[[check]]\nid = \"BIG-1\"\ntarget_kind = \"commit\"\nmin_files = 20\nmerge = true\nmessage = \"Link the design doc\"\n",
+    )
+    .unwrap();
+
+    let file = load_file(&path).unwrap();
+    assert_eq!(file.checks[0].target_kind, TargetKind::Commit);
+    assert_eq!(file.checks[0].commit.min_files, Some(20));
+    assert_eq!(file.checks[0].commit.merge, Some(true));
+
+    // A commit check needs a condition, a file check a target
+    fs::write(&path, "[[check]]\ntarget_kind = \"commit\"\nmessage = \"Always\"\n").unwrap();
+    let err = load_file(&path).unwrap_err().to_string();
+    assert!(err.contains("needs min_files, min_added_lines, or merge"), "{err}");
+    fs::write(&path, "[[check]]\nmessage = \"Nowhere\"\n").unwrap();
+    let err = load_file(&path).unwrap_err().to_string();
+    assert!(err.contains("has no target"), "{err}");
+}
+
+#[test]
+fn test_parse_severity_overrides() {
+    let temp = TempDir::new().unwrap();
+    let path = temp.path().join(".noslop.toml");
+    fs::write(
+        &path,
+        "[[check]]\ntarget = \"*.rs\"\nmessage = \"Reviewed?\"\n\n[[check.override]]\nscope = \"examples/**\"\nseverity = \"warn\"\n",
+    )
+    .unwrap();
+
+    let file = load_file(&path).unwrap();
+    let entry = &file.checks[0];
+    assert_eq!(entry.overrides.len(), 1);
+    let base = std::path::Path::new("/repo");
+    assert_eq!(entry.severity_for("examples/demo.rs", base, base), Severity::Warn);
+    assert_eq!(entry.severity_for("src/lib.rs", base, base), Severity::Block);
+
+    // Rewrites keep the override with its check
+    let rewritten = noslop::adapters::toml::format_noslop_file(&file);
+    assert!(rewritten.contains("[[check.override]]\nscope = \"examples/**\"\nseverity = \"warn\""));
+}
+
+#[test]
+fn test_includes_merge_with_origin() {
+    let temp = TempDir::new().unwrap();
+    let path = temp.path().join(".noslop.toml");
+    fs::create_dir_all(temp.path().join("noslop")).unwrap();
+    fs::write(
+        &path,
+        "include = [\"noslop/security.toml\"]\n\n[[check]]\ntarget = \"*.rs\"\nmessage = \"Root\"\n",
+    )
+    .unwrap();
+    fs::write(
+        temp.path().join("noslop/security.toml"),
+        "include = [\"../shared.toml\"]\n\n[[check]]\ntarget = \"src/auth/\"\nmessage = \"Auth\"\n",
+    )
+    .unwrap();
+    fs::write(
+        temp.path().join("shared.toml"),
+        "[[check]]\ntarget = \"*\"\nmessage = \"Shared\"\n",
+    )
+    .unwrap();
+
+    let file = load_file(&path).unwrap();
+    let messages: Vec<&str> = file.checks.iter().map(|c| c.message.as_str()).collect();
+    assert_eq!(messages, vec!["Root", "Auth", "Shared"]);
+    assert!(file.checks[0].origin.is_none());
+    assert_eq!(
+        file.checks[1].origin.as_deref(),
+        Some(temp.path().join("noslop/security.toml").as_path())
+    );
+    assert_eq!(
+        file.checks[2].origin.as_deref(),
+        Some(temp.path().join("shared.toml").as_path())
+    );
+}
+
+#[test]
+fn test_include_cycle_is_an_error() {
+    let temp = TempDir::new().unwrap();
+    let path = temp.path().join(".noslop.toml");
+    fs::write(&path, "include = [\"a.toml\"]\n").unwrap();
+    fs::write(temp.path().join("a.toml"), "include = [\"b.toml\"]\n").unwrap();
+    fs::write(temp.path().join("b.toml"), "include = [\"./a.toml\"]\n").unwrap();
+
+    let err = load_file(&path).unwrap_err().to_string();
+    assert!(err.contains("Include cycle"), "{err}");
+    assert!(err.contains("b.toml -> ") && err.ends_with("a.toml"), "{err}");
 }
 
 #[test]
@@ -62,8 +160,8 @@ severity = "warn"
 
     let file = load_file(&path).unwrap();
     assert_eq!(file.checks.len(), 2);
-    assert_eq!(file.checks[0].severity, "block");
-    assert_eq!(file.checks[1].severity, "warn");
+    assert_eq!(file.checks[0].severity, Severity::Block);
+    assert_eq!(file.checks[1].severity, Severity::Warn);
 }
 
 #[test]
@@ -122,6 +220,25 @@ fn test_find_noslop_files_walks_up_hierarchy() {
     assert_eq!(files.len(), 1);
 }
 
+#[test]
+fn test_root_marker_stops_discovery() {
+    let temp = TempDir::new().unwrap();
+    fs::write(temp.path().join(".noslop.toml"), "# outer").unwrap();
+    let pkg = temp.path().join("pkg");
+    let src = pkg.join("src");
+    fs::create_dir_all(&src).unwrap();
+    fs::write(pkg.join(".noslop.toml"), "root = true\n").unwrap();
+    fs::write(src.join(".noslop.toml"), "# inner").unwrap();
+
+    let expected = vec![pkg.join(".noslop.toml"), src.join(".noslop.toml")];
+    assert_eq!(find_noslop_files(&src), expected);
+    assert_eq!(find_noslop_files_within(&src.join("lib.rs"), temp.path()), expected);
+
+    // Without the marker the outer file applies again
+    fs::write(pkg.join(".noslop.toml"), "# pkg").unwrap();
+    assert_eq!(find_noslop_files_within(&src.join("lib.rs"), temp.path()).len(), 3);
+}
+
 #[test]
 fn test_find_noslop_files_none_found() {
     let temp = TempDir::new().unwrap();
@@ -138,9 +255,24 @@ fn test_check_entry_fields() {
     let entry = CheckEntry {
         id: Some("TEST-1".to_string()),
         target: "*.rs".to_string(),
+        target_kind: TargetKind::File,
+        commit: CommitCondition::default(),
         message: "Review".to_string(),
-        severity: "block".to_string(),
+        severity: Severity::Block,
         tags: vec!["security".to_string()],
+        on: vec![],
+        requires_human: false,
+        group: None,
+        owner: None,
+        overrides: Vec::new(),
+        disabled: false,
+        include_vendored: false,
+        codeowners: Vec::new(),
+        ramp: None,
+        must_match: None,
+        attest_within_days: None,
+        when: When::default(),
+        origin: None,
     };
 
     assert_eq!(entry.id, Some("TEST-1".to_string()));
@@ -153,11 +285,295 @@ fn test_check_entry_optional_id() {
     let entry = CheckEntry {
         id: None,
         target: "*.py".to_string(),
+        target_kind: TargetKind::File,
+        commit: CommitCondition::default(),
         message: "Review Python".to_string(),
-        severity: "warn".to_string(),
+        severity: Severity::Warn,
         tags: vec![],
+        on: vec![],
+        requires_human: false,
+        group: None,
+        owner: None,
+        overrides: Vec::new(),
+        disabled: false,
+        include_vendored: false,
+        codeowners: Vec::new(),
+        ramp: None,
+        must_match: None,
+        attest_within_days: None,
+        when: When::default(),
+        origin: None,
     };
 
     assert!(entry.id.is_none());
     assert!(entry.tags.is_empty());
 }
+
+#[test]
+fn test_check_entry_changes_from_names_each_field() {
+    let old = parse_file("[[check]]\nid = \"RS-1\"\ntarget = \"*.rs\"\nmessage = \"Reviewed?\"\n")
+        .unwrap();
+    let new = parse_file(
+        "[[check]]\nid = \"RS-1\"\ntarget = \"src/*.rs\"\nmessage = \"Reviewed?\"\n\
+         severity = \"warn\"\ndisabled = true\n",
+    )
+    .unwrap();
+
+    assert_eq!(
+        new.checks[0].changes_from(&old.checks[0]),
+        vec![
+            "target: *.rs -> src/*.rs".to_string(),
+            "severity: block -> warn".to_string(),
+            "disabled: false -> true".to_string(),
+        ]
+    );
+    assert!(old.checks[0].changes_from(&old.checks[0]).is_empty());
+}
+
+#[test]
+fn test_ramp_warns_until_its_date_then_blocks() {
+    let file = parse_file(
+        "[[check]]\nid = \"RS-1\"\ntarget = \"*.rs\"\nmessage = \"Reviewed?\"\n\
+         ramp = { warn_until = \"2025-09-01\" }\n\n\
+         [[check]]\nid = \"RS-2\"\ntarget = \"*.rs\"\nmessage = \"Reviewed?\"\n\
+         ramp = { warn_days = 14 }\n",
+    )
+    .unwrap();
+    let date = |s: &str| chrono::NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap();
+
+    let fixed = file.checks[0].ramp.unwrap();
+    assert_eq!(fixed.phase(None, date("2025-08-31")), Some(RampPhase::Warn(date("2025-09-01"))));
+    assert_eq!(
+        fixed.phase(None, date("2025-09-01")),
+        Some(RampPhase::Block(date("2025-09-01")))
+    );
+    assert_eq!(fixed.apply(Severity::Block, None, date("2025-08-31")), Severity::Warn);
+    assert_eq!(fixed.apply(Severity::Info, None, date("2025-08-31")), Severity::Info);
+    assert_eq!(fixed.to_toml(), "{ warn_until = \"2025-09-01\" }");
+
+    // warn_days count from the first commit; uncommitted, they have not started
+    let relative = file.checks[1].ramp.unwrap();
+    let introduced = Some(date("2025-08-01"));
+    assert_eq!(relative.apply(Severity::Block, introduced, date("2025-08-14")), Severity::Warn);
+    assert_eq!(relative.apply(Severity::Block, introduced, date("2025-08-15")), Severity::Block);
+    assert_eq!(relative.apply(Severity::Block, None, date("2030-01-01")), Severity::Warn);
+
+    assert!(
+        parse_file("[[check]]\ntarget = \"*\"\nmessage = \"m\"\nramp = { until = 1 }\n").is_err()
+    );
+}
+
+#[test]
+fn test_alias_table_survives_a_rewrite() {
+    let file = parse_file(
+        "[alias]\nship = [\"check --ci\", \"!git push\"]\n\n\
+         [[check]]\ntarget = \"*.rs\"\nmessage = \"Reviewed?\"\n",
+    )
+    .unwrap();
+    assert_eq!(file.alias["ship"], vec!["check --ci", "!git push"]);
+
+    let rewritten = parse_file(&format_noslop_file(&file)).unwrap();
+    assert_eq!(rewritten.alias, file.alias);
+    assert_eq!(rewritten.checks.len(), 1);
+}
+
+#[test]
+fn test_base_branches_survive_a_rewrite() {
+    let file = parse_file(
+        "[git]\nbase_branch = \"trunk\"\n\n[git.base_branches]\n\"hotfix/*\" = \"production\"\n",
+    )
+    .unwrap();
+    assert_eq!(file.git.base_branch.as_deref(), Some("trunk"));
+    assert_eq!(file.git.base_branches["hotfix/*"], "production");
+
+    let rewritten = parse_file(&format_noslop_file(&file)).unwrap();
+    assert_eq!(rewritten.git.base_branch, file.git.base_branch);
+    assert_eq!(rewritten.git.base_branches, file.git.base_branches);
+}
+
+#[test]
+fn test_composite_targets_survive_a_rewrite() {
+    let file = parse_file(
+        "[[check]]\ntarget = [\"src/**\", \"!src/generated/**\", \"proto/**\"]\nmessage = \"m\"\n\n\
+         [[check.override]]\nscope = [\"src/legacy/**\", \"!src/legacy/core/**\"]\nseverity = \"warn\"\n",
+    )
+    .unwrap();
+    let entry = &file.checks[0];
+    assert_eq!(entry.target, r#"["src/**","!src/generated/**","proto/**"]"#);
+    assert_eq!(entry.overrides[0].scope, r#"["src/legacy/**","!src/legacy/core/**"]"#);
+
+    let rewritten = parse_file(&format_noslop_file(&file)).unwrap();
+    assert_eq!(rewritten.checks[0].target, entry.target);
+    assert_eq!(rewritten.checks[0].overrides[0].scope, entry.overrides[0].scope);
+
+    assert!(parse_file("[[check]]\ntarget = []\nmessage = \"m\"\n").is_err());
+    assert!(parse_file("[[check]]\ntarget = [\"src/**\", \"!\"]\nmessage = \"m\"\n").is_err());
+}
+
+#[test]
+fn test_review_mapping_survives_a_rewrite() {
+    let file = parse_file(
+        "[reviews]\ngerrit_url = \"https://review.example.com\"\n\n\
+         [reviews.labels]\n\"@org/security\" = [\"SEC-1\", \"SEC-2\"]\n\"Security-Review\" = [\"SEC-1\"]\n",
+    )
+    .unwrap();
+    assert_eq!(file.reviews.labels["@org/security"], vec!["SEC-1", "SEC-2"]);
+
+    let rewritten = parse_file(&format_noslop_file(&file)).unwrap();
+    assert_eq!(rewritten.reviews.gerrit_url, file.reviews.gerrit_url);
+    assert_eq!(rewritten.reviews.labels, file.reviews.labels);
+}
+
+#[test]
+fn test_must_match_survives_a_rewrite_and_is_validated() {
+    let file = parse_file(
+        "[[check]]\ntarget = \"src/api/handlers/\"\nmessage = \"Instrumented?\"\n\
+         must_match = '#\\[tracing::instrument\\]'\n",
+    )
+    .unwrap();
+    let entry = &file.checks[0];
+    assert_eq!(entry.must_match.as_deref(), Some(r"#\[tracing::instrument\]"));
+    assert!(entry.content_satisfies(Some("#[tracing::instrument]\nasync fn get() {}\n")));
+    assert!(!entry.content_satisfies(Some("async fn get() {}\n")));
+    assert!(!entry.content_satisfies(None));
+
+    let rewritten = parse_file(&format_noslop_file(&file)).unwrap();
+    assert_eq!(rewritten.checks[0].must_match, entry.must_match);
+
+    assert!(
+        parse_file("[[check]]\ntarget = \"*\"\nmessage = \"m\"\nmust_match = \"(\"\n").is_err()
+    );
+}
+
+#[test]
+fn test_when_change_kind_takes_one_class_or_a_list() {
+    let file = parse_file(
+        "[[check]]\ntarget = \"src/\"\nmessage = \"m\"\nwhen.change_kind = \"new-function\"\n\n\
+         [[check]]\ntarget = \"src/\"\nmessage = \"n\"\n\
+         when = { change_kind = [\"signature-change\", \"dependency-bump\"] }\n",
+    )
+    .unwrap();
+    assert_eq!(file.checks[0].when.change_kind, [ChangeClass::NewFunction]);
+    assert_eq!(
+        file.checks[1].when.change_kind,
+        [ChangeClass::SignatureChange, ChangeClass::DependencyBump]
+    );
+    let rewritten = parse_file(&format_noslop_file(&file)).unwrap();
+    assert_eq!(rewritten.checks[0].when, file.checks[0].when);
+    assert_eq!(rewritten.checks[1].when, file.checks[1].when);
+
+    assert!(
+        parse_file("[[check]]\ntarget = \"*\"\nmessage = \"m\"\nwhen.change_kind = \"typo\"\n")
+            .is_err()
+    );
+}
+
+#[test]
+fn test_supply_chain_template_scopes_to_dependency_changes() {
+    let template = find_template("supply-chain").unwrap();
+    let checks = template.checks().unwrap();
+    assert_eq!(checks.len(), 1);
+    assert!(checks[0].when.dependency_change);
+    assert_eq!(checks[0].severity, Severity::Block);
+
+    let file =
+        parse_file("[[check]]\ntarget = \"*\"\nmessage = \"m\"\nwhen.dependency_change = true\n")
+            .unwrap();
+    let rewritten = parse_file(&format_noslop_file(&file)).unwrap();
+    assert!(rewritten.checks[0].when.dependency_change);
+}
+
+#[test]
+fn test_format_noslop_file_round_trips_every_section() {
+    let source = r#"
+root = true
+include = ["noslop/security.toml"]
+vendored = ["third_party/"]
+
+[project]
+prefix = "ACM"
+
+[remote]
+url = "https://ingest.example.com"
+token_env = "ACME_TOKEN"
+
+[discover]
+runner = "claude -p"
+
+[metrics]
+enabled = true
+
+[pending]
+enabled = true
+
+[reminders]
+enabled = false
+
+[git]
+submodules = "skip"
+base_branch = "develop"
+
+[git.base_branches]
+"release/*" = "release"
+
+[ack]
+in_message = true
+
+[ack.block]
+min_length = 20
+require_reference = true
+
+[hooks]
+pre_check = "./gen.sh"
+on_failure = "block"
+
+[alias]
+ship = ["check --ci", "!git push"]
+
+[reviews]
+gerrit_url = "https://review.example.com"
+
+[reviews.labels]
+"@org/security" = ["ACM-1"]
+
+[[check]]
+id = "ACM-1"
+target = "src/"
+message = "Reviewed?"
+severity = "warn"
+when.change_kind = "signature-change"
+when.dependency_change = true
+
+[[check.override]]
+scope = "src/legacy/"
+severity = "info"
+"#;
+    let file = parse_file(source).unwrap();
+    let written = format_noslop_file(&file);
+    let reread = parse_file(&written).unwrap();
+    assert_eq!(format_noslop_file(&reread), written);
+
+    assert!(reread.root);
+    assert_eq!(reread.include, ["noslop/security.toml"]);
+    assert_eq!(reread.vendored, ["third_party/"]);
+    assert_eq!(reread.project.prefix, "ACM");
+    assert_eq!(reread.remote.url.as_deref(), Some("https://ingest.example.com"));
+    assert_eq!(reread.remote.token_env.as_deref(), Some("ACME_TOKEN"));
+    assert_eq!(reread.discover.runner.as_deref(), Some("claude -p"));
+    assert!(reread.metrics.enabled && reread.pending.enabled && !reread.reminders.enabled);
+    assert_eq!(reread.git.base_branch.as_deref(), Some("develop"));
+    assert_eq!(reread.git.base_branches["release/*"], "release");
+    assert!(reread.ack.in_message);
+    assert_eq!(reread.ack.block.min_length, 20);
+    assert!(reread.ack.block.require_reference);
+    assert_eq!(reread.hooks.pre_check.as_deref(), Some("./gen.sh"));
+    assert_eq!(reread.hooks.on_failure, Severity::Block);
+    assert_eq!(reread.alias["ship"], ["check --ci", "!git push"]);
+    assert_eq!(reread.reviews.gerrit_url.as_deref(), Some("https://review.example.com"));
+    assert_eq!(reread.reviews.labels["@org/security"], ["ACM-1"]);
+    let check = &reread.checks[0];
+    assert_eq!(check.id.as_deref(), Some("ACM-1"));
+    assert_eq!(check.when, file.checks[0].when);
+    assert_eq!(check.overrides.len(), 1);
+    assert_eq!(check.overrides[0].severity, Severity::Info);
+}
@@ -13,12 +13,21 @@ pub struct TempGitRepo {
 impl TempGitRepo {
     /// Create a new temporary git repository
     pub fn new() -> Self {
+        Self::init(&["init"])
+    }
+
+    /// Create a new temporary git repository whose first branch is `branch`
+    pub fn with_branch(branch: &str) -> Self {
+        Self::init(&["init", "-b", branch])
+    }
+
+    fn init(args: &[&str]) -> Self {
         let temp_dir = TempDir::new().expect("Failed to create temp directory");
         let path = temp_dir.path().to_path_buf();
 
         // Initialize git repo
         Command::new("git")
-            .args(["init"])
+            .args(args)
             .current_dir(&path)
             .output()
             .expect("Failed to init git repo");
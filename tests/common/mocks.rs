@@ -34,7 +34,7 @@ impl Default for MockCheckRepository {
 }
 
 impl CheckRepository for MockCheckRepository {
-    fn find_for_files(&self, files: &[String]) -> anyhow::Result<Vec<(Check, String)>> {
+    fn find_for_files(&self, files: &[String]) -> noslop::Result<Vec<(Check, String)>> {
         let checks = self.checks.borrow();
         let mut results = Vec::new();
         for check in checks.iter() {
@@ -51,7 +51,7 @@ impl CheckRepository for MockCheckRepository {
         Ok(results)
     }
 
-    fn add(&self, target: &str, message: &str, severity: Severity) -> anyhow::Result<String> {
+    fn add(&self, target: &str, message: &str, severity: Severity) -> noslop::Result<String> {
         let id = format!("MOCK-{}", self.checks.borrow().len() + 1);
         let check = Check::new(
             Some(id.clone()),
@@ -63,12 +63,12 @@ impl CheckRepository for MockCheckRepository {
         Ok(id)
     }
 
-    fn remove(&self, id: &str) -> anyhow::Result<()> {
+    fn remove(&self, id: &str) -> noslop::Result<()> {
         self.checks.borrow_mut().retain(|c| c.id != id);
         Ok(())
     }
 
-    fn list(&self) -> anyhow::Result<Vec<Check>> {
+    fn list(&self) -> noslop::Result<Vec<Check>> {
         Ok(self.checks.borrow().clone())
     }
 }
@@ -99,16 +99,16 @@ impl Default for MockAckStore {
 }
 
 impl AcknowledgmentStore for MockAckStore {
-    fn stage(&self, ack: &Acknowledgment) -> anyhow::Result<()> {
+    fn stage(&self, ack: &Acknowledgment) -> noslop::Result<()> {
         self.staged.borrow_mut().push(ack.clone());
         Ok(())
     }
 
-    fn staged(&self) -> anyhow::Result<Vec<Acknowledgment>> {
+    fn staged(&self) -> noslop::Result<Vec<Acknowledgment>> {
         Ok(self.staged.borrow().clone())
     }
 
-    fn clear_staged(&self) -> anyhow::Result<()> {
+    fn clear_staged(&self) -> noslop::Result<()> {
         self.staged.borrow_mut().clear();
         Ok(())
     }
@@ -121,7 +121,7 @@ impl AcknowledgmentStore for MockAckStore {
             .join("\n")
     }
 
-    fn parse_from_commit(&self, _commit_sha: &str) -> anyhow::Result<Vec<Acknowledgment>> {
+    fn parse_from_commit(&self, _commit_sha: &str) -> noslop::Result<Vec<Acknowledgment>> {
         // Mock: return empty for simplicity
         Ok(Vec::new())
     }
@@ -159,7 +159,7 @@ impl Default for MockVersionControl {
 }
 
 impl VersionControl for MockVersionControl {
-    fn staged_files(&self) -> anyhow::Result<Vec<String>> {
+    fn staged_files(&self) -> noslop::Result<Vec<String>> {
         Ok(self.staged_files.clone())
     }
 
@@ -167,11 +167,11 @@ impl VersionControl for MockVersionControl {
         self.repo_name.clone()
     }
 
-    fn repo_root(&self) -> anyhow::Result<PathBuf> {
+    fn repo_root(&self) -> noslop::Result<PathBuf> {
         Ok(self.repo_root.clone())
     }
 
-    fn install_hooks(&self, _force: bool) -> anyhow::Result<()> {
+    fn install_hooks(&self, _force: bool) -> noslop::Result<()> {
         Ok(())
     }
 
@@ -179,7 +179,7 @@ impl VersionControl for MockVersionControl {
         path.starts_with(&self.repo_root)
     }
 
-    fn current_branch(&self) -> anyhow::Result<Option<String>> {
+    fn current_branch(&self) -> noslop::Result<Option<String>> {
         Ok(Some("main".to_string()))
     }
 }
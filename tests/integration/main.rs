@@ -1115,3 +1115,111 @@ fn ack_omits_tokens_for_unknown_actors() {
     assert!(!content.contains("tokens_to_answer"), "no fake spend: {content}");
     assert!(!content.contains("\"model\""), "no fake model: {content}");
 }
+
+// =============================================================================
+// SUBMODULE AND WORKTREE TESTS
+// =============================================================================
+
+/// Helper to run git with an identity, for repos `init_git_repo` didn't set up
+fn git_in(path: &std::path::Path, args: &[&str]) {
+    let output = Command::new("git")
+        .args(["-c", "user.name=Test User", "-c", "user.email=test@example.com"])
+        .args(["-c", "protocol.file.allow=always"])
+        .args(args)
+        .current_dir(path)
+        .output()
+        .expect("Failed to run git");
+    assert!(
+        output.status.success(),
+        "git {args:?}: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+/// A superproject with `vendor/lib` as a submodule whose pointer bump
+/// (adding `b.rs`) is staged
+fn setup_submodule_bump(temp: &TempDir) -> std::path::PathBuf {
+    let lib = temp.path().join("lib");
+    let app = temp.path().join("app");
+    fs::create_dir_all(&lib).unwrap();
+    fs::create_dir_all(&app).unwrap();
+
+    init_git_repo(&lib);
+    fs::write(lib.join("a.rs"), "fn a() {}\n").unwrap();
+    git_in(&lib, &["add", "a.rs"]);
+    git_in(&lib, &["commit", "-m", "lib"]);
+
+    init_git_repo(&app);
+    git_in(&app, &["submodule", "add", lib.to_str().unwrap(), "vendor/lib"]);
+    git_in(&app, &["commit", "-m", "add submodule"]);
+
+    let sub = app.join("vendor/lib");
+    fs::write(sub.join("b.rs"), "fn b() {}\n").unwrap();
+    git_in(&sub, &["add", "b.rs"]);
+    git_in(&sub, &["commit", "-m", "bump"]);
+    git_in(&app, &["add", "vendor/lib"]);
+    app
+}
+
+#[test]
+fn test_submodule_recurse_fires_on_files_inside() {
+    let temp = TempDir::new().unwrap();
+    let app = setup_submodule_bump(&temp);
+    let check = "[[check]]\nid = \"VEND-1\"\ntarget = \"vendor/**/*.rs\"\nmessage = \"Vendored code reviewed?\"\n";
+
+    fs::write(app.join(".noslop.toml"), format!("[git]\nsubmodules = \"recurse\"\n\n{check}"))
+        .unwrap();
+    noslop()
+        .arg("check")
+        .env("NOSLOP_ACTOR", "claude-code")
+        .current_dir(&app)
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("[VEND-1] vendor/lib/b.rs"))
+        .stdout(predicate::str::contains("a.rs").not());
+
+    fs::write(app.join(".noslop.toml"), format!("[git]\nsubmodules = \"skip\"\n\n{check}"))
+        .unwrap();
+    noslop()
+        .arg("check")
+        .env("NOSLOP_ACTOR", "claude-code")
+        .current_dir(&app)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No staged changes"));
+}
+
+#[test]
+fn test_worktree_gets_hooks_and_its_own_state() {
+    let temp = TempDir::new().unwrap();
+    let main = temp.path().join("main");
+    let wt = temp.path().join("wt");
+    fs::create_dir_all(&main).unwrap();
+
+    init_git_repo(&main);
+    fs::write(main.join("README.md"), "# repo\n").unwrap();
+    git_add(&main, "README.md");
+    git_commit(&main, "initial");
+    git_in(&main, &["worktree", "add", wt.to_str().unwrap(), "-b", "feature"]);
+
+    // `.git` is a file in a linked worktree; hooks resolve via git
+    noslop().arg("init").current_dir(&wt).assert().success();
+    assert!(main.join(".git/hooks/pre-commit").exists());
+
+    fs::write(
+        wt.join(".noslop.toml"),
+        "[[check]]\nid = \"RS-1\"\ntarget = \"*.rs\"\nmessage = \"Tests?\"\n",
+    )
+    .unwrap();
+    fs::write(wt.join("lib.rs"), "fn main() {}\n").unwrap();
+    git_add(&wt, "lib.rs");
+
+    noslop()
+        .args(["ack", "RS-1", "-m", "added tests"])
+        .env("NOSLOP_ACTOR", "human")
+        .current_dir(&wt)
+        .assert()
+        .success();
+    assert!(wt.join(".noslop/staged-acks.json").exists());
+    assert!(!main.join(".noslop/staged-acks.json").exists());
+}
@@ -7,6 +7,10 @@
 #[allow(dead_code)]
 mod common;
 
+#[path = "common/git_repo.rs"]
+#[allow(dead_code)]
+mod git_repo;
+
 #[path = "unit/cli_test.rs"]
 mod cli_test;
 
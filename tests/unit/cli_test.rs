@@ -4,6 +4,8 @@ use assert_cmd::cargo;
 use predicates::prelude::*;
 use tempfile::TempDir;
 
+use crate::git_repo::TempGitRepo;
+
 fn noslop() -> assert_cmd::Command {
     assert_cmd::Command::new(cargo::cargo_bin!("noslop"))
 }
@@ -308,6 +310,121 @@ fn test_check_remove() {
     assert!(content.contains("Second check"));
 }
 
+#[test]
+fn test_check_disable_skips_without_deleting() {
+    let temp = TempDir::new().unwrap();
+    std::process::Command::new("git")
+        .args(["init"])
+        .current_dir(temp.path())
+        .output()
+        .unwrap();
+    std::fs::write(
+        temp.path().join(".noslop.toml"),
+        "[[check]]\nid = \"RS-1\"\ntarget = \"*.rs\"\nmessage = \"Reviewed?\"\n",
+    )
+    .unwrap();
+    std::fs::write(temp.path().join("a.rs"), "fn a() {}\n").unwrap();
+    std::process::Command::new("git")
+        .args(["add", "a.rs"])
+        .current_dir(temp.path())
+        .output()
+        .unwrap();
+
+    noslop()
+        .args(["check", "disable", "RS-1"])
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Disabled RS-1"));
+    let content = std::fs::read_to_string(temp.path().join(".noslop.toml")).unwrap();
+    assert!(content.contains("id = \"RS-1\""));
+    assert!(content.contains("disabled = true"));
+
+    noslop()
+        .args(["check", "list"])
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Reviewed?  (disabled)"));
+
+    noslop()
+        .args(["check", "--explain-misses"])
+        .env("NOSLOP_ACTOR", "claude-code")
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("[RS-1] *.rs - check is disabled"))
+        .stderr(predicate::str::contains("skipped 1 disabled check(s): RS-1"));
+
+    noslop()
+        .args(["check", "enable", "RS-1"])
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Enabled RS-1"));
+    noslop()
+        .arg("check")
+        .env("NOSLOP_ACTOR", "claude-code")
+        .current_dir(temp.path())
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("[RS-1] a.rs"));
+
+    noslop()
+        .args(["check", "disable", "NOPE-1"])
+        .current_dir(temp.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("No check with ID 'NOPE-1'"));
+}
+
+#[test]
+fn test_check_show_reports_provenance() {
+    let temp = TempDir::new().unwrap();
+    let git = |author: &str, args: &[&str]| {
+        std::process::Command::new("git")
+            .args(args)
+            .current_dir(temp.path())
+            .env("GIT_AUTHOR_NAME", author)
+            .env("GIT_AUTHOR_EMAIL", "t@example.com")
+            .env("GIT_COMMITTER_NAME", author)
+            .env("GIT_COMMITTER_EMAIL", "t@example.com")
+            .output()
+            .unwrap()
+    };
+    git("alice", &["init"]);
+    std::fs::write(
+        temp.path().join(".noslop.toml"),
+        "[[check]]\nid = \"RS-1\"\ntarget = \"*.rs\"\nmessage = \"Reviewed?\"\n",
+    )
+    .unwrap();
+    git("alice", &["add", "-A"]);
+    git("alice", &["commit", "-m", "add RS-1"]);
+    std::fs::write(
+        temp.path().join(".noslop.toml"),
+        "[[check]]\nid = \"RS-1\"\ntarget = \"*.rs\"\nmessage = \"Reviewed?\"\nseverity = \"warn\"\n",
+    )
+    .unwrap();
+    git("bob", &["commit", "-am", "soften RS-1"]);
+    std::fs::create_dir(temp.path().join("sub")).unwrap();
+
+    noslop()
+        .args(["check", "show", "RS-1"])
+        .current_dir(temp.path().join("sub"))
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Defined in: .noslop.toml"))
+        .stdout(predicate::str::contains("by alice"))
+        .stdout(predicate::str::contains("bob              severity: block -> warn"));
+
+    noslop()
+        .args(["--json", "check", "show", "RS-1"])
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"created_by\":\"alice\""));
+}
+
 #[test]
 fn test_version_command() {
     noslop()
@@ -317,6 +434,34 @@ fn test_version_command() {
         .stdout(predicate::str::contains("noslop v"));
 }
 
+#[test]
+fn test_help_topic() {
+    noslop()
+        .arg("help-topic")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("trailers"));
+
+    // Piped output skips the pager; text mode drops markdown markers
+    noslop()
+        .args(["help-topic", "trailers"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Noslop-Ack: <check-id> | <message> | <actor>"))
+        .stdout(predicate::str::contains("# Trailers").not());
+    noslop()
+        .args(["help-topic", "trailers", "--format", "markdown"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("# Trailers"));
+
+    noslop()
+        .args(["help-topic", "nope"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Available: checks, acks, trailers, agents"));
+}
+
 #[test]
 fn test_json_output_version() {
     noslop()
@@ -660,105 +805,80 @@ fn test_stats_tracks_fires_acks_and_no_action() {
 
 #[test]
 fn test_check_diff_base_reconciles_ledger_not_local_state() {
-    let temp = TempDir::new().unwrap();
-    let git = |args: &[&str]| {
-        std::process::Command::new("git")
-            .args(args)
-            .current_dir(temp.path())
-            .env("GIT_AUTHOR_NAME", "t")
-            .env("GIT_AUTHOR_EMAIL", "t@t")
-            .env("GIT_COMMITTER_NAME", "t")
-            .env("GIT_COMMITTER_EMAIL", "t@t")
-            .output()
-            .unwrap()
-    };
-    git(&["init", "-b", "main"]);
+    let repo = TempGitRepo::with_branch("main");
 
-    std::fs::write(
-        temp.path().join(".noslop.toml"),
+    repo.write_file(
+        ".noslop.toml",
         "[project]\nprefix = \"TST\"\n\n[[check]]\nid = \"TST-1\"\ntarget = \"*.rs\"\nmessage = \"Reviewed?\"\nseverity = \"block\"\n",
-    )
-    .unwrap();
-    git(&["add", "-A"]);
-    git(&["commit", "-m", "base"]);
+    );
+    repo.git(&["add", "-A"]);
+    repo.commit("base");
 
     // Branch commits a matching file with NO ledger record (--no-verify style)
-    git(&["checkout", "-b", "feature"]);
-    std::fs::write(temp.path().join("lib.rs"), "fn main() {}\n").unwrap();
-    git(&["add", "lib.rs"]);
-    git(&["commit", "-m", "change without ack"]);
+    repo.git(&["checkout", "-b", "feature"]);
+    repo.write_file("lib.rs", "fn main() {}\n");
+    repo.stage("lib.rs");
+    repo.commit("change without ack");
 
     noslop()
         .args(["check", "--ci", "--diff-base", "main"])
-        .current_dir(temp.path())
+        .current_dir(repo.path())
         .assert()
         .failure()
-        .stdout(predicate::str::contains("TST-1"));
+        .stdout(predicate::str::contains("TST-1"))
+        .stderr(predicate::str::contains("Error: 1 unacknowledged check(s): TST-1"));
 
     // Ack writes a ledger record; commit it — now the branch carries proof
     noslop()
         .args(["ack", "TST-1", "-m", "reviewed the rust change"])
         .env("NOSLOP_ACTOR", "claude-code")
-        .current_dir(temp.path())
+        .current_dir(repo.path())
         .assert()
         .success();
-    git(&["add", "-A"]);
-    git(&["commit", "-m", "ack"]);
+    repo.git(&["add", "-A"]);
+    repo.commit("ack");
 
     noslop()
         .args(["check", "--ci", "--diff-base", "main"])
-        .current_dir(temp.path())
+        .current_dir(repo.path())
         .assert()
         .success();
 }
 
 #[test]
 fn test_envelope_wraps_check_with_touched_ledger_records() {
-    let temp = TempDir::new().unwrap();
-    let git = |args: &[&str]| {
-        std::process::Command::new("git")
-            .args(args)
-            .current_dir(temp.path())
-            .env("GIT_AUTHOR_NAME", "t")
-            .env("GIT_AUTHOR_EMAIL", "t@t")
-            .env("GIT_COMMITTER_NAME", "t")
-            .env("GIT_COMMITTER_EMAIL", "t@t")
-            .output()
-            .unwrap()
-    };
-    git(&["init", "-b", "main"]);
+    let repo = TempGitRepo::with_branch("main");
 
-    std::fs::write(
-        temp.path().join(".noslop.toml"),
+    repo.write_file(
+        ".noslop.toml",
         "[project]\nprefix = \"TST\"\n\n[[check]]\nid = \"TST-1\"\ntarget = \"*.rs\"\nmessage = \"Reviewed?\"\nseverity = \"block\"\n\n[[check]]\nid = \"TST-2\"\ntarget = \"*.md\"\nmessage = \"Docs current?\"\nseverity = \"block\"\n",
-    )
-    .unwrap();
-    git(&["add", "-A"]);
-    git(&["commit", "-m", "base"]);
+    );
+    repo.git(&["add", "-A"]);
+    repo.commit("base");
 
     // Branch touches only the .rs check; ack both so the ledger holds a
     // record the run touched (TST-1) and one it did not (TST-2)
-    git(&["checkout", "-b", "feature"]);
-    std::fs::write(temp.path().join("lib.rs"), "fn main() {}\n").unwrap();
-    git(&["add", "lib.rs"]);
+    repo.git(&["checkout", "-b", "feature"]);
+    repo.write_file("lib.rs", "fn main() {}\n");
+    repo.stage("lib.rs");
     for (id, msg) in [("TST-1", "verified the rust change"), ("TST-2", "unrelated ack")] {
         noslop()
             .args(["ack", id, "-m", msg])
             .env("NOSLOP_ACTOR", "claude-code")
-            .current_dir(temp.path())
+            .current_dir(repo.path())
             .assert()
             .success();
     }
-    git(&["add", "-A"]);
-    git(&["commit", "-m", "change with acks"]);
+    repo.git(&["add", "-A"]);
+    repo.commit("change with acks");
 
     // The Action flow: check --json to a file, then envelope wraps it
     let check_out = noslop()
         .args(["--json", "check", "--ci", "--diff-base", "main"])
-        .current_dir(temp.path())
+        .current_dir(repo.path())
         .output()
         .unwrap();
-    std::fs::write(temp.path().join("noslop-check.json"), &check_out.stdout).unwrap();
+    std::fs::write(repo.path().join("noslop-check.json"), &check_out.stdout).unwrap();
 
     let envelope_out = noslop()
         .args([
@@ -772,7 +892,7 @@ fn test_envelope_wraps_check_with_touched_ledger_records() {
             "--base",
             "main",
         ])
-        .current_dir(temp.path())
+        .current_dir(repo.path())
         .output()
         .unwrap();
     assert!(envelope_out.status.success());
@@ -811,46 +931,33 @@ fn test_envelope_rejects_non_check_payload() {
 
 #[test]
 fn test_ack_embeds_fire_evidence_from_local_events() {
-    let temp = TempDir::new().unwrap();
-    let git = |args: &[&str]| {
-        std::process::Command::new("git")
-            .args(args)
-            .current_dir(temp.path())
-            .env("GIT_AUTHOR_NAME", "t")
-            .env("GIT_AUTHOR_EMAIL", "t@t")
-            .env("GIT_COMMITTER_NAME", "t")
-            .env("GIT_COMMITTER_EMAIL", "t@t")
-            .output()
-            .unwrap()
-    };
-    git(&["init", "-b", "main"]);
+    let repo = TempGitRepo::with_branch("main");
 
-    std::fs::write(
-        temp.path().join(".noslop.toml"),
+    repo.write_file(
+        ".noslop.toml",
         "[project]\nprefix = \"TST\"\n\n[[check]]\nid = \"TST-1\"\ntarget = \"*.rs\"\nmessage = \"Reviewed?\"\nseverity = \"block\"\n",
-    )
-    .unwrap();
-    std::fs::write(temp.path().join("lib.rs"), "fn main() {}\n").unwrap();
-    git(&["add", "-A"]);
+    );
+    repo.write_file("lib.rs", "fn main() {}\n");
+    repo.git(&["add", "-A"]);
 
     // The gate fires TST-1 and logs a local fire event (agent actor, local mode)
     noslop()
         .args(["check"])
         .env("NOSLOP_ACTOR", "claude-code")
-        .current_dir(temp.path())
+        .current_dir(repo.path())
         .assert()
         .failure();
-    assert!(temp.path().join(".noslop/events.jsonl").exists());
+    assert!(repo.path().join(".noslop/events.jsonl").exists());
 
     // The ack copies the fire's tree oid + timestamp into the ledger record
     noslop()
         .args(["ack", "TST-1", "-m", "verified"])
         .env("NOSLOP_ACTOR", "claude-code")
-        .current_dir(temp.path())
+        .current_dir(repo.path())
         .assert()
         .success();
 
-    let acks_dir = temp.path().join(".noslop/acks");
+    let acks_dir = repo.path().join(".noslop/acks");
     let record_path = std::fs::read_dir(&acks_dir).unwrap().next().unwrap().unwrap().path();
     let record: serde_json::Value =
         serde_json::from_str(&std::fs::read_to_string(record_path).unwrap()).unwrap();
@@ -921,32 +1028,20 @@ fn test_remote_checks_gate_and_monitor_stays_silent() {
         ]}"#,
     );
 
-    let temp = TempDir::new().unwrap();
-    let git = |args: &[&str]| {
-        std::process::Command::new("git")
-            .args(args)
-            .current_dir(temp.path())
-            .env("GIT_AUTHOR_NAME", "t")
-            .env("GIT_AUTHOR_EMAIL", "t@t")
-            .env("GIT_COMMITTER_NAME", "t")
-            .env("GIT_COMMITTER_EMAIL", "t@t")
-            .output()
-            .unwrap()
-    };
-    git(&["init", "-b", "main"]);
+    let repo = TempGitRepo::with_branch("main");
     std::fs::write(
-        temp.path().join(".noslop.toml"),
+        repo.path().join(".noslop.toml"),
         format!("[project]\nprefix = \"TST\"\n\n[remote]\nurl = \"{url}\"\n"),
     )
     .unwrap();
-    std::fs::write(temp.path().join("lib.rs"), "fn main() {}\n").unwrap();
-    git(&["add", "-A"]);
+    repo.write_file("lib.rs", "fn main() {}\n");
+    repo.git(&["add", "-A"]);
 
     let out = noslop()
         .args(["--json", "check"])
         .env("NOSLOP_ACTOR", "claude-code")
         .env("NOSLOP_CLOUD_TOKEN", "nslp_test")
-        .current_dir(temp.path())
+        .current_dir(repo.path())
         .output()
         .unwrap();
     // The enforce-state org check blocks the agent
@@ -1061,3 +1156,3523 @@ fn test_remote_fetch_failure_falls_back_to_stale_cache_with_age() {
     assert!(result["check_set_age_seconds"].as_u64().unwrap() >= 3600);
     assert!(String::from_utf8_lossy(&out.stderr).contains("using cached set"));
 }
+
+#[test]
+fn test_check_fires_on_declared_change_kinds() {
+    let repo = TempGitRepo::with_branch("main");
+
+    repo.write_file(
+        ".noslop.toml",
+        "[[check]]\nid = \"TST-1\"\ntarget = \"*.rs\"\nmessage = \"Reviewed?\"\n\n[[check]]\nid = \"TST-2\"\ntarget = \"api/\"\nmessage = \"Deprecation notice published?\"\non = [\"delete\", \"rename\"]\n",
+    );
+    std::fs::create_dir_all(repo.path().join("api")).unwrap();
+    repo.write_file("api/users.py", "def users(): ...\n");
+    repo.write_file("lib.rs", "fn main() {}\n");
+    repo.git(&["add", "-A"]);
+    repo.commit("base");
+
+    // Modifying a delete/rename-only target stays silent
+    repo.write_file("api/users.py", "def users(): return []\n");
+    repo.git(&["add", "-A"]);
+    noslop()
+        .arg("check")
+        .env("NOSLOP_ACTOR", "claude-code")
+        .current_dir(repo.path())
+        .assert()
+        .success();
+    repo.commit("edit");
+
+    // Renaming it fires TST-2; deleting an add/modify-only target does not
+    repo.git(&["mv", "api/users.py", "api/members.py"]);
+    repo.git(&["rm", "-q", "lib.rs"]);
+    noslop()
+        .arg("check")
+        .env("NOSLOP_ACTOR", "claude-code")
+        .current_dir(repo.path())
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("TST-2"))
+        .stdout(predicate::str::contains("TST-1").not());
+}
+
+#[test]
+fn test_rename_out_of_a_directory_fires_its_config() {
+    let repo = TempGitRepo::with_branch("main");
+
+    std::fs::create_dir_all(repo.path().join("legacy")).unwrap();
+    std::fs::create_dir_all(repo.path().join("src")).unwrap();
+    repo.write_file(
+        "legacy/.noslop.toml",
+        "[[check]]\nid = \"LEG-1\"\ntarget = \"*.py\"\nmessage = \"Callers of the legacy module migrated?\"\non = [\"delete\", \"rename\"]\n",
+    );
+    repo.write_file("legacy/billing.py", "def charge(): ...\n");
+    repo.git(&["add", "-A"]);
+    repo.commit("base");
+
+    // The new path has no config of its own; the old directory's still applies
+    repo.git(&["mv", "legacy/billing.py", "src/billing.py"]);
+    noslop()
+        .arg("check")
+        .env("NOSLOP_ACTOR", "claude-code")
+        .current_dir(repo.path())
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("LEG-1"));
+
+    // And when the move is judged as a commit
+    repo.git(&["commit", "--no-verify", "-m", "move"]);
+    noslop()
+        .args(["verify-commit", "HEAD"])
+        .env("NOSLOP_ACTOR", "claude-code")
+        .current_dir(repo.path())
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("LEG-1"));
+}
+
+#[test]
+fn test_search_finds_checks_and_ack_answers() {
+    let temp = TempDir::new().unwrap();
+
+    std::process::Command::new("git")
+        .args(["init"])
+        .current_dir(temp.path())
+        .output()
+        .unwrap();
+
+    std::fs::write(
+        temp.path().join(".noslop.toml"),
+        "[[check]]\nid = \"SEC-1\"\ntarget = \"src/auth/*.rs\"\nmessage = \"JWT rotation schedule still documented?\"\n\n[[check]]\nid = \"DOC-1\"\ntarget = \"docs/\"\nmessage = \"Changelog updated?\"\n",
+    )
+    .unwrap();
+
+    noslop()
+        .args(["ack", "SEC-1", "-m", "Rotation moved to 24h, see ADR-7"])
+        .env("NOSLOP_ACTOR", "claude-code")
+        .current_dir(temp.path())
+        .assert()
+        .success();
+
+    noslop()
+        .args(["search", "rotation"])
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("check SEC-1"))
+        .stdout(predicate::str::contains("ADR-7"))
+        .stdout(predicate::str::contains("DOC-1").not());
+
+    noslop()
+        .args(["--json", "search", "changelog"])
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"kind\":\"check\""));
+}
+
+#[test]
+fn test_agent_log_shows_fires_and_acks_per_agent() {
+    let temp = TempDir::new().unwrap();
+
+    std::process::Command::new("git")
+        .args(["init"])
+        .current_dir(temp.path())
+        .output()
+        .unwrap();
+
+    std::fs::write(
+        temp.path().join(".noslop.toml"),
+        "[[check]]\nid = \"TST-1\"\ntarget = \"*.rs\"\nmessage = \"Reviewed?\"\n",
+    )
+    .unwrap();
+    std::fs::write(temp.path().join("lib.rs"), "fn main() {}\n").unwrap();
+    std::process::Command::new("git")
+        .args(["add", "lib.rs"])
+        .current_dir(temp.path())
+        .output()
+        .unwrap();
+
+    noslop()
+        .arg("check")
+        .env("NOSLOP_ACTOR", "claude-code")
+        .current_dir(temp.path())
+        .assert()
+        .failure();
+    noslop()
+        .args(["ack", "TST-1", "-m", "verified the change"])
+        .env("NOSLOP_ACTOR", "claude-code")
+        .current_dir(temp.path())
+        .assert()
+        .success();
+
+    let out = noslop()
+        .args(["agent", "log", "--agent", "claude-code", "--since", "1d"])
+        .current_dir(temp.path())
+        .assert()
+        .success();
+    let stdout = String::from_utf8_lossy(&out.get_output().stdout).to_string();
+    let fired = stdout.find("fired TST-1").expect("fire entry");
+    let acked = stdout.find("acked TST-1").expect("ack entry");
+    assert!(fired < acked, "log must be chronological:\n{stdout}");
+
+    noslop()
+        .args(["agent", "log", "--agent", "cursor"])
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No agent activity recorded."));
+
+    noslop()
+        .args(["agent", "log", "--since", "soon"])
+        .current_dir(temp.path())
+        .assert()
+        .failure();
+
+    for since in ["1д", "99999999999999d", "15000000w"] {
+        noslop()
+            .args(["agent", "log", "--since", since])
+            .current_dir(temp.path())
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains("--since"));
+    }
+}
+
+#[test]
+fn test_requires_human_check_needs_approval() {
+    let temp = TempDir::new().unwrap();
+
+    std::process::Command::new("git")
+        .args(["init"])
+        .current_dir(temp.path())
+        .output()
+        .unwrap();
+
+    std::fs::write(
+        temp.path().join(".noslop.toml"),
+        "[[check]]\nid = \"SEC-1\"\ntarget = \"*.rs\"\nmessage = \"Security reviewed?\"\nrequires_human = true\n",
+    )
+    .unwrap();
+    std::fs::write(temp.path().join("lib.rs"), "fn main() {}\n").unwrap();
+    std::process::Command::new("git")
+        .args(["add", "lib.rs"])
+        .current_dir(temp.path())
+        .output()
+        .unwrap();
+
+    noslop()
+        .args(["ack", "SEC-1", "-m", "no secrets touched"])
+        .env("NOSLOP_ACTOR", "claude-code")
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("noslop approve SEC-1"));
+
+    // The agent's ack alone does not satisfy the gate
+    noslop()
+        .arg("check")
+        .env("NOSLOP_ACTOR", "claude-code")
+        .current_dir(temp.path())
+        .assert()
+        .failure();
+
+    // Agents cannot approve themselves
+    noslop()
+        .args(["approve", "SEC-1"])
+        .env("NOSLOP_ACTOR", "claude-code")
+        .current_dir(temp.path())
+        .assert()
+        .failure();
+
+    noslop()
+        .args(["approve", "SEC-1"])
+        .env("NOSLOP_ACTOR", "human")
+        .current_dir(temp.path())
+        .assert()
+        .success();
+
+    noslop()
+        .arg("check")
+        .env("NOSLOP_ACTOR", "claude-code")
+        .current_dir(temp.path())
+        .assert()
+        .success();
+
+    let record = std::fs::read_dir(temp.path().join(".noslop/acks"))
+        .unwrap()
+        .map(|e| std::fs::read_to_string(e.unwrap().path()).unwrap())
+        .next()
+        .unwrap();
+    assert!(record.contains("\"approved_by\": \"human\""));
+}
+
+#[test]
+fn test_ack_show_diff_prints_matched_diff() {
+    let temp = TempDir::new().unwrap();
+
+    std::process::Command::new("git")
+        .args(["init"])
+        .current_dir(temp.path())
+        .output()
+        .unwrap();
+
+    std::fs::write(
+        temp.path().join(".noslop.toml"),
+        "[[check]]\nid = \"TST-1\"\ntarget = \"*.rs\"\nmessage = \"Reviewed?\"\n",
+    )
+    .unwrap();
+    std::fs::write(temp.path().join("lib.rs"), "fn reviewed_line() {}\n").unwrap();
+    std::fs::write(temp.path().join("notes.md"), "unrelated\n").unwrap();
+    std::process::Command::new("git")
+        .args(["add", "lib.rs", "notes.md"])
+        .current_dir(temp.path())
+        .output()
+        .unwrap();
+
+    noslop()
+        .args(["ack", "TST-1", "-m", "read the diff", "--show-diff"])
+        .env("NOSLOP_ACTOR", "claude-code")
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("+fn reviewed_line() {}"))
+        .stdout(predicate::str::contains("unrelated").not());
+
+    let record = std::fs::read_dir(temp.path().join(".noslop/acks"))
+        .unwrap()
+        .map(|e| std::fs::read_to_string(e.unwrap().path()).unwrap())
+        .next()
+        .unwrap();
+    // The reviewed content is pinned per file; no separate diff digest
+    assert!(record.contains("\"lib.rs\""));
+    assert!(!record.contains("diff_digest"));
+}
+
+#[test]
+fn test_ci_generate_prints_and_writes_pipeline() {
+    let temp = TempDir::new().unwrap();
+
+    std::process::Command::new("git")
+        .args(["init"])
+        .current_dir(temp.path())
+        .output()
+        .unwrap();
+
+    noslop()
+        .args(["ci", "generate", "--provider", "gitlab", "--base", "develop"])
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("noslop check --ci --diff-base"))
+        .stdout(predicate::str::contains("develop"));
+
+    noslop()
+        .args(["ci", "generate", "--write"])
+        .current_dir(temp.path())
+        .assert()
+        .success();
+    let workflow =
+        std::fs::read_to_string(temp.path().join(".github/workflows/noslop.yml")).unwrap();
+    assert!(workflow.contains(&format!("uses: noslop-sh/noslop@v{}", env!("CARGO_PKG_VERSION"))));
+
+    // Never clobbers an existing pipeline
+    noslop()
+        .args(["ci", "generate", "--write"])
+        .current_dir(temp.path())
+        .assert()
+        .failure();
+
+    noslop()
+        .args(["ci", "generate", "--provider", "jenkins"])
+        .current_dir(temp.path())
+        .assert()
+        .failure();
+}
+
+#[test]
+fn test_self_update_rejects_unknown_channel() {
+    noslop()
+        .args(["self-update", "--channel", "beta", "--check-only"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Unknown channel"));
+}
+
+#[test]
+fn test_metrics_recorded_only_when_opted_in() {
+    let temp = TempDir::new().unwrap();
+
+    std::process::Command::new("git")
+        .args(["init"])
+        .current_dir(temp.path())
+        .output()
+        .unwrap();
+
+    let check = "[[check]]\nid = \"RS-1\"\ntarget = \"*.rs\"\nmessage = \"Tests updated?\"\n";
+    std::fs::write(temp.path().join(".noslop.toml"), check).unwrap();
+    std::fs::write(temp.path().join("lib.rs"), "fn main() {}\n").unwrap();
+    std::process::Command::new("git")
+        .args(["add", "lib.rs"])
+        .current_dir(temp.path())
+        .output()
+        .unwrap();
+
+    noslop()
+        .arg("check")
+        .env("NOSLOP_ACTOR", "claude-code")
+        .current_dir(temp.path())
+        .assert()
+        .failure();
+    assert!(!temp.path().join(".noslop/metrics.jsonl").exists());
+
+    std::fs::write(
+        temp.path().join(".noslop.toml"),
+        format!("[metrics]\nenabled = true\n\n{check}"),
+    )
+    .unwrap();
+
+    // Blocked commits exit 1 without an error line
+    noslop()
+        .arg("check")
+        .env("NOSLOP_ACTOR", "claude-code")
+        .current_dir(temp.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Error:").not());
+
+    noslop()
+        .args(["ack", "RS-1", "-m", "added a test"])
+        .env("NOSLOP_ACTOR", "claude-code")
+        .current_dir(temp.path())
+        .assert()
+        .success();
+
+    noslop()
+        .args(["--json", "metrics", "report"])
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"command\":\"check\""))
+        .stdout(predicate::str::contains("\"blocked\":1"))
+        .stdout(predicate::str::contains("\"command\":\"ack\""));
+}
+
+#[test]
+fn test_check_explain_misses_reports_reasons() {
+    let temp = TempDir::new().unwrap();
+
+    std::process::Command::new("git")
+        .args(["init"])
+        .current_dir(temp.path())
+        .output()
+        .unwrap();
+
+    std::fs::write(
+        temp.path().join(".noslop.toml"),
+        "[[check]]\nid = \"PY-1\"\ntarget = \"*.py\"\nmessage = \"Typed?\"\n\n[[check]]\nid = \"RS-1\"\ntarget = \"*.rs\"\nmessage = \"Tests?\"\non = [\"delete\"]\n",
+    )
+    .unwrap();
+    std::fs::write(temp.path().join("lib.rs"), "fn main() {}\n").unwrap();
+    std::process::Command::new("git")
+        .args(["add", "lib.rs"])
+        .current_dir(temp.path())
+        .output()
+        .unwrap();
+
+    noslop()
+        .args(["check", "--explain-misses"])
+        .env("NOSLOP_ACTOR", "claude-code")
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No checks apply"))
+        .stdout(predicate::str::contains("[PY-1] *.py - target does not match"))
+        .stdout(predicate::str::contains("[RS-1] *.rs - 'add' changes are not in its `on` list"));
+
+    // Without the flag, misses stay out of the JSON payload
+    noslop()
+        .args(["--json", "check"])
+        .env("NOSLOP_ACTOR", "claude-code")
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("misses").not());
+}
+
+#[test]
+fn test_check_explain_misses_follows_a_rename_out_of_its_directory() {
+    let repo = TempGitRepo::new();
+    std::fs::create_dir_all(repo.path().join("old")).unwrap();
+    repo.write_file(
+        "old/.noslop.toml",
+        "[[check]]\nid = \"OLD-1\"\ntarget = \"*.py\"\nmessage = \"Typed?\"\n\n[[check]]\nid = \"OLD-2\"\ntarget = \"*.rs\"\nmessage = \"Tests?\"\non = [\"modify\"]\n",
+    );
+    repo.write_file("old/a.rs", "fn a() {}\n");
+    repo.git(&["add", "-A"]);
+    repo.git(&["commit", "-q", "--no-verify", "-m", "init"]);
+    std::fs::create_dir_all(repo.path().join("new")).unwrap();
+    repo.git(&["mv", "old/a.rs", "new/a.rs"]);
+
+    // The moved file still answers to old/'s checks, matched on both paths
+    noslop()
+        .args(["check", "--explain-misses"])
+        .env("NOSLOP_ACTOR", "claude-code")
+        .current_dir(repo.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("[OLD-1] *.py - target does not match"))
+        .stdout(predicate::str::contains(
+            "[OLD-2] *.rs - 'rename' changes are not in its `on` list",
+        ));
+}
+
+#[test]
+fn test_ack_enforces_per_severity_requirements() {
+    let temp = TempDir::new().unwrap();
+
+    std::process::Command::new("git")
+        .args(["init"])
+        .current_dir(temp.path())
+        .output()
+        .unwrap();
+
+    std::fs::write(
+        temp.path().join(".noslop.toml"),
+        "[ack.block]\nmin_length = 20\nrequire_reference = true\n\n[[check]]\nid = \"API-1\"\ntarget = \"*.py\"\nmessage = \"Rate limited?\"\n\n[[check]]\nid = \"DOC-1\"\ntarget = \"*.md\"\nmessage = \"Docs?\"\nseverity = \"warn\"\n",
+    )
+    .unwrap();
+
+    noslop()
+        .args(["ack", "API-1", "-m", "done"])
+        .current_dir(temp.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("[ack.block] requirements"))
+        .stderr(predicate::str::contains("at least 20 required"))
+        .stderr(predicate::str::contains("URL or ticket reference"));
+
+    noslop()
+        .args(["ack", "API-1", "-m", "Added @rate_limit to the router, PLAT-42"])
+        .current_dir(temp.path())
+        .assert()
+        .success();
+
+    // Warn-level acks stay one-liners
+    noslop()
+        .args(["ack", "DOC-1", "-m", "ok"])
+        .current_dir(temp.path())
+        .assert()
+        .success();
+
+    // The requirements survive a config rewrite
+    noslop()
+        .args(["check", "add", "*.rs", "-m", "Tests?"])
+        .current_dir(temp.path())
+        .assert()
+        .success();
+    let config = std::fs::read_to_string(temp.path().join(".noslop.toml")).unwrap();
+    assert!(config.contains("[ack.block]\nmin_length = 20\nrequire_reference = true\n"));
+}
+
+#[test]
+fn test_check_map_shows_coverage_gaps_and_overlaps() {
+    let temp = TempDir::new().unwrap();
+
+    std::process::Command::new("git")
+        .args(["init"])
+        .current_dir(temp.path())
+        .output()
+        .unwrap();
+
+    std::fs::write(
+        temp.path().join(".noslop.toml"),
+        "[[check]]\nid = \"RS-1\"\ntarget = \"*.rs\"\nmessage = \"Tests?\"\n\n[[check]]\nid = \"SEC-1\"\ntarget = \"src/auth.rs\"\nmessage = \"Security?\"\n",
+    )
+    .unwrap();
+    std::fs::create_dir_all(temp.path().join("src")).unwrap();
+    std::fs::create_dir_all(temp.path().join("docs")).unwrap();
+    std::fs::write(temp.path().join("src/auth.rs"), "").unwrap();
+    std::fs::write(temp.path().join("src/lib.rs"), "").unwrap();
+    std::fs::write(temp.path().join("docs/guide.md"), "").unwrap();
+    std::process::Command::new("git")
+        .args(["add", "."])
+        .current_dir(temp.path())
+        .output()
+        .unwrap();
+
+    noslop()
+        .args(["check", "map"])
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("docs/"))
+        .stdout(predicate::str::contains("gap: no checks"))
+        .stdout(predicate::str::contains("2/2 covered  [RS-1 x2, SEC-1 x1]  (1 overlapping)"));
+
+    noslop()
+        .args(["--json", "check", "map"])
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"dir\":\"src\""));
+
+    // Batched over threads: the same map as one pass
+    let single = noslop()
+        .args(["--json", "check", "map"])
+        .current_dir(temp.path())
+        .output()
+        .unwrap();
+    noslop()
+        .args(["--json", "check", "map", "--batch-size", "1", "--threads", "3"])
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout(String::from_utf8(single.stdout).unwrap());
+}
+
+#[test]
+fn test_duplicate_checks_grouped_and_linted() {
+    let temp = TempDir::new().unwrap();
+
+    std::process::Command::new("git")
+        .args(["init"])
+        .current_dir(temp.path())
+        .output()
+        .unwrap();
+
+    let rule = |id: &str| {
+        format!("[[check]]\nid = \"{id}\"\ntarget = \"*.rs\"\nmessage = \"Needs review\"\n")
+    };
+    std::fs::create_dir_all(temp.path().join("sub")).unwrap();
+    std::fs::write(temp.path().join(".noslop.toml"), rule("RS-1")).unwrap();
+    std::fs::write(temp.path().join("sub/.noslop.toml"), rule("SUB-1")).unwrap();
+    std::fs::write(temp.path().join("a.rs"), "").unwrap();
+    std::fs::write(temp.path().join("sub/b.rs"), "").unwrap();
+    std::process::Command::new("git")
+        .args(["add", "."])
+        .current_dir(temp.path())
+        .output()
+        .unwrap();
+
+    // One block for the shared message, listing every file
+    noslop()
+        .arg("check")
+        .env("NOSLOP_ACTOR", "claude-code")
+        .current_dir(temp.path())
+        .assert()
+        .failure()
+        .stdout(predicate::function(|out: &str| out.matches("Needs review").count() == 1))
+        .stdout(predicate::str::contains("[RS-1] a.rs, sub/b.rs"));
+
+    noslop()
+        .args(["check", "lint"])
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("[BLOCK] *.rs -> Needs review"))
+        .stdout(predicate::str::contains("defined in: .noslop.toml, sub/.noslop.toml"));
+}
+
+#[test]
+fn test_message_placeholders_filled_at_check_time() {
+    let temp = TempDir::new().unwrap();
+
+    std::process::Command::new("git")
+        .args(["init", "-b", "feature-x"])
+        .current_dir(temp.path())
+        .output()
+        .unwrap();
+    std::fs::write(
+        temp.path().join(".noslop.toml"),
+        "[[check]]\nid = \"RS-1\"\ntarget = \"*.rs\"\nmessage = \"Review {files} on {branch}\"\n",
+    )
+    .unwrap();
+    std::fs::write(temp.path().join("a.rs"), "").unwrap();
+    std::fs::write(temp.path().join("b.rs"), "").unwrap();
+    std::process::Command::new("git")
+        .args(["add", "."])
+        .current_dir(temp.path())
+        .output()
+        .unwrap();
+
+    noslop()
+        .arg("check")
+        .env("NOSLOP_ACTOR", "claude-code")
+        .current_dir(temp.path())
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("Review a.rs, b.rs on feature-x"));
+}
+
+#[test]
+fn test_onboard_sets_up_and_doctor_verifies() {
+    let temp = TempDir::new().unwrap();
+    let bin_dir = std::path::Path::new(cargo::cargo_bin!("noslop")).parent().unwrap();
+    let path = format!("{}:{}", bin_dir.display(), std::env::var("PATH").unwrap_or_default());
+
+    std::process::Command::new("git")
+        .args(["init"])
+        .current_dir(temp.path())
+        .output()
+        .unwrap();
+    std::fs::create_dir_all(temp.path().join("src")).unwrap();
+    std::fs::write(temp.path().join("src/lib.rs"), "").unwrap();
+    std::fs::write(temp.path().join("src/main.rs"), "").unwrap();
+    std::fs::write(temp.path().join("README.md"), "").unwrap();
+    std::process::Command::new("git")
+        .args(["add", "."])
+        .current_dir(temp.path())
+        .output()
+        .unwrap();
+
+    noslop()
+        .arg("doctor")
+        .env("PATH", &path)
+        .current_dir(temp.path())
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("FAIL no .noslop.toml"));
+
+    noslop()
+        .args(["onboard", "--yes"])
+        .env("PATH", &path)
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("target   = \"*.rs\""))
+        .stdout(predicate::str::contains("Tests cover the change to src/lib.rs?"))
+        .stdout(predicate::str::contains("Noslop-Ack: "))
+        .stdout(predicate::str::contains("All set."));
+
+    noslop()
+        .arg("doctor")
+        .env("PATH", &path)
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("ok   pre-commit hook installed"));
+}
+
+#[test]
+fn test_check_verdict_cached_by_staged_state() {
+    let temp = TempDir::new().unwrap();
+
+    std::process::Command::new("git")
+        .args(["init"])
+        .current_dir(temp.path())
+        .output()
+        .unwrap();
+
+    std::fs::write(
+        temp.path().join(".noslop.toml"),
+        "[[check]]\nid = \"RS-1\"\ntarget = \"*.rs\"\nmessage = \"Tests updated?\"\n",
+    )
+    .unwrap();
+    std::fs::write(temp.path().join("lib.rs"), "fn main() {}\n").unwrap();
+    std::process::Command::new("git")
+        .args(["add", "lib.rs"])
+        .current_dir(temp.path())
+        .output()
+        .unwrap();
+
+    let run_check = || {
+        noslop()
+            .arg("check")
+            .env("NOSLOP_ACTOR", "claude-code")
+            .current_dir(temp.path())
+            .assert()
+    };
+    run_check().failure();
+
+    // Same staged state: the stored verdict is replayed, not recomputed
+    let cache_dir = temp.path().join(".noslop/cache/check");
+    let entry = std::fs::read_dir(&cache_dir).unwrap().next().unwrap().unwrap().path();
+    let cached = std::fs::read_to_string(&entry).unwrap();
+    std::fs::write(&entry, cached.replace("Tests updated?", "from cache")).unwrap();
+    run_check().failure().stdout(predicate::str::contains("from cache"));
+
+    // Any input change is a new key: config edits and new acks both count
+    std::fs::write(
+        temp.path().join(".noslop.toml"),
+        "[[check]]\nid = \"RS-1\"\ntarget = \"*.rs\"\nmessage = \"Benchmarks run?\"\n",
+    )
+    .unwrap();
+    run_check().failure().stdout(predicate::str::contains("Benchmarks run?"));
+
+    noslop()
+        .args(["ack", "RS-1", "-m", "ran them"])
+        .env("NOSLOP_ACTOR", "claude-code")
+        .current_dir(temp.path())
+        .assert()
+        .success();
+    run_check().success();
+    assert_eq!(std::fs::read_dir(&cache_dir).unwrap().count(), 1);
+}
+
+#[test]
+fn test_check_verdict_cache_keyed_on_head_and_codeowners() {
+    let repo = TempGitRepo::new();
+    repo.write_file(
+        ".noslop.toml",
+        "[[check]]\nid = \"RS-1\"\ntarget = \"*.rs\"\nmessage = \"Tests updated?\"\n",
+    );
+    repo.stage(".noslop.toml");
+    repo.git(&["commit", "-q", "--no-verify", "-m", "config"]);
+    repo.write_file("lib.rs", "fn main() {}\n");
+    repo.stage("lib.rs");
+
+    let run_check = || {
+        noslop()
+            .arg("check")
+            .env("NOSLOP_ACTOR", "claude-code")
+            .current_dir(repo.path())
+            .assert()
+    };
+    let cache_dir = repo.path().join(".noslop/cache/check");
+    let tamper = || {
+        for entry in std::fs::read_dir(&cache_dir).unwrap() {
+            let entry = entry.unwrap().path();
+            let cached = std::fs::read_to_string(&entry).unwrap();
+            std::fs::write(&entry, cached.replace("Tests updated?", "from cache")).unwrap();
+        }
+    };
+    run_check().failure();
+    tamper();
+    run_check().failure().stdout(predicate::str::contains("from cache"));
+
+    // HEAD moves while the index stays put: ramps and stacked acks read
+    // HEAD's history, so the stored verdict no longer applies
+    let head = repo.git(&["commit-tree", "HEAD^{tree}", "-p", "HEAD", "-m", "next"]);
+    repo.git(&["update-ref", "HEAD", String::from_utf8_lossy(&head.stdout).trim()]);
+    run_check().failure().stdout(predicate::str::contains("Tests updated?"));
+
+    // So does a CODEOWNERS edit, which can change a check's severity
+    tamper();
+    repo.write_file("CODEOWNERS", "*.rs @core\n");
+    run_check().failure().stdout(predicate::str::contains("Tests updated?"));
+}
+
+#[test]
+fn test_group_ack_satisfies_all_members() {
+    let temp = TempDir::new().unwrap();
+
+    std::process::Command::new("git")
+        .args(["init"])
+        .current_dir(temp.path())
+        .output()
+        .unwrap();
+
+    std::fs::write(
+        temp.path().join(".noslop.toml"),
+        "[[check]]\nid = \"SEC-1\"\ntarget = \"auth.rs\"\nmessage = \"Tokens rotated?\"\ngroup = \"security-review\"\n\n[[check]]\nid = \"SEC-2\"\ntarget = \"*.rs\"\nmessage = \"No secrets logged?\"\ngroup = \"security-review\"\n",
+    )
+    .unwrap();
+    std::fs::write(temp.path().join("auth.rs"), "fn login() {}\n").unwrap();
+    std::process::Command::new("git")
+        .args(["add", "auth.rs"])
+        .current_dir(temp.path())
+        .output()
+        .unwrap();
+
+    noslop()
+        .arg("check")
+        .env("NOSLOP_ACTOR", "claude-code")
+        .current_dir(temp.path())
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("[SEC-1] auth.rs  (group: security-review)"))
+        .stdout(predicate::str::contains("[SEC-2] auth.rs  (group: security-review)"));
+
+    noslop()
+        .args(["ack", "security-review", "-m", "reviewed the auth change"])
+        .env("NOSLOP_ACTOR", "claude-code")
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("For: group security-review (SEC-1, SEC-2)"));
+
+    // Both members are still listed, each acknowledged
+    noslop()
+        .args(["--json", "check"])
+        .env("NOSLOP_ACTOR", "claude-code")
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"id\": \"SEC-1\""))
+        .stdout(predicate::str::contains("\"id\": \"SEC-2\""))
+        .stdout(predicate::str::contains("\"blocking\": []"));
+}
+
+#[test]
+fn test_check_diff_base_attributes_checks_to_commits() {
+    let temp = TempDir::new().unwrap();
+    let git = |author: &str, args: &[&str]| {
+        std::process::Command::new("git")
+            .args(args)
+            .current_dir(temp.path())
+            .env("GIT_AUTHOR_NAME", author)
+            .env("GIT_AUTHOR_EMAIL", "a@example.com")
+            .env("GIT_COMMITTER_NAME", author)
+            .env("GIT_COMMITTER_EMAIL", "a@example.com")
+            .output()
+            .unwrap()
+    };
+    git("base", &["init", "-b", "main"]);
+    std::fs::write(
+        temp.path().join(".noslop.toml"),
+        "[[check]]\nid = \"TST-1\"\ntarget = \"*.rs\"\nmessage = \"Reviewed?\"\n",
+    )
+    .unwrap();
+    git("base", &["add", "-A"]);
+    git("base", &["commit", "-m", "base"]);
+
+    git("base", &["checkout", "-b", "feature"]);
+    std::fs::write(temp.path().join("lib.rs"), "fn main() {}\n").unwrap();
+    git("Ada", &["add", "lib.rs"]);
+    git("Ada", &["commit", "-m", "add lib"]);
+    std::fs::write(temp.path().join("notes.md"), "notes\n").unwrap();
+    git("Grace", &["add", "notes.md"]);
+    git("Grace", &["commit", "-m", "notes"]);
+
+    noslop()
+        .args(["check", "--ci", "--diff-base", "main"])
+        .current_dir(temp.path())
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("introduced by:"))
+        .stdout(predicate::str::contains("(Ada)"))
+        .stdout(predicate::str::contains("Grace").not());
+
+    noslop()
+        .args(["--json", "check", "--ci", "--diff-base", "main"])
+        .current_dir(temp.path())
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("\"author\": \"Ada\""));
+}
+
+#[test]
+fn test_no_verify_commit_is_recorded_as_bypass() {
+    let repo = TempGitRepo::new();
+    repo.write_file(
+        ".noslop.toml",
+        "[[check]]\nid = \"TST-1\"\ntarget = \"*.rs\"\nmessage = \"Reviewed?\"\n",
+    );
+
+    // Gated commit: the pre-commit run passed this tree, so no bypass
+    repo.write_file("a.rs", "fn a() {}\n");
+    repo.git(&["add", "-A"]);
+    noslop()
+        .arg("check")
+        .env("NOSLOP_ACTOR", "human")
+        .current_dir(repo.path())
+        .assert()
+        .success();
+    repo.git(&["commit", "--no-verify", "-m", "gated"]);
+    noslop().arg("clear-staged").current_dir(repo.path()).assert().success();
+    noslop()
+        .arg("status")
+        .current_dir(repo.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No commits skipped the gate."));
+
+    // Commit that never ran the gate
+    repo.write_file("b.rs", "fn b() {}\n");
+    repo.stage("b.rs");
+    repo.git(&["commit", "--no-verify", "-m", "skipped"]);
+    noslop()
+        .arg("clear-staged")
+        .env("NOSLOP_ACTOR", "claude-code")
+        .current_dir(repo.path())
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("skipped the pre-commit gate"));
+    noslop()
+        .arg("status")
+        .current_dir(repo.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Commits that skipped the gate (1)"))
+        .stdout(predicate::str::contains("block  claude-code"))
+        .stdout(predicate::str::contains("TST-1"));
+    noslop()
+        .args(["--json", "status"])
+        .current_dir(repo.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"check_ids\":[\"TST-1\"]"));
+}
+
+#[test]
+fn test_gated_commit_reminds_about_unacknowledged_warnings() {
+    let repo = TempGitRepo::new();
+    let config = "[[check]]\nid = \"WRN-1\"\ntarget = \"*.rs\"\nmessage = \"Docs updated?\"\nseverity = \"warn\"\n";
+    std::fs::write(repo.path().join(".noslop.toml"), config).unwrap();
+
+    repo.write_file("a.rs", "fn a() {}\n");
+    repo.git(&["add", "-A"]);
+    noslop().arg("check").current_dir(repo.path()).assert().success();
+    repo.git(&["commit", "--no-verify", "-m", "gated"]);
+    noslop()
+        .arg("clear-staged")
+        .current_dir(repo.path())
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(
+            "1 check(s) left unacknowledged in this commit (WRN-1)",
+        ))
+        .stderr(predicate::str::contains("before pushing"));
+
+    // Opted out: the same situation stays quiet
+    std::fs::write(
+        repo.path().join(".noslop.toml"),
+        format!("{config}\n[reminders]\nenabled = false\n"),
+    )
+    .unwrap();
+    repo.write_file("b.rs", "fn b() {}\n");
+    repo.git(&["add", "-A"]);
+    noslop().arg("check").current_dir(repo.path()).assert().success();
+    repo.git(&["commit", "--no-verify", "-m", "quiet"]);
+    noslop()
+        .arg("clear-staged")
+        .current_dir(repo.path())
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("unacknowledged").not());
+}
+
+#[test]
+fn test_sparse_checkout_reads_configs_outside_the_cone() {
+    let repo = TempGitRepo::with_branch("main");
+    std::fs::create_dir_all(repo.path().join("docs")).unwrap();
+    std::fs::create_dir_all(repo.path().join("svc")).unwrap();
+    repo.write_file("docs/guide.md", "# Guide\n");
+    repo.write_file(
+        "svc/.noslop.toml",
+        "[[check]]\nid = \"SVC-1\"\ntarget = \"*.rs\"\nmessage = \"Service reviewed?\"\nseverity = \"block\"\n",
+    );
+    repo.write_file("svc/a.rs", "fn a() {}\n");
+    repo.git(&["add", "-A"]);
+    repo.commit("init");
+    repo.git(&["checkout", "-b", "feature"]);
+    repo.write_file("svc/a.rs", "fn a() { todo!() }\n");
+    repo.git(&["commit", "-am", "change svc"]);
+
+    // svc/ (and its config) leave the working tree but stay in the index
+    repo.git(&["sparse-checkout", "set", "docs"]);
+    assert!(!repo.path().join("svc/.noslop.toml").exists());
+
+    noslop()
+        .args(["check", "--ci", "--diff-base", "main"])
+        .current_dir(repo.path())
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("SVC-1"));
+}
+
+#[test]
+fn test_check_verify_branch_reads_trailers() {
+    let repo = TempGitRepo::with_branch("main");
+    repo.write_file(
+        ".noslop.toml",
+        "[[check]]\nid = \"RS-1\"\ntarget = \"*.rs\"\nmessage = \"Rust reviewed?\"\n\n\
+         [[check]]\nid = \"MD-1\"\ntarget = \"*.md\"\nmessage = \"Docs reviewed?\"\nseverity = \"warn\"\n",
+    );
+    repo.git(&["add", "-A"]);
+    repo.commit("base");
+
+    repo.git(&["checkout", "-b", "feature"]);
+    repo.write_file("lib.rs", "fn main() {}\n");
+    repo.write_file("notes.md", "notes\n");
+    repo.git(&["add", "-A"]);
+    repo.commit("work\n\nNoslop-Ack: RS-1 | checked the entry point | human");
+    repo.git(&["checkout", "main"]);
+
+    noslop()
+        .args(["check", "verify-branch", "--branch", "feature"])
+        .current_dir(repo.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("verified    [RS-1]"))
+        .stdout(predicate::str::contains("checked the entry point (human)"))
+        .stdout(predicate::str::contains("unverified  [MD-1]"))
+        .stdout(predicate::str::contains("2 check(s), 1 unverified."));
+
+    noslop()
+        .args(["--json", "check", "verify-branch", "--branch", "feature"])
+        .current_dir(repo.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"verified\":true"))
+        .stdout(predicate::str::contains("\"verified\":false"));
+}
+
+#[test]
+fn test_verify_branch_carries_acks_from_stacked_parent() {
+    let repo = TempGitRepo::with_branch("main");
+    repo.write_file(
+        ".noslop.toml",
+        "[[check]]\nid = \"RS-1\"\ntarget = \"*.rs\"\nmessage = \"Rust reviewed?\"\n",
+    );
+    repo.git(&["add", "-A"]);
+    repo.commit("base");
+
+    // feature-2 stacks on feature-1 before feature-1's review lands
+    repo.git(&["checkout", "-b", "feature-1"]);
+    repo.write_file("lib.rs", "fn main() {}\n");
+    repo.git(&["add", "-A"]);
+    repo.commit("feature 1");
+    repo.git(&["checkout", "-b", "feature-2"]);
+    repo.write_file("notes.txt", "notes\n");
+    repo.git(&["add", "-A"]);
+    repo.commit("feature 2");
+    repo.git(&["checkout", "feature-1"]);
+    repo.git(&[
+        "commit",
+        "--allow-empty",
+        "-m",
+        "review\n\nNoslop-Ack: RS-1 | reviewed lib.rs | human",
+    ]);
+
+    noslop()
+        .args(["check", "verify-branch", "--branch", "feature-2"])
+        .current_dir(repo.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("verified    [RS-1]"))
+        .stderr(predicate::str::contains("ack(s) made on stacked parent 'feature-1'"));
+
+    // An explicit base that is no stacked parent carries nothing
+    repo.git(&["config", "branch.feature-2.noslopBase", "main"]);
+    noslop()
+        .args(["check", "verify-branch", "--branch", "feature-2"])
+        .current_dir(repo.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("unverified  [RS-1]"));
+}
+
+#[test]
+fn test_invalid_severity_is_rejected() {
+    let temp = TempDir::new().unwrap();
+    std::process::Command::new("git")
+        .args(["init"])
+        .current_dir(temp.path())
+        .output()
+        .unwrap();
+
+    noslop()
+        .args(["check", "add", "*.rs", "-m", "Reviewed?", "-s", "blokc"])
+        .current_dir(temp.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid severity: blokc"));
+    assert!(!temp.path().join(".noslop.toml").exists());
+
+    // A typo on disk is an error, not a silent block
+    std::fs::write(
+        temp.path().join(".noslop.toml"),
+        "[[check]]\ntarget = \"*.rs\"\nmessage = \"Reviewed?\"\nseverity = \"wran\"\n",
+    )
+    .unwrap();
+    noslop()
+        .args(["check", "list"])
+        .current_dir(temp.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid severity: wran"));
+}
+
+#[test]
+fn test_check_owner_shown_and_filterable() {
+    let temp = TempDir::new().unwrap();
+    std::process::Command::new("git")
+        .args(["init"])
+        .current_dir(temp.path())
+        .output()
+        .unwrap();
+    std::fs::write(
+        temp.path().join(".noslop.toml"),
+        "[[check]]\nid = \"PAY-1\"\ntarget = \"*.rs\"\nmessage = \"Refund path reviewed?\"\n\
+         owner = \"@team-payments\"\n\n\
+         [[check]]\nid = \"DOC-1\"\ntarget = \"*.md\"\nmessage = \"Docs reviewed?\"\n",
+    )
+    .unwrap();
+    std::fs::write(temp.path().join("pay.rs"), "fn refund() {}\n").unwrap();
+    std::process::Command::new("git")
+        .args(["add", "pay.rs"])
+        .current_dir(temp.path())
+        .output()
+        .unwrap();
+
+    noslop()
+        .arg("check")
+        .env("NOSLOP_ACTOR", "claude-code")
+        .current_dir(temp.path())
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("contact @team-payments"));
+
+    noslop()
+        .args(["check", "list", "--owner", "@team-payments"])
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Refund path reviewed?  (@team-payments)"))
+        .stdout(predicate::str::contains("Docs reviewed?").not())
+        .stdout(predicate::str::contains("1 check(s) found."));
+}
+
+#[test]
+fn test_verify_commit_uses_config_and_acks_at_that_commit() {
+    let repo = TempGitRepo::new();
+    repo.write_file(
+        ".noslop.toml",
+        "[[check]]\nid = \"RS-1\"\ntarget = \"*.rs\"\nmessage = \"Reviewed?\"\n",
+    );
+    repo.git(&["add", "-A"]);
+    repo.commit("config");
+
+    repo.write_file("a.rs", "fn a() {}\n");
+    repo.stage("a.rs");
+    repo.commit("unanswered");
+    repo.write_file("b.rs", "fn b() {}\n");
+    repo.stage("b.rs");
+    repo.commit("answered\n\nNoslop-Ack: RS-1 | reviewed b | human");
+
+    // The working-tree config no longer matters: each commit is judged by its own
+    std::fs::remove_file(repo.path().join(".noslop.toml")).unwrap();
+
+    noslop()
+        .args(["verify-commit", "HEAD~1"])
+        .current_dir(repo.path())
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("add    a.rs"))
+        .stdout(predicate::str::contains("unanswered  [RS-1] a.rs (block)"))
+        .stdout(predicate::str::contains("FAIL: 1 blocking check(s) unanswered."));
+
+    noslop()
+        .args(["verify-commit", "HEAD"])
+        .current_dir(repo.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("answered    [RS-1] b.rs (block)"))
+        .stdout(predicate::str::contains("reviewed b (human)"))
+        .stdout(predicate::str::contains("PASS"));
+
+    noslop()
+        .args(["--json", "verify-commit", "HEAD"])
+        .current_dir(repo.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"passed\":true"))
+        .stdout(predicate::str::contains("\"files\":[\"b.rs\"]"));
+
+    noslop()
+        .args(["verify-commit", "nope"])
+        .current_dir(repo.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Not a commit: 'nope'"));
+}
+
+#[test]
+fn test_verify_commit_diffs_a_merge_against_its_first_parent() {
+    let repo = TempGitRepo::with_branch("main");
+    repo.write_file(
+        ".noslop.toml",
+        "[[check]]\nid = \"RS-1\"\ntarget = \"*.rs\"\nmessage = \"Reviewed?\"\n",
+    );
+    repo.git(&["add", "-A"]);
+    repo.commit("config");
+
+    repo.git(&["checkout", "-q", "-b", "feature"]);
+    repo.write_file("a.rs", "fn a() {}\n");
+    repo.stage("a.rs");
+    repo.git(&["commit", "--no-verify", "-m", "feature"]);
+    repo.git(&["checkout", "-q", "main"]);
+    repo.write_file("notes.md", "notes\n");
+    repo.stage("notes.md");
+    repo.commit("notes");
+    repo.git(&["merge", "--no-ff", "--no-verify", "-m", "merge feature", "feature"]);
+
+    // What the merge brought into main, not the empty combined diff
+    noslop()
+        .args(["verify-commit", "HEAD"])
+        .current_dir(repo.path())
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("Files changed (1)"))
+        .stdout(predicate::str::contains("add    a.rs"))
+        .stdout(predicate::str::contains("notes.md").not())
+        .stdout(predicate::str::contains("unanswered  [RS-1] a.rs (block)"));
+}
+
+#[test]
+fn test_ack_evidence_is_stored_and_verified_by_hash() {
+    let repo = TempGitRepo::new();
+    repo.write_file(
+        ".noslop.toml",
+        "[[check]]\nid = \"SEC-1\"\ntarget = \"*.rs\"\nmessage = \"Pen tested?\"\n",
+    );
+    repo.git(&["add", "-A"]);
+    repo.commit("config");
+
+    repo.write_file("a.rs", "fn a() {}\n");
+    repo.write_file("pentest.txt", "no findings\n");
+    repo.stage("a.rs");
+    noslop()
+        .args(["ack", "SEC-1", "-m", "pen test done", "--evidence", "pentest.txt"])
+        .env("NOSLOP_ACTOR", "claude-code")
+        .current_dir(repo.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Evidence: pentest.txt"))
+        .stdout(predicate::str::contains("Noslop-Evidence: SEC-1 | pentest.txt | "));
+    repo.commit("with evidence");
+
+    noslop()
+        .args(["verify-commit", "HEAD"])
+        .current_dir(repo.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("evidence: pentest.txt"))
+        .stdout(predicate::str::contains("PASS"));
+
+    // Rewriting the stored copy before commit breaks the hash it is named by
+    repo.write_file("b.rs", "fn b() {}\n");
+    repo.write_file("audit.txt", "signed off\n");
+    repo.stage("b.rs");
+    noslop()
+        .args(["ack", "SEC-1", "-m", "audit signed", "--evidence", "audit.txt"])
+        .env("NOSLOP_ACTOR", "claude-code")
+        .current_dir(repo.path())
+        .assert()
+        .success();
+    let blob = String::from_utf8(repo.git(&["hash-object", "audit.txt"]).stdout).unwrap();
+    let stored = repo.path().join(".noslop/evidence").join(blob.trim());
+    std::fs::write(&stored, "edited after the fact\n").unwrap();
+    repo.git(&["add", "-f", stored.to_str().unwrap()]);
+    repo.commit("tampered evidence");
+
+    noslop()
+        .args(["verify-commit", "HEAD"])
+        .current_dir(repo.path())
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("SEC-1: evidence audit.txt was altered"))
+        .stdout(predicate::str::contains("FAIL: attached evidence"));
+}
+
+#[test]
+fn test_check_against_range_in_bare_repo() {
+    let temp = TempDir::new().unwrap();
+    let work = temp.path().join("work");
+    std::fs::create_dir(&work).unwrap();
+    let git = |args: &[&str]| {
+        std::process::Command::new("git")
+            .args(args)
+            .current_dir(&work)
+            .env("GIT_AUTHOR_NAME", "t")
+            .env("GIT_AUTHOR_EMAIL", "t@example.com")
+            .env("GIT_COMMITTER_NAME", "t")
+            .env("GIT_COMMITTER_EMAIL", "t@example.com")
+            .output()
+            .unwrap()
+    };
+    git(&["init"]);
+    std::fs::write(
+        work.join(".noslop.toml"),
+        "[[check]]\nid = \"RS-1\"\ntarget = \"*.rs\"\nmessage = \"Reviewed {file}?\"\n",
+    )
+    .unwrap();
+    git(&["add", "-A"]);
+    git(&["commit", "-m", "config"]);
+    std::fs::write(work.join("a.rs"), "fn a() {}\n").unwrap();
+    git(&["add", "a.rs"]);
+    git(&["commit", "-m", "unanswered"]);
+    std::fs::write(work.join("b.rs"), "fn b() {}\n").unwrap();
+    git(&["add", "b.rs"]);
+    git(&["commit", "-m", "answered\n\nNoslop-Ack: RS-1 | reviewed b | human"]);
+    git(&["clone", "--bare", ".", "../repo.git"]);
+    let bare = temp.path().join("repo.git");
+
+    // A receive hook runs in the bare repository with GIT_DIR set
+    noslop()
+        .args(["check", "--against", "HEAD~2..HEAD~1"])
+        .env("GIT_DIR", ".")
+        .current_dir(&bare)
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("[RS-1] a.rs"))
+        .stdout(predicate::str::contains("Reviewed a.rs?"))
+        .stderr(predicate::str::contains("Error: 1 unacknowledged check(s): RS-1"));
+
+    noslop()
+        .args(["check", "--against", "HEAD~1..HEAD"])
+        .env("GIT_DIR", ".")
+        .current_dir(&bare)
+        .assert()
+        .success();
+
+    noslop()
+        .args(["--json", "check", "--against", "HEAD~2..HEAD"])
+        .env("GIT_DIR", "repo.git")
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"passed\": true"));
+
+    noslop()
+        .args(["check", "--against", "0000000000000000000000000000000000000000..HEAD"])
+        .current_dir(&bare)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("null object id"));
+}
+
+#[test]
+fn test_included_checks_fire_and_keep_their_origin() {
+    let temp = TempDir::new().unwrap();
+    std::process::Command::new("git")
+        .args(["init"])
+        .current_dir(temp.path())
+        .output()
+        .unwrap();
+    std::fs::create_dir_all(temp.path().join("noslop")).unwrap();
+    std::fs::write(temp.path().join(".noslop.toml"), "include = [\"noslop/security.toml\"]\n")
+        .unwrap();
+    std::fs::write(
+        temp.path().join("noslop/security.toml"),
+        "[[check]]\nid = \"SEC-1\"\ntarget = \"*.rs\"\nmessage = \"Auth reviewed?\"\n\n\
+         [[check]]\nid = \"SEC-2\"\ntarget = \"*.py\"\nmessage = \"Py reviewed?\"\n",
+    )
+    .unwrap();
+    std::fs::write(temp.path().join("auth.rs"), "fn auth() {}\n").unwrap();
+    std::process::Command::new("git")
+        .args(["add", "auth.rs"])
+        .current_dir(temp.path())
+        .output()
+        .unwrap();
+
+    noslop()
+        .args(["check", "--explain-misses"])
+        .env("NOSLOP_ACTOR", "claude-code")
+        .current_dir(temp.path())
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("[SEC-1] auth.rs"))
+        .stdout(predicate::str::contains("target does not match (noslop/security.toml)"));
+
+    noslop()
+        .args(["check", "list"])
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Auth reviewed?  [from noslop/security.toml]"));
+
+    // New checks land in the including file; included ones stay put
+    noslop()
+        .args(["check", "add", "*.md", "-m", "Docs reviewed?"])
+        .current_dir(temp.path())
+        .assert()
+        .success();
+    let root = std::fs::read_to_string(temp.path().join(".noslop.toml")).unwrap();
+    assert!(root.starts_with("# noslop checks\n\ninclude = [\"noslop/security.toml\"]"));
+    assert!(root.contains("Docs reviewed?"));
+    assert!(!root.contains("Auth reviewed?"));
+}
+
+#[test]
+fn test_history_export_csv() {
+    let repo = TempGitRepo::new();
+    repo.write_file("a.rs", "fn a() {}\n");
+    repo.stage("a.rs");
+    repo.commit("plain");
+    repo.write_file("b.rs", "fn b() {}\n");
+    repo.stage("b.rs");
+    repo.commit("acked\n\nNoslop-Ack: SEC-1 | checked, twice | claude-code");
+
+    let output = noslop()
+        .args(["history", "export", "--since", "2000-01-01"])
+        .current_dir(repo.path())
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let csv = String::from_utf8(output).unwrap();
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(lines.len(), 2, "{csv}");
+    assert_eq!(lines[0], "commit,check_id,acknowledged_by,timestamp,files,message");
+    assert!(lines[1].contains(",SEC-1,claude-code,"), "{csv}");
+    assert!(lines[1].ends_with(",b.rs,\"checked, twice\""), "{csv}");
+
+    noslop()
+        .args(["history", "export", "--format", "parquet"])
+        .current_dir(repo.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("export csv or jsonl"));
+    noslop()
+        .args(["history", "export", "--since", "last tuesday"])
+        .current_dir(repo.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("expected YYYY-MM-DD"));
+}
+
+#[test]
+fn test_sandbox_runs_hooks_without_touching_the_repo() {
+    let repo = TempGitRepo::new();
+    repo.write_file(
+        ".noslop.toml",
+        "[[check]]\nid = \"RS-1\"\ntarget = \"*.rs\"\nmessage = \"Reviewed?\"\n",
+    );
+    repo.git(&["add", "-A"]);
+    repo.commit("config");
+    repo.write_file("a.rs", "fn a() {}\n");
+    repo.stage("a.rs");
+
+    let sandbox = || {
+        let mut cmd = noslop();
+        cmd.args(["sandbox", "-m", "add a"])
+            .current_dir(repo.path())
+            .env("NOSLOP_ACTOR", "claude-code")
+            .env("GIT_AUTHOR_NAME", "t")
+            .env("GIT_AUTHOR_EMAIL", "t@example.com")
+            .env("GIT_COMMITTER_NAME", "t")
+            .env("GIT_COMMITTER_EMAIL", "t@example.com");
+        cmd
+    };
+
+    sandbox()
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("FAIL pre-commit"))
+        .stdout(predicate::str::contains("[RS-1] a.rs"))
+        .stdout(predicate::str::contains("The pre-commit hook would stop this commit."));
+
+    noslop()
+        .args(["ack", "RS-1", "-m", "reviewed a"])
+        .current_dir(repo.path())
+        .env("NOSLOP_ACTOR", "claude-code")
+        .assert()
+        .success();
+    sandbox()
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("ok   commit-msg"))
+        .stdout(predicate::str::contains("ok   post-commit"))
+        .stdout(predicate::str::contains("Noslop-Ack: RS-1 | reviewed a | claude-code"));
+
+    // The real repository is untouched: one commit, acks still staged, no worktree
+    let log = repo.git(&["rev-list", "--count", "HEAD"]);
+    assert_eq!(String::from_utf8_lossy(&log.stdout).trim(), "1");
+    assert!(repo.path().join(".noslop/staged-acks.json").exists());
+    let worktrees = repo.git(&["worktree", "list"]);
+    assert_eq!(String::from_utf8_lossy(&worktrees.stdout).lines().count(), 1);
+}
+
+#[test]
+fn test_check_test_reports_files_and_recent_commits() {
+    let repo = TempGitRepo::new();
+    std::fs::create_dir(repo.path().join("src")).unwrap();
+    repo.write_file("src/a.rs", "fn a() {}\n");
+    repo.write_file("README.md", "# r\n");
+    repo.git(&["add", "-A"]);
+    repo.commit("init");
+    repo.write_file("README.md", "# readme\n");
+    repo.git(&["commit", "-am", "docs"]);
+    repo.git(&["rm", "-q", "src/a.rs"]);
+    repo.commit("drop a");
+    std::fs::create_dir_all(repo.path().join("src")).unwrap();
+    repo.write_file("src/b.rs", "fn b() {}\n");
+    repo.git(&["add", "-A"]);
+    repo.commit("add b");
+
+    noslop()
+        .args(["check", "test", "src/*.rs"])
+        .current_dir(repo.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Working tree: 1 tracked file(s) match"))
+        .stdout(predicate::str::contains("  src/b.rs"))
+        .stdout(predicate::str::contains("Last 4 commit(s): would have fired on 2 (50%)"));
+
+    // Deletions count only when asked for; --commits limits the replay
+    noslop()
+        .args(["check", "test", "src/*.rs", "--on", "delete", "--commits", "2"])
+        .current_dir(repo.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Last 2 commit(s): would have fired on 1 (50%)"));
+}
+
+#[test]
+fn test_without_git_local_commands_still_work() {
+    let temp = TempDir::new().unwrap();
+    let empty_path = TempDir::new().unwrap();
+    std::fs::write(
+        temp.path().join(".noslop.toml"),
+        "[[check]]\nid = \"RS-1\"\ntarget = \"*.rs\"\nmessage = \"Reviewed?\"\n",
+    )
+    .unwrap();
+
+    noslop()
+        .arg("check")
+        .current_dir(temp.path())
+        .env("PATH", empty_path.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "'noslop check' needs git, which was not found in PATH",
+        ))
+        .stderr(predicate::str::contains("Without git these still work: check add/list/remove"));
+
+    noslop()
+        .args(["check", "list"])
+        .current_dir(temp.path())
+        .env("PATH", empty_path.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("*.rs -> Reviewed?"));
+    noslop()
+        .args(["check", "add", "*.md", "-m", "Docs reviewed?"])
+        .current_dir(temp.path())
+        .env("PATH", empty_path.path())
+        .assert()
+        .success();
+}
+
+#[test]
+fn test_hooks_run_invokes_installed_hooks() {
+    let temp = TempDir::new().unwrap();
+    std::process::Command::new("git")
+        .args(["init"])
+        .current_dir(temp.path())
+        .output()
+        .unwrap();
+
+    noslop()
+        .args(["hooks", "run", "pre-commit"])
+        .current_dir(temp.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("No pre-commit hook installed"));
+
+    noslop().arg("init").current_dir(temp.path()).assert().success();
+    std::fs::write(
+        temp.path().join(".noslop.toml"),
+        "[[check]]\nid = \"RS-1\"\ntarget = \"*.rs\"\nmessage = \"Reviewed?\"\n",
+    )
+    .unwrap();
+    std::fs::write(temp.path().join("a.rs"), "fn a() {}\n").unwrap();
+    std::process::Command::new("git")
+        .args(["add", "a.rs"])
+        .current_dir(temp.path())
+        .output()
+        .unwrap();
+
+    // The hooks call `noslop` from PATH, as they do for a real commit
+    let bin_dir = std::path::Path::new(cargo::cargo_bin!("noslop")).parent().unwrap();
+    let path = format!("{}:{}", bin_dir.display(), std::env::var("PATH").unwrap_or_default());
+
+    noslop()
+        .args(["hooks", "run", "pre-commit"])
+        .current_dir(temp.path())
+        .env("PATH", &path)
+        .env("NOSLOP_ACTOR", "claude-code")
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("noslop:    installed in this hook"))
+        .stdout(predicate::str::contains("[RS-1] a.rs"))
+        .stdout(predicate::str::contains("Exit: 1 (git would abort)"));
+
+    noslop()
+        .args(["hooks", "run", "commit-msg"])
+        .current_dir(temp.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--message-file <path>"));
+
+    noslop()
+        .args(["ack", "RS-1", "-m", "reviewed a"])
+        .current_dir(temp.path())
+        .env("NOSLOP_ACTOR", "claude-code")
+        .assert()
+        .success();
+    std::fs::write(temp.path().join("msg.txt"), "add a\n").unwrap();
+    noslop()
+        .args(["hooks", "run", "commit-msg", "--message-file", "msg.txt"])
+        .current_dir(temp.path())
+        .env("PATH", &path)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Message before:\n    add a\n"))
+        .stdout(predicate::str::contains("Noslop-Ack: RS-1 | reviewed a | claude-code"))
+        .stdout(predicate::str::contains("Exit: 0 (git would continue)"));
+}
+
+#[test]
+fn test_acks_written_in_the_commit_message() {
+    let temp = TempDir::new().unwrap();
+    // The hooks call `noslop` from PATH, as they do for a real commit
+    let bin_dir = std::path::Path::new(cargo::cargo_bin!("noslop")).parent().unwrap();
+    let path = format!("{}:{}", bin_dir.display(), std::env::var("PATH").unwrap_or_default());
+    let git = |args: &[&str]| {
+        std::process::Command::new("git")
+            .args(args)
+            .current_dir(temp.path())
+            .env("PATH", &path)
+            .env("NOSLOP_ACTOR", "claude-code")
+            .env("GIT_AUTHOR_NAME", "t")
+            .env("GIT_AUTHOR_EMAIL", "t@example.com")
+            .env("GIT_COMMITTER_NAME", "t")
+            .env("GIT_COMMITTER_EMAIL", "t@example.com")
+            .output()
+            .unwrap()
+    };
+    git(&["init"]);
+    noslop().arg("init").current_dir(temp.path()).assert().success();
+    std::fs::write(
+        temp.path().join(".noslop.toml"),
+        "[ack]\nin_message = true\n\n[[check]]\nid = \"RS-1\"\ntarget = \"*.rs\"\nmessage = \"Reviewed?\"\n",
+    )
+    .unwrap();
+
+    std::fs::write(temp.path().join("a.rs"), "fn a() {}\n").unwrap();
+    git(&["add", "-A"]);
+    let out = git(&["commit", "-m", "add a\n\n[ack RS-1: reviewed the parser]"]);
+    assert!(out.status.success(), "{}", String::from_utf8_lossy(&out.stderr));
+    let log = String::from_utf8_lossy(&git(&["log", "-1", "--format=%B"]).stdout).into_owned();
+    assert!(log.contains("Noslop-Ack: RS-1 | reviewed the parser | claude-code"), "{log}");
+    assert!(!log.contains("[ack RS-1"), "{log}");
+
+    // Still gated: no ack anywhere
+    std::fs::write(temp.path().join("b.rs"), "fn b() {}\n").unwrap();
+    git(&["add", "b.rs"]);
+    assert!(!git(&["commit", "-m", "add b"]).status.success());
+
+    // Unknown IDs abort the commit
+    let out = git(&["commit", "-m", "add b\n\n[ack NOPE-1: looked]"]);
+    assert!(!out.status.success());
+    assert!(String::from_utf8_lossy(&out.stderr).contains("No check with ID 'NOPE-1'"));
+}
+
+#[test]
+fn test_hooks_install_only_chains_and_uninstall_keeps_other_hooks() {
+    let temp = TempDir::new().unwrap();
+    std::process::Command::new("git")
+        .args(["init"])
+        .current_dir(temp.path())
+        .output()
+        .unwrap();
+    let hooks = temp.path().join(".git/hooks");
+    std::fs::write(hooks.join("pre-commit"), "#!/bin/sh\nmake lint\n").unwrap();
+
+    noslop()
+        .args(["hooks", "install", "--only", "pre-commit,commit-msg"])
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Installed pre-commit hook: appended to the existing"))
+        .stdout(predicate::str::contains("Installed commit-msg hook: wrote"))
+        .stdout(predicate::str::contains("post-commit").not());
+    let pre_commit = std::fs::read_to_string(hooks.join("pre-commit")).unwrap();
+    assert!(pre_commit.starts_with("#!/bin/sh\nmake lint"));
+    assert!(pre_commit.contains("noslop check"));
+    assert!(!hooks.join("post-commit").exists());
+
+    noslop()
+        .args(["hooks", "uninstall", "pre-commit", "commit-msg", "post-commit"])
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Removed noslop from pre-commit hook"))
+        .stdout(predicate::str::contains("Removed commit-msg hook: deleted"))
+        .stdout(predicate::str::contains("post-commit hook does not call noslop"));
+    assert_eq!(
+        std::fs::read_to_string(hooks.join("pre-commit")).unwrap(),
+        "#!/bin/sh\nmake lint\n"
+    );
+    assert!(!hooks.join("commit-msg").exists());
+
+    noslop()
+        .args(["hooks", "install", "--only", "pre-push"])
+        .current_dir(temp.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Unknown hook 'pre-push'"));
+}
+
+#[test]
+fn test_commit_template_lists_pending_checks() {
+    let repo = TempGitRepo::new();
+    noslop()
+        .args(["hooks", "install", "--commit-template"])
+        .current_dir(repo.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Installed prepare-commit-msg hook"));
+    let hook = std::fs::read_to_string(repo.path().join(".git/hooks/prepare-commit-msg")).unwrap();
+    assert!(hook.contains("noslop prepare-message"));
+
+    repo.write_file(
+        ".noslop.toml",
+        "[[check]]\nid = \"RS-1\"\ntarget = \"*.rs\"\nmessage = \"Reviewed?\"\n",
+    );
+    repo.write_file("a.rs", "fn a() {}\n");
+    repo.stage("a.rs");
+
+    let editor_msg = "\n# Please enter the commit message for your changes.\n";
+    std::fs::write(repo.path().join("msg.txt"), editor_msg).unwrap();
+    noslop()
+        .args(["prepare-message", "msg.txt"])
+        .current_dir(repo.path())
+        .assert()
+        .success();
+    let msg = std::fs::read_to_string(repo.path().join("msg.txt")).unwrap();
+    assert!(msg.starts_with(editor_msg), "{msg}");
+    assert!(msg.contains("# noslop: checks on the staged changes"), "{msg}");
+    assert!(msg.contains("#   block  RS-1  a.rs: Reviewed?"), "{msg}");
+
+    // `git commit -m` messages are left alone
+    repo.write_file("msg.txt", "add a\n");
+    noslop()
+        .args(["prepare-message", "msg.txt", "message"])
+        .current_dir(repo.path())
+        .assert()
+        .success();
+    assert_eq!(std::fs::read_to_string(repo.path().join("msg.txt")).unwrap(), "add a\n");
+}
+
+#[test]
+fn test_severity_override_per_path() {
+    let repo = TempGitRepo::new();
+    repo.write_file(
+        ".noslop.toml",
+        "[[check]]\nid = \"RS-1\"\ntarget = \"*.rs\"\nmessage = \"Reviewed?\"\nseverity = \"block\"\n\n[[check.override]]\nscope = \"examples/**\"\nseverity = \"warn\"\n",
+    );
+    std::fs::create_dir_all(repo.path().join("examples")).unwrap();
+    std::fs::create_dir_all(repo.path().join("src")).unwrap();
+    repo.write_file("examples/demo.rs", "fn main() {}\n");
+    repo.write_file("src/lib.rs", "pub fn a() {}\n");
+
+    noslop()
+        .args(["check", "list"])
+        .current_dir(repo.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("warn in examples/**"));
+
+    // Only the example changed: a warning, not a block
+    repo.stage("examples/demo.rs");
+    noslop()
+        .args(["--json", "check"])
+        .env("NOSLOP_ACTOR", "claude-code")
+        .current_dir(repo.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"severity\": \"warn\""));
+
+    repo.stage("src/lib.rs");
+    noslop()
+        .arg("check")
+        .env("NOSLOP_ACTOR", "claude-code")
+        .current_dir(repo.path())
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("src/lib.rs"));
+}
+
+#[test]
+fn test_pending_file_follows_the_verdict() {
+    let repo = TempGitRepo::new();
+    repo.write_file(
+        ".noslop.toml",
+        "[pending]\nenabled = true\n\n[[check]]\nid = \"RS-1\"\ntarget = \"*.rs\"\nmessage = \"Reviewed?\"\n",
+    );
+    repo.write_file("a.rs", "fn a() {}\n");
+    repo.stage("a.rs");
+    let pending = repo.path().join(".noslop/pending.md");
+
+    noslop()
+        .arg("check")
+        .env("NOSLOP_ACTOR", "claude-code")
+        .current_dir(repo.path())
+        .assert()
+        .failure();
+    let md = std::fs::read_to_string(&pending).unwrap();
+    assert!(md.contains("## RS-1"), "{md}");
+    assert!(md.contains("- `a.rs`"), "{md}");
+
+    noslop()
+        .args(["ack", "RS-1", "-m", "reviewed a"])
+        .env("NOSLOP_ACTOR", "claude-code")
+        .current_dir(repo.path())
+        .assert()
+        .success();
+    noslop()
+        .arg("check")
+        .env("NOSLOP_ACTOR", "claude-code")
+        .current_dir(repo.path())
+        .assert()
+        .success();
+    assert!(!pending.exists());
+}
+
+#[test]
+fn test_commit_check_fires_on_change_set_size() {
+    let repo = TempGitRepo::new();
+    repo.write_file(
+        ".noslop.toml",
+        "[[check]]\nid = \"BIG-1\"\ntarget_kind = \"commit\"\nmin_added_lines = 3\nmessage = \"Large change: link the design doc\"\n",
+    );
+    repo.git(&["add", "-A"]);
+    repo.commit("config");
+
+    repo.write_file("small.txt", "one\n");
+    repo.stage("small.txt");
+    noslop()
+        .arg("check")
+        .current_dir(repo.path())
+        .env("NOSLOP_ACTOR", "claude-code")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No checks apply"));
+
+    repo.write_file("big.txt", "one\ntwo\nthree\n");
+    repo.stage("big.txt");
+    noslop()
+        .arg("check")
+        .current_dir(repo.path())
+        .env("NOSLOP_ACTOR", "claude-code")
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("[BIG-1] (commit)"))
+        .stdout(predicate::str::contains("Large change: link the design doc"));
+
+    noslop()
+        .args(["ack", "BIG-1", "-m", "design doc: docs/big.md"])
+        .current_dir(repo.path())
+        .env("NOSLOP_ACTOR", "claude-code")
+        .assert()
+        .success();
+    noslop()
+        .arg("check")
+        .current_dir(repo.path())
+        .env("NOSLOP_ACTOR", "claude-code")
+        .assert()
+        .success();
+
+    noslop()
+        .args(["check", "list"])
+        .current_dir(repo.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("commit(added lines >= 3) -> Large change"));
+}
+
+#[test]
+fn test_commit_check_fires_on_ranges_and_single_commits() {
+    let repo = TempGitRepo::new();
+    repo.write_file(
+        ".noslop.toml",
+        "[[check]]\nid = \"WIDE-1\"\ntarget_kind = \"commit\"\nmin_files = 2\nmessage = \"Wide change: split it?\"\n",
+    );
+    repo.git(&["add", "-A"]);
+    repo.commit("config");
+
+    repo.write_file("a.txt", "a\n");
+    repo.write_file("b.txt", "b\n");
+    repo.git(&["add", "-A"]);
+    repo.git(&["commit", "--no-verify", "-m", "wide"]);
+
+    noslop()
+        .args(["check", "--against", "HEAD~1..HEAD"])
+        .current_dir(repo.path())
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("Checking 2 file(s) changed in HEAD~1..HEAD"))
+        .stdout(predicate::str::contains("[WIDE-1] (commit)"))
+        .stdout(predicate::str::contains("check(s) in this range"))
+        .stdout(predicate::str::contains("Commit may proceed").not());
+
+    noslop()
+        .args(["verify-commit", "HEAD"])
+        .current_dir(repo.path())
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("WIDE-1"));
+
+    // One file is under the threshold
+    repo.write_file("a.txt", "aa\n");
+    repo.git(&["add", "-A"]);
+    repo.git(&["commit", "--no-verify", "-m", "narrow"]);
+    noslop()
+        .args(["check", "--against", "HEAD~1..HEAD"])
+        .current_dir(repo.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No checks apply. Range may proceed."));
+    noslop()
+        .args(["verify-commit", "HEAD"])
+        .current_dir(repo.path())
+        .assert()
+        .success();
+}
+
+#[test]
+fn test_whoami_and_identity_on_acks() {
+    let temp = TempDir::new().unwrap();
+    std::process::Command::new("git")
+        .args(["init"])
+        .current_dir(temp.path())
+        .output()
+        .unwrap();
+    std::process::Command::new("git")
+        .args(["config", "user.email", "git@example.com"])
+        .current_dir(temp.path())
+        .output()
+        .unwrap();
+
+    noslop()
+        .arg("whoami")
+        .current_dir(temp.path())
+        .env("NOSLOP_ACTOR", "claude-code")
+        .env_remove("NOSLOP_IDENTITY")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Actor:    claude-code (agent"))
+        .stdout(predicate::str::contains(
+            "Identity: git@example.com (from git config user.email)",
+        ));
+
+    noslop()
+        .args(["whoami", "--set", "dev@example.com"])
+        .current_dir(temp.path())
+        .assert()
+        .success();
+    noslop()
+        .arg("whoami")
+        .current_dir(temp.path())
+        .env_remove("NOSLOP_IDENTITY")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Identity: dev@example.com (from .noslop/identity)"));
+    noslop()
+        .args(["whoami", "--identity", "flag@example.com"])
+        .current_dir(temp.path())
+        .env("NOSLOP_IDENTITY", "env@example.com")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Identity: flag@example.com (from --identity flag)"));
+
+    std::fs::write(
+        temp.path().join(".noslop.toml"),
+        "[[check]]\nid = \"RS-1\"\ntarget = \"*.rs\"\nmessage = \"Reviewed?\"\n",
+    )
+    .unwrap();
+    noslop()
+        .args(["ack", "RS-1", "-m", "reviewed"])
+        .current_dir(temp.path())
+        .env("NOSLOP_ACTOR", "claude-code")
+        .env_remove("NOSLOP_IDENTITY")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Staged acknowledgment (as claude-code, dev@example.com)",
+        ));
+    let staged = std::fs::read_to_string(temp.path().join(".noslop/staged-acks.json")).unwrap();
+    assert!(staged.contains("\"identity\": \"dev@example.com\""), "{staged}");
+}
+
+#[test]
+fn test_hooks_scripts_run_around_the_pipeline() {
+    let temp = TempDir::new().unwrap();
+    std::process::Command::new("git")
+        .args(["init"])
+        .current_dir(temp.path())
+        .output()
+        .unwrap();
+    std::fs::write(temp.path().join("a.txt"), "a\n").unwrap();
+    std::process::Command::new("git")
+        .args(["add", "a.txt"])
+        .current_dir(temp.path())
+        .output()
+        .unwrap();
+
+    std::fs::write(
+        temp.path().join(".noslop.toml"),
+        "[hooks]\npre_check = \"echo manifest generated\"\npost_check = \"echo passed=$NOSLOP_CHECK_PASSED; exit 3\"\n",
+    )
+    .unwrap();
+    noslop()
+        .arg("check")
+        .current_dir(temp.path())
+        .env("NOSLOP_ACTOR", "claude-code")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Hook pre_check: echo manifest generated  [ok]"))
+        .stdout(predicate::str::contains("    manifest generated"))
+        .stdout(predicate::str::contains("[exit 3 (warn)]"))
+        .stdout(predicate::str::contains("    passed=1"));
+
+    std::fs::write(
+        temp.path().join(".noslop.toml"),
+        "[hooks]\npre_check = \"exit 1\"\non_failure = \"block\"\n",
+    )
+    .unwrap();
+    noslop()
+        .args(["--json", "check"])
+        .current_dir(temp.path())
+        .env("NOSLOP_ACTOR", "claude-code")
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("\"stage\": \"pre_check\""))
+        .stdout(predicate::str::contains("\"passed\": false"));
+}
+
+#[test]
+fn test_package_checks_fire_per_workspace_package() {
+    let repo = TempGitRepo::new();
+    for (dir, name) in [("crates/api", "api"), ("crates/web", "web")] {
+        std::fs::create_dir_all(repo.path().join(dir).join("src")).unwrap();
+        std::fs::write(
+            repo.path().join(dir).join("Cargo.toml"),
+            format!("[package]\nname = \"{name}\"\nversion = \"0.1.0\"\n"),
+        )
+        .unwrap();
+        std::fs::write(repo.path().join(dir).join("src/lib.rs"), "// lib\n").unwrap();
+    }
+    repo.write_file("Cargo.toml", "[workspace]\nmembers = [\"crates/*\"]\n");
+    repo.write_file(
+        ".noslop.toml",
+        "[[check]]\nid = \"PKG-1\"\ntarget = \"{package}/src/**\"\nmessage = \"Owner of {package} reviewed?\"\n",
+    );
+    repo.git(&["add", "-A"]);
+
+    noslop()
+        .arg("check")
+        .current_dir(repo.path())
+        .env("NOSLOP_ACTOR", "claude-code")
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("[PKG-1] crates/api/src/lib.rs"))
+        .stdout(predicate::str::contains("Owner of api reviewed?"))
+        .stdout(predicate::str::contains("Owner of web reviewed?"))
+        // The virtual workspace root is not a package
+        .stdout(predicate::str::contains("[PKG-1] Cargo.toml").not());
+}
+
+#[test]
+fn test_status_and_doctor_report_storage_backends() {
+    let temp = TempDir::new().unwrap();
+    std::process::Command::new("git")
+        .args(["init"])
+        .current_dir(temp.path())
+        .output()
+        .unwrap();
+    let record = |schema: u32| {
+        format!(
+            "{{\"schema\":{schema},\"check_id\":\"TST-1\",\"message\":\"ok\",\
+             \"acknowledged_by\":\"human\",\"created_at\":\"2026-01-01T00:00:00Z\"}}"
+        )
+    };
+    std::fs::create_dir_all(temp.path().join(".noslop/acks")).unwrap();
+    std::fs::write(temp.path().join(".noslop/acks/TST-1-a.json"), record(1)).unwrap();
+    std::fs::write(
+        temp.path().join(".noslop/history.jsonl"),
+        format!("{}\n{}\nnot json\n", record(1), record(99)),
+    )
+    .unwrap();
+
+    noslop()
+        .args(["--json", "status"])
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"active\":\"trailers\""))
+        .stdout(predicate::str::contains("\"pending_compaction\":1"))
+        .stdout(predicate::str::contains("\"newer_schema\":1"))
+        .stdout(predicate::str::contains("\"name\":\"ledger\""));
+
+    noslop()
+        .arg("doctor")
+        .current_dir(temp.path())
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("acks are stored in trailers; records also in: ledger"))
+        .stdout(predicate::str::contains(
+            "FAIL 1 unreadable record(s) in .noslop/acks, .noslop/history.jsonl",
+        ))
+        .stdout(predicate::str::contains("FAIL 1 ledger record(s) come from a newer noslop"))
+        .stdout(predicate::str::contains("1 ledger record(s) pending compaction"));
+}
+
+#[test]
+fn test_check_staged_from_list_skips_the_index() {
+    let temp = TempDir::new().unwrap();
+    std::process::Command::new("git")
+        .args(["init"])
+        .current_dir(temp.path())
+        .output()
+        .unwrap();
+    std::fs::write(
+        temp.path().join(".noslop.toml"),
+        "[[check]]\nid = \"TST-1\"\ntarget = \"src/*.rs\"\nmessage = \"Reviewed?\"\n\n\
+         [[check]]\nid = \"TST-2\"\ntarget = \"*.lock\"\nmessage = \"Deps reviewed?\"\n\
+         on = [\"delete\"]\n",
+    )
+    .unwrap();
+
+    // Nothing is staged: only the list decides what changed
+    noslop()
+        .args(["check", "--ci", "--staged-from", "-"])
+        .write_stdin("src/a.rs\nD\tCargo.lock\n")
+        .current_dir(temp.path())
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("TST-1"))
+        .stdout(predicate::str::contains("TST-2"));
+
+    std::fs::write(temp.path().join("changed.txt"), "README.md\nA\tdocs/new.md\n").unwrap();
+    noslop()
+        .args(["check", "--ci", "--staged-from", "changed.txt"])
+        .current_dir(temp.path())
+        .assert()
+        .success();
+
+    noslop()
+        .args(["check", "--staged-from", "missing.txt"])
+        .current_dir(temp.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Failed to read file list 'missing.txt'"));
+}
+
+#[test]
+fn test_check_ramp_warns_until_date_then_blocks() {
+    let repo = TempGitRepo::new();
+    let config = |ramp: &str| {
+        std::fs::write(
+            repo.path().join(".noslop.toml"),
+            format!(
+                "[[check]]\nid = \"TST-1\"\ntarget = \"*.rs\"\nmessage = \"Reviewed?\"\n\
+                 ramp = {ramp}\n"
+            ),
+        )
+        .unwrap();
+    };
+    repo.write_file("a.rs", "fn a() {}\n");
+
+    // Ramping: an agent is warned, not blocked
+    config("{ warn_until = \"2999-01-01\" }");
+    repo.git(&["add", "-A"]);
+    noslop()
+        .arg("check")
+        .env("NOSLOP_ACTOR", "claude-code")
+        .current_dir(repo.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("TST-1"));
+    noslop()
+        .args(["check", "list"])
+        .current_dir(repo.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("ramping: warns until 2999-01-01, blocks from then"));
+
+    // Ramp over: the check blocks
+    config("{ warn_until = \"2000-01-01\" }");
+    repo.git(&["add", "-A"]);
+    noslop()
+        .arg("check")
+        .env("NOSLOP_ACTOR", "claude-code")
+        .current_dir(repo.path())
+        .assert()
+        .failure();
+    noslop()
+        .args(["check", "list"])
+        .current_dir(repo.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("ramp ended: blocking since 2000-01-01"));
+
+    // warn_days count from the commit that added the check
+    config("{ warn_days = 30 }");
+    repo.git(&["add", "-A"]);
+    repo.git(&["commit", "--no-verify", "-m", "add check"]);
+    let today = chrono::Utc::now().date_naive();
+    noslop()
+        .args(["check", "list"])
+        .current_dir(repo.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(format!(
+            "warns until {}",
+            today + chrono::Days::new(30)
+        )));
+}
+
+#[test]
+fn test_list_formats_and_columns_share_one_renderer() {
+    let repo = TempGitRepo::new();
+    repo.write_file(
+        ".noslop.toml",
+        "[[check]]\nid = \"TST-1\"\ntarget = \"*.rs\"\nmessage = \"Reviewed?\"\n\
+         owner = \"@core\"\n\n\
+         [[check]]\nid = \"TST-22\"\ntarget = \"docs/**\"\nmessage = \"Docs built?\"\n\
+         severity = \"warn\"\ndisabled = true\n",
+    );
+
+    noslop()
+        .args(["check", "list", "--format", "table"])
+        .current_dir(repo.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("ID      SEVERITY  TARGET   MESSAGE\n"))
+        .stdout(predicate::str::contains("TST-1   block     *.rs     Reviewed?\n"));
+    noslop()
+        .args(["check", "list", "--format", "tsv", "--columns", "id,status,owner"])
+        .current_dir(repo.path())
+        .assert()
+        .success()
+        .stdout("id\tstatus\towner\nTST-1\tenabled\t@core\nTST-22\tdisabled\t\n");
+    noslop()
+        .args(["check", "list", "--format", "json", "--columns", "id,file"])
+        .current_dir(repo.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"file\": \".noslop.toml\""));
+    noslop()
+        .args(["check", "list", "--columns", "id,due"])
+        .current_dir(repo.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Unknown column: due. Use: id, severity"));
+
+    repo.write_file("a.rs", "fn a() {}\n");
+    repo.stage("a.rs");
+    repo.commit("acked\n\nNoslop-Ack: TST-1 | checked\tit | claude-code");
+    noslop()
+        .args(["history", "export", "--format", "tsv", "--columns", "check_id,files,message"])
+        .current_dir(repo.path())
+        .assert()
+        .success()
+        .stdout("check_id\tfiles\tmessage\nTST-1\ta.rs\tchecked it\n");
+    noslop()
+        .args(["history", "export", "--columns", "check_id"])
+        .current_dir(repo.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--columns applies to --format table or tsv"));
+}
+
+#[test]
+fn test_vendored_paths_only_warn_and_are_labelled() {
+    let repo = TempGitRepo::new();
+    repo.write_file(
+        ".noslop.toml",
+        "vendored = [\"gen/\"]\n\n\
+         [[check]]\nid = \"TST-1\"\ntarget = \"*.go\"\nmessage = \"Reviewed?\"\n",
+    );
+    for dir in ["vendor/lib", "gen"] {
+        std::fs::create_dir_all(repo.path().join(dir)).unwrap();
+    }
+    repo.write_file("vendor/lib/x.go", "package lib\n");
+    repo.write_file("gen/api_generated.go", "package gen\n");
+    repo.write_file("gen/y.go", "package gen\n");
+    repo.git(&["add", "-A"]);
+
+    // Only vendored files changed: the check warns, labelled, and passes
+    noslop()
+        .args(["check"])
+        .env("NOSLOP_ACTOR", "claude-code")
+        .current_dir(repo.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("vendor/lib/x.go"))
+        .stdout(predicate::str::contains("(vendored)"));
+    noslop()
+        .args(["--json", "check"])
+        .env("NOSLOP_ACTOR", "claude-code")
+        .current_dir(repo.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"vendored\": true"));
+
+    // A first-party file still blocks
+    repo.write_file("main.go", "package main\n");
+    repo.stage("main.go");
+    noslop()
+        .args(["check"])
+        .env("NOSLOP_ACTOR", "claude-code")
+        .current_dir(repo.path())
+        .assert()
+        .failure();
+
+    // A broad check that opts in keeps its severity on vendored paths
+    repo.git(&["rm", "-q", "--cached", "main.go"]);
+    repo.write_file(
+        ".noslop.toml",
+        "[[check]]\nid = \"TST-1\"\ntarget = \"*.go\"\nmessage = \"Vendor bump reviewed?\"\n\
+         include_vendored = true\n",
+    );
+    noslop()
+        .args(["check"])
+        .env("NOSLOP_ACTOR", "claude-code")
+        .current_dir(repo.path())
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("Vendor bump reviewed?"));
+}
+
+#[test]
+fn test_staged_show_lists_acks_with_their_checks() {
+    let temp = TempDir::new().unwrap();
+    std::process::Command::new("git")
+        .args(["init"])
+        .current_dir(temp.path())
+        .output()
+        .unwrap();
+    std::fs::write(
+        temp.path().join(".noslop.toml"),
+        "[[check]]\nid = \"TST-1\"\ntarget = \"*.rs\"\nmessage = \"Reviewed?\"\n\
+         group = \"review\"\n\n\
+         [[check]]\nid = \"TST-2\"\ntarget = \"*.md\"\nmessage = \"Docs read?\"\n",
+    )
+    .unwrap();
+    std::fs::write(temp.path().join("a.rs"), "fn a() {}\n").unwrap();
+    std::process::Command::new("git")
+        .args(["add", "-A"])
+        .current_dir(temp.path())
+        .output()
+        .unwrap();
+
+    noslop()
+        .args(["ack", "review", "-m", "walked through the change"])
+        .env("NOSLOP_ACTOR", "claude-code")
+        .current_dir(temp.path())
+        .assert()
+        .success();
+
+    noslop()
+        .args(["staged", "show", "TST-1"])
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("pending commit (1)"))
+        .stdout(predicate::str::contains("check:   [BLOCK] TST-1 *.rs -> Reviewed?"))
+        .stdout(predicate::str::contains("by:      claude-code at "))
+        .stdout(predicate::str::contains("message: walked through the change"));
+    noslop()
+        .args(["--json", "staged", "show"])
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"check_id\": \"review\""))
+        .stdout(predicate::str::contains("\"id\": \"TST-1\""));
+    noslop()
+        .args(["staged", "show", "TST-2"])
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout("No staged acknowledgment for TST-2.\n");
+}
+
+#[test]
+fn test_clear_staged_keeps_acks_the_commit_does_not_carry() {
+    let repo = TempGitRepo::new();
+    repo.write_file(
+        ".noslop.toml",
+        "[[check]]\nid = \"TST-1\"\ntarget = \"*.rs\"\nmessage = \"Reviewed?\"\n",
+    );
+    repo.write_file("a.rs", "fn a() {}\n");
+    repo.git(&["add", "-A"]);
+    noslop()
+        .args(["ack", "TST-1", "-m", "looked at a.rs"])
+        .current_dir(repo.path())
+        .assert()
+        .success();
+    let staged = repo.path().join(".noslop/staged-acks.json");
+    let consumed = repo.path().join(".noslop/consumed-acks.json");
+
+    // --no-verify skips commit-msg, but post-commit still runs
+    repo.git(&["commit", "--no-verify", "-m", "no trailers"]);
+    noslop()
+        .arg("clear-staged")
+        .current_dir(repo.path())
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("kept 1 staged acknowledgment(s)"));
+    assert!(staged.exists());
+
+    // commit-msg marks the ack consumed; a commit without the trailer
+    // restores it, one carrying it clears both files
+    repo.write_file("b.rs", "fn b() {}\n");
+    repo.stage("b.rs");
+    let msg = repo.path().join("MSG");
+    std::fs::write(&msg, "add b\n").unwrap();
+    noslop()
+        .args(["add-trailers", msg.to_str().unwrap()])
+        .current_dir(repo.path())
+        .assert()
+        .success();
+    assert!(consumed.exists());
+    std::fs::remove_file(&staged).unwrap();
+    repo.commit("message rewritten");
+    noslop().arg("clear-staged").current_dir(repo.path()).assert().success();
+    assert!(staged.exists());
+    assert!(!consumed.exists());
+
+    repo.write_file("c.rs", "fn c() {}\n");
+    repo.stage("c.rs");
+    std::fs::write(&msg, "add c\n").unwrap();
+    noslop()
+        .args(["add-trailers", msg.to_str().unwrap()])
+        .current_dir(repo.path())
+        .assert()
+        .success();
+    repo.git(&["commit", "-F", msg.to_str().unwrap()]);
+    noslop()
+        .arg("clear-staged")
+        .current_dir(repo.path())
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("kept").not());
+    assert!(!staged.exists());
+    assert!(!consumed.exists());
+}
+
+#[test]
+fn test_quarantine_commit_then_promote_once_acknowledged() {
+    let repo = TempGitRepo::with_branch("main");
+    repo.write_file(
+        ".noslop.toml",
+        "[[check]]\nid = \"TST-1\"\ntarget = \"*.rs\"\nmessage = \"Reviewed?\"\nseverity = \"block\"\n",
+    );
+    repo.git(&["add", "-A"]);
+    repo.commit("init");
+
+    repo.write_file("hotfix.rs", "fn fix() {}\n");
+    repo.stage("hotfix.rs");
+    noslop()
+        .args(["quarantine", "commit", "-m", "hotfix"])
+        .env("GIT_COMMITTER_NAME", "t")
+        .env("GIT_COMMITTER_EMAIL", "t@example.com")
+        .env("GIT_AUTHOR_NAME", "t")
+        .env("GIT_AUTHOR_EMAIL", "t@example.com")
+        .current_dir(repo.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("pending review of 1 check(s): TST-1"));
+    let head = String::from_utf8(repo.git(&["log", "-1", "--format=%B"]).stdout).unwrap();
+    assert!(head.contains("Noslop-Quarantine: main"));
+    noslop()
+        .args(["quarantine", "list"])
+        .current_dir(repo.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("(from main)  needs TST-1"));
+
+    // Unanswered: nothing is merged
+    noslop()
+        .args(["quarantine", "promote"])
+        .current_dir(repo.path())
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("TST-1"));
+
+    noslop()
+        .args(["ack", "TST-1", "-m", "reviewed after the release"])
+        .current_dir(repo.path())
+        .assert()
+        .success();
+    noslop()
+        .args(["quarantine", "promote"])
+        .env("GIT_COMMITTER_NAME", "t")
+        .env("GIT_COMMITTER_EMAIL", "t@example.com")
+        .env("GIT_AUTHOR_NAME", "t")
+        .env("GIT_AUTHOR_EMAIL", "t@example.com")
+        .current_dir(repo.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("into main"));
+    let branch = String::from_utf8(repo.git(&["branch", "--show-current"]).stdout).unwrap();
+    assert_eq!(branch.trim(), "main");
+    assert!(repo.path().join("hotfix.rs").exists());
+    noslop()
+        .args(["check", "--against", "HEAD~1..HEAD"])
+        .current_dir(repo.path())
+        .assert()
+        .success();
+    noslop()
+        .args(["quarantine", "list"])
+        .current_dir(repo.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No quarantined commits."));
+}
+
+#[test]
+fn test_alias_runs_steps_in_order_and_stops_on_failure() {
+    let temp = TempDir::new().unwrap();
+    std::fs::write(
+        temp.path().join(".noslop.toml"),
+        "[alias]\n\
+         ship = [\"version\", \"!echo shipped\"]\n\
+         broken = [\"!exit 3\", \"!echo unreachable\"]\n\
+         again = [\"again\"]\n",
+    )
+    .unwrap();
+
+    // Passthrough arguments go to the last step
+    noslop()
+        .args(["ship", "v1"])
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("noslop v"))
+        .stdout(predicate::str::contains("shipped v1"))
+        .stderr(predicate::str::contains("[ship 2/2] !echo shipped"));
+    noslop()
+        .arg("broken")
+        .current_dir(temp.path())
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("unreachable").not());
+    noslop()
+        .arg("again")
+        .current_dir(temp.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("calls itself"));
+    noslop()
+        .arg("missing")
+        .current_dir(temp.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("no [alias] entry"));
+}
+
+#[test]
+fn test_codeowners_rules_gate_acks_and_soften_for_owners() {
+    let repo = TempGitRepo::new();
+    std::fs::create_dir_all(repo.path().join(".github")).unwrap();
+    repo.write_file(".github/CODEOWNERS", "/src/payments/ alice@example.com\n");
+    repo.write_file(
+        ".noslop.toml",
+        "[[check]]\nid = \"PAY-1\"\ntarget = \"src/payments/\"\nmessage = \"Money moves?\"\n\
+         severity = \"block\"\ncodeowners = [\"ack\", \"warn\"]\n",
+    );
+    std::fs::create_dir_all(repo.path().join("src/payments")).unwrap();
+    repo.write_file("src/payments/charge.rs", "fn charge() {}\n");
+    repo.git(&["add", "-A"]);
+
+    // The owner committing: the check only warns
+    noslop()
+        .arg("check")
+        .env("NOSLOP_ACTOR", "claude-code")
+        .env("NOSLOP_IDENTITY", "alice@example.com")
+        .current_dir(repo.path())
+        .assert()
+        .success();
+
+    // Anyone else: blocked, and their own ack does not count
+    noslop()
+        .args(["ack", "PAY-1", "-m", "looked fine to me"])
+        .env("NOSLOP_ACTOR", "claude-code")
+        .env("NOSLOP_IDENTITY", "carol@example.com")
+        .current_dir(repo.path())
+        .assert()
+        .success();
+    noslop()
+        .arg("check")
+        .env("NOSLOP_ACTOR", "claude-code")
+        .env("NOSLOP_IDENTITY", "carol@example.com")
+        .current_dir(repo.path())
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("ack by an owner: alice@example.com"));
+
+    // An owner's ack does
+    noslop()
+        .args(["ack", "PAY-1", "-m", "amounts and currency verified"])
+        .env("NOSLOP_ACTOR", "human")
+        .env("NOSLOP_IDENTITY", "alice@example.com")
+        .current_dir(repo.path())
+        .assert()
+        .success();
+    noslop()
+        .arg("check")
+        .env("NOSLOP_ACTOR", "claude-code")
+        .env("NOSLOP_IDENTITY", "carol@example.com")
+        .current_dir(repo.path())
+        .assert()
+        .success();
+}
+
+#[test]
+fn test_codeowners_ack_trusts_the_asserted_identity_and_records_it() {
+    let temp = TempDir::new().unwrap();
+    std::process::Command::new("git")
+        .args(["init"])
+        .current_dir(temp.path())
+        .output()
+        .unwrap();
+    std::fs::write(temp.path().join("CODEOWNERS"), "/src/ alice@example.com\n").unwrap();
+    std::fs::write(
+        temp.path().join(".noslop.toml"),
+        "[[check]]\nid = \"SRC-1\"\ntarget = \"src/\"\nmessage = \"Reviewed?\"\n\
+         severity = \"block\"\ncodeowners = [\"ack\"]\n",
+    )
+    .unwrap();
+    std::fs::create_dir_all(temp.path().join("src")).unwrap();
+    std::fs::write(temp.path().join("src/lib.rs"), "fn f() {}\n").unwrap();
+    std::process::Command::new("git")
+        .args(["add", "-A"])
+        .current_dir(temp.path())
+        .output()
+        .unwrap();
+
+    // An agent claiming the owner's identity: honor-based, so the ack
+    // counts, but the claim and the actor behind it are on the record
+    noslop()
+        .args(["--identity", "alice@example.com", "ack", "SRC-1", "-m", "reviewed the change"])
+        .env("NOSLOP_ACTOR", "claude-code")
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("as claude-code, alice@example.com"));
+    noslop()
+        .arg("check")
+        .env("NOSLOP_ACTOR", "claude-code")
+        .env("NOSLOP_IDENTITY", "carol@example.com")
+        .current_dir(temp.path())
+        .assert()
+        .success();
+    let staged = std::fs::read_to_string(temp.path().join(".noslop/staged-acks.json")).unwrap();
+    assert!(staged.contains("\"identity\": \"alice@example.com\""));
+    assert!(staged.contains("\"acknowledged_by\": \"claude-code\""));
+}
+
+#[test]
+fn test_check_prompt_prints_instructions_per_blocking_check() {
+    let repo = TempGitRepo::new();
+    repo.write_file(
+        ".noslop.toml",
+        "[ack.block]\nmin_length = 15\n\n[[check]]\nid = \"TST-1\"\ntarget = \"*.rs\"\n\
+         message = \"Tests updated\"\nseverity = \"block\"\n",
+    );
+    repo.write_file("lib.rs", "fn f() {}\n");
+    repo.git(&["add", "-A"]);
+
+    noslop()
+        .args(["check", "--prompt"])
+        .env("NOSLOP_ACTOR", "claude-code")
+        .current_dir(repo.path())
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains(
+            "Check TST-1 blocks this commit. Verify: Tests updated. Files: lib.rs.",
+        ))
+        .stdout(predicate::str::contains("noslop ack TST-1 -m"))
+        .stdout(predicate::str::contains("at least 15 characters"));
+
+    noslop()
+        .args(["--json", "check", "--prompt"])
+        .env("NOSLOP_ACTOR", "claude-code")
+        .current_dir(repo.path())
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("\"prompt\":"))
+        .stdout(predicate::str::contains("\"command\":"));
+
+    noslop()
+        .args(["ack", "TST-1", "-m", "tests cover the new path"])
+        .env("NOSLOP_ACTOR", "claude-code")
+        .current_dir(repo.path())
+        .assert()
+        .success();
+    noslop()
+        .args(["check", "--prompt"])
+        .env("NOSLOP_ACTOR", "claude-code")
+        .current_dir(repo.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("no acknowledgment is needed"));
+}
+
+#[test]
+fn test_verify_branch_uses_configured_base_on_the_remote() {
+    let temp = TempDir::new().unwrap();
+    let origin = temp.path().join("origin");
+    let clone = temp.path().join("clone");
+    std::fs::create_dir_all(&origin).unwrap();
+    let git = |dir: &std::path::Path, args: &[&str]| {
+        std::process::Command::new("git")
+            .args(args)
+            .current_dir(dir)
+            .env("GIT_AUTHOR_NAME", "t")
+            .env("GIT_AUTHOR_EMAIL", "t@example.com")
+            .env("GIT_COMMITTER_NAME", "t")
+            .env("GIT_COMMITTER_EMAIL", "t@example.com")
+            .output()
+            .unwrap()
+    };
+    git(&origin, &["init", "-b", "main"]);
+    std::fs::write(
+        origin.join(".noslop.toml"),
+        "[git]\nbase_branch = \"trunk\"\n\n\
+         [[check]]\nid = \"RS-1\"\ntarget = \"*.rs\"\nmessage = \"Rust reviewed?\"\n",
+    )
+    .unwrap();
+    git(&origin, &["add", "-A"]);
+    git(&origin, &["commit", "-m", "base"]);
+    git(&origin, &["checkout", "-b", "trunk"]);
+    std::fs::write(origin.join("old.rs"), "fn old() {}\n").unwrap();
+    git(&origin, &["add", "-A"]);
+    git(&origin, &["commit", "-m", "trunk work"]);
+    git(&origin, &["checkout", "-b", "feature"]);
+    std::fs::write(origin.join("lib.rs"), "fn main() {}\n").unwrap();
+    git(&origin, &["add", "-A"]);
+    git(&origin, &["commit", "-m", "work\n\nNoslop-Ack: RS-1 | checked | human"]);
+    git(&origin, &["checkout", "main"]);
+
+    // Only feature is a local branch: trunk is origin/trunk
+    git(
+        temp.path(),
+        &["clone", "--quiet", "--branch", "feature", origin.to_str().unwrap(), "clone"],
+    );
+    noslop()
+        .args(["--json", "check", "verify-branch"])
+        .current_dir(&clone)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("lib.rs"))
+        .stdout(predicate::str::contains("old.rs").not());
+
+    noslop()
+        .args(["check", "verify-branch", "--base", "nope"])
+        .current_dir(&clone)
+        .assert()
+        .failure();
+}
+
+#[test]
+fn test_snapshot_audits_later_commits_against_release_policy() {
+    let repo = TempGitRepo::with_branch("main");
+    repo.write_file(
+        "shared.toml",
+        "[[check]]\nid = \"RS-1\"\ntarget = \"*.rs\"\nmessage = \"Rust reviewed?\"\n",
+    );
+    repo.write_file(".noslop.toml", "include = [\"shared.toml\"]\n");
+    repo.git(&["add", "-A"]);
+    repo.commit("policy");
+
+    noslop()
+        .args(["snapshot", "v1.0.0"])
+        .current_dir(repo.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Wrote .noslop/snapshots/v1.0.0.json: 1 check(s) from 2 file(s)",
+        ));
+    noslop()
+        .args(["snapshot", "v1.0.0"])
+        .current_dir(repo.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--force"));
+    repo.git(&["add", "-A"]);
+    repo.commit("Release v1.0.0");
+    repo.git(&["tag", "v1.0.0"]);
+
+    // The policy is loosened later, then code lands without an ack
+    repo.write_file("shared.toml", "");
+    repo.write_file("lib.rs", "fn main() {}\n");
+    std::fs::remove_dir_all(repo.path().join(".noslop/snapshots")).unwrap();
+    repo.git(&["add", "-A"]);
+    repo.git(&["commit", "--no-verify", "-m", "unreviewed"]);
+
+    noslop()
+        .args(["verify-commit", "HEAD"])
+        .current_dir(repo.path())
+        .assert()
+        .success();
+    // Read from the tag's tree: the snapshot is gone from the working tree
+    noslop()
+        .args(["verify-commit", "HEAD", "--policy-snapshot", "v1.0.0"])
+        .current_dir(repo.path())
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("Policy: snapshot v1.0.0"))
+        .stdout(predicate::str::contains("unanswered  [RS-1] lib.rs"));
+}
+
+#[test]
+fn test_diff_base_asks_to_reack_only_files_changed_since_the_ack() {
+    let repo = TempGitRepo::with_branch("main");
+    repo.write_file(
+        ".noslop.toml",
+        "[[check]]\nid = \"TST-1\"\ntarget = \"*.rs\"\nmessage = \"Reviewed?\"\n",
+    );
+    repo.git(&["add", "-A"]);
+    repo.commit("base");
+
+    repo.git(&["checkout", "-b", "feature"]);
+    repo.write_file("a.rs", "fn a() {}\n");
+    repo.write_file("b.rs", "fn b() {}\n");
+    repo.git(&["add", "-A"]);
+    noslop()
+        .args(["ack", "TST-1", "-m", "reviewed both files"])
+        .env("NOSLOP_ACTOR", "claude-code")
+        .current_dir(repo.path())
+        .assert()
+        .success();
+    repo.git(&["add", "-A"]);
+    repo.commit("work");
+    noslop()
+        .args(["check", "--ci", "--diff-base", "main"])
+        .current_dir(repo.path())
+        .assert()
+        .success();
+
+    // A conflict resolution rewrites b.rs: only b.rs needs a fresh ack
+    repo.write_file("b.rs", "fn b() { resolved() }\n");
+    repo.git(&["commit", "-am", "resolve conflict"]);
+    noslop()
+        .args(["check", "--ci", "--diff-base", "main"])
+        .current_dir(repo.path())
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("[TST-1] b.rs"))
+        .stdout(predicate::str::contains("changed since it was acknowledged"))
+        .stdout(predicate::str::contains("[TST-1] a.rs").not());
+}
+
+#[test]
+fn test_check_emits_github_annotations_in_actions() {
+    let repo = TempGitRepo::with_branch("main");
+    repo.write_file(
+        ".noslop.toml",
+        "[[check]]\nid = \"TST-1\"\ntarget = \"*.rs\"\nmessage = \"Reviewed?\"\n",
+    );
+    repo.write_file("lib.rs", "fn a() {}\n");
+    repo.git(&["add", "-A"]);
+    repo.commit("base");
+    repo.git(&["checkout", "-b", "feature"]);
+    repo.write_file("lib.rs", "fn a() {}\nfn b() {}\n");
+    repo.git(&["commit", "-am", "add b"]);
+
+    noslop()
+        .args(["check", "--ci", "--diff-base", "main"])
+        .env("GITHUB_ACTIONS", "true")
+        .current_dir(repo.path())
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains(
+            "::error file=lib.rs,line=2,title=noslop TST-1::Reviewed?",
+        ));
+
+    // JSON stays parseable; outside Actions nothing is emitted
+    noslop()
+        .args(["--json", "check", "--ci", "--diff-base", "main"])
+        .env("GITHUB_ACTIONS", "true")
+        .current_dir(repo.path())
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("::error").not());
+    noslop()
+        .args(["check", "--ci", "--diff-base", "main"])
+        .env_remove("GITHUB_ACTIONS")
+        .current_dir(repo.path())
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("::error").not());
+}
+
+#[test]
+fn test_composite_target_excludes_generated_paths() {
+    let repo = TempGitRepo::new();
+    repo.write_file(
+        ".noslop.toml",
+        "[[check]]\nid = \"SRC-1\"\ntarget = [\"src/**\", \"!src/generated/**\"]\nmessage = \"Reviewed?\"\n",
+    );
+    std::fs::create_dir_all(repo.path().join("src/generated")).unwrap();
+    repo.write_file("src/generated/out.rs", "fn a() {}\n");
+    repo.write_file("src/lib.rs", "fn b() {}\n");
+    repo.stage("src/generated/out.rs");
+
+    noslop()
+        .args(["check", "--explain-misses"])
+        .env("NOSLOP_ACTOR", "claude-code")
+        .current_dir(repo.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No checks apply"))
+        .stdout(predicate::str::contains("target does not match"));
+
+    repo.stage("src/lib.rs");
+    noslop()
+        .arg("check")
+        .env("NOSLOP_ACTOR", "claude-code")
+        .current_dir(repo.path())
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("SRC-1"));
+}
+
+#[test]
+#[cfg(unix)]
+fn test_import_reviews_stages_acks_for_mapped_github_approvals() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let repo = TempGitRepo::new();
+    repo.write_file(
+        ".noslop.toml",
+        "[reviews.labels]\n\"@org/security\" = [\"SEC-1\"]\n\"@carol\" = [\"DOC-1\"]\n\
+         \"@dave\" = [\"API-1\"]\nsecurity = [\"SEC-2\"]\n\n\
+         [[check]]\nid = \"SEC-1\"\ntarget = \"src/auth/\"\nmessage = \"Threat model updated?\"\n\n\
+         [[check]]\nid = \"SEC-2\"\ntarget = \"src/auth/\"\nmessage = \"Secrets rotated?\"\n\n\
+         [[check]]\nid = \"DOC-1\"\ntarget = \"docs/\"\nmessage = \"Docs reviewed?\"\n\n\
+         [[check]]\nid = \"API-1\"\ntarget = \"src/api/\"\nmessage = \"API stable?\"\n",
+    );
+    repo.git(&["add", "-A"]);
+    repo.commit("config");
+    let head = String::from_utf8(repo.git(&["rev-parse", "HEAD"]).stdout).unwrap();
+
+    // A stand-in for the GitHub CLI: bob approved HEAD after asking for
+    // changes, carol only asked for changes, dave approved an older
+    // revision, and eve (mapped by no `@` key) approved the labelled PR
+    let bin = repo.path().join("bin");
+    std::fs::create_dir_all(&bin).unwrap();
+    let gh = bin.join("gh");
+    std::fs::write(
+        &gh,
+        format!(
+            r#"#!/bin/sh
+case "$*" in
+  "repo view"*) echo org/repo ;;
+  *issues/7/labels*) echo security ;;
+  *pulls/7/reviews*)
+    echo '{{"login":"bob","state":"CHANGES_REQUESTED","url":"https://gh/r/1","commit":"{head}"}}'
+    echo '{{"login":"carol","state":"CHANGES_REQUESTED","url":"https://gh/r/2","commit":"{head}"}}'
+    echo '{{"login":"eve","state":"APPROVED","url":"https://gh/r/4","commit":"{head}"}}'
+    echo '{{"login":"dave","state":"APPROVED","url":"https://gh/r/5","commit":"0123456789"}}'
+    echo '{{"login":"bob","state":"APPROVED","url":"https://gh/r/3","commit":"{head}"}}' ;;
+  *teams/security/memberships/bob*) echo active ;;
+  *) exit 1 ;;
+esac
+"#,
+            head = head.trim()
+        ),
+    )
+    .unwrap();
+    std::fs::set_permissions(&gh, std::fs::Permissions::from_mode(0o755)).unwrap();
+    let path = format!("{}:{}", bin.display(), std::env::var("PATH").unwrap_or_default());
+
+    noslop()
+        .args(["import-reviews", "--from", "github-review", "7"])
+        .env("PATH", &path)
+        .env("NOSLOP_ACTOR", "claude-code")
+        .current_dir(repo.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Staged 2 acknowledgment(s)"))
+        .stdout(predicate::str::contains("SEC-1  approved by bob (@org/security)"))
+        .stdout(predicate::str::contains("SEC-2  approved by bob (security)"))
+        .stdout(predicate::str::contains("DOC-1").not())
+        .stdout(predicate::str::contains("API-1").not())
+        .stderr(predicate::str::contains("ignoring dave's approval: 0123456"));
+
+    // Signed by whoever imported, not as a human on bob's behalf
+    noslop()
+        .args(["--json", "status"])
+        .current_dir(repo.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("https://gh/r/3"))
+        .stdout(predicate::str::contains("claude-code"))
+        .stdout(predicate::str::contains("\"human\"").not());
+}
+
+#[test]
+fn test_scaffold_service_starts_with_pack_checks_and_hooks() {
+    let temp = TempDir::new().unwrap();
+    std::fs::write(
+        temp.path().join("pack.toml"),
+        "[[check]]\nid = \"ORG-7\"\ntarget = \"migrations/\"\nmessage = \"Migration reversible?\"\n",
+    )
+    .unwrap();
+
+    // Outside a repository: a repository of its own, with hooks
+    noslop()
+        .args(["scaffold", "service", "payments-api", "--pack", "pack.toml"])
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Created .noslop.toml (3 checks, prefix PAA)"))
+        .stdout(predicate::str::contains("Starter tasks:"));
+    let service = temp.path().join("payments-api");
+    let config = std::fs::read_to_string(service.join(".noslop.toml")).unwrap();
+    assert!(config.contains("root = true"));
+    assert!(config.contains("id = \"PAA-1\"\ntarget = \"migrations/\""));
+    assert!(config.contains("target = \"README.md\""));
+    assert!(service.join(".git/hooks/pre-commit").exists());
+
+    // Inside one: a nested config the repo's hooks already cover
+    noslop()
+        .args(["scaffold", "service", "ledger"])
+        .current_dir(&service)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Created ledger/.noslop.toml (2 checks, prefix LED)"));
+    let nested = std::fs::read_to_string(service.join("ledger/.noslop.toml")).unwrap();
+    assert!(!nested.contains("root = true"));
+
+    noslop()
+        .args(["scaffold", "service", "payments-api"])
+        .current_dir(temp.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("already exists"));
+}
+
+#[test]
+fn test_gc_removes_stale_state_after_a_dry_run() {
+    let temp = TempDir::new().unwrap();
+    std::process::Command::new("git")
+        .args(["init"])
+        .current_dir(temp.path())
+        .output()
+        .unwrap();
+    std::fs::write(
+        temp.path().join(".noslop.toml"),
+        "[[check]]\nid = \"TST-1\"\ntarget = \"*.rs\"\nmessage = \"Reviewed?\"\n",
+    )
+    .unwrap();
+    let state = temp.path().join(".noslop");
+    std::fs::create_dir_all(state.join("evidence")).unwrap();
+    std::fs::create_dir_all(state.join("cache/check")).unwrap();
+    let ack = |id: &str| {
+        format!(
+            r#"{{"check_id":"{id}","message":"m","acknowledged_by":"human","created_at":"2025-01-01T00:00:00Z"}}"#
+        )
+    };
+    std::fs::write(state.join("staged-acks.json"), format!("[{},{}]", ack("TST-1"), ack("GONE-1")))
+        .unwrap();
+    std::fs::write(state.join("evidence/0123abcd"), "log output\n").unwrap();
+    std::fs::write(state.join("cache/check/k.json"), "{}").unwrap();
+
+    noslop()
+        .args(["gc", "--dry-run"])
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Would remove:"))
+        .stdout(predicate::str::contains("staged ack for GONE-1"))
+        .stdout(predicate::str::contains("staged ack for TST-1").not())
+        .stdout(predicate::str::contains(".noslop/evidence/0123abcd"))
+        .stdout(predicate::str::contains(".noslop/cache/check"));
+    assert!(state.join("evidence/0123abcd").exists());
+
+    noslop()
+        .arg("gc")
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Reclaimed"));
+    assert!(!state.join("evidence/0123abcd").exists());
+    assert!(!state.join("cache/check").exists());
+    let staged = std::fs::read_to_string(state.join("staged-acks.json")).unwrap();
+    assert!(staged.contains("TST-1") && !staged.contains("GONE-1"));
+
+    noslop()
+        .arg("gc")
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Nothing to clean up."));
+}
+
+#[test]
+#[cfg(unix)]
+fn test_gc_removes_worktrees_of_exited_sandbox_runs() {
+    let temp = TempDir::new().unwrap();
+    let repo = temp.path().join("repo");
+    std::fs::create_dir_all(&repo).unwrap();
+    let git = |args: &[&str]| {
+        std::process::Command::new("git")
+            .args(args)
+            .current_dir(&repo)
+            .env("GIT_AUTHOR_NAME", "t")
+            .env("GIT_AUTHOR_EMAIL", "t@example.com")
+            .env("GIT_COMMITTER_NAME", "t")
+            .env("GIT_COMMITTER_EMAIL", "t@example.com")
+            .output()
+            .unwrap()
+    };
+    git(&["init"]);
+    git(&["commit", "--allow-empty", "-m", "root"]);
+
+    // A sandbox whose run has exited, and one whose run is still going
+    let mut exited = std::process::Command::new("true").spawn().unwrap();
+    exited.wait().unwrap();
+    let dead = temp.path().join(format!("noslop-sandbox-{}", exited.id()));
+    let mut running = std::process::Command::new("sleep").arg("30").spawn().unwrap();
+    let live = temp.path().join(format!("noslop-sandbox-{}", running.id()));
+    for path in [&dead, &live] {
+        git(&["worktree", "add", "--detach", "--quiet", path.to_str().unwrap(), "HEAD"]);
+    }
+
+    let out = noslop().arg("gc").current_dir(&repo).assert().success();
+    running.kill().unwrap();
+    running.wait().unwrap();
+    out.stdout(predicate::str::contains(format!("sandbox process {} is gone", exited.id())))
+        .stdout(predicate::str::contains(running.id().to_string()).not());
+    assert!(!dead.exists());
+    assert!(live.exists());
+}
+
+#[test]
+fn test_must_match_fires_only_where_the_content_is_missing() {
+    let repo = TempGitRepo::new();
+    repo.write_file(
+        ".noslop.toml",
+        "[[check]]\nid = \"OBS-1\"\ntarget = \"src/api/handlers/\"\nmessage = \"Handlers traced?\"\n\
+         must_match = '#\\[tracing::instrument\\]'\n",
+    );
+    let handlers = repo.path().join("src/api/handlers");
+    std::fs::create_dir_all(&handlers).unwrap();
+    std::fs::write(handlers.join("users.rs"), "#[tracing::instrument]\nasync fn get() {}\n")
+        .unwrap();
+    repo.stage("src/api/handlers/users.rs");
+
+    noslop()
+        .args(["check", "--explain-misses"])
+        .env("NOSLOP_ACTOR", "claude-code")
+        .current_dir(repo.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No checks apply"))
+        .stdout(predicate::str::contains("file already has what `must_match` asks for"));
+
+    std::fs::write(handlers.join("orders.rs"), "async fn list() {}\n").unwrap();
+    repo.stage("src/api/handlers/orders.rs");
+    noslop()
+        .arg("check")
+        .env("NOSLOP_ACTOR", "claude-code")
+        .current_dir(repo.path())
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("src/api/handlers/orders.rs"))
+        .stdout(predicate::str::contains(r"missing `#\[tracing::instrument\]`"))
+        .stdout(predicate::str::contains("users.rs").not());
+}
+
+#[test]
+fn test_unattested_warning_becomes_a_debt_that_blocks_once_overdue() {
+    let repo = TempGitRepo::new();
+    repo.write_file(
+        ".noslop.toml",
+        "[[check]]\nid = \"LOG-1\"\ntarget = \"src/\"\nmessage = \"Errors logged?\"\n\
+         severity = \"warn\"\nattest_within_days = 3\n",
+    );
+    std::fs::create_dir(repo.path().join("src")).unwrap();
+    repo.write_file("src/a.rs", "fn a() {}\n");
+    repo.git(&["add", "-A"]);
+    repo.commit("add a");
+    noslop()
+        .arg("clear-staged")
+        .current_dir(repo.path())
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("LOG-1 left unanswered; attest it by"));
+    noslop()
+        .arg("status")
+        .current_dir(repo.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Unattested warnings (1)"))
+        .stdout(predicate::str::contains("[LOG-1] due"));
+
+    // Past its due date, the debt blocks a commit that never touches src/
+    let debts = repo.path().join(".noslop/debts.json");
+    let mut recorded: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&debts).unwrap()).unwrap();
+    recorded[0]["due"] = "2020-01-01".into();
+    std::fs::write(&debts, recorded.to_string()).unwrap();
+    repo.write_file("notes.txt", "notes\n");
+    repo.stage("notes.txt");
+    noslop()
+        .arg("check")
+        .env("NOSLOP_ACTOR", "claude-code")
+        .current_dir(repo.path())
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("[LOG-1] src/a.rs"))
+        .stdout(predicate::str::contains("was due 2020-01-01"));
+    noslop()
+        .arg("status")
+        .current_dir(repo.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("OVERDUE"));
+
+    // A staged ack lets the commit through; carried by it, the debt is paid
+    noslop()
+        .args(["ack", "LOG-1", "-m", "errors in a.rs are logged"])
+        .current_dir(repo.path())
+        .assert()
+        .success();
+    noslop()
+        .arg("check")
+        .env("NOSLOP_ACTOR", "claude-code")
+        .current_dir(repo.path())
+        .assert()
+        .success();
+    let msg = repo.path().join("MSG");
+    std::fs::write(&msg, "add notes\n").unwrap();
+    noslop()
+        .args(["add-trailers", msg.to_str().unwrap()])
+        .current_dir(repo.path())
+        .assert()
+        .success();
+    repo.git(&["commit", "-F", msg.to_str().unwrap()]);
+    noslop().arg("clear-staged").current_dir(repo.path()).assert().success();
+    assert!(!debts.exists());
+}
+
+#[test]
+fn test_change_kind_scopes_a_check_to_signature_changes() {
+    let repo = TempGitRepo::new();
+    repo.write_file(
+        ".noslop.toml",
+        "[[check]]\nid = \"API-1\"\ntarget = \"src/\"\nmessage = \"Callers updated?\"\n\
+         when.change_kind = \"signature-change\"\n",
+    );
+    std::fs::create_dir(repo.path().join("src")).unwrap();
+    let api = repo.path().join("src/api.rs");
+    std::fs::write(&api, "pub fn get(id: u32) -> u32 {\n    id\n}\n").unwrap();
+    repo.git(&["add", "-A"]);
+    repo.commit("add api");
+
+    // A body-only edit is no signature change
+    std::fs::write(&api, "pub fn get(id: u32) -> u32 {\n    id + 1\n}\n").unwrap();
+    repo.stage("src/api.rs");
+    noslop()
+        .arg("check")
+        .env("NOSLOP_ACTOR", "claude-code")
+        .current_dir(repo.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No checks apply"));
+
+    std::fs::write(&api, "pub fn get(id: u64) -> u64 {\n    id + 1\n}\n").unwrap();
+    repo.stage("src/api.rs");
+    noslop()
+        .args(["check", "--json"])
+        .env("NOSLOP_ACTOR", "claude-code")
+        .current_dir(repo.path())
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("\"id\": \"API-1\""))
+        .stdout(predicate::str::contains("\"signature-change\""));
+}
+
+#[test]
+fn test_check_compare_reports_changes_since_the_last_run() {
+    let repo = TempGitRepo::new();
+    repo.write_file(
+        ".noslop.toml",
+        "[[check]]\nid = \"RS-1\"\ntarget = \"*.rs\"\nmessage = \"Rust reviewed?\"\n\n\
+         [[check]]\nid = \"PY-1\"\ntarget = \"*.py\"\nmessage = \"Python reviewed?\"\n",
+    );
+    repo.write_file("a.rs", "fn a() {}\n");
+    repo.stage("a.rs");
+    noslop()
+        .args(["check", "--compare"])
+        .env("NOSLOP_ACTOR", "claude-code")
+        .current_dir(repo.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("nothing to compare with yet"));
+
+    repo.write_file("b.py", "b = 1\n");
+    repo.stage("b.py");
+    noslop()
+        .args(["ack", "RS-1", "-m", "a.rs reviewed"])
+        .env("NOSLOP_ACTOR", "claude-code")
+        .current_dir(repo.path())
+        .assert()
+        .success();
+    noslop()
+        .args(["check", "--compare"])
+        .env("NOSLOP_ACTOR", "claude-code")
+        .current_dir(repo.path())
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("Since the last check:"))
+        .stdout(predicate::str::contains("newly blocking   [PY-1] b.py"))
+        .stdout(predicate::str::contains("newly satisfied  [RS-1] a.rs"))
+        .stdout(predicate::str::contains("now matched      b.py"));
+
+    noslop()
+        .args(["check", "--compare"])
+        .env("NOSLOP_ACTOR", "claude-code")
+        .current_dir(repo.path())
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("Since the last check: no change."));
+}
+
+#[test]
+fn test_supply_chain_template_blocks_new_dependencies() {
+    let repo = TempGitRepo::new();
+    noslop()
+        .args(["check", "template", "supply-chain"])
+        .current_dir(repo.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Added the supply-chain template"));
+    let manifest = repo.path().join("package.json");
+    std::fs::write(&manifest, r#"{"name": "web", "dependencies": {"react": "^18.0.0"}}"#).unwrap();
+    repo.git(&["add", "-A"]);
+    repo.commit("init");
+
+    // A manifest edit that leaves the dependencies alone
+    std::fs::write(
+        &manifest,
+        r#"{"name": "web", "private": true, "dependencies": {"react": "^18.0.0"}}"#,
+    )
+    .unwrap();
+    repo.stage("package.json");
+    noslop()
+        .arg("check")
+        .env("NOSLOP_ACTOR", "claude-code")
+        .current_dir(repo.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No checks apply"));
+
+    std::fs::write(
+        &manifest,
+        r#"{"name": "web", "dependencies": {"react": "^18.0.0", "left-pad": "^1.3.0"}}"#,
+    )
+    .unwrap();
+    repo.stage("package.json");
+    noslop()
+        .arg("check")
+        .env("NOSLOP_ACTOR", "claude-code")
+        .current_dir(repo.path())
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("Supply-chain review"))
+        .stdout(predicate::str::contains("(new dependencies: left-pad ^1.3.0)"));
+}
+
+#[test]
+fn test_config_show_effective_annotates_origins() {
+    let temp = TempDir::new().unwrap();
+    std::process::Command::new("git")
+        .args(["init"])
+        .current_dir(temp.path())
+        .output()
+        .unwrap();
+    std::fs::write(
+        temp.path().join(".noslop.toml"),
+        "include = [\"noslop/security.toml\"]\n\n[project]\nprefix = \"ACM\"\n\n\
+         [[check]]\nid = \"ACM-1\"\ntarget = \"src/\"\nmessage = \"Tests?\"\n",
+    )
+    .unwrap();
+    std::fs::create_dir(temp.path().join("noslop")).unwrap();
+    std::fs::write(
+        temp.path().join("noslop/security.toml"),
+        "[[check]]\nid = \"SEC-1\"\ntarget = \"*.pem\"\nmessage = \"Secret?\"\n",
+    )
+    .unwrap();
+
+    noslop()
+        .args(["config", "show"])
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("includes noslop/security.toml"));
+    noslop()
+        .args(["config", "show", "--effective"])
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("prefix = \"ACM\"  # .noslop.toml"))
+        .stdout(predicate::str::contains("enabled = true  # default"))
+        .stdout(predicate::str::contains("# noslop/security.toml\n[[check]]\nid = \"SEC-1\""));
+    noslop()
+        .args(["config", "show", "--effective", "--json"])
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"key\": \"project.prefix\""))
+        .stdout(predicate::str::contains("\"origin\": \"noslop/security.toml\""));
+}
+
+#[test]
+fn test_verify_commit_resolves_package_checks_from_the_commit_tree() {
+    let repo = TempGitRepo::new();
+    std::fs::create_dir_all(repo.path().join("foo/src")).unwrap();
+    repo.write_file("foo/Cargo.toml", "[package]\nname = \"foo\"\nversion = \"0.1.0\"\n");
+    repo.write_file(
+        ".noslop.toml",
+        "[[check]]\nid = \"PK-1\"\ntarget = \"{package}/**\"\nmessage = \"Owner of package {package} reviewed?\"\n",
+    );
+    repo.git(&["add", "-A"]);
+    repo.commit("workspace");
+    repo.write_file("foo/src/lib.rs", "// lib\n");
+    repo.git(&["add", "-A"]);
+    repo.commit("unanswered");
+
+    noslop()
+        .args(["verify-commit", "HEAD"])
+        .current_dir(repo.path())
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("unanswered  [PK-1] foo/src/lib.rs (block)"))
+        .stdout(predicate::str::contains("FAIL: 1 blocking check(s) unanswered."));
+}
+
+#[test]
+fn test_check_remove_keeps_the_rest_of_the_config() {
+    let temp = TempDir::new().unwrap();
+    std::process::Command::new("git")
+        .args(["init"])
+        .current_dir(temp.path())
+        .output()
+        .unwrap();
+    std::fs::write(
+        temp.path().join(".noslop.toml"),
+        "[project]\nprefix = \"ACM\"\n\n[ack.block]\nmin_length = 20\n\n\
+         [hooks]\npre_check = \"./gen.sh\"\n\n\
+         [[check]]\nid = \"ACM-1\"\ntarget = \"*.rs\"\nmessage = \"First\"\n\n\
+         [[check]]\nid = \"ACM-2\"\ntarget = \"src/\"\nmessage = \"Second\"\n\
+         when.change_kind = \"new-function\"\n",
+    )
+    .unwrap();
+
+    noslop()
+        .args(["check", "remove", ".noslop.toml:0"])
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Removed check: First"));
+    let config = std::fs::read_to_string(temp.path().join(".noslop.toml")).unwrap();
+    assert!(!config.contains("First"));
+    assert!(config.contains("prefix = \"ACM\""));
+    assert!(config.contains("[ack.block]\nmin_length = 20"));
+    assert!(config.contains("pre_check = \"./gen.sh\""));
+    assert!(config.contains("id = \"ACM-2\""));
+    assert!(config.contains("when.change_kind = \"new-function\""));
+}
@@ -3,6 +3,8 @@
 //! Output provides structured result types that can be rendered as either
 //! human-readable text or machine-parseable JSON.
 
+use noslop::core::models::Severity;
+use noslop::core::services::ack_policy::AckRequirement;
 use noslop::output::{
     AckResult, CheckInfo, CheckListResult, CheckMatch, CheckResult, ENVELOPE_SCHEMA,
     OperationResult, OutputMode, UploadEnvelope,
@@ -38,10 +40,22 @@ fn check_result_serialization() {
             file: "src/auth.rs".to_string(),
             target: "*.rs".to_string(),
             message: "Check auth".to_string(),
-            severity: "block".to_string(),
+            severity: Severity::Block,
+            group: None,
+            owner: None,
+            vendored: false,
+            requires_human: false,
+            code_owners: Vec::new(),
             acknowledged: true,
+            stale_digest: None,
+            introduced_by: vec![],
+            change_classes: vec![],
         }],
         monitor: vec![],
+        misses: vec![],
+        hooks: vec![],
+        comparison: None,
+        range: None,
     };
 
     let json = serde_json::to_string(&result).unwrap();
@@ -65,17 +79,190 @@ fn check_result_blocking() {
             file: "src/api.rs".to_string(),
             target: "src/api/".to_string(),
             message: "Review API changes".to_string(),
-            severity: "block".to_string(),
+            severity: Severity::Block,
+            group: None,
+            owner: None,
+            vendored: false,
+            requires_human: false,
+            code_owners: Vec::new(),
             acknowledged: false,
+            stale_digest: None,
+            introduced_by: vec![],
+            change_classes: vec![],
         }],
         warnings: vec![],
         acknowledged: vec![],
         monitor: vec![],
+        misses: vec![],
+        hooks: vec![],
+        comparison: None,
+        range: None,
     };
 
     let json = serde_json::to_string(&result).unwrap();
     assert!(json.contains("\"passed\":false"));
     assert!(json.contains("\"acknowledged\":false"));
+
+    // The gate's error names each unanswered check once
+    let parsed: CheckResult = serde_json::from_str(&json).unwrap();
+    let mut second = parsed.blocking.into_iter().next().unwrap();
+    second.file = "src/api/v2.rs".to_string();
+    let mut result = result;
+    result.blocking.push(second);
+    match result.ensure_passed() {
+        Err(noslop::Error::CheckBlocked { ids }) => assert_eq!(ids, vec!["TEST-2".to_string()]),
+        other => panic!("expected CheckBlocked, got {other:?}"),
+    }
+    // An unenforced run passes whatever it shows
+    result.passed = true;
+    assert!(result.ensure_passed().is_ok());
+}
+
+#[test]
+fn check_result_pending_markdown_groups_files_per_check() {
+    let item = |file: &str| CheckMatch {
+        id: "API-1".to_string(),
+        file: file.to_string(),
+        target: "src/api/".to_string(),
+        message: "Rate limiting added?".to_string(),
+        severity: Severity::Block,
+        group: None,
+        owner: Some("@team-api".to_string()),
+        vendored: false,
+        requires_human: false,
+        code_owners: Vec::new(),
+        acknowledged: false,
+        stale_digest: None,
+        introduced_by: vec![],
+        change_classes: vec![],
+    };
+    let result = CheckResult {
+        passed: false,
+        files_checked: 2,
+        actor: "claude-code".to_string(),
+        enforced: true,
+        tree_oid: None,
+        check_set_version: None,
+        check_set_age_seconds: None,
+        blocking: vec![item("src/api/a.rs"), item("src/api/b.rs")],
+        warnings: vec![],
+        acknowledged: vec![],
+        monitor: vec![],
+        misses: vec![],
+        hooks: vec![],
+        comparison: None,
+        range: None,
+    };
+
+    let md = result.pending_markdown();
+    assert_eq!(md.matches("## API-1").count(), 1);
+    assert!(md.contains("Verify: Rate limiting added?"));
+    assert!(md.contains("- `src/api/a.rs`\n- `src/api/b.rs`\n"));
+    assert!(md.contains("Contact: @team-api"));
+    assert!(md.contains("noslop ack API-1 -m"));
+}
+
+#[test]
+fn check_result_github_annotations_escape_and_locate() {
+    let item = |id: &str, file: &str, severity: Severity| CheckMatch {
+        id: id.to_string(),
+        file: file.to_string(),
+        target: "*".to_string(),
+        message: "100% covered?\nSay how".to_string(),
+        severity,
+        group: None,
+        owner: None,
+        vendored: false,
+        requires_human: false,
+        code_owners: Vec::new(),
+        acknowledged: false,
+        stale_digest: None,
+        introduced_by: vec![],
+        change_classes: vec![],
+    };
+    let mut result = CheckResult {
+        passed: false,
+        files_checked: 2,
+        actor: "github-actions".to_string(),
+        enforced: true,
+        tree_oid: None,
+        check_set_version: None,
+        check_set_age_seconds: None,
+        blocking: vec![item("API-1", "src/a,b.rs", Severity::Block)],
+        warnings: vec![item("DOC-1", "README.md", Severity::Warn)],
+        acknowledged: vec![],
+        monitor: vec![],
+        misses: vec![],
+        hooks: vec![],
+        comparison: None,
+        range: None,
+    };
+
+    let out = result.github_annotations(|file| (file == "README.md").then_some(7));
+    let lines: Vec<&str> = out.lines().collect();
+    assert_eq!(
+        lines[0],
+        "::error file=src/a%2Cb.rs,title=noslop API-1::100%25 covered?%0ASay how%0A\
+         Acknowledge with: noslop ack API-1 -m \"...\""
+    );
+    assert!(lines[1].starts_with("::warning file=README.md,line=7,title=noslop DOC-1::"));
+
+    // Not enforced: nothing blocks, so nothing is an error
+    result.enforced = false;
+    assert!(result.github_annotations(|_| None).starts_with("::warning file=src/a%2Cb.rs"));
+}
+
+#[test]
+fn check_result_prompts_one_instruction_per_check() {
+    let item = |file: &str| CheckMatch {
+        id: "API-1".to_string(),
+        file: file.to_string(),
+        target: "src/api/".to_string(),
+        message: "Rate limiting added?".to_string(),
+        severity: Severity::Block,
+        group: None,
+        owner: None,
+        vendored: false,
+        requires_human: true,
+        code_owners: Vec::new(),
+        acknowledged: false,
+        stale_digest: None,
+        introduced_by: vec![],
+        change_classes: vec![],
+    };
+    let result = CheckResult {
+        passed: false,
+        files_checked: 2,
+        actor: "claude-code".to_string(),
+        enforced: true,
+        tree_oid: None,
+        check_set_version: None,
+        check_set_age_seconds: None,
+        blocking: vec![item("src/api/a.rs"), item("src/api/b.rs")],
+        warnings: vec![],
+        acknowledged: vec![],
+        monitor: vec![],
+        misses: vec![],
+        hooks: vec![],
+        comparison: None,
+        range: None,
+    };
+
+    let prompts = result.prompts(|_| AckRequirement {
+        min_length: 20,
+        require_reference: true,
+    });
+    assert_eq!(prompts.len(), 1);
+    assert_eq!(prompts[0].files, ["src/api/a.rs", "src/api/b.rs"]);
+    assert_eq!(
+        prompts[0].command,
+        "noslop ack API-1 -m \"<what you verified, citing a URL or ticket>\""
+    );
+    let prompt = &prompts[0].prompt;
+    assert!(prompt.starts_with("Check API-1 blocks this commit. Verify: Rate limiting added?"));
+    assert!(prompt.contains("Files: src/api/a.rs, src/api/b.rs."));
+    assert!(prompt.contains("at least 20 characters"));
+    assert!(prompt.contains("`noslop approve API-1`"));
 }
 
 #[test]
@@ -94,11 +281,23 @@ fn check_result_with_warnings() {
             file: "src/utils.rs".to_string(),
             target: "src/utils/".to_string(),
             message: "Consider reviewing utility changes".to_string(),
-            severity: "warn".to_string(),
+            severity: Severity::Warn,
+            group: None,
+            owner: None,
+            vendored: false,
+            requires_human: false,
+            code_owners: Vec::new(),
             acknowledged: false,
+            stale_digest: None,
+            introduced_by: vec![],
+            change_classes: vec![],
         }],
         acknowledged: vec![],
         monitor: vec![],
+        misses: vec![],
+        hooks: vec![],
+        comparison: None,
+        range: None,
     };
 
     let json = serde_json::to_string(&result).unwrap();
@@ -117,8 +316,16 @@ fn check_match_serialization() {
         file: "test.rs".to_string(),
         target: "*.rs".to_string(),
         message: "Test message".to_string(),
-        severity: "warn".to_string(),
+        severity: Severity::Warn,
+        group: None,
+        owner: None,
+        vendored: false,
+        requires_human: false,
+        code_owners: Vec::new(),
         acknowledged: true,
+        stale_digest: None,
+        introduced_by: vec![],
+        change_classes: vec![],
     };
 
     let json = serde_json::to_string(&m).unwrap();
@@ -138,7 +345,7 @@ fn check_list_result_serialization() {
             id: ".noslop.toml:0".to_string(),
             target: "src/**/*.rs".to_string(),
             message: "Check Rust files".to_string(),
-            severity: "block".to_string(),
+            severity: Severity::Block,
             source_file: ".noslop.toml".to_string(),
         }],
     };
@@ -230,6 +437,10 @@ fn check_result_tree_oid_serializes_when_present() {
         warnings: vec![],
         acknowledged: vec![],
         monitor: vec![],
+        misses: vec![],
+        hooks: vec![],
+        comparison: None,
+        range: None,
     };
 
     let json = serde_json::to_string(&result).unwrap();
@@ -250,6 +461,10 @@ fn check_result_tree_oid_omitted_when_absent() {
         warnings: vec![],
         acknowledged: vec![],
         monitor: vec![],
+        misses: vec![],
+        hooks: vec![],
+        comparison: None,
+        range: None,
     };
 
     // Additive schema-1 field: older-payload consumers never see the key